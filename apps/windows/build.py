@@ -28,6 +28,7 @@ Bundle-cli notes:
   find a globally-installed `openclaw` binary.
 """
 
+import hashlib
 import os
 import shutil
 import subprocess
@@ -199,9 +200,37 @@ def bundle_cli(platform: str) -> None:
     print(f"  Removed {removed} non-target files/dirs")
 
     (openclaw_dest / ".keep").touch()
+
+    # 5. Write the checksum manifest the app verifies before launching
+    #    openclaw.mjs (sha256sum format). It covers every file in the bundle,
+    #    node_modules included; the app rejects files it doesn't list.
+    write_checksum_manifest(openclaw_dest)
     print("\nBundle-cli step complete.")
 
 
+def write_checksum_manifest(bundle_dir: Path) -> None:
+    """Hash every file under bundle_dir into bundle_dir/SHA256SUMS.
+
+    Paths are '/'-separated and sorted; a symlink is hashed by its target
+    path, matching runtime_update.rs.
+    """
+    lines = []
+    for path in bundle_dir.rglob("*"):
+        if path.is_dir() and not path.is_symlink():
+            continue
+        rel = path.relative_to(bundle_dir).as_posix()
+        if rel == "SHA256SUMS":
+            continue
+        if path.is_symlink():
+            data = os.readlink(path).encode()
+        else:
+            data = path.read_bytes()
+        lines.append((rel, hashlib.sha256(data).hexdigest()))
+    manifest = "".join(f"{digest}  {rel}\n" for rel, digest in sorted(lines))
+    (bundle_dir / "SHA256SUMS").write_text(manifest, newline="\n")
+    print(f"  Wrote SHA256SUMS ({len(lines)} files)")
+
+
 def build_linux(bundles: list[str]) -> None:
     env = os.environ.copy()
     env["NO_STRIP"] = "1"
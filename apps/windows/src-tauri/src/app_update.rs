@@ -0,0 +1,218 @@
+// Self-update of the app itself (Tauri updater).
+//
+// Release builds check a per-channel manifest
+// (`<UPDATE_MANIFEST_BASE>/<channel>.json`, Tauri's static update JSON) in
+// the background every `update_check_interval_hours` and emit
+// `update-available` once per new version. Installing stops the node host
+// first so the installer never races a running child process, and starts it
+// again if the install fails.
+//
+// Update packages are verified against the minisign public key baked in at
+// build time (`OPENCLAW_UPDATER_PUBKEY`); builds without one can check for
+// updates but not install them.
+
+use serde::Serialize;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+use crate::AppState;
+
+const UPDATE_MANIFEST_BASE: &str =
+    "https://raw.githubusercontent.com/openclaw/openclaw/main/apps/windows/updates";
+const CHANNELS: &[&str] = &["stable", "beta"];
+const UPDATER_PUBKEY: Option<&str> = option_env!("OPENCLAW_UPDATER_PUBKEY");
+const CHECK_TIMEOUT_SECS: u64 = 30;
+/// Delay before the first background check, so it doesn't compete with
+/// node host startup.
+const FIRST_CHECK_DELAY_SECS: u64 = 60;
+const SCHEDULER_POLL_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppUpdateInfo {
+    current_version: String,
+    latest_version: String,
+    channel: String,
+    notes: Option<String>,
+}
+
+/// The updater plugin, with the build's signing key when it has one.
+pub fn plugin<R: Runtime>() -> tauri::plugin::TauriPlugin<R, tauri_plugin_updater::Config> {
+    let builder = tauri_plugin_updater::Builder::new();
+    match UPDATER_PUBKEY.filter(|key| !key.is_empty()) {
+        Some(key) => builder.pubkey(key),
+        None => builder,
+    }
+    .build()
+}
+
+fn channel(app: &AppHandle) -> Result<String, String> {
+    let channel = app
+        .state::<AppState>()
+        .config
+        .lock()
+        .map_err(|err| err.to_string())?
+        .update_channel
+        .clone();
+    if !CHANNELS.contains(&channel.as_str()) {
+        return Err(format!(
+            "unknown update channel {} (expected {})",
+            channel,
+            CHANNELS.join(" or ")
+        ));
+    }
+    Ok(channel)
+}
+
+fn emit_stage(app: &AppHandle, stage: &str, detail: Option<String>) {
+    let _ = app.emit(
+        "app-update",
+        serde_json::json!({ "stage": stage, "detail": detail }),
+    );
+}
+
+async fn check(app: &AppHandle) -> Result<Option<(Update, AppUpdateInfo)>, String> {
+    let channel = channel(app)?;
+    let endpoint = format!("{}/{}.json", UPDATE_MANIFEST_BASE, channel)
+        .parse()
+        .map_err(|err| format!("invalid update endpoint: {}", err))?;
+    let handle = app.clone();
+    let updater = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(|err| err.to_string())?
+        .timeout(Duration::from_secs(CHECK_TIMEOUT_SECS))
+        // The Windows installer exits the process directly, skipping
+        // RunEvent::Exit; unregister the exec-host socket here instead
+        .on_before_exit(move || {
+            let _ = crate::stop_node_internal(&handle);
+            if let Ok(path) = crate::exec_approvals_path() {
+                let _ = crate::clear_exec_approvals_socket(&path);
+            }
+        })
+        .build()
+        .map_err(|err| err.to_string())?;
+    let update = updater
+        .check()
+        .await
+        .map_err(|err| format!("update check failed: {}", err))?;
+    Ok(update.map(|update| {
+        let info = AppUpdateInfo {
+            current_version: update.current_version.clone(),
+            latest_version: update.version.clone(),
+            channel,
+            notes: update.body.clone(),
+        };
+        (update, info)
+    }))
+}
+
+fn node_running(app: &AppHandle) -> bool {
+    app.state::<AppState>().node_status.borrow().running
+}
+
+/// Check on the configured interval and announce each new version once.
+pub async fn run_update_checks(app: AppHandle) {
+    if tauri::is_dev() {
+        return;
+    }
+    tokio::time::sleep(Duration::from_secs(FIRST_CHECK_DELAY_SECS)).await;
+    let mut last_check: Option<std::time::Instant> = None;
+    let mut announced: Option<String> = None;
+    loop {
+        let interval_hours = app
+            .state::<AppState>()
+            .config
+            .lock()
+            .map(|config| config.update_check_interval_hours)
+            .unwrap_or(0);
+        let due = interval_hours > 0
+            && last_check
+                .is_none_or(|at| at.elapsed() >= Duration::from_secs(interval_hours * 3600));
+        if due {
+            last_check = Some(std::time::Instant::now());
+            match check(&app).await {
+                Ok(Some((_, info))) if announced.as_ref() != Some(&info.latest_version) => {
+                    crate::push_log_line(
+                        &app,
+                        format!(
+                            "app update available: {} ({} channel)",
+                            info.latest_version, info.channel
+                        ),
+                    );
+                    announced = Some(info.latest_version.clone());
+                    let _ = app.emit("update-available", &info);
+                }
+                Ok(_) => {}
+                Err(err) => eprintln!("{}", err),
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(SCHEDULER_POLL_SECS)).await;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Check the configured channel now. Emits `update-available` when there is
+/// a newer release.
+#[tauri::command]
+pub async fn check_for_app_update(app: AppHandle) -> Result<Option<AppUpdateInfo>, String> {
+    let info = check(&app).await?.map(|(_, info)| info);
+    if let Some(info) = &info {
+        let _ = app.emit("update-available", info);
+    }
+    Ok(info)
+}
+
+/// Download, verify and install the latest release, then restart into it.
+#[tauri::command]
+pub async fn install_app_update(app: AppHandle) -> Result<(), String> {
+    if UPDATER_PUBKEY.is_none_or(str::is_empty) {
+        return Err(
+            "this build has no update signing key; download the new release manually".to_string(),
+        );
+    }
+    emit_stage(&app, "checking", None);
+    let Some((update, info)) = check(&app).await? else {
+        emit_stage(&app, "up-to-date", None);
+        return Ok(());
+    };
+
+    emit_stage(&app, "downloading", Some(info.latest_version.clone()));
+    let bytes = update.download(|_, _| {}, || {}).await.map_err(|err| {
+        let err = format!("update download failed: {}", err);
+        emit_stage(&app, "failed", Some(err.clone()));
+        err
+    })?;
+
+    emit_stage(&app, "stopping-node", None);
+    let was_running = node_running(&app);
+    let handle = app.clone();
+    tauri::async_runtime::spawn_blocking(move || crate::stop_node_internal(&handle))
+        .await
+        .map_err(|err| err.to_string())??;
+
+    emit_stage(&app, "installing", Some(info.latest_version.clone()));
+    crate::push_log_line(
+        &app,
+        format!("installing app update {}", info.latest_version),
+    );
+    if let Err(err) = update.install(bytes) {
+        let err = format!("update install failed: {}", err);
+        crate::push_log_line(&app, err.clone());
+        emit_stage(&app, "failed", Some(err.clone()));
+        if was_running {
+            let handle = app.clone();
+            let _ =
+                tauri::async_runtime::spawn_blocking(move || crate::start_node_internal(&handle))
+                    .await;
+        }
+        return Err(err);
+    }
+    // Windows never gets here: the installer has already exited the app
+    emit_stage(&app, "restarting", None);
+    app.restart();
+}
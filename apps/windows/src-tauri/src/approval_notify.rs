@@ -0,0 +1,172 @@
+// Actionable desktop notifications for exec approvals.
+//
+// With `approval_notifications` on, each pending approval posts a
+// notification with Allow once / Deny buttons (plus Open on macOS) instead
+// of opening the approval popup. Button presses go straight to
+// `send_approval_decision`, so an approval can be answered while the main
+// window stays hidden; clicking the notification body opens the popup.
+//
+//   Windows  WinRT toast (tauri-winrt-notification)
+//   macOS    user notification with an action dropdown (mac-notification-sys)
+//
+// Elsewhere, or if posting fails, the approval popup is shown as before.
+
+use tauri::{AppHandle, Manager};
+
+use crate::i18n::tf;
+use crate::{AppState, ApprovalPreview};
+
+/// Button label key and decision, in display order.
+#[cfg(target_os = "windows")]
+const ACTIONS: &[(&str, &str)] = &[
+    ("approval.allow_once", "allow-once"),
+    ("approval.deny", "deny"),
+];
+
+/// Notification body text: the command, then where it runs.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn describe(preview: &ApprovalPreview) -> (String, String) {
+    let command = preview
+        .raw_command
+        .clone()
+        .unwrap_or_else(|| preview.argv.join(" "));
+    let context = [
+        preview
+            .agent_id
+            .as_deref()
+            .map(|agent| tf("approval.agent", &[("agent", &agent)])),
+        preview
+            .cwd
+            .as_deref()
+            .map(|cwd| tf("approval.cwd", &[("cwd", &cwd)])),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+    (command, context)
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    app.state::<AppState>()
+        .config
+        .lock()
+        .map(|config| config.approval_notifications)
+        .unwrap_or(false)
+}
+
+/// Handle a notification activation: a button press carries the decision,
+/// a click on the body carries none.
+#[cfg_attr(not(any(target_os = "windows", target_os = "macos")), allow(dead_code))]
+fn activated(app: &AppHandle, id: &str, decision: Option<&str>) {
+    match decision {
+        Some(decision) => {
+            if let Err(err) = crate::send_approval_decision(app, id, decision) {
+                crate::push_log_line(app, format!("approval notification: {}", err));
+            }
+        }
+        None => crate::approval_window::show(app),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn post(app: &AppHandle, preview: &ApprovalPreview) -> Result<bool, String> {
+    use crate::i18n::t;
+    use tauri_winrt_notification::{Scenario, Toast};
+
+    // Dev builds aren't registered under the bundle identifier, so toasts
+    // posted with it would be dropped
+    let app_id = if tauri::is_dev() {
+        Toast::POWERSHELL_APP_ID.to_string()
+    } else {
+        app.config().identifier.clone()
+    };
+    let (command, context) = describe(preview);
+    let mut toast = Toast::new(&app_id)
+        .title(t("approval.title"))
+        .text1(&command)
+        .text2(&context)
+        .scenario(Scenario::Reminder);
+    for (label, decision) in ACTIONS {
+        toast = toast.add_button(t(label), decision);
+    }
+    let handle = app.clone();
+    let id = preview.id.clone();
+    toast
+        .on_activated(move |action| {
+            activated(&handle, &id, action.as_deref());
+            Ok(())
+        })
+        .show()
+        .map_err(|err| err.to_string())?;
+    Ok(true)
+}
+
+#[cfg(target_os = "macos")]
+fn post(app: &AppHandle, preview: &ApprovalPreview) -> Result<bool, String> {
+    use crate::i18n::t;
+    use mac_notification_sys::{MainButton, Notification, NotificationResponse};
+
+    let (allow, deny, open) = (
+        t("approval.allow_once"),
+        t("approval.deny"),
+        t("approval.open"),
+    );
+
+    // Unbundled dev builds have no identifier of their own; the crate then
+    // posts on behalf of a default app
+    if !tauri::is_dev() {
+        let _ = mac_notification_sys::set_application(&app.config().identifier);
+    }
+    let (command, context) = describe(preview);
+    let handle = app.clone();
+    let id = preview.id.clone();
+    // `send` blocks until the user responds, so it gets its own thread
+    std::thread::spawn(move || {
+        let response = Notification::new()
+            .title(t("approval.title"))
+            .maybe_subtitle(Some(context.as_str()).filter(|c| !c.is_empty()))
+            .message(&command)
+            .main_button(MainButton::DropdownActions(allow, &[allow, open]))
+            .close_button(deny)
+            .wait_for_click(true)
+            .send();
+        match response {
+            Ok(NotificationResponse::ActionButton(action)) if action == open => {
+                activated(&handle, &id, None)
+            }
+            Ok(NotificationResponse::ActionButton(_)) => {
+                activated(&handle, &id, Some("allow-once"))
+            }
+            Ok(NotificationResponse::CloseButton(_)) => activated(&handle, &id, Some("deny")),
+            Ok(NotificationResponse::Click) => activated(&handle, &id, None),
+            Ok(_) => {}
+            Err(err) => {
+                crate::push_log_line(&handle, format!("approval notification failed: {}", err));
+                crate::approval_window::show(&handle);
+            }
+        }
+    });
+    Ok(true)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn post(_app: &AppHandle, _preview: &ApprovalPreview) -> Result<bool, String> {
+    Ok(false)
+}
+
+/// Post a notification for a newly pending approval. Returns false when
+/// notifications are off, unsupported or failed, so the caller can fall back
+/// to the approval popup.
+pub fn notify(app: &AppHandle, preview: &ApprovalPreview) -> bool {
+    if !enabled(app) {
+        return false;
+    }
+    match post(app, preview) {
+        Ok(posted) => posted,
+        Err(err) => {
+            crate::push_log_line(app, format!("approval notification failed: {}", err));
+            false
+        }
+    }
+}
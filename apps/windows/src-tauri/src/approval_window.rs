@@ -0,0 +1,63 @@
+// Small always-on-top window for exec approvals.
+//
+// Raising the full main window for every approval steals focus and buries
+// whatever the user was doing. Instead a compact popup shows the pending
+// `ApprovalPreview`s with the same Deny / Allow Once / Allow Always buttons,
+// and closes once nothing is left to decide. The main window's Approvals
+// page still lists everything, including gateway-side approvals.
+
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use crate::AppState;
+
+pub const LABEL: &str = "approval";
+
+const WIDTH: f64 = 480.0;
+const HEIGHT: f64 = 300.0;
+
+/// Show the approval popup, creating it on first use.
+pub fn show(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window(LABEL) {
+        let _ = window.show();
+        let _ = window.set_focus();
+        return;
+    }
+    let built = WebviewWindowBuilder::new(
+        app,
+        LABEL,
+        WebviewUrl::App("index.html?window=approval".into()),
+    )
+    .title(crate::i18n::t("approval.title"))
+    .inner_size(WIDTH, HEIGHT)
+    .resizable(false)
+    .maximizable(false)
+    .minimizable(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .center()
+    .focused(true)
+    .build();
+    if let Err(err) = built {
+        crate::push_log_line(app, format!("failed to open approval window: {}", err));
+        // Fall back to the main window so the approval isn't missed
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.show();
+            let _ = window.set_focus();
+        }
+    }
+}
+
+/// Close the popup once no approvals are waiting.
+pub fn close_if_idle(app: &AppHandle) {
+    let idle = app
+        .state::<AppState>()
+        .pending_approvals
+        .borrow()
+        .is_empty();
+    if !idle {
+        return;
+    }
+    if let Some(window) = app.get_webview_window(LABEL) {
+        let _ = window.close();
+    }
+}
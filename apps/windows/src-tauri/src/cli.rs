@@ -0,0 +1,180 @@
+// Command-line flags for the app binary.
+//
+// Scripted and kiosk deployments control startup with flags instead of
+// editing node-client.json first. Flags only affect the current launch;
+// nothing is written back to the config.
+
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const USAGE: &str = "\
+Usage: openclaw-node-client [options]
+       openclaw-node-client <command> [--json]
+
+Commands (sent to the already-running app):
+  status              Show node host and gateway status
+  start | stop | restart
+                      Control the node host
+  approvals           List pending exec approvals
+  approve <id> [--always]
+                      Allow a pending approval once (or always)
+  deny <id>           Deny a pending approval
+
+Options:
+  --headless          Run without a window (tray icon only)
+  --minimized         Start with the window minimized (hidden in the tray
+                      when startMinimizedToTray is set)
+  --start-node        Start the node host on launch
+  --config <path>     Use this config file instead of node-client.json
+  --profile <name>    Use ~/.openclaw/node-client.<name>.json
+  -h, --help          Show this help
+  -V, --version       Show the app version";
+
+static CONFIG_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    pub headless: bool,
+    pub minimized: bool,
+    pub start_node: bool,
+    pub config: Option<PathBuf>,
+    pub profile: Option<String>,
+}
+
+/// What `main` should do after parsing argv.
+pub enum Launch {
+    Run(LaunchOptions),
+    Control(crate::control::ControlCommand),
+    Exit { message: String, code: i32 },
+}
+
+fn valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn parse_control(command: String, rest: Vec<String>) -> Result<Launch, String> {
+    let json = rest.iter().any(|arg| arg == "--json");
+    let args: Vec<String> = rest.into_iter().filter(|arg| arg != "--json").collect();
+    let valid = match command.as_str() {
+        "approve" => {
+            matches!(args.as_slice(), [_] | [_, _]) && args.iter().skip(1).all(|a| a == "--always")
+        }
+        "deny" => args.len() == 1,
+        _ => args.is_empty(),
+    };
+    if !valid || args.first().is_some_and(|id| id.starts_with('-')) {
+        return Err(format!("invalid arguments for {}", command));
+    }
+    Ok(Launch::Control(crate::control::ControlCommand {
+        command,
+        args,
+        json,
+    }))
+}
+
+fn parse(args: impl IntoIterator<Item = String>) -> Result<Launch, String> {
+    let mut args = args.into_iter().peekable();
+    if let Some(command) = args.next_if(|arg| crate::control::COMMANDS.contains(&arg.as_str())) {
+        return parse_control(command, args.collect());
+    }
+    let mut options = LaunchOptions::default();
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let mut value = |name: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .filter(|v| !v.is_empty())
+                .ok_or_else(|| format!("{} requires a value", name))
+        };
+        match flag.as_str() {
+            "--headless" => options.headless = true,
+            "--minimized" => options.minimized = true,
+            "--start-node" => options.start_node = true,
+            "--config" => options.config = Some(PathBuf::from(value("--config")?)),
+            "--profile" => {
+                let name = value("--profile")?;
+                if !valid_profile_name(&name) {
+                    return Err(format!(
+                        "invalid profile name {:?} (use letters, digits, '-' and '_')",
+                        name
+                    ));
+                }
+                options.profile = Some(name);
+            }
+            "-h" | "--help" => {
+                return Ok(Launch::Exit {
+                    message: USAGE.to_string(),
+                    code: 0,
+                })
+            }
+            "-V" | "--version" => {
+                return Ok(Launch::Exit {
+                    message: format!("openclaw-node-client {}", env!("CARGO_PKG_VERSION")),
+                    code: 0,
+                })
+            }
+            // macOS passes a process serial number when launched from Finder
+            _ if flag.starts_with("-psn_") => {}
+            _ => return Err(format!("unknown option {}", arg)),
+        }
+    }
+    if options.config.is_some() && options.profile.is_some() {
+        return Err("--config and --profile cannot be used together".to_string());
+    }
+    Ok(Launch::Run(options))
+}
+
+/// Parse the process arguments.
+pub fn parse_args() -> Launch {
+    match parse(std::env::args().skip(1)) {
+        Ok(launch) => launch,
+        Err(err) => Launch::Exit {
+            message: format!("error: {}\n\n{}", err, USAGE),
+            code: 2,
+        },
+    }
+}
+
+/// Print `message` and exit. Release builds on Windows have no console of
+/// their own, so attach to the one the app was launched from.
+pub fn exit_with(message: &str, code: i32) -> ! {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+    if code == 0 {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+    std::process::exit(code)
+}
+
+/// Point `config_path()` at the file selected by `--config` or `--profile`.
+/// Must run before the config is first loaded.
+pub fn apply_config_override(options: &LaunchOptions) -> Result<(), String> {
+    let path = match (&options.config, &options.profile) {
+        (Some(path), _) => std::path::absolute(path).map_err(|err| err.to_string())?,
+        (None, Some(profile)) => {
+            crate::openclaw_dir()?.join(format!("node-client.{}.json", profile))
+        }
+        (None, None) => return Ok(()),
+    };
+    let _ = CONFIG_OVERRIDE.set(path);
+    Ok(())
+}
+
+/// The config file chosen on the command line, if any.
+pub fn config_override() -> Option<PathBuf> {
+    CONFIG_OVERRIDE.get().cloned()
+}
@@ -0,0 +1,282 @@
+// Clipboard exchange with the gateway.
+//
+// Opt-in (`clipboard_sync_enabled`) and never automatic: this machine's
+// clipboard is only sent when the user pushes it, and clipboard contents
+// from the gateway are only written here when the user pulls them or
+// accepts an offer. Only kinds listed in `clipboard_sync_types` ("text",
+// "image") up to `clipboard_sync_max_bytes` pass, in either direction.
+//
+//   clipboard.push {kind, text?, width?, height?, data?}   send ours
+//   clipboard.pull {} -> clip | null                       fetch theirs
+//   event clipboard.push {kind, ..., from?}                an offer
+//
+// Images travel as base64 RGBA with their dimensions.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tauri::image::Image;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::gateway::{self, GatewayState};
+use crate::AppState;
+
+const PREVIEW_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Clip {
+    /// "text" | "image"
+    kind: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    width: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    height: Option<u32>,
+    /// Base64 RGBA pixels of an image.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<String>,
+}
+
+/// What the UI is shown about a clip, without the contents.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardSummary {
+    kind: String,
+    size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preview: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    from: Option<String>,
+}
+
+/// Clipboard contents offered by the gateway, waiting for the user.
+#[derive(Default)]
+pub struct ClipboardOfferState {
+    offer: Mutex<Option<(Clip, ClipboardSummary)>>,
+}
+
+struct Settings {
+    enabled: bool,
+    types: Vec<String>,
+    max_bytes: u64,
+}
+
+fn settings(app: &AppHandle) -> Result<Settings, String> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().map_err(|err| err.to_string())?;
+    Ok(Settings {
+        enabled: config.clipboard_sync_enabled,
+        types: config.clipboard_sync_types.clone(),
+        max_bytes: config.clipboard_sync_max_bytes,
+    })
+}
+
+fn enabled_settings(app: &AppHandle) -> Result<Settings, String> {
+    let settings = settings(app)?;
+    if !settings.enabled {
+        return Err("clipboard sync is turned off".to_string());
+    }
+    Ok(settings)
+}
+
+/// Check a clip against the type filter and size limit.
+fn summarize(
+    clip: &Clip,
+    settings: &Settings,
+    from: Option<String>,
+) -> Result<ClipboardSummary, String> {
+    if !settings.types.contains(&clip.kind) {
+        return Err(format!("clipboard type {} is not allowed", clip.kind));
+    }
+    let (size_bytes, preview) = match clip.kind.as_str() {
+        "text" => {
+            let text = clip.text.as_deref().ok_or("text clip has no text")?;
+            (
+                text.len() as u64,
+                Some(text.chars().take(PREVIEW_CHARS).collect()),
+            )
+        }
+        "image" => {
+            let (Some(width), Some(height), Some(data)) = (clip.width, clip.height, &clip.data)
+            else {
+                return Err("image clip is missing its dimensions or data".to_string());
+            };
+            // Base64 is 4 chars per 3 bytes; exact enough for the limit
+            let size = data.len() as u64 / 4 * 3;
+            (size, Some(format!("{}×{}", width, height)))
+        }
+        other => return Err(format!("unknown clipboard type {}", other)),
+    };
+    if size_bytes > settings.max_bytes {
+        return Err(format!(
+            "clipboard contents ({} bytes) exceed the {} byte limit",
+            size_bytes, settings.max_bytes
+        ));
+    }
+    Ok(ClipboardSummary {
+        kind: clip.kind.clone(),
+        size_bytes,
+        preview,
+        from,
+    })
+}
+
+fn read_clipboard(app: &AppHandle, kind: &str) -> Result<Clip, String> {
+    match kind {
+        "text" => Ok(Clip {
+            kind: kind.to_string(),
+            text: Some(app.clipboard().read_text().map_err(|err| err.to_string())?),
+            width: None,
+            height: None,
+            data: None,
+        }),
+        "image" => {
+            let image = app
+                .clipboard()
+                .read_image()
+                .map_err(|err| err.to_string())?;
+            Ok(Clip {
+                kind: kind.to_string(),
+                text: None,
+                width: Some(image.width()),
+                height: Some(image.height()),
+                data: Some(STANDARD.encode(image.rgba())),
+            })
+        }
+        other => Err(format!("unknown clipboard type {}", other)),
+    }
+}
+
+fn write_clipboard(app: &AppHandle, clip: Clip) -> Result<(), String> {
+    match (
+        clip.kind.as_str(),
+        clip.text,
+        clip.width,
+        clip.height,
+        clip.data,
+    ) {
+        ("text", Some(text), ..) => app
+            .clipboard()
+            .write_text(text)
+            .map_err(|err| err.to_string()),
+        ("image", _, Some(width), Some(height), Some(data)) => {
+            let rgba = STANDARD
+                .decode(data)
+                .map_err(|err| format!("invalid image data: {}", err))?;
+            if rgba.len() as u64 != u64::from(width) * u64::from(height) * 4 {
+                return Err("image data does not match its dimensions".to_string());
+            }
+            app.clipboard()
+                .write_image(&Image::new_owned(rgba, width, height))
+                .map_err(|err| err.to_string())
+        }
+        _ => Err("clipboard contents are incomplete".to_string()),
+    }
+}
+
+async fn call(app: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    let state = app.state::<Arc<GatewayState>>().inner().clone();
+    if !state.supports(method) {
+        return Err(format!(
+            "the gateway does not support clipboard sync ({})",
+            method
+        ));
+    }
+    gateway::request(&state, method.to_string(), Some(params))
+        .await?
+        .map_err(|err| format!("{} failed: {}", method, err))
+}
+
+/// A `clipboard.push` event: hold it as an offer until the user decides.
+pub fn dispatch(app: &AppHandle, event: &str, payload: &Value) {
+    if event != "clipboard.push" {
+        return;
+    }
+    let Ok(settings) = settings(app) else {
+        return;
+    };
+    if !settings.enabled {
+        return;
+    }
+    let from = payload
+        .get("from")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let result = serde_json::from_value::<Clip>(payload.clone())
+        .map_err(|err| err.to_string())
+        .and_then(|clip| summarize(&clip, &settings, from).map(|summary| (clip, summary)));
+    match result {
+        Ok((clip, summary)) => {
+            let _ = app.emit("clipboard-offered", &summary);
+            if let Ok(mut offer) = app.state::<ClipboardOfferState>().offer.lock() {
+                *offer = Some((clip, summary));
+            }
+        }
+        Err(err) => crate::push_log_line(app, format!("ignored clipboard from gateway: {}", err)),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Send this machine's clipboard (`kind` "text" by default) to the gateway.
+#[tauri::command]
+pub async fn push_clipboard(
+    app: AppHandle,
+    kind: Option<String>,
+) -> Result<ClipboardSummary, String> {
+    let settings = enabled_settings(&app)?;
+    let clip = read_clipboard(&app, kind.as_deref().unwrap_or("text"))?;
+    let summary = summarize(&clip, &settings, None)?;
+    let params = serde_json::to_value(&clip).map_err(|err| err.to_string())?;
+    call(&app, "clipboard.push", params).await?;
+    Ok(summary)
+}
+
+/// Fetch the gateway's clipboard and write it here. `None` when it's empty.
+#[tauri::command]
+pub async fn pull_clipboard(app: AppHandle) -> Result<Option<ClipboardSummary>, String> {
+    let settings = enabled_settings(&app)?;
+    let reply = call(&app, "clipboard.pull", serde_json::json!({})).await?;
+    if reply.is_null() {
+        return Ok(None);
+    }
+    let clip: Clip = serde_json::from_value(reply).map_err(|err| err.to_string())?;
+    let summary = summarize(&clip, &settings, None)?;
+    write_clipboard(&app, clip)?;
+    Ok(Some(summary))
+}
+
+/// The clipboard contents the gateway last offered, if not yet handled.
+#[tauri::command]
+pub fn get_clipboard_offer(
+    offers: tauri::State<'_, ClipboardOfferState>,
+) -> Result<Option<ClipboardSummary>, String> {
+    Ok(offers
+        .offer
+        .lock()
+        .map_err(|err| err.to_string())?
+        .as_ref()
+        .map(|(_, summary)| summary.clone()))
+}
+
+/// Write the offered contents to the clipboard (`accept`) or discard them.
+#[tauri::command]
+pub fn resolve_clipboard_offer(app: AppHandle, accept: bool) -> Result<(), String> {
+    let offer = app
+        .state::<ClipboardOfferState>()
+        .offer
+        .lock()
+        .map_err(|err| err.to_string())?
+        .take();
+    match offer {
+        Some((clip, _)) if accept => write_clipboard(&app, clip),
+        Some(_) => Ok(()),
+        None => Err("no clipboard offer pending".to_string()),
+    }
+}
@@ -0,0 +1,188 @@
+// Versioned backups of node-client.json and exec-approvals.json.
+//
+// Every write first copies the file being replaced to
+// ~/.openclaw/backups/<name>.<unix-ms>.json, keeping the most recent
+// `BACKUP_KEEP` copies per file, so a bad save from the UI or a corrupted
+// write can be rolled back.
+
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+
+use crate::{AppState, ConfigChangeImpact};
+
+const BACKUP_KEEP: usize = 10;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigBackup {
+    /// Identifier passed to `rollback_config`.
+    version: String,
+    /// "node-client.json" or "exec-approvals.json" (or the `--config` file).
+    file: String,
+    created_at_ms: u64,
+    size_bytes: u64,
+}
+
+fn backups_dir() -> Result<PathBuf, String> {
+    Ok(crate::openclaw_dir()?.join("backups"))
+}
+
+fn file_stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config".to_string())
+}
+
+/// Backups of `path`, newest first.
+fn backups_of(path: &Path) -> Vec<(u64, PathBuf)> {
+    let Ok(dir) = backups_dir() else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", file_stem(path));
+    let mut backups: Vec<(u64, PathBuf)> = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    let ms = name
+                        .strip_prefix(&prefix)?
+                        .strip_suffix(".json")?
+                        .parse::<u64>()
+                        .ok()?;
+                    Some((ms, entry.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    backups.sort_by_key(|(ms, _)| std::cmp::Reverse(*ms));
+    backups
+}
+
+/// Copy the current contents of `path` into the backups directory before it
+/// is overwritten. Failures are logged, never fatal to the write itself.
+pub fn backup_before_write(path: &Path) {
+    if let Err(err) = try_backup(path) {
+        eprintln!("failed to back up {}: {}", path.display(), err);
+    }
+}
+
+fn try_backup(path: &Path) -> Result<(), String> {
+    let Ok(current) = fs::read(path) else {
+        return Ok(());
+    };
+    let existing = backups_of(path);
+    // Unchanged since the last backup (e.g. a save with no edits)
+    if let Some((_, newest)) = existing.first() {
+        if fs::read(newest).is_ok_and(|bytes| bytes == current) {
+            return Ok(());
+        }
+    }
+    let dir = backups_dir()?;
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    let backup = dir.join(format!("{}.{}.json", file_stem(path), crate::now_ms()));
+    fs::write(&backup, current).map_err(|err| err.to_string())?;
+    // Backups may hold gateway credentials or the exec-host token
+    crate::restrict_file_permissions(&backup);
+    for (_, stale) in existing.iter().skip(BACKUP_KEEP - 1) {
+        let _ = fs::remove_file(stale);
+    }
+    Ok(())
+}
+
+fn list_backups(path: &Path) -> Vec<ConfigBackup> {
+    let file = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    backups_of(path)
+        .into_iter()
+        .map(|(created_at_ms, backup)| ConfigBackup {
+            version: backup
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            file: file.clone(),
+            created_at_ms,
+            size_bytes: fs::metadata(&backup).map(|meta| meta.len()).unwrap_or(0),
+        })
+        .collect()
+}
+
+/// Replace `target` with `backup`, backing up the current file first so the
+/// rollback itself can be undone.
+fn restore(target: &Path, backup: &Path) -> Result<(), String> {
+    backup_before_write(target);
+    let tmp_path = target.with_extension("json.tmp");
+    fs::copy(backup, &tmp_path).map_err(|err| err.to_string())?;
+    fs::rename(&tmp_path, target).map_err(|err| err.to_string())?;
+    crate::restrict_file_permissions(target);
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Available backups of both files, newest first.
+#[tauri::command]
+pub fn list_config_backups() -> Result<Vec<ConfigBackup>, String> {
+    let mut backups = list_backups(&crate::config_path()?);
+    backups.extend(list_backups(&crate::exec_approvals_path()?));
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at_ms));
+    Ok(backups)
+}
+
+/// Restore a backup listed by `list_config_backups`. Returns the resulting
+/// config change when node-client.json was rolled back.
+#[tauri::command]
+pub fn rollback_config(
+    app: AppHandle,
+    version: String,
+) -> Result<Option<ConfigChangeImpact>, String> {
+    let config_path = crate::config_path()?;
+    let approvals_path = crate::exec_approvals_path()?;
+    for target in [&config_path, &approvals_path] {
+        let Some((_, backup)) = backups_of(target).into_iter().find(|(_, backup)| {
+            backup
+                .file_name()
+                .is_some_and(|name| name == version.as_str())
+        }) else {
+            continue;
+        };
+
+        if target == &config_path {
+            // Parse first so a broken backup never replaces a working file
+            let raw = fs::read_to_string(&backup).map_err(|err| err.to_string())?;
+            let config = crate::parse_config(&raw)
+                .map_err(|err| format!("backup {} is not a valid config: {}", version, err))?;
+            restore(target, &backup)?;
+            crate::push_log_line(&app, format!("rolled back node-client.json to {}", version));
+            let allow_restart = config.auto_restart_on_config_change;
+            return crate::apply_config(&app, config, allow_restart).map(Some);
+        }
+
+        restore(target, &backup)?;
+        // The backup carries an earlier launch's exec-host token
+        let state = app.state::<AppState>();
+        let in_keychain = state
+            .config
+            .lock()
+            .map(|c| c.exec_host_token_in_keychain)
+            .unwrap_or(false);
+        crate::merge_exec_approvals_socket(
+            target,
+            &crate::exec_host_socket_path()?,
+            &state.exec_host_token,
+            in_keychain,
+        )?;
+        crate::push_log_line(
+            &app,
+            format!("rolled back exec-approvals.json to {}", version),
+        );
+        return Ok(None);
+    }
+    Err(format!("no config backup named {}", version))
+}
@@ -0,0 +1,114 @@
+// Hot reload of node-client.json.
+//
+// Other tools (the CLI, dotfile managers, a text editor) may rewrite the
+// config while the app is running. The containing directory is watched rather
+// than the file itself because atomic saves replace the inode. Changes are
+// applied to the in-memory config immediately; anything that needs a node
+// restart is reported through `config-changed` and left for the user to
+// confirm.
+//
+// The CLI's openclaw.json is watched the same way; changes there are only
+// reported (see `openclaw_sync`), never applied without the user.
+
+use notify::{RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+use tauri::AppHandle;
+
+/// Editors often write a file in several steps; wait for the burst to end.
+const DEBOUNCE_MS: u64 = 500;
+
+fn reload(app: &AppHandle, path: &std::path::Path) {
+    // The file is about to be overwritten with in-memory changes; reloading
+    // it now would revert them first
+    if crate::config_save_pending(app) {
+        return;
+    }
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        // Deleted or mid-rename; the next event will pick up the new file
+        Err(_) => return,
+    };
+    let config = match crate::parse_config(&raw) {
+        Ok(config) => config,
+        Err(err) => {
+            crate::push_log_line(app, format!("ignoring invalid {}: {}", path.display(), err));
+            return;
+        }
+    };
+    // Our own saves land here too; they match the in-memory config and
+    // produce an empty diff
+    match crate::apply_config(app, config, false) {
+        Ok(impact) if !impact.changed_fields.is_empty() => crate::push_log_line(
+            app,
+            format!(
+                "reloaded {} after external edit ({})",
+                path.display(),
+                impact.changed_fields.join(", ")
+            ),
+        ),
+        Ok(_) => {}
+        Err(err) => crate::push_log_line(app, format!("config reload failed: {}", err)),
+    }
+}
+
+/// Watched paths the event refers to.
+fn touched(event: &notify::Result<notify::Event>, watched: &[&PathBuf]) -> HashSet<PathBuf> {
+    match event {
+        Ok(event) if !event.kind.is_access() => event
+            .paths
+            .iter()
+            .filter(|p| watched.contains(p))
+            .cloned()
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
+/// Start watching node-client.json and openclaw.json on a background thread.
+pub fn watch_config_file(app: AppHandle) -> Result<(), String> {
+    let path = crate::config_path()?;
+    let openclaw_path = crate::openclaw_sync::openclaw_config_path()?;
+    let (tx, rx) = mpsc::channel::<notify::Result<notify::Event>>();
+    let mut watcher = notify::recommended_watcher(tx).map_err(|err| err.to_string())?;
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for file in [&path, &openclaw_path] {
+        let dir = file
+            .parent()
+            .map(|dir| dir.to_path_buf())
+            .ok_or_else(|| format!("{} has no parent directory", file.display()))?;
+        if dirs.contains(&dir) {
+            continue;
+        }
+        fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .map_err(|err| err.to_string())?;
+        dirs.push(dir);
+    }
+
+    std::thread::spawn(move || {
+        // Keep the watcher alive for the lifetime of the thread
+        let _watcher = watcher;
+        let watched = [&path, &openclaw_path];
+        while let Ok(event) = rx.recv() {
+            let mut changed = touched(&event, &watched);
+            if changed.is_empty() {
+                continue;
+            }
+            while let Ok(event) = rx.recv_timeout(Duration::from_millis(DEBOUNCE_MS)) {
+                changed.extend(touched(&event, &watched));
+            }
+            if changed.contains(&path) {
+                reload(&app, &path);
+            }
+            if changed.contains(&openclaw_path) {
+                crate::openclaw_sync::notify_openclaw_config_changed(&app);
+            }
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,328 @@
+// Companion CLI control mode.
+//
+// `openclaw-node-client status|start|stop|restart|approvals|approve|deny`
+// drives the instance that is already running instead of opening a second
+// window. The command is sent as one JSON line over the exec-host socket
+// (named pipe on Windows):
+//
+//   {"type": "control", "token": "...", "command": "approve", "args": ["<id>"]}
+//
+// and answered with {"type": "control-res", "ok": true, "payload": ...}.
+//
+// Control commands can approve exec requests, so they are authenticated with
+// their own per-run token in `~/.openclaw/node-client-control.token` (0600),
+// never with the exec-host token: that one sits in exec-approvals.json where
+// the node host reads it, and would let the node host approve itself.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use crate::gateway::GatewayState;
+use crate::AppState;
+
+/// Subcommands accepted on the command line.
+pub const COMMANDS: &[&str] = &[
+    "status",
+    "start",
+    "stop",
+    "restart",
+    "approvals",
+    "approve",
+    "deny",
+];
+
+const TOKEN_FILE: &str = "node-client-control.token";
+
+#[cfg(not(target_os = "windows"))]
+const RESPONSE_TIMEOUT_SECS: u64 = 60;
+
+#[derive(Debug, Clone)]
+pub struct ControlCommand {
+    pub command: String,
+    pub args: Vec<String>,
+    /// Print the raw JSON payload instead of a summary.
+    pub json: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ControlRequest {
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    token: Option<String>,
+    command: String,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ControlResponse {
+    #[serde(rename = "type")]
+    msg_type: String,
+    ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    payload: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn response(result: Result<Value, String>) -> String {
+    let (ok, payload, error) = match result {
+        Ok(payload) => (true, Some(payload), None),
+        Err(err) => (false, None, Some(err)),
+    };
+    serde_json::to_string(&ControlResponse {
+        msg_type: "control-res".to_string(),
+        ok,
+        payload,
+        error,
+    })
+    .unwrap_or_default()
+}
+
+fn token_path() -> Result<PathBuf, String> {
+    Ok(crate::openclaw_dir()?.join(TOKEN_FILE))
+}
+
+// ---------------------------------------------------------------------------
+// Server side (running app)
+// ---------------------------------------------------------------------------
+
+/// Write this run's control token where only the desktop user can read it.
+pub fn write_token(token: &str) -> Result<(), String> {
+    let path = token_path()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        // `mode` only applies to new files; tighten one left by an older run
+        if path.exists() {
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|err| format!("{}: {}", path.display(), err))?;
+        }
+    }
+    let mut file = options
+        .open(&path)
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+    file.write_all(token.as_bytes())
+        .map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+/// Remove the control token on exit, alongside the socket registration.
+pub fn remove_token() {
+    if let Ok(path) = token_path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn status_payload(app: &AppHandle) -> Result<Value, String> {
+    let status = crate::get_status(app.state::<AppState>())?;
+    let pending = app.state::<AppState>().pending_approvals.borrow().len();
+    Ok(json!({
+        "node": status.status,
+        "running": status.running,
+        "gateway": app.state::<Arc<GatewayState>>().get_status().state,
+        "gatewayUrl": status.gateway_url,
+        "nodeId": status.node_id,
+        "pendingApprovals": pending,
+        "lastError": status.last_error,
+    }))
+}
+
+async fn node_action(
+    app: &AppHandle,
+    action: fn(&AppHandle) -> Result<(), String>,
+) -> Result<Value, String> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || action(&app))
+        .await
+        .map_err(|err| err.to_string())??;
+    Ok(json!({}))
+}
+
+/// Handle a "control" line from the socket server.
+pub async fn handle(request: ControlRequest, app: &AppHandle) -> String {
+    let expected = app.state::<AppState>().control_token.clone();
+    let presented = request.token.as_deref().unwrap_or_default();
+    if !crate::constant_time_eq(presented.as_bytes(), expected.as_bytes()) {
+        return response(Err("invalid token".to_string()));
+    }
+    let result = match (request.command.as_str(), request.args.as_slice()) {
+        ("status", []) => status_payload(app),
+        ("start", []) => node_action(app, crate::start_node_internal).await,
+        ("stop", []) => node_action(app, crate::stop_node_internal).await,
+        ("restart", []) => node_action(app, crate::restart_node_internal).await,
+        ("approvals", []) => {
+            crate::get_pending_approvals(app.state::<AppState>()).map(|approvals| json!(approvals))
+        }
+        ("approve", [id]) => {
+            crate::send_approval_decision(app, id, "allow-once").map(|_| json!({}))
+        }
+        ("approve", [id, flag]) if flag == "--always" => {
+            crate::send_approval_decision(app, id, "allow-always").map(|_| json!({}))
+        }
+        ("deny", [id]) => crate::send_approval_decision(app, id, "deny").map(|_| json!({})),
+        (command, _) => Err(format!("invalid control command: {}", command)),
+    };
+    response(result)
+}
+
+// ---------------------------------------------------------------------------
+// Client side (`openclaw-node-client <command>`)
+// ---------------------------------------------------------------------------
+
+/// Socket path of the running instance, from exec-approvals.json, and its
+/// control token.
+fn running_instance() -> Result<(String, String), String> {
+    let path = crate::exec_approvals_path()?;
+    let raw = std::fs::read_to_string(&path)
+        .map_err(|_| "the node client is not running (no exec-approvals.json)".to_string())?;
+    let file: crate::ExecApprovalsFile =
+        serde_json::from_str(&raw).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let socket = file
+        .socket
+        .ok_or_else(|| "the node client is not running (no control socket)".to_string())?;
+    let token = std::fs::read_to_string(token_path()?)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+        .ok_or_else(|| "the node client is not running (no control token)".to_string())?;
+    let socket_path = match socket.path.filter(|path| !path.is_empty()) {
+        Some(path) => path,
+        None => crate::exec_host_socket_path()?,
+    };
+    Ok((socket_path, token))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn exchange(socket_path: &str, line: &str) -> Result<String, String> {
+    use std::os::unix::net::UnixStream;
+    let mut stream = UnixStream::connect(socket_path)
+        .map_err(|err| format!("the node client is not running ({}: {})", socket_path, err))?;
+    let _ = stream.set_read_timeout(Some(std::time::Duration::from_secs(RESPONSE_TIMEOUT_SECS)));
+    stream
+        .write_all(format!("{}\n", line).as_bytes())
+        .map_err(|err| err.to_string())?;
+    let mut reply = String::new();
+    BufReader::new(stream)
+        .read_line(&mut reply)
+        .map_err(|err| format!("no reply from the node client: {}", err))?;
+    Ok(reply)
+}
+
+#[cfg(target_os = "windows")]
+fn exchange(socket_path: &str, line: &str) -> Result<String, String> {
+    let mut pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(socket_path)
+        .map_err(|err| format!("the node client is not running ({}: {})", socket_path, err))?;
+    pipe.write_all(format!("{}\n", line).as_bytes())
+        .map_err(|err| err.to_string())?;
+    let mut reply = String::new();
+    BufReader::new(pipe)
+        .read_line(&mut reply)
+        .map_err(|err| format!("no reply from the node client: {}", err))?;
+    Ok(reply)
+}
+
+fn summarize(command: &str, payload: &Value) -> String {
+    match command {
+        "status" => {
+            let field = |key: &str| match payload.get(key) {
+                Some(Value::String(s)) => s.clone(),
+                Some(Value::Null) | None => "-".to_string(),
+                Some(other) => other.to_string(),
+            };
+            let mut lines = vec![
+                format!("node:              {}", field("node")),
+                format!(
+                    "gateway:           {} ({})",
+                    field("gateway"),
+                    field("gatewayUrl")
+                ),
+                format!("node id:           {}", field("nodeId")),
+                format!("pending approvals: {}", field("pendingApprovals")),
+            ];
+            if payload.get("lastError").is_some_and(|v| !v.is_null()) {
+                lines.push(format!("last error:        {}", field("lastError")));
+            }
+            lines.join("\n")
+        }
+        "approvals" => {
+            let approvals = payload.as_array().cloned().unwrap_or_default();
+            if approvals.is_empty() {
+                return "no pending approvals".to_string();
+            }
+            approvals
+                .iter()
+                .map(|approval| {
+                    let id = approval.get("id").and_then(Value::as_str).unwrap_or("?");
+                    let command = approval
+                        .get("rawCommand")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                        .unwrap_or_else(|| {
+                            approval
+                                .get("argv")
+                                .and_then(Value::as_array)
+                                .map(|argv| {
+                                    argv.iter()
+                                        .filter_map(Value::as_str)
+                                        .collect::<Vec<_>>()
+                                        .join(" ")
+                                })
+                                .unwrap_or_default()
+                        });
+                    format!("{}  {}", id, command)
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        _ => "ok".to_string(),
+    }
+}
+
+/// Send `command` to the running instance. Returns the text to print and the
+/// exit code.
+pub fn run(command: &ControlCommand) -> (String, i32) {
+    let result = running_instance().and_then(|(socket_path, token)| {
+        let request = serde_json::to_string(&ControlRequest {
+            msg_type: "control".to_string(),
+            token: Some(token),
+            command: command.command.clone(),
+            args: command.args.clone(),
+        })
+        .map_err(|err| err.to_string())?;
+        let reply = exchange(&socket_path, &request)?;
+        serde_json::from_str::<ControlResponse>(reply.trim())
+            .map_err(|_| format!("unexpected reply from the node client: {}", reply.trim()))
+    });
+    match result {
+        Ok(reply) if reply.ok => {
+            let payload = reply.payload.unwrap_or(Value::Null);
+            let text = if command.json {
+                serde_json::to_string_pretty(&payload).unwrap_or_default()
+            } else {
+                summarize(&command.command, &payload)
+            };
+            (text, 0)
+        }
+        Ok(reply) => (
+            format!(
+                "error: {}",
+                reply.error.unwrap_or_else(|| "request failed".to_string())
+            ),
+            1,
+        ),
+        Err(err) => (format!("error: {}", err), 1),
+    }
+}
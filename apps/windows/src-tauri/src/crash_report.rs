@@ -0,0 +1,350 @@
+// Crash capture for the app itself.
+//
+// "The tray icon just disappeared" reports are impossible to act on without
+// evidence. Crash files are written to `<app data dir>/crashes`:
+//
+//   panic-<ms>.txt          Rust panic message, location and backtrace
+//   crash-<ms>.dmp          minidump of an unhandled native exception (Windows)
+//   unclean-exit-<ms>.json  a previous run that never reached a clean exit
+//                           (macOS/Linux, where no minidump can be written)
+//
+// `list_crash_reports` indexes them for the UI. With
+// `crash_report_upload_url` set (opt-in), reports not yet sent are POSTed
+// there on the next launch.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Manager};
+
+use crate::AppState;
+
+const UPLOADED_SUFFIX: &str = ".uploaded";
+const SUMMARY_MAX_CHARS: usize = 200;
+
+static CRASH_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// `<app data dir>/crashes`, resolved without an `AppHandle` so the hooks
+/// can be installed before Tauri starts.
+fn crash_dir() -> Result<PathBuf, String> {
+    if let Some(dir) = CRASH_DIR.get() {
+        return Ok(dir.clone());
+    }
+    let base = directories::BaseDirs::new().ok_or("unable to resolve user directories")?;
+    Ok(base
+        .data_dir()
+        .join(crate::credentials::KEYCHAIN_SERVICE)
+        .join("crashes"))
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    file: String,
+    /// "panic" | "minidump" | "unclean-exit"
+    kind: &'static str,
+    created_at_ms: u64,
+    size_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<String>,
+    uploaded: bool,
+}
+
+/// Marker left by a running instance; see `unclean-exit` above.
+#[cfg(not(target_os = "windows"))]
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Session {
+    pid: u32,
+    started_at_ms: u64,
+    version: String,
+}
+
+fn write_panic_report(info: &std::panic::PanicHookInfo<'_>) {
+    let Ok(dir) = crash_dir() else {
+        return;
+    };
+    let thread = std::thread::current();
+    let report = format!(
+        "openclaw-node-client {} ({} {})\nthread '{}' {}\n\n{}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        thread.name().unwrap_or("<unnamed>"),
+        info,
+        std::backtrace::Backtrace::force_capture()
+    );
+    let _ = fs::create_dir_all(&dir);
+    let _ = fs::write(dir.join(format!("panic-{}.txt", crate::now_ms())), report);
+}
+
+#[cfg(target_os = "windows")]
+unsafe extern "system" fn write_minidump(
+    info: *const windows_sys::Win32::System::Diagnostics::Debug::EXCEPTION_POINTERS,
+) -> i32 {
+    use std::os::windows::io::AsRawHandle;
+    use windows_sys::Win32::System::Diagnostics::Debug::{
+        MiniDumpWithDataSegs, MiniDumpWriteDump, EXCEPTION_CONTINUE_SEARCH,
+        MINIDUMP_EXCEPTION_INFORMATION,
+    };
+    use windows_sys::Win32::System::Threading::{
+        GetCurrentProcess, GetCurrentProcessId, GetCurrentThreadId,
+    };
+
+    if let Some(dir) = CRASH_DIR.get() {
+        let path = dir.join(format!("crash-{}.dmp", crate::now_ms()));
+        if let Ok(file) = fs::File::create(path) {
+            let exception = MINIDUMP_EXCEPTION_INFORMATION {
+                ThreadId: GetCurrentThreadId(),
+                ExceptionPointers: info as *mut _,
+                ClientPointers: 0,
+            };
+            MiniDumpWriteDump(
+                GetCurrentProcess(),
+                GetCurrentProcessId(),
+                file.as_raw_handle() as _,
+                MiniDumpWithDataSegs,
+                &exception,
+                std::ptr::null(),
+                std::ptr::null(),
+            );
+        }
+    }
+    // Let Windows Error Reporting run as usual
+    EXCEPTION_CONTINUE_SEARCH
+}
+
+#[cfg(not(target_os = "windows"))]
+fn process_alive(pid: u32) -> bool {
+    // Signal 0 only checks that the process exists; EPERM means it does
+    unsafe {
+        libc::kill(pid as libc::pid_t, 0) == 0
+            || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn session_path(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("session-{}.json", pid))
+}
+
+/// Turn markers of dead instances into unclean-exit reports, then leave one
+/// for this run.
+#[cfg(not(target_os = "windows"))]
+fn track_session(dir: &Path) {
+    let own_pid = std::process::id();
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if !name.starts_with("session-") {
+                continue;
+            }
+            let session = fs::read_to_string(&path)
+                .ok()
+                .and_then(|raw| serde_json::from_str::<Session>(&raw).ok());
+            match session {
+                Some(session) if session.pid == own_pid || process_alive(session.pid) => {}
+                Some(session) => {
+                    let report = dir.join(format!("unclean-exit-{}.json", session.started_at_ms));
+                    let _ = fs::rename(&path, report);
+                }
+                None => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+    }
+    let session = Session {
+        pid: own_pid,
+        started_at_ms: crate::now_ms(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    if let Ok(json) = serde_json::to_string(&session) {
+        let _ = fs::write(session_path(dir, own_pid), json);
+    }
+}
+
+/// Install the panic hook (and the minidump writer on Windows). Call once,
+/// early in `main`.
+pub fn install() {
+    let Ok(dir) = crash_dir() else {
+        return;
+    };
+    if fs::create_dir_all(&dir).is_err() {
+        return;
+    }
+    let _ = CRASH_DIR.set(dir.clone());
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        write_panic_report(info);
+        previous(info);
+    }));
+
+    #[cfg(target_os = "windows")]
+    unsafe {
+        windows_sys::Win32::System::Diagnostics::Debug::SetUnhandledExceptionFilter(Some(
+            write_minidump,
+        ));
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    track_session(&dir);
+}
+
+/// Record that this run ended cleanly.
+pub fn mark_clean_exit() {
+    #[cfg(not(target_os = "windows"))]
+    if let Some(dir) = CRASH_DIR.get() {
+        let _ = fs::remove_file(session_path(dir, std::process::id()));
+    }
+}
+
+fn report_kind(name: &str) -> Option<&'static str> {
+    if name.starts_with("panic-") && name.ends_with(".txt") {
+        Some("panic")
+    } else if name.starts_with("crash-") && name.ends_with(".dmp") {
+        Some("minidump")
+    } else if name.starts_with("unclean-exit-") && name.ends_with(".json") {
+        Some("unclean-exit")
+    } else {
+        None
+    }
+}
+
+fn summarize(path: &Path, kind: &str) -> Option<String> {
+    let text = match kind {
+        // Second line: "thread '<name>' panicked at <location>:"
+        "panic" => fs::read_to_string(path)
+            .ok()?
+            .lines()
+            .skip(1)
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(" "),
+        "unclean-exit" => {
+            let raw = fs::read_to_string(path).ok()?;
+            let session: serde_json::Value = serde_json::from_str(&raw).ok()?;
+            format!(
+                "version {} (pid {}) exited without shutting down",
+                session["version"].as_str().unwrap_or("?"),
+                session["pid"]
+            )
+        }
+        _ => return None,
+    };
+    Some(text.chars().take(SUMMARY_MAX_CHARS).collect())
+}
+
+fn list_reports() -> Result<Vec<(PathBuf, CrashReport)>, String> {
+    let dir = crash_dir()?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut reports: Vec<(PathBuf, CrashReport)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let kind = report_kind(&name)?;
+            let path = entry.path();
+            let meta = entry.metadata().ok()?;
+            let created_at_ms = name
+                .rsplit('-')
+                .next()
+                .and_then(|tail| tail.split('.').next())
+                .and_then(|ms| ms.parse().ok())
+                .unwrap_or(0);
+            let report = CrashReport {
+                uploaded: dir.join(format!("{}{}", name, UPLOADED_SUFFIX)).exists(),
+                summary: summarize(&path, kind),
+                file: name,
+                kind,
+                created_at_ms,
+                size_bytes: meta.len(),
+            };
+            Some((path, report))
+        })
+        .collect();
+    reports.sort_by_key(|(_, report)| std::cmp::Reverse(report.created_at_ms));
+    Ok(reports)
+}
+
+/// POST reports that haven't been sent yet to `crash_report_upload_url`.
+pub async fn upload_pending(app: AppHandle) {
+    let url = app
+        .state::<AppState>()
+        .config
+        .lock()
+        .ok()
+        .and_then(|config| crate::non_empty(&config.crash_report_upload_url).map(str::to_string));
+    let Some(url) = url else {
+        return;
+    };
+    let Ok(reports) = list_reports() else {
+        return;
+    };
+    let client = reqwest::Client::new();
+    for (path, report) in reports.into_iter().filter(|(_, report)| !report.uploaded) {
+        let Ok(body) = fs::read(&path) else {
+            continue;
+        };
+        let sent = client
+            .post(&url)
+            .header("Content-Type", "application/octet-stream")
+            .header("X-OpenClaw-Crash-Kind", report.kind)
+            .header("X-OpenClaw-Crash-File", &report.file)
+            .header("X-OpenClaw-Version", env!("CARGO_PKG_VERSION"))
+            .header("X-OpenClaw-Platform", std::env::consts::OS)
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        match sent {
+            Ok(_) => {
+                let marker = path.with_file_name(format!("{}{}", report.file, UPLOADED_SUFFIX));
+                let _ = fs::write(marker, "");
+                crate::push_log_line(&app, format!("uploaded crash report {}", report.file));
+            }
+            Err(err) => {
+                crate::push_log_line(
+                    &app,
+                    format!("crash report upload failed ({}): {}", report.file, err),
+                );
+                return;
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Crash reports on disk, newest first.
+#[tauri::command]
+pub fn list_crash_reports() -> Result<Vec<CrashReport>, String> {
+    Ok(list_reports()?
+        .into_iter()
+        .map(|(_, report)| report)
+        .collect())
+}
+
+/// Delete every crash report (and upload marker).
+#[tauri::command]
+pub fn clear_crash_reports() -> Result<(), String> {
+    for (path, report) in list_reports()? {
+        fs::remove_file(&path).map_err(|err| err.to_string())?;
+        let _ = fs::remove_file(path.with_file_name(format!("{}{}", report.file, UPLOADED_SUFFIX)));
+    }
+    Ok(())
+}
+
+/// Open the crash report folder in the system file manager.
+#[tauri::command]
+pub fn open_crash_dir() -> Result<(), String> {
+    let dir = crash_dir()?;
+    fs::create_dir_all(&dir).map_err(|err| err.to_string())?;
+    crate::open_in_file_manager(&dir)
+}
@@ -0,0 +1,389 @@
+// OS credential store for gateway secrets.
+//
+// The gateway token and password live in the platform keychain (macOS
+// Keychain, Windows Credential Manager, Secret Service on Linux) instead of
+// node-client.json. The file keeps a `keychainRef` marker naming the keychain
+// service the entries are stored under. Where no store is usable (headless
+// Linux without a D-Bus session, locked-down hosts) the secrets stay in the
+// file as before.
+//
+// With `encryptAtRest` the whole file is additionally sealed with a random
+// key held in the same store, so copies in home-directory backups are
+// unreadable on another machine or account.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use keyring::Entry;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+use crate::NodeClientConfig;
+
+/// Keychain service name; matches the app bundle identifier.
+pub const KEYCHAIN_SERVICE: &str = "ai.openclaw.nodeclient";
+const TOKEN_ACCOUNT: &str = "gateway-token";
+const PASSWORD_ACCOUNT: &str = "gateway-password";
+const CONFIG_KEY_ACCOUNT: &str = "config-encryption-key";
+const EXEC_HOST_TOKEN_ACCOUNT: &str = "exec-host-token";
+const KEYCHAIN_REF_SCHEME: &str = "keychain:";
+const SEALED_CONFIG_FORMAT: &str = "openclaw-node-client-sealed/v1";
+
+/// Cheap check for a reachable credential store, so headless Linux doesn't
+/// wait on a D-Bus connection that can never succeed.
+fn keychain_available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("DBUS_SESSION_BUS_ADDRESS").is_some() {
+            return true;
+        }
+        std::env::var_os("XDG_RUNTIME_DIR")
+            .map(|dir| std::path::Path::new(&dir).join("bus").exists())
+            .unwrap_or(false)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        true
+    }
+}
+
+fn read_secret(service: &str, account: &str) -> Result<Option<String>, String> {
+    let entry = Entry::new(service, account).map_err(|err| err.to_string())?;
+    match entry.get_password() {
+        Ok(secret) => Ok(Some(secret)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+fn write_secret(service: &str, account: &str, value: Option<&str>) -> Result<(), String> {
+    let entry = Entry::new(service, account).map_err(|err| err.to_string())?;
+    match value.filter(|v| !v.is_empty()) {
+        Some(secret) => entry.set_password(secret).map_err(|err| err.to_string()),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(err) => Err(err.to_string()),
+        },
+    }
+}
+
+/// Fill in the gateway secrets for a config read from disk.
+pub fn load_secrets(config: &mut NodeClientConfig) {
+    let Some(service) = config.keychain_ref.clone() else {
+        return;
+    };
+    let loaded = read_secret(&service, TOKEN_ACCOUNT).and_then(|token| {
+        let password = read_secret(&service, PASSWORD_ACCOUNT)?;
+        Ok((token, password))
+    });
+    match loaded {
+        Ok((token, password)) => {
+            // Values hand-edited into the file still win over a missing entry
+            config.gateway_token = token.or(config.gateway_token.take());
+            config.gateway_password = password.or(config.gateway_password.take());
+        }
+        Err(err) => eprintln!(
+            "failed to read gateway credentials from keychain {}: {}",
+            service, err
+        ),
+    }
+}
+
+/// Move the gateway secrets into the keychain and return the copy of
+/// `config` to write to disk. Falls back to keeping them in the file when
+/// the keychain can't be written.
+pub fn prepare_for_disk(config: &NodeClientConfig) -> NodeClientConfig {
+    let mut on_disk = config.clone();
+    let has_secrets = config.gateway_token.is_some() || config.gateway_password.is_some();
+    if (!has_secrets && config.keychain_ref.is_none()) || !keychain_available() {
+        on_disk.keychain_ref = None;
+        return on_disk;
+    }
+
+    let service = config
+        .keychain_ref
+        .clone()
+        .unwrap_or_else(|| KEYCHAIN_SERVICE.to_string());
+    let stored =
+        write_secret(&service, TOKEN_ACCOUNT, config.gateway_token.as_deref()).and_then(|_| {
+            write_secret(
+                &service,
+                PASSWORD_ACCOUNT,
+                config.gateway_password.as_deref(),
+            )
+        });
+    match stored {
+        Ok(()) => {
+            on_disk.gateway_token = None;
+            on_disk.gateway_password = None;
+            on_disk.keychain_ref = has_secrets.then_some(service);
+        }
+        Err(err) => {
+            eprintln!(
+                "keychain unavailable ({}); storing gateway credentials in node-client.json",
+                err
+            );
+            on_disk.keychain_ref = None;
+        }
+    }
+    on_disk
+}
+
+// ---------------------------------------------------------------------------
+// exec-host token
+// ---------------------------------------------------------------------------
+
+/// Store the per-run exec-host token in the keychain and return the
+/// `tokenRef` written to exec-approvals.json in its place.
+pub fn store_exec_host_token(token: &str) -> Result<String, String> {
+    if !keychain_available() {
+        return Err("no credential store available".to_string());
+    }
+    write_secret(KEYCHAIN_SERVICE, EXEC_HOST_TOKEN_ACCOUNT, Some(token))?;
+    Ok(format!(
+        "{}{}/{}",
+        KEYCHAIN_REF_SCHEME, KEYCHAIN_SERVICE, EXEC_HOST_TOKEN_ACCOUNT
+    ))
+}
+
+/// Remove the stored exec-host token once the socket is unregistered.
+pub fn clear_exec_host_token() {
+    if keychain_available() {
+        let _ = write_secret(KEYCHAIN_SERVICE, EXEC_HOST_TOKEN_ACCOUNT, None);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// At-rest encryption of node-client.json
+// ---------------------------------------------------------------------------
+
+#[derive(Serialize, Deserialize)]
+struct SealedConfig {
+    sealed: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// The config encryption key, generated and stored on first use when
+/// `create` is set.
+fn config_key(create: bool) -> Result<Key, String> {
+    if let Some(encoded) = read_secret(KEYCHAIN_SERVICE, CONFIG_KEY_ACCOUNT)? {
+        let bytes = hex::decode(encoded.trim()).map_err(|err| err.to_string())?;
+        if bytes.len() != 32 {
+            return Err("config encryption key in keychain is malformed".to_string());
+        }
+        return Ok(*Key::from_slice(&bytes));
+    }
+    if !create {
+        return Err("config encryption key is missing from the keychain".to_string());
+    }
+    let mut key = [0u8; 32];
+    OsRng.fill_bytes(&mut key);
+    write_secret(
+        KEYCHAIN_SERVICE,
+        CONFIG_KEY_ACCOUNT,
+        Some(&hex::encode(key)),
+    )?;
+    Ok(Key::from(key))
+}
+
+/// Encrypt serialized config into the sealed envelope written to disk.
+pub fn seal_config(payload: &str) -> Result<String, String> {
+    if !keychain_available() {
+        return Err("no OS keychain available to hold the encryption key".to_string());
+    }
+    let key = config_key(true)?;
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+    let ciphertext = ChaCha20Poly1305::new(&key)
+        .encrypt(Nonce::from_slice(&nonce), payload.as_bytes())
+        .map_err(|_| "failed to encrypt config".to_string())?;
+    let sealed = SealedConfig {
+        sealed: SEALED_CONFIG_FORMAT.to_string(),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    };
+    serde_json::to_string_pretty(&sealed).map_err(|err| err.to_string())
+}
+
+/// Decrypt a sealed config file. Returns `Ok(None)` for plain JSON.
+pub fn unseal_config(raw: &str) -> Result<Option<String>, String> {
+    let Ok(sealed) = serde_json::from_str::<SealedConfig>(raw) else {
+        return Ok(None);
+    };
+    if sealed.sealed != SEALED_CONFIG_FORMAT {
+        return Err(format!(
+            "unsupported sealed config format {}",
+            sealed.sealed
+        ));
+    }
+    let decode = |value: &str| STANDARD.decode(value).map_err(|err| err.to_string());
+    let nonce = decode(&sealed.nonce)?;
+    let ciphertext = decode(&sealed.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err("sealed config has a malformed nonce".to_string());
+    }
+    let plaintext = ChaCha20Poly1305::new(&config_key(false)?)
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "failed to decrypt config (key does not match)".to_string())?;
+    String::from_utf8(plaintext)
+        .map(Some)
+        .map_err(|err| err.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Masking secrets sent to the webview
+// ---------------------------------------------------------------------------
+
+/// Stands in for a stored secret in configs handed to the webview. Configs
+/// coming back with it keep the stored value.
+pub const SECRET_PLACEHOLDER: &str = "********";
+
+/// A config field that holds a credential: its camelCase key (as serialized)
+/// and an accessor for it.
+pub struct SecretField {
+    pub key: &'static str,
+    pub get: fn(&mut NodeClientConfig) -> &mut Option<String>,
+}
+
+/// Every credential field. Masking, restoring, revealing, log redaction and
+/// settings bundles all work from this list.
+pub const SECRET_FIELDS: &[SecretField] = &[
+    SecretField {
+        key: "gatewayToken",
+        get: |c| &mut c.gateway_token,
+    },
+    SecretField {
+        key: "gatewayPassword",
+        get: |c| &mut c.gateway_password,
+    },
+    SecretField {
+        key: "localApiToken",
+        get: |c| &mut c.local_api_token,
+    },
+    SecretField {
+        key: "mqttPassword",
+        get: |c| &mut c.mqtt_password,
+    },
+    SecretField {
+        key: "runAsPassword",
+        get: |c| &mut c.run_as_password,
+    },
+    SecretField {
+        key: "crashReportUploadUrl",
+        get: |c| &mut c.crash_report_upload_url,
+    },
+    SecretField {
+        key: "errorReportDsn",
+        get: |c| &mut c.error_report_dsn,
+    },
+    SecretField {
+        key: "slackWebhookUrl",
+        get: |c| &mut c.slack_webhook_url,
+    },
+    SecretField {
+        key: "discordWebhookUrl",
+        get: |c| &mut c.discord_webhook_url,
+    },
+    SecretField {
+        key: "ntfyToken",
+        get: |c| &mut c.ntfy_token,
+    },
+    SecretField {
+        key: "pushoverUserKey",
+        get: |c| &mut c.pushover_user_key,
+    },
+    SecretField {
+        key: "pushoverAppToken",
+        get: |c| &mut c.pushover_app_token,
+    },
+];
+
+/// Gateway secrets waiting for the user to save, e.g. just imported from
+/// openclaw.json and shown masked in the form.
+#[derive(Debug, Clone, Default)]
+pub struct GatewaySecrets {
+    pub token: Option<String>,
+    pub password: Option<String>,
+}
+
+fn mask(secret: &Option<String>) -> Option<String> {
+    secret
+        .as_deref()
+        .filter(|s| !s.is_empty())
+        .map(|_| SECRET_PLACEHOLDER.to_string())
+}
+
+/// Copy of `config` safe to hand to the webview.
+pub fn redact_config_secrets(config: &NodeClientConfig) -> NodeClientConfig {
+    let mut redacted = config.clone();
+    for field in SECRET_FIELDS {
+        let value = (field.get)(&mut redacted);
+        *value = mask(value);
+    }
+    for value in redacted.otlp_headers.values_mut() {
+        if !value.is_empty() {
+            *value = SECRET_PLACEHOLDER.to_string();
+        }
+    }
+    redacted
+}
+
+/// Mask secret entries in a camelCase config map (change events).
+pub fn redact_secret_values(values: &mut serde_json::Map<String, serde_json::Value>) {
+    for field in SECRET_FIELDS {
+        if let Some(value) = values.get_mut(field.key) {
+            if value.as_str().is_some_and(|s| !s.is_empty()) {
+                *value = serde_json::Value::String(SECRET_PLACEHOLDER.to_string());
+            }
+        }
+    }
+    // Collector headers usually carry an API key
+    if let Some(serde_json::Value::Object(headers)) = values.get_mut("otlpHeaders") {
+        for value in headers.values_mut() {
+            if value.as_str().is_some_and(|s| !s.is_empty()) {
+                *value = serde_json::Value::String(SECRET_PLACEHOLDER.to_string());
+            }
+        }
+    }
+}
+
+/// Replace a placeholder with the real secret.
+pub fn resolve_placeholder(value: Option<String>, stored: &Option<String>) -> Option<String> {
+    match value {
+        Some(v) if v == SECRET_PLACEHOLDER => stored.clone(),
+        other => other,
+    }
+}
+
+/// Swap placeholders in a config from the webview for the staged or stored
+/// secrets.
+pub fn restore_secrets(
+    incoming: &mut NodeClientConfig,
+    stored: &NodeClientConfig,
+    staged: Option<&GatewaySecrets>,
+) {
+    let mut source = stored.clone();
+    if let Some(staged) = staged {
+        source.gateway_token = staged.token.clone();
+        source.gateway_password = staged.password.clone();
+    }
+    for field in SECRET_FIELDS {
+        let value = (field.get)(incoming).take();
+        *(field.get)(incoming) = resolve_placeholder(value, (field.get)(&mut source));
+    }
+    // A masked header the stored config no longer has is dropped
+    incoming.otlp_headers.retain(|name, value| {
+        if value != SECRET_PLACEHOLDER {
+            return true;
+        }
+        match stored.otlp_headers.get(name) {
+            Some(stored) => {
+                value.clone_from(stored);
+                true
+            }
+            None => false,
+        }
+    });
+}
@@ -0,0 +1,211 @@
+// D-Bus service on the Linux session bus.
+//
+// GNOME Shell / KDE Plasma extensions and desktop automation want to show
+// the node client's state and drive it without scraping the tray. The app
+// owns `ai.openclaw.NodeClient` and exports `/ai/openclaw/NodeClient` with
+// interface `ai.openclaw.NodeClient1`:
+//
+//   properties  Status (s), GatewayState (s), PendingApprovals (u)
+//   methods     StartNode(), StopNode(), RestartNode()
+//   signals     ApprovalPending(id s, command s), ApprovalResolved(id s, decision s)
+//
+// Property changes are announced with the standard PropertiesChanged signal.
+
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Listener, Manager};
+use tokio::sync::mpsc;
+use zbus::{fdo, interface, SignalContext};
+
+use crate::gateway::GatewayState;
+use crate::AppState;
+
+const BUS_NAME: &str = "ai.openclaw.NodeClient";
+const OBJECT_PATH: &str = "/ai/openclaw/NodeClient";
+const STATE_POLL_INTERVAL_MS: u64 = 1_000;
+
+struct NodeClient {
+    app: AppHandle,
+}
+
+fn node_status(app: &AppHandle) -> String {
+    let status = app.state::<AppState>().node_status.borrow().status.clone();
+    status.as_str().to_string()
+}
+
+fn gateway_state(app: &AppHandle) -> String {
+    app.state::<Arc<GatewayState>>().get_status().state
+}
+
+fn pending_approvals(app: &AppHandle) -> u32 {
+    app.state::<AppState>()
+        .pending_approvals
+        .borrow()
+        .len() as u32
+}
+
+/// Run a blocking node host operation off the bus executor.
+async fn node_action(
+    app: &AppHandle,
+    action: fn(&AppHandle) -> Result<(), String>,
+) -> fdo::Result<()> {
+    let app = app.clone();
+    tauri::async_runtime::spawn_blocking(move || action(&app))
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|result| result)
+        .map_err(fdo::Error::Failed)
+}
+
+#[interface(name = "ai.openclaw.NodeClient1")]
+impl NodeClient {
+    #[zbus(property)]
+    async fn status(&self) -> String {
+        node_status(&self.app)
+    }
+
+    #[zbus(property)]
+    async fn gateway_state(&self) -> String {
+        gateway_state(&self.app)
+    }
+
+    #[zbus(property)]
+    async fn pending_approvals(&self) -> u32 {
+        pending_approvals(&self.app)
+    }
+
+    async fn start_node(&self) -> fdo::Result<()> {
+        node_action(&self.app, crate::start_node_internal).await
+    }
+
+    async fn stop_node(&self) -> fdo::Result<()> {
+        node_action(&self.app, crate::stop_node_internal).await
+    }
+
+    async fn restart_node(&self) -> fdo::Result<()> {
+        node_action(&self.app, crate::restart_node_internal).await
+    }
+
+    #[zbus(signal)]
+    async fn approval_pending(
+        ctxt: &SignalContext<'_>,
+        id: &str,
+        command: &str,
+    ) -> zbus::Result<()>;
+
+    #[zbus(signal)]
+    async fn approval_resolved(
+        ctxt: &SignalContext<'_>,
+        id: &str,
+        decision: &str,
+    ) -> zbus::Result<()>;
+}
+
+/// Approval events forwarded from the Tauri event bus.
+enum ApprovalEvent {
+    Pending { id: String, command: String },
+    Resolved { id: String, decision: String },
+}
+
+fn str_field(payload: &Value, key: &str) -> String {
+    payload
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn approval_command(preview: &Value) -> String {
+    preview
+        .get("rawCommand")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .unwrap_or_else(|| {
+            preview
+                .get("argv")
+                .and_then(Value::as_array)
+                .map(|argv| {
+                    argv.iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default()
+        })
+}
+
+async fn serve(app: AppHandle) -> zbus::Result<()> {
+    let connection = zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, NodeClient { app: app.clone() })?
+        .build()
+        .await?;
+    let iface = connection
+        .object_server()
+        .interface::<_, NodeClient>(OBJECT_PATH)
+        .await?;
+    crate::push_log_line(&app, format!("D-Bus service registered as {}", BUS_NAME));
+
+    let (events_tx, mut events_rx) = mpsc::unbounded_channel::<ApprovalEvent>();
+    let pending_tx = events_tx.clone();
+    app.listen("approval-pending", move |event| {
+        let preview = serde_json::from_str::<Value>(event.payload()).unwrap_or(Value::Null);
+        let _ = pending_tx.send(ApprovalEvent::Pending {
+            id: str_field(&preview, "id"),
+            command: approval_command(&preview),
+        });
+    });
+    app.listen("approval-resolved", move |event| {
+        let payload = serde_json::from_str::<Value>(event.payload()).unwrap_or(Value::Null);
+        let _ = events_tx.send(ApprovalEvent::Resolved {
+            id: str_field(&payload, "id"),
+            decision: str_field(&payload, "decision"),
+        });
+    });
+
+    let mut tick = tokio::time::interval(Duration::from_millis(STATE_POLL_INTERVAL_MS));
+    let mut shown = (
+        node_status(&app),
+        gateway_state(&app),
+        pending_approvals(&app),
+    );
+    loop {
+        tokio::select! {
+            Some(event) = events_rx.recv() => {
+                let ctxt = iface.signal_context();
+                let _ = match event {
+                    ApprovalEvent::Pending { id, command } => {
+                        NodeClient::approval_pending(ctxt, &id, &command).await
+                    }
+                    ApprovalEvent::Resolved { id, decision } => {
+                        NodeClient::approval_resolved(ctxt, &id, &decision).await
+                    }
+                };
+            }
+            _ = tick.tick() => {
+                let next = (node_status(&app), gateway_state(&app), pending_approvals(&app));
+                let ctxt = iface.signal_context();
+                let node_client = iface.get().await;
+                if next.0 != shown.0 {
+                    let _ = node_client.status_changed(ctxt).await;
+                }
+                if next.1 != shown.1 {
+                    let _ = node_client.gateway_state_changed(ctxt).await;
+                }
+                if next.2 != shown.2 {
+                    let _ = node_client.pending_approvals_changed(ctxt).await;
+                }
+                shown = next;
+            }
+        }
+    }
+}
+
+/// Register on the session bus and serve until the app exits. Headless
+/// sessions without a bus (or a second instance) just log and skip it.
+pub async fn run_dbus(app: AppHandle) {
+    if let Err(err) = serve(app.clone()).await {
+        crate::push_log_line(&app, format!("D-Bus service unavailable: {}", err));
+    }
+}
@@ -0,0 +1,264 @@
+// Error reporting to a Sentry-compatible service.
+//
+// Handled errors that point at a broken install or environment (the node
+// host failing to spawn, gateway handshake failures, exec-host socket
+// errors) only reach the app log, which nobody reads until asked to. With
+// `error_report_dsn` set (opt-in, off by default) they are also sent as
+// events to that DSN through the envelope endpoint that Sentry, GlitchTip
+// and other compatible backends accept.
+//
+// Nothing leaves the machine unscrubbed: configured secrets and
+// credential-looking `key=value` pairs are masked and the home directory is
+// shown as `~`, in the message and in every context string. The same error
+// is sent at most once per `DEDUPE_SECS`, and at most `MAX_PER_HOUR` events
+// go out an hour.
+
+use serde_json::{json, Map, Value};
+use std::collections::{HashMap, VecDeque};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+use crate::AppState;
+
+const DEDUPE_SECS: u64 = 3600;
+const MAX_PER_HOUR: usize = 30;
+const CLIENT: &str = concat!("openclaw-node-client/", env!("CARGO_PKG_VERSION"));
+
+struct Report {
+    kind: &'static str,
+    message: String,
+    context: Value,
+}
+
+static QUEUE: OnceLock<mpsc::UnboundedSender<Report>> = OnceLock::new();
+
+/// A parsed `https://<key>@<host>/<project>` DSN.
+struct Dsn {
+    envelope_url: String,
+    key: String,
+}
+
+fn parse_dsn(dsn: &str) -> Result<Dsn, String> {
+    let url = url::Url::parse(dsn.trim()).map_err(|err| format!("invalid DSN: {}", err))?;
+    let key = url.username();
+    if key.is_empty() {
+        return Err("invalid DSN: no public key".to_string());
+    }
+    let path = url.path().trim_matches('/');
+    let (prefix, project) = match path.rsplit_once('/') {
+        Some((prefix, project)) => (format!("/{}", prefix), project),
+        None => (String::new(), path),
+    };
+    if project.is_empty() {
+        return Err("invalid DSN: no project id".to_string());
+    }
+    let host = url.host_str().ok_or("invalid DSN: no host")?;
+    let port = url
+        .port()
+        .map(|port| format!(":{}", port))
+        .unwrap_or_default();
+    Ok(Dsn {
+        envelope_url: format!(
+            "{}://{}{}{}/api/{}/envelope/",
+            url.scheme(),
+            host,
+            port,
+            prefix,
+            project
+        ),
+        key: key.to_string(),
+    })
+}
+
+struct Scrubber {
+    secrets: Vec<String>,
+    home: Option<String>,
+}
+
+impl Scrubber {
+    fn new(app: &AppHandle) -> Self {
+        let state = app.state::<AppState>();
+        let mut secrets = vec![state.exec_host_token.clone()];
+        if let Ok(config) = state.config.lock() {
+            secrets.extend(crate::config_secrets(&config));
+        }
+        Scrubber {
+            secrets,
+            home: directories::BaseDirs::new()
+                .map(|base| base.home_dir().to_string_lossy().to_string())
+                .filter(|home| home.len() > 1),
+        }
+    }
+
+    fn text(&self, text: &str) -> String {
+        let text = crate::redact_secrets(text, &self.secrets);
+        match &self.home {
+            Some(home) => text.replace(home.as_str(), "~"),
+            None => text,
+        }
+    }
+
+    fn value(&self, value: Value) -> Value {
+        match value {
+            Value::String(text) => Value::String(self.text(&text)),
+            Value::Array(items) => Value::Array(items.into_iter().map(|v| self.value(v)).collect()),
+            Value::Object(fields) => Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, self.value(value)))
+                    .collect::<Map<_, _>>(),
+            ),
+            other => other,
+        }
+    }
+}
+
+fn event(kind: &str, message: &str, context: Value) -> (String, Value) {
+    let event_id = crate::uuid_v4().replace('-', "");
+    let event = json!({
+        "event_id": event_id,
+        "timestamp": crate::now_ms() as f64 / 1000.0,
+        "platform": "native",
+        "level": "error",
+        "logger": kind,
+        "release": format!("openclaw-node-client@{}", env!("CARGO_PKG_VERSION")),
+        "message": { "formatted": message },
+        "tags": {
+            "kind": kind,
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+        },
+        "contexts": {
+            "os": { "name": std::env::consts::OS },
+        },
+        "extra": context,
+    });
+    (event_id, event)
+}
+
+async fn send(
+    client: &reqwest::Client,
+    dsn: &Dsn,
+    event_id: &str,
+    event: &Value,
+) -> Result<(), String> {
+    let body = format!(
+        "{}\n{}\n{}\n",
+        json!({ "event_id": event_id }),
+        json!({ "type": "event" }),
+        event
+    );
+    client
+        .post(&dsn.envelope_url)
+        .header("Content-Type", "application/x-sentry-envelope")
+        .header(
+            "X-Sentry-Auth",
+            format!(
+                "Sentry sentry_version=7, sentry_client={}, sentry_key={}",
+                CLIENT, dsn.key
+            ),
+        )
+        .body(body)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+fn configured_dsn(app: &AppHandle) -> Option<String> {
+    app.state::<AppState>()
+        .config
+        .lock()
+        .ok()
+        .and_then(|config| crate::non_empty(&config.error_report_dsn).map(str::to_string))
+}
+
+/// Send queued reports one at a time, applying the dedupe and hourly limits.
+async fn run_reporter(app: AppHandle, mut reports: mpsc::UnboundedReceiver<Report>) {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(15))
+        .build()
+        .unwrap_or_default();
+    let mut last_sent: HashMap<(&'static str, String), Instant> = HashMap::new();
+    let mut sent: VecDeque<Instant> = VecDeque::new();
+    while let Some(report) = reports.recv().await {
+        let Some(dsn) = configured_dsn(&app) else {
+            continue;
+        };
+        let key = (report.kind, report.message.clone());
+        if last_sent
+            .get(&key)
+            .is_some_and(|at| at.elapsed() < Duration::from_secs(DEDUPE_SECS))
+        {
+            continue;
+        }
+        while sent
+            .front()
+            .is_some_and(|at| at.elapsed() >= Duration::from_secs(3600))
+        {
+            sent.pop_front();
+        }
+        if sent.len() >= MAX_PER_HOUR {
+            continue;
+        }
+        let dsn = match parse_dsn(&dsn) {
+            Ok(dsn) => dsn,
+            Err(err) => {
+                eprintln!("error report skipped: {}", err);
+                continue;
+            }
+        };
+        let scrubber = Scrubber::new(&app);
+        let (event_id, event) = event(
+            report.kind,
+            &scrubber.text(&report.message),
+            scrubber.value(report.context),
+        );
+        last_sent.insert(key, Instant::now());
+        sent.push_back(Instant::now());
+        if let Err(err) = send(&client, &dsn, &event_id, &event).await {
+            crate::push_log_line(&app, format!("error report failed: {}", err));
+        }
+    }
+}
+
+/// Start the reporting task.
+pub fn register(app: &AppHandle) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    if QUEUE.set(tx).is_ok() {
+        tauri::async_runtime::spawn(run_reporter(app.clone(), rx));
+    }
+}
+
+/// Report a handled error of `kind` (e.g. "node.spawn") with `context`.
+/// A no-op unless a DSN is configured.
+pub fn capture(kind: &'static str, message: &str, context: Value) {
+    if let Some(queue) = QUEUE.get() {
+        let _ = queue.send(Report {
+            kind,
+            message: message.to_string(),
+            context,
+        });
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Send a test event to the configured DSN, bypassing the rate limits.
+#[tauri::command]
+pub async fn send_test_error_report(app: AppHandle) -> Result<(), String> {
+    let dsn = configured_dsn(&app).ok_or("no error reporting DSN configured")?;
+    let dsn = parse_dsn(&dsn)?;
+    let (event_id, mut event) = event(
+        "test",
+        "Test event from the OpenClaw node client",
+        json!({}),
+    );
+    event["level"] = json!("info");
+    send(&reqwest::Client::new(), &dsn, &event_id, &event).await
+}
@@ -0,0 +1,222 @@
+// Audit events in the Windows Event Log.
+//
+// With `windows_event_log` on, security-relevant actions are written to the
+// Application log under the source "OpenClaw Node Client", where enterprise
+// collectors (Windows Event Forwarding, SIEM agents) already look:
+//
+//   100  approval allowed          information
+//   101  approval denied           warning
+//   200  command executed          information
+//   300  exec policy changed       warning
+//   400  token rotated             information
+//
+// Each event carries one text string of `key: value` lines. The source is
+// registered on first use with EventCreate.exe as its message file so the
+// viewer shows that text as is; creating the registry key needs admin
+// rights once (or a GPO doing the same), and without it events are still
+// written, just with a "description cannot be found" preamble. On other
+// platforms nothing is written.
+
+use tauri::{AppHandle, Manager};
+
+use crate::{AppState, ApprovalPreview};
+
+#[cfg(target_os = "windows")]
+const SOURCE: &str = "OpenClaw Node Client";
+/// The Event Log limit is 31,839 characters per string.
+const MAX_MESSAGE_CHARS: usize = 30_000;
+
+pub enum AuditEvent<'a> {
+    ApprovalDecision {
+        preview: &'a ApprovalPreview,
+        decision: &'a str,
+    },
+    CommandExecuted {
+        argv: &'a [String],
+        cwd: Option<&'a str>,
+        /// How it was approved: "allow-once", "allow-always", "gateway", ...
+        approval: &'a str,
+    },
+    PolicyChanged {
+        change: String,
+    },
+    TokenRotated {
+        token: &'a str,
+    },
+}
+
+impl AuditEvent<'_> {
+    /// Event id, whether it's a warning, and the message.
+    fn describe(&self) -> (u32, bool, String) {
+        match self {
+            AuditEvent::ApprovalDecision { preview, decision } => {
+                let denied = *decision == "deny";
+                let command = preview
+                    .raw_command
+                    .clone()
+                    .unwrap_or_else(|| preview.argv.join(" "));
+                let mut lines = vec![
+                    format!("Decision: {}", decision),
+                    format!("Approval: {}", preview.id),
+                    format!("Command: {}", command),
+                ];
+                lines.extend(
+                    preview
+                        .cwd
+                        .as_ref()
+                        .map(|cwd| format!("Directory: {}", cwd)),
+                );
+                lines.extend(
+                    preview
+                        .agent_id
+                        .as_ref()
+                        .map(|agent| format!("Agent: {}", agent)),
+                );
+                (if denied { 101 } else { 100 }, denied, lines.join("\n"))
+            }
+            AuditEvent::CommandExecuted {
+                argv,
+                cwd,
+                approval,
+            } => {
+                let mut lines = vec![
+                    format!("Command: {}", argv.join(" ")),
+                    format!("Approval: {}", approval),
+                ];
+                lines.extend(cwd.map(|cwd| format!("Directory: {}", cwd)));
+                (200, false, lines.join("\n"))
+            }
+            AuditEvent::PolicyChanged { change } => {
+                (300, true, format!("Exec policy changed: {}", change))
+            }
+            AuditEvent::TokenRotated { token } => (400, false, format!("Token rotated: {}", token)),
+        }
+    }
+}
+
+/// Write `event` to the Event Log if enabled.
+pub fn record(app: &AppHandle, event: AuditEvent) {
+    let state = app.state::<AppState>();
+    let enabled = state
+        .config
+        .lock()
+        .map(|config| config.windows_event_log)
+        .unwrap_or(false);
+    if !enabled {
+        return;
+    }
+    let (id, warning, message) = event.describe();
+    let message = crate::redact_secrets(&message, std::slice::from_ref(&state.exec_host_token));
+    let message: String = message.chars().take(MAX_MESSAGE_CHARS).collect();
+    write(id, warning, &message);
+}
+
+#[cfg(not(target_os = "windows"))]
+fn write(_id: u32, _warning: bool, _message: &str) {}
+
+#[cfg(target_os = "windows")]
+fn wide(text: &str) -> Vec<u16> {
+    text.encode_utf16().chain(std::iter::once(0)).collect()
+}
+
+/// Create the source's registry key unless it exists. Needs admin rights.
+#[cfg(target_os = "windows")]
+fn register_source() {
+    use windows_sys::Win32::Foundation::ERROR_SUCCESS;
+    use windows_sys::Win32::System::Registry::{
+        RegCloseKey, RegCreateKeyExW, RegOpenKeyExW, RegSetValueExW, HKEY, HKEY_LOCAL_MACHINE,
+        KEY_READ, KEY_SET_VALUE, REG_DWORD, REG_EXPAND_SZ, REG_OPTION_NON_VOLATILE,
+    };
+
+    static REGISTERED: std::sync::Once = std::sync::Once::new();
+    REGISTERED.call_once(|| {
+        let path = wide(&format!(
+            "SYSTEM\\CurrentControlSet\\Services\\EventLog\\Application\\{}",
+            SOURCE
+        ));
+        let mut key: HKEY = std::ptr::null_mut();
+        unsafe {
+            if RegOpenKeyExW(HKEY_LOCAL_MACHINE, path.as_ptr(), 0, KEY_READ, &mut key)
+                == ERROR_SUCCESS
+            {
+                RegCloseKey(key);
+                return;
+            }
+            let status = RegCreateKeyExW(
+                HKEY_LOCAL_MACHINE,
+                path.as_ptr(),
+                0,
+                std::ptr::null(),
+                REG_OPTION_NON_VOLATILE,
+                KEY_SET_VALUE,
+                std::ptr::null(),
+                &mut key,
+                std::ptr::null_mut(),
+            );
+            if status != ERROR_SUCCESS {
+                eprintln!(
+                    "event log source not registered (error {}); run the app as \
+                     administrator once to register it",
+                    status
+                );
+                return;
+            }
+            let message_file = wide("%SystemRoot%\\System32\\EventCreate.exe");
+            RegSetValueExW(
+                key,
+                wide("EventMessageFile").as_ptr(),
+                0,
+                REG_EXPAND_SZ,
+                message_file.as_ptr().cast(),
+                (message_file.len() * 2) as u32,
+            );
+            // Error, warning and information
+            let types: u32 = 7;
+            RegSetValueExW(
+                key,
+                wide("TypesSupported").as_ptr(),
+                0,
+                REG_DWORD,
+                (&types as *const u32).cast(),
+                4,
+            );
+            RegCloseKey(key);
+        }
+    });
+}
+
+#[cfg(target_os = "windows")]
+fn write(id: u32, warning: bool, message: &str) {
+    use windows_sys::Win32::System::EventLog::{
+        DeregisterEventSource, RegisterEventSourceW, ReportEventW, EVENTLOG_INFORMATION_TYPE,
+        EVENTLOG_WARNING_TYPE,
+    };
+
+    register_source();
+    let source = wide(SOURCE);
+    let text = wide(message);
+    let strings = [text.as_ptr()];
+    let kind = if warning {
+        EVENTLOG_WARNING_TYPE
+    } else {
+        EVENTLOG_INFORMATION_TYPE
+    };
+    unsafe {
+        let handle = RegisterEventSourceW(std::ptr::null(), source.as_ptr());
+        if handle.is_null() {
+            return;
+        }
+        ReportEventW(
+            handle,
+            kind,
+            0,
+            id,
+            std::ptr::null_mut(),
+            1,
+            0,
+            strings.as_ptr(),
+            std::ptr::null(),
+        );
+        DeregisterEventSource(handle);
+    }
+}
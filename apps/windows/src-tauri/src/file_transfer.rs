@@ -0,0 +1,322 @@
+// File transfer over the app's gateway connection.
+//
+// `gateway_send_file` uploads a local file and `gateway_fetch_file` downloads
+// one, in base64 chunks over the same RPC channel as everything else, so
+// artifacts can move to and from this machine without another port or
+// credential. Requires a gateway that advertises both methods:
+//
+//   files.upload    {transferId, name, size, sha256, offset, data}
+//                   -> {received, complete?}
+//   files.download  {name, offset, length} -> {size, sha256, data}
+//
+// Uploads are resumable: the transfer id is derived from the file's name,
+// size and hash, and the first (empty) chunk returns how many bytes the
+// gateway already holds. Downloads resume from `<dest>.part`. Both sides
+// check the SHA-256 of the whole file before a transfer counts as done.
+// Progress is emitted as `file-transfer-progress`.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Serialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::gateway::{self, GatewayState};
+
+const UPLOAD_METHOD: &str = "files.upload";
+const DOWNLOAD_METHOD: &str = "files.download";
+/// Raw bytes per chunk; about 340 KiB once base64-encoded.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTransferResult {
+    transfer_id: String,
+    name: String,
+    /// Local file that was sent or written.
+    path: String,
+    size: u64,
+    sha256: String,
+    /// Bytes already transferred by an earlier, interrupted attempt.
+    resumed_from: u64,
+}
+
+fn emit_progress(
+    app: &AppHandle,
+    transfer_id: &str,
+    direction: &str,
+    name: &str,
+    done: u64,
+    total: u64,
+) {
+    let _ = app.emit(
+        "file-transfer-progress",
+        json!({
+            "transferId": transfer_id,
+            "direction": direction,
+            "name": name,
+            "transferred": done,
+            "total": total,
+        }),
+    );
+}
+
+fn gateway_state(app: &AppHandle, method: &str) -> Result<Arc<GatewayState>, String> {
+    let state = app.state::<Arc<GatewayState>>().inner().clone();
+    if state.get_status().state != "connected" {
+        return Err("gateway is not connected".to_string());
+    }
+    if !state.supports(method) {
+        return Err(format!(
+            "the gateway does not support file transfer ({})",
+            method
+        ));
+    }
+    Ok(state)
+}
+
+async fn call(state: &GatewayState, method: &str, params: Value) -> Result<Value, String> {
+    gateway::request(state, method.to_string(), Some(params))
+        .await?
+        .map_err(|err| format!("{} failed: {}", method, err))
+}
+
+fn u64_field(payload: &Value, key: &str) -> Result<u64, String> {
+    payload
+        .get(key)
+        .and_then(Value::as_u64)
+        .ok_or_else(|| format!("gateway reply is missing {}", key))
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buf).map_err(|err| err.to_string())?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn read_chunk(path: &Path, offset: u64) -> Result<Vec<u8>, String> {
+    let mut file = fs::File::open(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|err| err.to_string())?;
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    file.take(CHUNK_SIZE as u64)
+        .read_to_end(&mut chunk)
+        .map_err(|err| err.to_string())?;
+    Ok(chunk)
+}
+
+/// Plain file name for the gateway side: no directories, no "..".
+fn transfer_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name != "."
+        && name != ".."
+        && !name.contains(['/', '\\'])
+        && !name.contains('\0');
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(format!("invalid file name: {}", name))
+    }
+}
+
+async fn send_file(
+    app: &AppHandle,
+    path: PathBuf,
+    name: Option<String>,
+) -> Result<FileTransferResult, String> {
+    let state = gateway_state(app, UPLOAD_METHOD)?;
+    let name = match name {
+        Some(name) => transfer_name(&name)?,
+        None => transfer_name(&path.file_name().unwrap_or_default().to_string_lossy())?,
+    };
+    let size = fs::metadata(&path)
+        .map_err(|err| format!("{}: {}", path.display(), err))?
+        .len();
+    let hash_path = path.clone();
+    let sha256 = tauri::async_runtime::spawn_blocking(move || sha256_file(&hash_path))
+        .await
+        .map_err(|err| err.to_string())??;
+    // Same file and name -> same id, so a retry picks up where it stopped
+    let key = format!("{}\n{}\n{}", name, size, sha256);
+    let transfer_id = hex::encode(&Sha256::digest(key)[..16]);
+    let upload = |offset: u64, data: &[u8]| {
+        json!({
+            "transferId": transfer_id,
+            "name": name,
+            "size": size,
+            "sha256": sha256,
+            "offset": offset,
+            "data": STANDARD.encode(data),
+        })
+    };
+
+    let mut reply = call(&state, UPLOAD_METHOD, upload(0, &[])).await?;
+    let resumed_from = u64_field(&reply, "received")?;
+    let mut offset = resumed_from;
+    emit_progress(app, &transfer_id, "upload", &name, offset, size);
+    while offset < size {
+        let chunk = read_chunk(&path, offset)?;
+        if chunk.is_empty() {
+            return Err(format!("{} shrank during upload", path.display()));
+        }
+        reply = call(&state, UPLOAD_METHOD, upload(offset, &chunk)).await?;
+        let received = u64_field(&reply, "received")?;
+        if received > size {
+            return Err(format!(
+                "gateway reports {} of {} bytes received",
+                received, size
+            ));
+        }
+        // The gateway answers with its own offset; a mismatch (e.g. a lost
+        // reply) just moves the cursor to what it actually has
+        offset = received;
+        emit_progress(app, &transfer_id, "upload", &name, offset, size);
+    }
+    if reply.get("complete").and_then(Value::as_bool) != Some(true) {
+        return Err("gateway did not confirm the upload (hash mismatch?)".to_string());
+    }
+    crate::push_log_line(
+        app,
+        format!("sent {} ({} bytes) to the gateway", name, size),
+    );
+    Ok(FileTransferResult {
+        transfer_id,
+        name,
+        path: path.to_string_lossy().to_string(),
+        size,
+        sha256,
+        resumed_from,
+    })
+}
+
+async fn fetch_file(
+    app: &AppHandle,
+    name: String,
+    dest: PathBuf,
+) -> Result<FileTransferResult, String> {
+    let state = gateway_state(app, DOWNLOAD_METHOD)?;
+    let name = transfer_name(&name)?;
+    let part = {
+        let mut part = dest.clone().into_os_string();
+        part.push(".part");
+        PathBuf::from(part)
+    };
+    let resumed_from = fs::metadata(&part).map(|meta| meta.len()).unwrap_or(0);
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&part)
+        .map_err(|err| format!("{}: {}", part.display(), err))?;
+    let transfer_id = hex::encode(&Sha256::digest(format!("{}\n{}", name, dest.display()))[..16]);
+
+    let mut offset = resumed_from;
+    let (size, sha256) = loop {
+        let reply = call(
+            &state,
+            DOWNLOAD_METHOD,
+            json!({ "name": name, "offset": offset, "length": CHUNK_SIZE }),
+        )
+        .await?;
+        let size = u64_field(&reply, "size")?;
+        let sha256 = reply
+            .get("sha256")
+            .and_then(Value::as_str)
+            .ok_or_else(|| "gateway reply is missing sha256".to_string())?
+            .to_lowercase();
+        if offset > size {
+            // The remote file changed since the partial download; start over
+            drop(file);
+            let _ = fs::remove_file(&part);
+            return Err(format!(
+                "{} changed on the gateway; retry the download",
+                name
+            ));
+        }
+        let data = STANDARD
+            .decode(
+                reply
+                    .get("data")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default(),
+            )
+            .map_err(|err| format!("invalid chunk from the gateway: {}", err))?;
+        file.write_all(&data).map_err(|err| err.to_string())?;
+        offset += data.len() as u64;
+        emit_progress(app, &transfer_id, "download", &name, offset, size);
+        if offset >= size {
+            break (size, sha256);
+        }
+        if data.is_empty() {
+            return Err(format!(
+                "gateway sent no data at offset {} of {}",
+                offset, size
+            ));
+        }
+    };
+    file.flush().map_err(|err| err.to_string())?;
+    drop(file);
+
+    let hash_path = part.clone();
+    let actual = tauri::async_runtime::spawn_blocking(move || sha256_file(&hash_path))
+        .await
+        .map_err(|err| err.to_string())??;
+    if actual != sha256 || offset != size {
+        let _ = fs::remove_file(&part);
+        return Err(format!(
+            "{} failed verification (sha256 {}, expected {})",
+            name, actual, sha256
+        ));
+    }
+    fs::rename(&part, &dest).map_err(|err| format!("{}: {}", dest.display(), err))?;
+    crate::push_log_line(
+        app,
+        format!("fetched {} ({} bytes) from the gateway", name, size),
+    );
+    Ok(FileTransferResult {
+        transfer_id,
+        name,
+        path: dest.to_string_lossy().to_string(),
+        size,
+        sha256,
+        resumed_from,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Upload `path` to the gateway as `name` (default: the file's own name).
+/// Calling it again after an interruption resumes the upload.
+#[tauri::command]
+pub async fn gateway_send_file(
+    app: AppHandle,
+    path: String,
+    name: Option<String>,
+) -> Result<FileTransferResult, String> {
+    send_file(&app, PathBuf::from(path), name).await
+}
+
+/// Download `name` from the gateway to `dest`. Calling it again after an
+/// interruption resumes from `<dest>.part`.
+#[tauri::command]
+pub async fn gateway_fetch_file(
+    app: AppHandle,
+    name: String,
+    dest: String,
+) -> Result<FileTransferResult, String> {
+    fetch_file(&app, name, PathBuf::from(dest)).await
+}
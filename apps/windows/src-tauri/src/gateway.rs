@@ -14,13 +14,15 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{
     atomic::{AtomicU64, Ordering},
-    Arc, Mutex,
+    Arc,
 };
 use std::time::Duration;
 use tauri::{AppHandle, Emitter, Manager};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, watch};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
+use crate::telemetry::{Span, SpanKind};
+
 // ---------------------------------------------------------------------------
 // Wire types
 // ---------------------------------------------------------------------------
@@ -108,36 +110,31 @@ pub struct DeviceIdentity {
 // Managed gateway state
 // ---------------------------------------------------------------------------
 
+// Shared values are watch channels: read from sync commands and async tasks
+// alike without blocking the runtime, and never poisoned.
 pub struct GatewayState {
-    status: Mutex<GatewayConnectionStatus>,
+    status: watch::Sender<GatewayConnectionStatus>,
     // Sender to the background WS task for outgoing RPC calls
-    tx: Mutex<Option<mpsc::UnboundedSender<RpcRequest>>>,
+    tx: watch::Sender<Option<mpsc::UnboundedSender<RpcRequest>>>,
     // Counter for generating unique RPC request IDs
     seq: AtomicU64,
     // Connection attempt generation used to ignore stale tasks.
     connect_attempt: AtomicU64,
-}
-
-fn lock_or_recover<'a, T>(
-    mutex: &'a Mutex<T>,
-    label: &str,
-) -> std::sync::MutexGuard<'a, T> {
-    match mutex.lock() {
-        Ok(guard) => guard,
-        Err(poisoned) => {
-            eprintln!("recovering poisoned mutex: {}", label);
-            poisoned.into_inner()
-        }
-    }
+    // RPC methods advertised in the last hello-ok (`features.methods`)
+    methods: watch::Sender<Vec<String>>,
+    // Device identity as last read or written; `None` until first use
+    identity: watch::Sender<Option<DeviceIdentity>>,
 }
 
 impl GatewayState {
     pub fn new() -> Self {
         Self {
-            status: Mutex::new(GatewayConnectionStatus::default()),
-            tx: Mutex::new(None),
+            status: watch::Sender::new(GatewayConnectionStatus::default()),
+            tx: watch::Sender::new(None),
             seq: AtomicU64::new(0),
             connect_attempt: AtomicU64::new(0),
+            methods: watch::Sender::new(Vec::new()),
+            identity: watch::Sender::new(None),
         }
     }
 
@@ -155,15 +152,44 @@ impl GatewayState {
     }
 
     pub fn get_status(&self) -> GatewayConnectionStatus {
-        lock_or_recover(&self.status, "gateway.status").clone()
+        self.status.borrow().clone()
     }
 
     fn set_status(&self, status: GatewayConnectionStatus) {
-        *lock_or_recover(&self.status, "gateway.status") = status;
+        self.status.send_replace(status);
     }
 
     fn set_tx(&self, tx: Option<mpsc::UnboundedSender<RpcRequest>>) {
-        *lock_or_recover(&self.tx, "gateway.tx") = tx;
+        self.tx.send_replace(tx);
+    }
+
+    /// The device identity, read from `data_dir` (or created) on first use
+    /// and kept in memory after that.
+    pub fn device_identity(&self, data_dir: &Path) -> Result<DeviceIdentity, String> {
+        if let Some(identity) = self.identity.borrow().as_ref() {
+            return Ok(identity.clone());
+        }
+        let loaded = load_or_create_device_identity(data_dir)?;
+        // A concurrent first load may have won; keep whichever came first
+        let mut identity = loaded.clone();
+        self.identity
+            .send_modify(|cached| identity = cached.get_or_insert(loaded).clone());
+        Ok(identity)
+    }
+
+    /// Persist a changed identity (a rotated device token) and refresh the
+    /// cached copy.
+    fn update_device_identity(&self, data_dir: &Path, identity: &DeviceIdentity) {
+        save_device_identity(data_dir, identity);
+        self.identity.send_replace(Some(identity.clone()));
+    }
+
+    /// Whether the connected gateway advertised `method` in its hello-ok.
+    pub fn supports(&self, method: &str) -> bool {
+        self.methods
+            .borrow()
+            .iter()
+            .any(|name| name == method)
     }
 }
 
@@ -184,6 +210,10 @@ fn emit_disconnected_if_current(
     if !state.is_current_attempt(attempt) {
         return;
     }
+    // Only the connect / handshake failures pass an error
+    if let Some(error) = &error {
+        crate::error_report::capture("gateway.handshake", error, serde_json::json!({}));
+    }
     let _ = app.emit("gateway-disconnected", serde_json::json!({ "error": error }));
 }
 
@@ -191,7 +221,7 @@ fn emit_disconnected_if_current(
 // Device identity persistence
 // ---------------------------------------------------------------------------
 
-pub fn load_or_create_device_identity(data_dir: &Path) -> Result<DeviceIdentity, String> {
+fn load_or_create_device_identity(data_dir: &Path) -> Result<DeviceIdentity, String> {
     let identity_dir = data_dir.join("identity");
     let identity_path = identity_dir.join("node-client-device.json");
 
@@ -279,9 +309,13 @@ pub async fn run_gateway_connection(
 
     let (rpc_tx, mut rpc_rx) = mpsc::unbounded_channel::<RpcRequest>();
 
-    // Pending RPC callbacks keyed by request ID
-    let pending: Arc<Mutex<HashMap<String, oneshot::Sender<Result<Value, String>>>>> =
-        Arc::new(Mutex::new(HashMap::new()));
+    // Pending RPC callbacks keyed by request ID, owned by this task
+    let mut pending: HashMap<String, oneshot::Sender<Result<Value, String>>> = HashMap::new();
+
+    // Spans left unended by an early return below are recorded as failed
+    let mut connect_span = Span::new("gateway.connect", SpanKind::Client);
+    connect_span.attr("server.address", url.clone());
+    let ws_open_span = connect_span.child("gateway.ws_open");
 
     // Try to connect
     let ws_result = tokio::time::timeout(
@@ -291,7 +325,10 @@ pub async fn run_gateway_connection(
     .await;
 
     let ws_stream = match ws_result {
-        Ok(Ok((stream, _))) => stream,
+        Ok(Ok((stream, _))) => {
+            ws_open_span.end();
+            stream
+        }
         Ok(Err(e)) => {
             let msg = format!("WS connect failed: {}", e);
             let _ = set_status_if_current(&state, attempt, GatewayConnectionStatus {
@@ -320,7 +357,7 @@ pub async fn run_gateway_connection(
     }
 
     // Load device identity
-    let mut identity = load_or_create_device_identity(&data_dir).unwrap_or_else(|_| {
+    let mut identity = state.device_identity(&data_dir).unwrap_or_else(|_| {
         // Fallback: generate in-memory identity without persistence
         let mut csprng = OsRng;
         let signing_key = SigningKey::generate(&mut csprng);
@@ -490,6 +527,7 @@ pub async fn run_gateway_connection(
     }
 
     // Wait for hello-ok response
+    let hello_span = connect_span.child("gateway.hello");
     let hello_ok = loop {
         if !state.is_current_attempt(attempt) {
             return;
@@ -617,6 +655,8 @@ pub async fn run_gateway_connection(
         }
     };
 
+    hello_span.end();
+
     // Extract hello-ok fields
     let conn_id = hello_ok
         .get("server")
@@ -632,6 +672,12 @@ pub async fn run_gateway_connection(
         .and_then(|s| s.get("version"))
         .and_then(|v| v.as_str())
         .map(|s| s.to_string());
+    let methods: Vec<String> = hello_ok
+        .get("features")
+        .and_then(|f| f.get("methods"))
+        .and_then(|m| m.as_array())
+        .map(|m| m.iter().filter_map(|v| v.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
 
     // Store device token if provided in hello-ok
     if let Some(device_token) = hello_ok
@@ -651,7 +697,7 @@ pub async fn run_gateway_connection(
                 issued_at_ms: now_ms,
             },
         );
-        save_device_identity(&data_dir, &identity);
+        state.update_device_identity(&data_dir, &identity);
     }
 
     let connected_at_ms = std::time::SystemTime::now()
@@ -675,15 +721,18 @@ pub async fn run_gateway_connection(
     if !state.is_current_attempt(attempt) {
         return;
     }
+    state.methods.send_replace(methods);
     state.set_tx(Some(rpc_tx));
 
     if state.is_current_attempt(attempt) {
         let _ = app.emit("gateway-connected", &hello_ok);
     }
+    if let Some(protocol) = protocol {
+        connect_span.attr("gateway.protocol", protocol);
+    }
+    connect_span.end();
 
     // Main loop: handle inbound messages and outbound RPC requests
-    let pending_clone = pending.clone();
-
     loop {
         if !state.is_current_attempt(attempt) {
             break;
@@ -714,7 +763,7 @@ pub async fn run_gateway_connection(
                         if let Err(e) = write.send(Message::Text(json.into())).await {
                             let _ = req.reply.send(Err(format!("send failed: {}", e)));
                         } else {
-                            lock_or_recover(&pending_clone, "gateway.pending").insert(req.id, req.reply);
+                            pending.insert(req.id, req.reply);
                         }
                     }
                 }
@@ -735,7 +784,7 @@ pub async fn run_gateway_connection(
                             "res" => {
                                 let id = parsed.get("id").and_then(|i| i.as_str()).unwrap_or("").to_string();
                                 let ok = parsed.get("ok").and_then(|o| o.as_bool()).unwrap_or(false);
-                                if let Some(reply) = lock_or_recover(&pending_clone, "gateway.pending").remove(&id) {
+                                if let Some(reply) = pending.remove(&id) {
                                     let result = if ok {
                                         Ok(parsed.get("payload").cloned().unwrap_or(Value::Null))
                                     } else {
@@ -753,6 +802,19 @@ pub async fn run_gateway_connection(
                             "event" => {
                                 let event_name = parsed.get("event").and_then(|e| e.as_str()).unwrap_or("").to_string();
                                 let event_payload = parsed.get("payload").cloned().unwrap_or(Value::Null);
+                                // Tunnel, terminal and clipboard traffic is handled here, not in the webview
+                                if event_name.starts_with("tunnel.") {
+                                    crate::tunnel::dispatch(&app, &event_name, &event_payload);
+                                    continue;
+                                }
+                                if event_name.starts_with("terminal.") {
+                                    crate::terminal::dispatch(&app, &event_name, &event_payload);
+                                    continue;
+                                }
+                                if event_name.starts_with("clipboard.") {
+                                    crate::clipboard_sync::dispatch(&app, &event_name, &event_payload);
+                                    continue;
+                                }
                                 let _ = app.emit(
                                     "gateway-event",
                                     serde_json::json!({
@@ -777,13 +839,14 @@ pub async fn run_gateway_connection(
     }
 
     // Fail all pending RPC requests
-    let mut pending_map = lock_or_recover(&pending, "gateway.pending");
-    for (_, reply) in pending_map.drain() {
+    for (_, reply) in pending.drain() {
         let _ = reply.send(Err("Connection closed".to_string()));
     }
 
     if state.is_current_attempt(attempt) {
         state.set_tx(None);
+        crate::tunnel::connection_closed(&app);
+        crate::terminal::connection_closed(&app);
         state.set_status(GatewayConnectionStatus {
             state: "disconnected".to_string(),
             ..Default::default()
@@ -810,6 +873,19 @@ pub async fn gateway_connect(
 ) -> Result<serde_json::Value, String> {
     let scheme = if tls { "wss" } else { "ws" };
     let url = format!("{}://{}:{}", scheme, host, port);
+    // The webview holds masked credentials; swap in the stored ones
+    let (token, password) = {
+        let app_state = app.state::<crate::AppState>();
+        let stored = app_state
+            .config
+            .lock()
+            .map_err(|err| err.to_string())?
+            .clone();
+        (
+            crate::credentials::resolve_placeholder(token, &stored.gateway_token),
+            crate::credentials::resolve_placeholder(password, &stored.gateway_password),
+        )
+    };
     let attempt = state.begin_attempt();
 
     // Drop any previous sender so older loops observe closure and exit.
@@ -882,11 +958,13 @@ pub async fn gateway_connect(
 }
 
 #[tauri::command]
-pub fn gateway_disconnect(state: tauri::State<'_, Arc<GatewayState>>) {
+pub fn gateway_disconnect(state: tauri::State<'_, Arc<GatewayState>>, app: AppHandle) {
     // Invalidate any in-flight handshake or stream loop.
     state.begin_attempt();
     // Drop the sender, which causes the background task to break its loop
     state.set_tx(None);
+    crate::tunnel::connection_closed(&app);
+    crate::terminal::connection_closed(&app);
     state.set_status(GatewayConnectionStatus::default());
 }
 
@@ -895,16 +973,31 @@ pub fn gateway_status(state: tauri::State<'_, Arc<GatewayState>>) -> GatewayConn
     state.get_status()
 }
 
-#[tauri::command]
-pub async fn gateway_rpc(
+/// Send an RPC over the managed connection. The outer error is a transport
+/// failure; the inner result is the gateway's reply.
+pub async fn request(
+    state: &GatewayState,
     method: String,
     params: Option<Value>,
-    state: tauri::State<'_, Arc<GatewayState>>,
-) -> Result<serde_json::Value, String> {
-    let tx = {
-        let lock = lock_or_recover(&state.tx, "gateway.tx");
-        lock.clone()
-    };
+) -> Result<Result<Value, String>, String> {
+    let mut span = Span::new(format!("gateway.rpc {}", method), SpanKind::Client);
+    span.attr("rpc.system", "openclaw-gateway");
+    span.attr("rpc.method", method.clone());
+    let result = send_request(state, method, params).await;
+    match &result {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) | Err(err) => span.fail(err.clone()),
+    }
+    span.end();
+    result
+}
+
+async fn send_request(
+    state: &GatewayState,
+    method: String,
+    params: Option<Value>,
+) -> Result<Result<Value, String>, String> {
+    let tx = state.tx.borrow().clone();
 
     let tx = tx.ok_or_else(|| "Gateway not connected".to_string())?;
 
@@ -923,7 +1016,17 @@ pub async fn gateway_rpc(
     tokio::time::timeout(Duration::from_secs(30), reply_rx)
         .await
         .map_err(|_| "RPC timed out".to_string())?
-        .map_err(|_| "Reply channel closed".to_string())?
+        .map_err(|_| "Reply channel closed".to_string())
+}
+
+#[tauri::command]
+pub async fn gateway_rpc(
+    method: String,
+    params: Option<Value>,
+    state: tauri::State<'_, Arc<GatewayState>>,
+) -> Result<serde_json::Value, String> {
+    request(&state, method, params)
+        .await?
         .map(|v| serde_json::json!({ "ok": true, "payload": v }))
         .map_err(|e| e)
         .or_else(|e| Ok(serde_json::json!({ "ok": false, "error": { "code": "RPC_ERROR", "message": e } })))
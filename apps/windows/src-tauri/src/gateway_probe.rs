@@ -0,0 +1,467 @@
+// Gateway settings probe.
+//
+// Before new host/port/TLS/auth values are saved, the UI can ask for a quick
+// end-to-end check: resolve the host, open a TCP connection, complete the TLS
+// handshake (checking the pinned or trusted fingerprint), and send a gateway
+// `connect` frame with the configured credentials. The first failing stage is
+// reported with a stable code so typos surface immediately instead of as node
+// host retries.
+//
+// `run_connectivity_check` runs the same stages against the saved settings as
+// a doctor: every stage is reported pass/fail/skip with timing and, on
+// failure, a remediation hint.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_tungstenite::tungstenite::{self, Message};
+
+use crate::NodeClientConfig;
+
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// Error codes for `connect` rejections about device identity or pairing.
+/// The gateway checks shared credentials first, so these mean the token or
+/// password was accepted.
+const DEVICE_ERROR_CODES: &[&str] = &["NOT_PAIRED"];
+
+#[derive(Debug, Clone, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GatewayProbeReport {
+    ok: bool,
+    /// "dns-failed" | "tcp-refused" | "tls-mismatch" | "handshake-failed" |
+    /// "auth-rejected"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolved_addr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tls_fingerprint: Option<String>,
+    latency_ms: u64,
+}
+
+impl GatewayProbeReport {
+    pub fn message(&self) -> &str {
+        self.message.as_deref().unwrap_or("gateway check failed")
+    }
+}
+
+struct ProbeFailure {
+    code: &'static str,
+    message: String,
+}
+
+fn fail(code: &'static str, message: impl Into<String>) -> ProbeFailure {
+    ProbeFailure {
+        code,
+        message: message.into(),
+    }
+}
+
+trait ReadWrite: Read + Write {}
+impl<T: Read + Write> ReadWrite for T {}
+
+fn resolve(config: &NodeClientConfig) -> Result<Vec<SocketAddr>, ProbeFailure> {
+    let addrs: Vec<SocketAddr> = (config.host.as_str(), config.port)
+        .to_socket_addrs()
+        .map_err(|err| {
+            fail(
+                "dns-failed",
+                format!("cannot resolve {}: {}", config.host, err),
+            )
+        })?
+        .collect();
+    if addrs.is_empty() {
+        return Err(fail(
+            "dns-failed",
+            format!("{} has no addresses", config.host),
+        ));
+    }
+    Ok(addrs)
+}
+
+fn connect(addrs: &[SocketAddr], port: u16) -> Result<TcpStream, ProbeFailure> {
+    let timeout = Duration::from_secs(PROBE_TIMEOUT_SECS);
+    let mut last_err = None;
+    for addr in addrs {
+        match TcpStream::connect_timeout(addr, timeout) {
+            Ok(sock) => {
+                let _ = sock.set_read_timeout(Some(timeout));
+                let _ = sock.set_write_timeout(Some(timeout));
+                return Ok(sock);
+            }
+            Err(err) => last_err = Some(err),
+        }
+    }
+    Err(fail(
+        "tcp-refused",
+        format!(
+            "nothing accepted a connection on port {} ({})",
+            port,
+            last_err.map(|err| err.to_string()).unwrap_or_default()
+        ),
+    ))
+}
+
+/// The fingerprint the node host would pin: the configured one, else the
+/// trust-on-first-use record.
+fn expected_fingerprint(config: &NodeClientConfig) -> Option<String> {
+    match crate::non_empty(&config.tls_fingerprint) {
+        Some(fp) => crate::normalize_fingerprint(fp),
+        None => crate::tls_trust::trusted_fingerprint(&config.host, config.port),
+    }
+}
+
+type ProbeSocket = tungstenite::WebSocket<Box<dyn ReadWrite>>;
+
+fn upgrade(
+    stream: Box<dyn ReadWrite>,
+    config: &NodeClientConfig,
+) -> Result<ProbeSocket, ProbeFailure> {
+    let url = config.gateway_url();
+    let (socket, _) = tungstenite::client(url.as_str(), stream).map_err(|err| {
+        fail(
+            "handshake-failed",
+            format!("{} did not accept a WebSocket upgrade: {}", url, err),
+        )
+    })?;
+    Ok(socket)
+}
+
+/// Send `connect` with the configured credentials and classify the reply.
+fn authenticate(mut socket: ProbeSocket, config: &NodeClientConfig) -> Result<(), ProbeFailure> {
+    let mut auth = serde_json::Map::new();
+    if let Some(token) = crate::non_empty(&config.gateway_token) {
+        auth.insert("token".into(), token.into());
+    }
+    if let Some(password) = crate::non_empty(&config.gateway_password) {
+        auth.insert("password".into(), password.into());
+    }
+    let mut params = serde_json::json!({
+        "minProtocol": 3,
+        "maxProtocol": 5,
+        "client": {
+            "id": "openclaw-probe",
+            "displayName": "OpenClaw Node Client (settings check)",
+            "version": env!("CARGO_PKG_VERSION"),
+            "platform": std::env::consts::OS,
+            "mode": "probe",
+        },
+        "role": "operator",
+        "scopes": [],
+    });
+    if !auth.is_empty() {
+        params["auth"] = Value::Object(auth);
+    }
+    let request = serde_json::json!({
+        "type": "req",
+        "id": "settings-probe",
+        "method": "connect",
+        "params": params,
+    });
+    socket
+        .send(Message::Text(request.to_string()))
+        .map_err(|err| fail("handshake-failed", err.to_string()))?;
+
+    let deadline = Instant::now() + Duration::from_secs(PROBE_TIMEOUT_SECS);
+    let outcome = loop {
+        if Instant::now() > deadline {
+            break Err(fail(
+                "handshake-failed",
+                "gateway did not answer the connect request",
+            ));
+        }
+        let frame = match socket.read() {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(frame)) => {
+                let reason = frame.map(|f| f.reason.to_string()).unwrap_or_default();
+                break if reason.starts_with("unauthorized") {
+                    Err(fail("auth-rejected", reason))
+                } else {
+                    Err(fail(
+                        "handshake-failed",
+                        format!("gateway closed the connection: {}", reason),
+                    ))
+                };
+            }
+            Ok(_) => continue,
+            Err(err) => break Err(fail("handshake-failed", err.to_string())),
+        };
+        let Ok(parsed) = serde_json::from_str::<Value>(&frame) else {
+            continue;
+        };
+        if parsed["type"] != "res" || parsed["id"] != "settings-probe" {
+            continue;
+        }
+        if parsed["ok"] == true {
+            break Ok(());
+        }
+        let error = &parsed["error"];
+        let message = error["message"]
+            .as_str()
+            .unwrap_or("connect rejected")
+            .to_string();
+        let device_only = error["code"]
+            .as_str()
+            .is_some_and(|code| DEVICE_ERROR_CODES.contains(&code));
+        break if device_only {
+            Ok(())
+        } else if error["details"]["authReason"].is_string() || message.starts_with("unauthorized")
+        {
+            Err(fail("auth-rejected", message))
+        } else {
+            Err(fail("handshake-failed", message))
+        };
+    };
+    let _ = socket.close(None);
+    outcome
+}
+
+/// TLS handshake (when enabled) and fingerprint check. Returns the stream
+/// and the certificate fingerprint.
+fn secure(
+    sock: TcpStream,
+    config: &NodeClientConfig,
+) -> Result<(Box<dyn ReadWrite>, Option<String>), ProbeFailure> {
+    if !config.tls {
+        return Ok((Box::new(sock), None));
+    }
+    let (stream, fingerprint) =
+        crate::tls_trust::handshake_capturing_fingerprint(&config.host, sock)
+            .map_err(|err| fail("tls-mismatch", err))?;
+    if !config.tls_insecure_skip_verify {
+        if let Some(expected) = expected_fingerprint(config) {
+            if expected != fingerprint {
+                return Err(fail(
+                    "tls-mismatch",
+                    format!(
+                        "gateway certificate sha256 {} does not match the expected {}",
+                        fingerprint, expected
+                    ),
+                ));
+            }
+        }
+    }
+    Ok((Box::new(stream), Some(fingerprint)))
+}
+
+/// Run every stage against `config`. Blocking; call off the async runtime.
+pub fn probe(config: &NodeClientConfig) -> GatewayProbeReport {
+    let started = Instant::now();
+    let mut report = GatewayProbeReport::default();
+    let result = (|| {
+        let addrs = resolve(config)?;
+        let sock = connect(&addrs, config.port)?;
+        report.resolved_addr = sock.peer_addr().ok().map(|addr| addr.to_string());
+        let (stream, fingerprint) = secure(sock, config)?;
+        report.tls_fingerprint = fingerprint;
+        authenticate(upgrade(stream, config)?, config)
+    })();
+    report.latency_ms = started.elapsed().as_millis() as u64;
+    match result {
+        Ok(()) => report.ok = true,
+        Err(failure) => {
+            report.code = Some(failure.code.to_string());
+            report.message = Some(failure.message);
+        }
+    }
+    report
+}
+
+/// Probe and report a failure through `gateway-validation-failed`.
+pub fn validate_before_save(app: &AppHandle, config: &NodeClientConfig) -> Result<(), String> {
+    let report = probe(config);
+    if report.ok {
+        return Ok(());
+    }
+    let _ = app.emit("gateway-validation-failed", &report);
+    Err(format!(
+        "{}: {}",
+        report.code.as_deref().unwrap_or("gateway-check-failed"),
+        report.message()
+    ))
+}
+
+// ---------------------------------------------------------------------------
+// Connectivity doctor
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityStage {
+    /// "dns" | "tcp" | "tls" | "websocket" | "auth"
+    name: &'static str,
+    /// "pass" | "fail" | "skip"
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+    /// What to try next when the stage failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hint: Option<String>,
+    duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityReport {
+    ok: bool,
+    gateway_url: String,
+    stages: Vec<ConnectivityStage>,
+}
+
+const STAGES: &[&str] = &["dns", "tcp", "tls", "websocket", "auth"];
+
+fn hint(stage: &str, failure: &ProbeFailure, config: &NodeClientConfig) -> String {
+    match stage {
+        "dns" => "Check the gateway host for typos, or use its IP address. For a tailnet \
+                  host, make sure Tailscale (or your VPN) is connected."
+            .to_string(),
+        "tcp" => format!(
+            "Make sure the gateway is running (`openclaw gateway status`) and listening on \
+             port {} on an interface this machine can reach, and that no firewall blocks it.",
+            config.port
+        ),
+        "tls" if failure.message.contains("does not match") => {
+            "The gateway presented a different certificate than the one pinned. If it was \
+             renewed on purpose, clear the fingerprint (or forget the known host) and trust \
+             the new one; otherwise don't connect."
+                .to_string()
+        }
+        "tls" => "The TLS handshake failed. Turn TLS off if the gateway serves plain ws://, \
+                  or add the gateway's CA under TLS settings."
+            .to_string(),
+        "websocket" => format!(
+            "Port {} answered but not as an OpenClaw gateway. Check the port and the TLS \
+             setting, and any reverse proxy's WebSocket support.",
+            config.port
+        ),
+        _ if failure.code == "auth-rejected" => {
+            "The gateway rejected the credentials. Copy gateway.auth.token (or the password) \
+             from the gateway's openclaw.json into the node client config."
+                .to_string()
+        }
+        _ => "The gateway refused the connect request. Update the node client or the \
+              gateway if their protocol versions differ."
+            .to_string(),
+    }
+}
+
+/// Time one stage and record its outcome. Returns the stage's value on
+/// success.
+fn run_stage<T>(
+    stages: &mut Vec<ConnectivityStage>,
+    name: &'static str,
+    config: &NodeClientConfig,
+    run: impl FnOnce() -> Result<(T, Option<String>), ProbeFailure>,
+) -> Option<T> {
+    let started = Instant::now();
+    let result = run();
+    let duration_ms = started.elapsed().as_millis() as u64;
+    let (value, stage) = match result {
+        Ok((value, detail)) => (
+            Some(value),
+            ConnectivityStage {
+                name,
+                status: "pass",
+                detail,
+                hint: None,
+                duration_ms,
+            },
+        ),
+        Err(failure) => (
+            None,
+            ConnectivityStage {
+                name,
+                status: "fail",
+                hint: Some(hint(name, &failure, config)),
+                detail: Some(failure.message),
+                duration_ms,
+            },
+        ),
+    };
+    stages.push(stage);
+    value
+}
+
+/// Run the connection stages one by one and report each, stopping at the
+/// first failure. Blocking; call off the async runtime.
+pub fn connectivity_check(config: &NodeClientConfig) -> ConnectivityReport {
+    let mut stages = Vec::new();
+    let passed = (|| {
+        let addrs = run_stage(&mut stages, "dns", config, || {
+            let addrs = resolve(config)?;
+            let listed: Vec<String> = addrs.iter().map(|addr| addr.ip().to_string()).collect();
+            Ok((addrs, Some(listed.join(", "))))
+        })?;
+        let sock = run_stage(&mut stages, "tcp", config, || {
+            let sock = connect(&addrs, config.port)?;
+            let peer = sock.peer_addr().ok().map(|addr| addr.to_string());
+            Ok((sock, peer))
+        })?;
+        let stream = run_stage(&mut stages, "tls", config, || {
+            let (stream, fingerprint) = secure(sock, config)?;
+            let detail = fingerprint
+                .map(|fp| format!("sha256 {}", fp))
+                .unwrap_or_else(|| "TLS disabled".to_string());
+            Ok((stream, Some(detail)))
+        })?;
+        let socket = run_stage(&mut stages, "websocket", config, || {
+            Ok((upgrade(stream, config)?, Some(config.gateway_url())))
+        })?;
+        run_stage(&mut stages, "auth", config, || {
+            authenticate(socket, config)?;
+            Ok(((), None))
+        })
+    })()
+    .is_some();
+
+    for name in STAGES.iter().skip(stages.len()) {
+        stages.push(ConnectivityStage {
+            name,
+            status: "skip",
+            detail: None,
+            hint: None,
+            duration_ms: 0,
+        });
+    }
+    ConnectivityReport {
+        ok: passed,
+        gateway_url: config.gateway_url(),
+        stages,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Check gateway settings without saving them.
+#[tauri::command]
+pub async fn validate_gateway_settings(
+    app: AppHandle,
+    config: NodeClientConfig,
+) -> Result<GatewayProbeReport, String> {
+    let config = crate::with_stored_secrets(&app, config)?;
+    tauri::async_runtime::spawn_blocking(move || probe(&config))
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Staged connectivity check against the saved gateway settings.
+#[tauri::command]
+pub async fn run_connectivity_check(app: AppHandle) -> Result<ConnectivityReport, String> {
+    let config = app
+        .state::<crate::AppState>()
+        .config
+        .lock()
+        .map_err(|err| err.to_string())?
+        .clone();
+    tauri::async_runtime::spawn_blocking(move || connectivity_check(&config))
+        .await
+        .map_err(|err| err.to_string())
+}
@@ -0,0 +1,146 @@
+// Node client health reporting to the gateway.
+//
+// Operators watching the central control surface can't see why a node
+// host on some machine keeps dropping. With `gateway_reporting` on, the
+// app's own gateway connection sends presence beacons (the `system-event`
+// RPC the macOS app uses, so the machine shows up under Instances) when it
+// connects, every few minutes, whenever the node host changes state, and
+// when new errors are logged. The beacon's `reason` carries the transition
+// or the latest error line.
+
+use serde_json::json;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use crate::gateway::{self, GatewayState};
+use crate::AppState;
+
+const POLL_INTERVAL_SECS: u64 = 5;
+const PERIODIC_INTERVAL_SECS: u64 = 180;
+const REASON_MAX_CHARS: usize = 160;
+
+fn enabled(app: &AppHandle) -> bool {
+    app.state::<AppState>()
+        .config
+        .lock()
+        .map(|config| config.gateway_reporting)
+        .unwrap_or(false)
+}
+
+/// Name shown for this machine: the configured display name, else the
+/// host name.
+pub fn host_label(app: &AppHandle) -> String {
+    let display_name = app
+        .state::<AppState>()
+        .config
+        .lock()
+        .ok()
+        .and_then(|config| crate::non_empty(&config.display_name).map(str::to_string));
+    display_name
+        .or_else(|| std::env::var("COMPUTERNAME").ok())
+        .or_else(|| std::env::var("HOSTNAME").ok())
+        .or_else(|| {
+            std::fs::read_to_string("/etc/hostname")
+                .ok()
+                .map(|name| name.trim().to_string())
+        })
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "node-client".to_string())
+}
+
+fn node_status(app: &AppHandle) -> String {
+    let status = app.state::<AppState>().node_status.borrow().status.clone();
+    status.as_str().to_string()
+}
+
+/// Error lines logged after `since_ms`, and the newest timestamp seen.
+fn new_errors(app: &AppHandle, since_ms: u64) -> (Vec<String>, u64) {
+    let state = app.state::<AppState>();
+    let Ok(runtime) = state.runtime.lock() else {
+        return (Vec::new(), since_ms);
+    };
+    let latest = runtime
+        .logs
+        .back()
+        .map(|entry| entry.ts_ms)
+        .unwrap_or(since_ms);
+    let errors = runtime
+        .logs
+        .iter()
+        .filter(|entry| entry.ts_ms > since_ms && entry.level == "error")
+        .map(|entry| entry.message.clone())
+        .collect();
+    (errors, latest.max(since_ms))
+}
+
+fn truncate(text: &str) -> String {
+    if text.chars().count() <= REASON_MAX_CHARS {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(REASON_MAX_CHARS - 1).collect();
+    cut.push('…');
+    cut
+}
+
+async fn send_beacon(app: &AppHandle, gateway: &GatewayState, node: &str, reason: &str) {
+    let host = host_label(app);
+    let version = env!("CARGO_PKG_VERSION");
+    let text = format!(
+        "Node: {} · node-client {} · node {} · reason {}",
+        host, version, node, reason
+    );
+    let params = json!({
+        "text": text,
+        "instanceId": crate::get_device_id(app.clone()).ok(),
+        "host": host,
+        "mode": "node-client",
+        "version": version,
+        "platform": std::env::consts::OS,
+        "reason": reason,
+        "tags": ["node-client", format!("node:{}", node)],
+    });
+    match gateway::request(gateway, "system-event".to_string(), Some(params)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) | Err(err) => eprintln!("gateway report failed: {}", err),
+    }
+}
+
+/// Send beacons while reporting is enabled and the gateway is connected.
+pub async fn run_gateway_report(app: AppHandle) {
+    let gateway = app.state::<Arc<GatewayState>>().inner().clone();
+    // Node status last reported; `None` until the first beacon of a connection
+    let mut reported: Option<String> = None;
+    let mut last_beacon = Instant::now();
+    let mut errors_since = crate::now_ms();
+    loop {
+        tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+        if !enabled(&app) || gateway.get_status().state != "connected" {
+            reported = None;
+            errors_since = crate::now_ms();
+            continue;
+        }
+        let node = node_status(&app);
+        let (errors, latest) = new_errors(&app, errors_since);
+        errors_since = latest;
+        let reason = match (&reported, errors.last()) {
+            (None, _) => Some("connect".to_string()),
+            (_, Some(error)) if errors.len() > 1 => Some(truncate(&format!(
+                "{} errors, latest: {}",
+                errors.len(),
+                error
+            ))),
+            (_, Some(error)) => Some(truncate(&format!("error: {}", error))),
+            (Some(before), None) if *before != node => Some(format!("node {}", node)),
+            _ if last_beacon.elapsed() >= Duration::from_secs(PERIODIC_INTERVAL_SECS) => {
+                Some("periodic".to_string())
+            }
+            _ => None,
+        };
+        if let Some(reason) = reason {
+            send_beacon(&app, &gateway, &node, &reason).await;
+            reported = Some(node);
+            last_beacon = Instant::now();
+        }
+    }
+}
@@ -0,0 +1,221 @@
+// User-scriptable event hooks.
+//
+// Power users want to react to node client events (page someone when the
+// node crashes, flash a light on an approval) without forking the app.
+// `event_hooks` maps an event name to a script; when the event fires the
+// script runs with the event as JSON on stdin:
+//
+//   {"event": "node-crashed", "tsMs": 1767225600000, "payload": {...}}
+//
+// Scripts get the same sanitized environment as the node host (AppImage
+// vars stripped, launchd PATH fixed up, `extra_env` applied) plus
+// OPENCLAW_EVENT, run in the configured working directory, and are killed
+// after `HOOK_TIMEOUT_SECS`. Output is collected for `HOOK_DRAIN_GRACE_MS`
+// past the exit at most, since a background process the script started can
+// keep its pipes open.
+
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Listener, Manager};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::AppState;
+
+/// App events a hook can be attached to.
+const HOOK_EVENTS: &[&str] = &[
+    "node-crashed",
+    "node-status-changed",
+    "approval-pending",
+    "approval-resolved",
+    "gateway-connected",
+    "gateway-disconnected",
+];
+
+const HOOK_TIMEOUT_SECS: u64 = 30;
+
+/// How long output may keep draining after the hook exits or is killed.
+const HOOK_DRAIN_GRACE_MS: u64 = 500;
+
+/// Output captured per stream; the rest is read and discarded.
+const HOOK_CAPTURE_MAX_BYTES: usize = 64 * 1024;
+
+/// Output kept from a failing hook for the log.
+const HOOK_OUTPUT_MAX_CHARS: usize = 500;
+
+fn hook_command(app: &AppHandle, script: &str, event: &str) -> Command {
+    let mut command = Command::new(script);
+
+    #[cfg(target_os = "linux")]
+    crate::sanitize_appimage_env(&mut command);
+
+    #[cfg(target_os = "macos")]
+    command.env(
+        "PATH",
+        crate::launchd_path_fixup(std::env::var("PATH").unwrap_or_default()),
+    );
+
+    let state = app.state::<AppState>();
+    if let Ok(config) = state.config.lock() {
+        for (key, value) in &config.extra_env {
+            let key = key.trim();
+            if key.is_empty() || key.contains('=') || key.contains('\0') {
+                continue;
+            }
+            command.env(key, value);
+        }
+        let cwd = crate::non_empty(&config.working_dir)
+            .map(PathBuf::from)
+            .or_else(|| directories::BaseDirs::new().map(|b| b.home_dir().to_path_buf()));
+        if let Some(dir) = cwd.filter(|dir| dir.is_dir()) {
+            command.current_dir(dir);
+        }
+    }
+    command
+        .env("OPENCLAW_EVENT", event)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    command.creation_flags(crate::CREATE_NO_WINDOW);
+
+    command
+}
+
+/// Run `script` for `event`, logging anything other than a clean exit.
+fn run_hook(app: &AppHandle, script: &str, event: &str, payload: Value) {
+    let input = json!({
+        "event": event,
+        "tsMs": crate::now_ms(),
+        "payload": payload,
+    })
+    .to_string();
+    let mut child = match hook_command(app, script, event).spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            crate::push_log_line(
+                app,
+                format!("hook {} ({}) failed to start: {}", event, script, err),
+            );
+            return;
+        }
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(input.as_bytes());
+    }
+    // Drain output on threads so a chatty script can't block on a full pipe
+    let (done_tx, done_rx) = mpsc::channel::<()>();
+    let captured = [
+        child
+            .stdout
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+        child
+            .stderr
+            .take()
+            .map(|s| Box::new(s) as Box<dyn Read + Send>),
+    ]
+    .map(|pipe| {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        if let Some(mut pipe) = pipe {
+            let sink = buffer.clone();
+            let done = done_tx.clone();
+            std::thread::spawn(move || {
+                let mut chunk = [0u8; 4096];
+                loop {
+                    match pipe.read(&mut chunk) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => {
+                            if let Ok(mut sink) = sink.lock() {
+                                let room = HOOK_CAPTURE_MAX_BYTES.saturating_sub(sink.len());
+                                sink.extend_from_slice(&chunk[..read.min(room)]);
+                            }
+                        }
+                    }
+                }
+                let _ = done.send(());
+            });
+        }
+        buffer
+    });
+    drop(done_tx);
+    let deadline = Instant::now() + Duration::from_secs(HOOK_TIMEOUT_SECS);
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if Instant::now() < deadline => {
+                std::thread::sleep(Duration::from_millis(100));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+        }
+    };
+    // Readers finish at EOF, once nothing holds the pipes open; whatever is
+    // still attached after the grace period is left behind
+    let grace = Instant::now() + Duration::from_millis(HOOK_DRAIN_GRACE_MS);
+    while done_rx
+        .recv_timeout(grace.saturating_duration_since(Instant::now()))
+        .is_ok()
+    {}
+    let output: String = captured
+        .map(|buffer| {
+            buffer
+                .lock()
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                .unwrap_or_default()
+        })
+        .join("")
+        .trim()
+        .chars()
+        .take(HOOK_OUTPUT_MAX_CHARS)
+        .collect();
+    let failure = match status {
+        None => Some(format!("timed out after {}s", HOOK_TIMEOUT_SECS)),
+        Some(status) if !status.success() => Some(format!("exited with {}", status)),
+        Some(_) => None,
+    };
+    if let Some(failure) = failure {
+        let detail = if output.is_empty() {
+            String::new()
+        } else {
+            format!(": {}", output)
+        };
+        crate::push_log_line(
+            app,
+            format!("hook {} ({}) {}{}", event, script, failure, detail),
+        );
+    }
+}
+
+/// Listen for every hookable event; the script is looked up when the event
+/// fires, so config changes apply without re-registering.
+pub fn register(app: &AppHandle) {
+    for &event in HOOK_EVENTS {
+        let handle = app.clone();
+        app.listen(event, move |fired| {
+            let script = handle
+                .state::<AppState>()
+                .config
+                .lock()
+                .ok()
+                .and_then(|config| config.event_hooks.get(event).cloned())
+                .map(|script| script.trim().to_string())
+                .filter(|script| !script.is_empty());
+            let Some(script) = script else {
+                return;
+            };
+            let payload = serde_json::from_str::<Value>(fired.payload()).unwrap_or(Value::Null);
+            let app = handle.clone();
+            std::thread::spawn(move || run_hook(&app, &script, event, payload));
+        });
+    }
+}
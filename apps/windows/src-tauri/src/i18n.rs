@@ -0,0 +1,293 @@
+// Localized backend strings.
+//
+// Tray labels, notification text and the node host annotations the app adds
+// to the log and `lastError` are looked up here by key, in the language set
+// by the `locale` config field (the UI language; unset follows the OS
+// locale). Unknown languages and missing entries fall back to English.
+//
+// Log lines still drive level inference (`infer_log_level` looks for
+// "error"/"failed"/"warn"), so only strings whose level doesn't depend on
+// those words are localized.
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    En = 0,
+    De = 1,
+    Es = 2,
+    Fr = 3,
+}
+
+/// Language codes accepted in `locale`, in catalog column order.
+pub const LANGUAGES: &[(&str, Lang)] = &[
+    ("en", Lang::En),
+    ("de", Lang::De),
+    ("es", Lang::Es),
+    ("fr", Lang::Fr),
+];
+
+static LANG: AtomicU8 = AtomicU8::new(Lang::En as u8);
+
+/// Key, then the text in each language of `Lang`. `{name}` placeholders are
+/// filled by `tf`.
+const MESSAGES: &[(&str, [&str; 4])] = &[
+    // Tray menu
+    ("tray.open", ["Open", "Öffnen", "Abrir", "Ouvrir"]),
+    (
+        "tray.start",
+        [
+            "Start Node Host",
+            "Node-Host starten",
+            "Iniciar host de nodo",
+            "Démarrer l'hôte de nœud",
+        ],
+    ),
+    (
+        "tray.stop",
+        [
+            "Stop Node Host",
+            "Node-Host stoppen",
+            "Detener host de nodo",
+            "Arrêter l'hôte de nœud",
+        ],
+    ),
+    (
+        "tray.restart",
+        [
+            "Restart Node Host",
+            "Node-Host neu starten",
+            "Reiniciar host de nodo",
+            "Redémarrer l'hôte de nœud",
+        ],
+    ),
+    (
+        "tray.pending_approvals",
+        [
+            "Pending Approvals ({count})",
+            "Ausstehende Freigaben ({count})",
+            "Aprobaciones pendientes ({count})",
+            "Approbations en attente ({count})",
+        ],
+    ),
+    (
+        "tray.recent_activity",
+        [
+            "Recent Activity",
+            "Letzte Aktivität",
+            "Actividad reciente",
+            "Activité récente",
+        ],
+    ),
+    (
+        "tray.no_recent_activity",
+        [
+            "No recent errors or reconnects",
+            "Keine aktuellen Fehler oder Neuverbindungen",
+            "Sin errores ni reconexiones recientes",
+            "Aucune erreur ni reconnexion récente",
+        ],
+    ),
+    (
+        "tray.open_config_dir",
+        [
+            "Open Config Folder",
+            "Konfigurationsordner öffnen",
+            "Abrir carpeta de configuración",
+            "Ouvrir le dossier de configuration",
+        ],
+    ),
+    (
+        "tray.open_logs_dir",
+        [
+            "Open Logs Folder",
+            "Protokollordner öffnen",
+            "Abrir carpeta de registros",
+            "Ouvrir le dossier des journaux",
+        ],
+    ),
+    (
+        "tray.copy_device_id",
+        [
+            "Copy Device ID",
+            "Geräte-ID kopieren",
+            "Copiar ID de dispositivo",
+            "Copier l'ID de l'appareil",
+        ],
+    ),
+    (
+        "tray.copy_gateway_url",
+        [
+            "Copy Gateway URL",
+            "Gateway-URL kopieren",
+            "Copiar URL del gateway",
+            "Copier l'URL de la passerelle",
+        ],
+    ),
+    ("tray.quit", ["Quit", "Beenden", "Salir", "Quitter"]),
+    (
+        "tray.tooltip.node",
+        ["Node: {status}", "Node: {status}", "Nodo: {status}", "Nœud : {status}"],
+    ),
+    (
+        "tray.tooltip.gateway",
+        [
+            "Gateway: {status}",
+            "Gateway: {status}",
+            "Gateway: {status}",
+            "Passerelle : {status}",
+        ],
+    ),
+    (
+        "tray.tooltip.one_approval",
+        [
+            "1 approval pending",
+            "1 Freigabe ausstehend",
+            "1 aprobación pendiente",
+            "1 approbation en attente",
+        ],
+    ),
+    (
+        "tray.tooltip.approvals",
+        [
+            "{count} approvals pending",
+            "{count} Freigaben ausstehend",
+            "{count} aprobaciones pendientes",
+            "{count} approbations en attente",
+        ],
+    ),
+    // Approvals
+    (
+        "approval.title",
+        [
+            "OpenClaw — Approval Required",
+            "OpenClaw — Freigabe erforderlich",
+            "OpenClaw — Se requiere aprobación",
+            "OpenClaw — Approbation requise",
+        ],
+    ),
+    (
+        "approval.allow_once",
+        [
+            "Allow once",
+            "Einmal erlauben",
+            "Permitir una vez",
+            "Autoriser une fois",
+        ],
+    ),
+    ("approval.deny", ["Deny", "Ablehnen", "Denegar", "Refuser"]),
+    ("approval.open", ["Open", "Öffnen", "Abrir", "Ouvrir"]),
+    (
+        "approval.agent",
+        ["agent {agent}", "Agent {agent}", "agente {agent}", "agent {agent}"],
+    ),
+    ("approval.cwd", ["in {cwd}", "in {cwd}", "en {cwd}", "dans {cwd}"]),
+    (
+        "terminal.approval",
+        [
+            "Open a remote terminal session ({shell})",
+            "Remote-Terminalsitzung öffnen ({shell})",
+            "Abrir una sesión de terminal remota ({shell})",
+            "Ouvrir une session de terminal à distance ({shell})",
+        ],
+    ),
+    // Node host lifecycle annotations
+    (
+        "node.exited_cleanly",
+        [
+            "node host exited cleanly",
+            "Node-Host wurde ordnungsgemäß beendet",
+            "el host de nodo terminó correctamente",
+            "l'hôte de nœud s'est arrêté proprement",
+        ],
+    ),
+    (
+        "node.exited_with_status",
+        [
+            "node host exited with status {status}",
+            "Node-Host wurde mit Status {status} beendet",
+            "el host de nodo terminó con estado {status}",
+            "l'hôte de nœud s'est arrêté avec le statut {status}",
+        ],
+    ),
+    (
+        "node.adopted_exited",
+        [
+            "adopted node host (pid {pid}) exited",
+            "übernommener Node-Host (PID {pid}) wurde beendet",
+            "el host de nodo adoptado (pid {pid}) terminó",
+            "l'hôte de nœud adopté (pid {pid}) s'est arrêté",
+        ],
+    ),
+    (
+        "node.stopped",
+        [
+            "stopped node host process",
+            "Node-Host-Prozess gestoppt",
+            "proceso del host de nodo detenido",
+            "processus de l'hôte de nœud arrêté",
+        ],
+    ),
+    (
+        "node.service_managed",
+        [
+            "node host is managed by the installed OS service; start it with the service manager or uninstall the service",
+            "Der Node-Host wird vom installierten Systemdienst verwaltet; starten Sie ihn über die Dienstverwaltung oder deinstallieren Sie den Dienst",
+            "el host de nodo lo gestiona el servicio del sistema instalado; inícielo con el gestor de servicios o desinstale el servicio",
+            "l'hôte de nœud est géré par le service système installé ; démarrez-le avec le gestionnaire de services ou désinstallez le service",
+        ],
+    ),
+];
+
+/// Language for a BCP 47 tag ("de", "fr-CA", "es_MX.UTF-8"); `None` when
+/// there is no catalog for it.
+fn parse(tag: &str) -> Option<Lang> {
+    let primary = tag
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    LANGUAGES
+        .iter()
+        .find(|(code, _)| *code == primary)
+        .map(|(_, lang)| *lang)
+}
+
+/// Switch the language from the `locale` config value; unset or empty
+/// follows the OS locale.
+pub fn set_locale(locale: Option<&str>) {
+    let lang = locale
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .or_else(tauri_plugin_os::locale)
+        .and_then(|tag| parse(&tag))
+        .unwrap_or(Lang::En);
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+pub fn lang() -> Lang {
+    match LANG.load(Ordering::Relaxed) {
+        1 => Lang::De,
+        2 => Lang::Es,
+        3 => Lang::Fr,
+        _ => Lang::En,
+    }
+}
+
+/// The text for `key` in the current language.
+pub fn t(key: &'static str) -> &'static str {
+    MESSAGES
+        .iter()
+        .find(|(name, _)| *name == key)
+        .map(|(_, texts)| texts[lang() as usize])
+        .unwrap_or(key)
+}
+
+/// `t(key)` with each `{name}` replaced by its value.
+pub fn tf(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(t(key).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
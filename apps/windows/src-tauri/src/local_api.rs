@@ -0,0 +1,295 @@
+// Optional localhost HTTP API.
+//
+// Scripts and launcher workflows (Raycast, Alfred, shell aliases) want to
+// check status, start or stop the node host and answer approvals without the
+// webview. When `local_api_enabled` is set the app listens on
+// 127.0.0.1:<local_api_port> and every request must carry
+// `Authorization: Bearer <local_api_token>`:
+//
+//   GET  /v1/status                 node and gateway status
+//   POST /v1/node/start|stop|restart
+//   GET  /v1/approvals              pending exec approvals
+//   POST /v1/approvals/<id>         {"decision": "allow-once" | "allow-always" | "deny"}
+//
+// Responses are JSON. The listener follows config changes without a restart.
+
+use serde_json::{json, Value};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::AppState;
+
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const READ_TIMEOUT_SECS: u64 = 10;
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    body: Value,
+}
+
+impl Response {
+    fn ok(body: Value) -> Self {
+        Self { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: json!({ "error": message.into() }),
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Internal Server Error",
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> Result<Request, Response> {
+    let mut buf = Vec::new();
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD_BYTES {
+            return Err(Response::error(413, "request headers too large"));
+        }
+        let mut chunk = [0u8; 4096];
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|err| Response::error(400, err.to_string()))?;
+        if read == 0 {
+            return Err(Response::error(400, "incomplete request"));
+        }
+        buf.extend_from_slice(&chunk[..read]);
+    };
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or("").split_whitespace();
+    let method = request_line.next().unwrap_or("").to_string();
+    let path = request_line.next().unwrap_or("").to_string();
+    let mut authorization = None;
+    let mut content_length = 0usize;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("content-length") {
+            content_length = value
+                .parse()
+                .map_err(|_| Response::error(400, "invalid Content-Length"))?;
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(Response::error(413, "request body too large"));
+    }
+    let mut body = buf[head_end + 4..].to_vec();
+    while body.len() < content_length {
+        let mut chunk = [0u8; 4096];
+        let read = stream
+            .read(&mut chunk)
+            .await
+            .map_err(|err| Response::error(400, err.to_string()))?;
+        if read == 0 {
+            return Err(Response::error(400, "incomplete request body"));
+        }
+        body.extend_from_slice(&chunk[..read]);
+    }
+    body.truncate(content_length);
+    Ok(Request {
+        method,
+        path,
+        authorization,
+        body,
+    })
+}
+
+/// Run a blocking node host operation off the async runtime.
+async fn node_action(app: &AppHandle, action: fn(&AppHandle) -> Result<(), String>) -> Response {
+    let app = app.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || action(&app))
+        .await
+        .map_err(|err| err.to_string())
+        .and_then(|result| result);
+    match result {
+        Ok(()) => Response::ok(json!({ "ok": true })),
+        Err(err) => Response::error(500, err),
+    }
+}
+
+async fn route(app: &AppHandle, request: &Request) -> Response {
+    let path = request.path.split('?').next().unwrap_or("");
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["v1", "status"]) => {
+            match crate::get_status(app.state::<AppState>()) {
+                Ok(status) => Response::ok(json!(status)),
+                Err(err) => Response::error(500, err),
+            }
+        }
+        ("POST", ["v1", "node", "start"]) => node_action(app, crate::start_node_internal).await,
+        ("POST", ["v1", "node", "stop"]) => node_action(app, crate::stop_node_internal).await,
+        ("POST", ["v1", "node", "restart"]) => node_action(app, crate::restart_node_internal).await,
+        ("GET", ["v1", "approvals"]) => {
+            match crate::get_pending_approvals(app.state::<AppState>()) {
+                Ok(approvals) => Response::ok(json!(approvals)),
+                Err(err) => Response::error(500, err),
+            }
+        }
+        ("POST", ["v1", "approvals", id]) => {
+            let decision = serde_json::from_slice::<Value>(&request.body)
+                .ok()
+                .and_then(|body| body.get("decision")?.as_str().map(str::to_string));
+            let Some(decision) = decision else {
+                return Response::error(400, "body must be {\"decision\": \"...\"}");
+            };
+            match crate::send_approval_decision(app, id, &decision) {
+                Ok(()) => Response::ok(json!({ "ok": true })),
+                Err(err) if err.starts_with("no pending approval") => Response::error(404, err),
+                Err(err) => Response::error(400, err),
+            }
+        }
+        (_, ["v1", "status"])
+        | (_, ["v1", "node", "start" | "stop" | "restart"])
+        | (_, ["v1", "approvals"])
+        | (_, ["v1", "approvals", _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "not found"),
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, app: AppHandle, token: String) {
+    let response = match tokio::time::timeout(
+        Duration::from_secs(READ_TIMEOUT_SECS),
+        read_request(&mut stream),
+    )
+    .await
+    {
+        Err(_) => Response::error(400, "timed out reading request"),
+        Ok(Err(response)) => response,
+        Ok(Ok(request)) => {
+            let expected = format!("Bearer {}", token);
+            let presented = request.authorization.as_deref().unwrap_or_default();
+            if !crate::constant_time_eq(presented.as_bytes(), expected.as_bytes()) {
+                Response::error(401, "missing or invalid bearer token")
+            } else {
+                route(&app, &request).await
+            }
+        }
+    };
+    let body = response.body.to_string();
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason(response.status),
+        body.len()
+    );
+    let _ = stream.write_all(head.as_bytes()).await;
+    let _ = stream.write_all(body.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn serve(app: AppHandle, port: u16, token: String) {
+    let listener = match TcpListener::bind(("127.0.0.1", port)).await {
+        Ok(listener) => listener,
+        Err(err) => {
+            crate::push_log_line(
+                &app,
+                format!("local API failed to listen on 127.0.0.1:{}: {}", port, err),
+            );
+            return;
+        }
+    };
+    crate::push_log_line(&app, format!("local API listening on 127.0.0.1:{}", port));
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(stream, app.clone(), token.clone()));
+            }
+            Err(err) => eprintln!("local API accept error: {}", err),
+        }
+    }
+}
+
+/// Settings the listener depends on; `None` when the API is off.
+fn wanted(app: &AppHandle) -> Option<(u16, Option<String>)> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().ok()?;
+    if !config.local_api_enabled {
+        return None;
+    }
+    Some((
+        config.local_api_port,
+        crate::non_empty(&config.local_api_token).map(str::to_string),
+    ))
+}
+
+/// Start, stop or restart the listener as the config changes. A token is
+/// generated and saved the first time the API is enabled without one.
+pub async fn run_local_api(app: AppHandle) {
+    let mut changed = app.state::<AppState>().config_changed.subscribe();
+    let mut running: Option<(u16, String, JoinHandle<()>)> = None;
+    loop {
+        let next = match wanted(&app) {
+            Some((port, Some(token))) => Some((port, token)),
+            Some((port, None)) => {
+                let token = crate::generate_token();
+                let saved = token.clone();
+                match crate::update_config(&app, move |config| {
+                    config.local_api_token = Some(saved);
+                }) {
+                    Ok(_) => Some((port, token)),
+                    Err(err) => {
+                        crate::push_log_line(
+                            &app,
+                            format!("local API token could not be saved: {}", err),
+                        );
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+        let unchanged = match (&running, &next) {
+            (Some((port, token, _)), Some((next_port, next_token))) => {
+                port == next_port && token == next_token
+            }
+            (None, None) => true,
+            _ => false,
+        };
+        if !unchanged {
+            if let Some((_, _, handle)) = running.take() {
+                handle.abort();
+                crate::push_log_line(&app, "local API stopped");
+            }
+            if let Some((port, token)) = next {
+                let handle = tauri::async_runtime::spawn(serve(app.clone(), port, token.clone()));
+                running = Some((port, token, handle));
+            }
+        }
+        if changed.changed().await.is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,259 @@
+// Log export for bug reports.
+//
+// Writes the persisted crash-report log tails (older reports are gzipped on
+// disk) plus the in-memory session log to a plain-text file picked with the
+// save dialog. Captured lines are already
+// scrubbed of configured secrets on ingestion; `redact` additionally strips
+// details that identify the machine (home directory, gateway host).
+
+use serde::Deserialize;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::{AppState, LogEntry, NodeCrashReport};
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LogExportOptions {
+    /// Streams to include ("stdout", "stderr", "app"); empty means all.
+    streams: Vec<String>,
+    min_level: Option<String>,
+    /// Case-insensitive substring filter.
+    query: Option<String>,
+    redact: bool,
+    include_crash_reports: bool,
+}
+
+impl Default for LogExportOptions {
+    fn default() -> Self {
+        Self {
+            streams: Vec::new(),
+            min_level: None,
+            query: None,
+            redact: true,
+            include_crash_reports: true,
+        }
+    }
+}
+
+fn level_rank(level: &str) -> u8 {
+    match level {
+        "debug" => 0,
+        "info" => 1,
+        "warn" => 2,
+        _ => 3,
+    }
+}
+
+impl LogExportOptions {
+    fn matches_text(&self, message: &str) -> bool {
+        match self
+            .query
+            .as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty())
+        {
+            Some(query) => message.to_lowercase().contains(&query.to_lowercase()),
+            None => true,
+        }
+    }
+
+    fn matches(&self, entry: &LogEntry) -> bool {
+        if !self.streams.is_empty() && !self.streams.contains(&entry.stream) {
+            return false;
+        }
+        if let Some(min) = self.min_level.as_deref() {
+            if level_rank(&entry.level) < level_rank(min) {
+                return false;
+            }
+        }
+        self.matches_text(&entry.message)
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.mmmZ` for a unix timestamp in milliseconds.
+fn format_utc_ms(ts_ms: u64) -> String {
+    let secs = ts_ms / 1000;
+    let (days, rem) = ((secs / 86_400) as i64, secs % 86_400);
+    // Civil-from-days (Howard Hinnant)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60,
+        ts_ms % 1000
+    )
+}
+
+struct Redactor {
+    secrets: Vec<String>,
+    home: Option<String>,
+    gateway_host: Option<String>,
+}
+
+impl Redactor {
+    fn new(app: &AppHandle) -> Self {
+        let state = app.state::<AppState>();
+        let mut secrets = vec![state.exec_host_token.clone()];
+        let mut gateway_host = None;
+        if let Ok(config) = state.config.lock() {
+            secrets.extend(config.gateway_token.clone());
+            secrets.extend(config.gateway_password.clone());
+            secrets.extend(config.local_api_token.clone());
+            secrets.extend(config.mqtt_password.clone());
+            secrets.extend(config.run_as_password.clone());
+            let host = config.host.trim();
+            if !matches!(host, "" | "127.0.0.1" | "localhost" | "::1") {
+                gateway_host = Some(host.to_string());
+            }
+        }
+        let home = directories::BaseDirs::new()
+            .map(|dirs| dirs.home_dir().to_string_lossy().to_string())
+            .filter(|home| home.len() > 1);
+        Self {
+            secrets,
+            home,
+            gateway_host,
+        }
+    }
+
+    fn apply(&self, line: &str) -> String {
+        let mut text = crate::redact_secrets(line, &self.secrets);
+        if let Some(ref home) = self.home {
+            text = text.replace(home.as_str(), "~");
+        }
+        if let Some(ref host) = self.gateway_host {
+            text = text.replace(host.as_str(), "<gateway-host>");
+        }
+        text
+    }
+}
+
+/// Read a crash report, decompressing `.gz` files.
+fn read_crash_report(path: &Path) -> Option<String> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("gz") {
+        return fs::read_to_string(path).ok();
+    }
+    let mut raw = String::new();
+    flate2::read::GzDecoder::new(fs::File::open(path).ok()?)
+        .read_to_string(&mut raw)
+        .ok()?;
+    Some(raw)
+}
+
+fn load_crash_reports() -> Vec<(String, NodeCrashReport)> {
+    let Ok(dir) = crate::logs_dir().map(|dir| dir.join("node-client-crashes")) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<(String, NodeCrashReport)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let name = name.strip_suffix(".gz").unwrap_or(&name).to_string();
+            let raw = read_crash_report(&entry.path())?;
+            let report = serde_json::from_str(&raw).ok()?;
+            Some((name, report))
+        })
+        .collect();
+    reports.sort_by_key(|(_, report)| report.exited_at_ms);
+    reports
+}
+
+fn render_export(app: &AppHandle, options: &LogExportOptions) -> Result<String, String> {
+    let redactor = options.redact.then(|| Redactor::new(app));
+    let scrub = |line: &str| match redactor {
+        Some(ref redactor) => redactor.apply(line),
+        None => line.to_string(),
+    };
+
+    let mut out = format!(
+        "# OpenClaw node client logs\n# app version: {}\n# exported at: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        format_utc_ms(crate::now_ms())
+    );
+
+    if options.include_crash_reports {
+        for (name, report) in load_crash_reports() {
+            out.push_str(&format!(
+                "\n## Crash report {} (exited {}, {})\n",
+                name,
+                format_utc_ms(report.exited_at_ms),
+                report.exit_status
+            ));
+            for line in report.log_tail.iter().filter(|l| options.matches_text(l)) {
+                out.push_str(&scrub(line));
+                out.push('\n');
+            }
+        }
+    }
+
+    let entries: Vec<LogEntry> = {
+        let state = app.state::<AppState>();
+        let runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+        runtime
+            .logs
+            .iter()
+            .filter(|entry| options.matches(entry))
+            .cloned()
+            .collect()
+    };
+    out.push_str("\n## Current session\n");
+    for entry in entries {
+        out.push_str(&format!(
+            "{} {:<5} {}\n",
+            format_utc_ms(entry.ts_ms),
+            entry.level,
+            scrub(&entry.render())
+        ));
+    }
+    Ok(out)
+}
+
+/// Ask for a destination and write the log export there. Returns the path
+/// written, or `None` when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_logs(
+    app: AppHandle,
+    options: Option<LogExportOptions>,
+) -> Result<Option<String>, String> {
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        let contents = render_export(&app, &options)?;
+        let file_name = format!("openclaw-node-client-{}.log", crate::now_ms());
+        let Some(selected) = app
+            .dialog()
+            .file()
+            .set_title("Export logs")
+            .set_file_name(file_name)
+            .add_filter("Log files", &["log", "txt"])
+            .blocking_save_file()
+        else {
+            return Ok(None);
+        };
+        let path: PathBuf = selected.into_path().map_err(|err| err.to_string())?;
+        fs::write(&path, contents)
+            .map_err(|err| format!("failed to write {}: {}", path.display(), err))?;
+        crate::push_log_line(&app, format!("exported logs to {}", path.display()));
+        Ok(Some(path.to_string_lossy().to_string()))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
@@ -0,0 +1,274 @@
+// Forwarding of node host output to the system log (Linux).
+//
+// Fleets usually collect logs from journald or a syslog server rather than
+// from per-app files. With `log_forwarding` set, every stdout/stderr line of
+// the node host is also sent there, after secret redaction:
+//
+//   "journald"  the journal's native socket, with structured fields
+//               MESSAGE, PRIORITY, SYSLOG_IDENTIFIER=openclaw-node,
+//               OPENCLAW_STREAM (stdout | stderr), OPENCLAW_LEVEL
+//   "syslog"    RFC 5424 over UDP to `log_forward_syslog_address`
+//               (`host:port`), or the local `/dev/log` when that is empty
+//
+// Levels map to syslog severities error (3), warning (4), info (6) and
+// debug (7), facility user. Delivery is best effort: a send that fails drops
+// the line, and an unreachable target is retried after `RETRY_SECS`.
+
+use std::io;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::os::unix::net::UnixDatagram;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use crate::{LogEntry, NodeClientConfig};
+
+const JOURNAL_SOCKET: &str = "/run/systemd/journal/socket";
+const SYSLOG_SOCKET: &str = "/dev/log";
+const IDENTIFIER: &str = "openclaw-node";
+/// Longer lines are cut; syslog receivers commonly drop larger datagrams.
+const MAX_MESSAGE_BYTES: usize = 8 * 1024;
+const RETRY_SECS: u64 = 30;
+/// Syslog facility "user".
+const FACILITY: u8 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Journald,
+    /// UDP `host:port`, or the local syslog socket when `None`.
+    Syslog(Option<String>),
+}
+
+impl Target {
+    pub fn from_config(config: &NodeClientConfig) -> Option<Self> {
+        match config.log_forwarding.as_str() {
+            "journald" => Some(Target::Journald),
+            "syslog" => Some(Target::Syslog(
+                crate::non_empty(&config.log_forward_syslog_address).map(str::to_string),
+            )),
+            _ => None,
+        }
+    }
+}
+
+enum Sink {
+    Unix(UnixDatagram),
+    Udp(UdpSocket),
+}
+
+impl Sink {
+    fn open(target: &Target) -> io::Result<Self> {
+        let sink = match target {
+            Target::Journald => Sink::Unix(unix_socket(JOURNAL_SOCKET)?),
+            Target::Syslog(None) => Sink::Unix(unix_socket(SYSLOG_SOCKET)?),
+            Target::Syslog(Some(address)) => {
+                let remote = address.to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "address did not resolve")
+                })?;
+                let local: SocketAddr = if remote.is_ipv4() {
+                    ([0, 0, 0, 0], 0).into()
+                } else {
+                    ([0u16; 8], 0).into()
+                };
+                let socket = UdpSocket::bind(local)?;
+                socket.connect(remote)?;
+                socket.set_nonblocking(true)?;
+                Sink::Udp(socket)
+            }
+        };
+        Ok(sink)
+    }
+
+    fn send(&self, datagram: &[u8]) -> io::Result<usize> {
+        match self {
+            Sink::Unix(socket) => socket.send(datagram),
+            Sink::Udp(socket) => socket.send(datagram),
+        }
+    }
+}
+
+fn unix_socket(path: &str) -> io::Result<UnixDatagram> {
+    let socket = UnixDatagram::unbound()?;
+    socket.connect(path)?;
+    socket.set_nonblocking(true)?;
+    Ok(socket)
+}
+
+struct Forwarder {
+    target: Target,
+    sink: Option<Sink>,
+    failed_at: Option<Instant>,
+}
+
+static FORWARDER: Mutex<Option<Forwarder>> = Mutex::new(None);
+
+fn severity(level: &str) -> u8 {
+    match level {
+        "error" => 3,
+        "warn" => 4,
+        "debug" => 7,
+        _ => 6,
+    }
+}
+
+fn truncate(message: &str) -> &str {
+    if message.len() <= MAX_MESSAGE_BYTES {
+        return message;
+    }
+    let mut end = MAX_MESSAGE_BYTES;
+    while !message.is_char_boundary(end) {
+        end -= 1;
+    }
+    &message[..end]
+}
+
+/// A journal native-protocol datagram. Values with newlines use the
+/// length-prefixed form.
+fn journal_datagram(entry: &LogEntry) -> Vec<u8> {
+    let priority = severity(&entry.level).to_string();
+    let fields = [
+        ("MESSAGE", truncate(&entry.message)),
+        ("PRIORITY", priority.as_str()),
+        ("SYSLOG_IDENTIFIER", IDENTIFIER),
+        ("SYSLOG_FACILITY", "1"),
+        ("OPENCLAW_STREAM", entry.stream.as_str()),
+        ("OPENCLAW_LEVEL", entry.level.as_str()),
+    ];
+    let mut datagram = Vec::new();
+    for (key, value) in fields {
+        datagram.extend_from_slice(key.as_bytes());
+        if value.contains('\n') {
+            datagram.push(b'\n');
+            datagram.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            datagram.push(b'=');
+        }
+        datagram.extend_from_slice(value.as_bytes());
+        datagram.push(b'\n');
+    }
+    datagram
+}
+
+fn hostname() -> &'static str {
+    static HOSTNAME: OnceLock<String> = OnceLock::new();
+    HOSTNAME.get_or_init(|| {
+        std::fs::read_to_string("/proc/sys/kernel/hostname")
+            .ok()
+            .map(|name| name.trim().replace(' ', "_"))
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "-".to_string())
+    })
+}
+
+/// `ts_ms` as an RFC 3339 UTC timestamp with milliseconds.
+fn timestamp(ts_ms: u64) -> String {
+    let secs = ts_ms / 1000;
+    let (hour, minute, second) = (secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    // Days since the epoch to a civil date (Howard Hinnant's algorithm)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        hour,
+        minute,
+        second,
+        ts_ms % 1000
+    )
+}
+
+/// RFC 5424 for a remote receiver; the local socket gets the traditional
+/// `<PRI>TAG: MSG` form that every syslog daemon parses.
+fn syslog_datagram(entry: &LogEntry, remote: bool) -> Vec<u8> {
+    let priority = FACILITY * 8 + severity(&entry.level);
+    let message = truncate(&entry.message).replace('\n', " ");
+    let line = if remote {
+        format!(
+            "<{}>1 {} {} {} {} {} - {}",
+            priority,
+            timestamp(entry.ts_ms),
+            hostname(),
+            IDENTIFIER,
+            std::process::id(),
+            entry.stream,
+            message
+        )
+    } else {
+        format!(
+            "<{}>{}[{}]: {}",
+            priority,
+            IDENTIFIER,
+            std::process::id(),
+            message
+        )
+    };
+    line.into_bytes()
+}
+
+/// Send a node host log line to `target`.
+pub fn forward(target: &Target, entry: &LogEntry) {
+    let Ok(mut forwarder) = FORWARDER.lock() else {
+        return;
+    };
+    if forwarder
+        .as_ref()
+        .is_none_or(|current| current.target != *target)
+    {
+        *forwarder = Some(Forwarder {
+            target: target.clone(),
+            sink: None,
+            failed_at: None,
+        });
+    }
+    let Some(forwarder) = forwarder.as_mut() else {
+        return;
+    };
+    if forwarder.sink.is_none() {
+        let retry = forwarder
+            .failed_at
+            .is_none_or(|at| at.elapsed() >= Duration::from_secs(RETRY_SECS));
+        if !retry {
+            return;
+        }
+        match Sink::open(target) {
+            Ok(sink) => {
+                forwarder.sink = Some(sink);
+                forwarder.failed_at = None;
+            }
+            Err(err) => {
+                if forwarder.failed_at.is_none() {
+                    eprintln!("log forwarding to {:?} unavailable: {}", target, err);
+                }
+                forwarder.failed_at = Some(Instant::now());
+                return;
+            }
+        }
+    }
+    let datagram = match target {
+        Target::Journald => journal_datagram(entry),
+        Target::Syslog(address) => syslog_datagram(entry, address.is_some()),
+    };
+    let sent = forwarder.sink.as_ref().map(|sink| sink.send(&datagram));
+    match sent {
+        Some(Ok(_)) => {}
+        // A full socket buffer drops this line only
+        Some(Err(err)) if err.kind() == io::ErrorKind::WouldBlock => {}
+        _ => {
+            // The daemon restarted or the network changed; reopen next time
+            forwarder.sink = None;
+        }
+    }
+}
@@ -1,20 +1,63 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod app_update;
+mod approval_notify;
+mod approval_window;
+mod cli;
+mod clipboard_sync;
+mod control;
+mod config_backup;
+mod config_watch;
+mod crash_report;
+mod credentials;
+mod error_report;
+#[cfg(target_os = "linux")]
+mod dbus_service;
+mod event_log;
+mod file_transfer;
 mod gateway;
-
+mod gateway_probe;
+mod gateway_report;
+mod hooks;
+mod i18n;
+mod local_api;
+mod log_export;
+#[cfg(target_os = "linux")]
+mod log_forward;
+mod managed_node;
+mod metrics;
+mod mqtt;
+mod node_service;
+mod notifier;
+mod openclaw_sync;
+mod run_as;
+mod runtime_update;
+mod settings_bundle;
+#[cfg(not(target_os = "windows"))]
+mod shell_profile;
+mod shims;
+mod telemetry;
+mod terminal;
+mod tls_trust;
+mod tray_status;
+mod tunnel;
+mod watchdog;
+mod wsl;
+
+use bytes::{BufMut, Bytes, BytesMut};
 use directories::BaseDirs;
 use hmac::{Hmac, Mac};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
-use std::collections::{HashMap, VecDeque};
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs;
-use std::io::{BufRead, BufReader, Read};
+use std::io::Read;
 use std::path::{Path, PathBuf};
-use std::process::{Child, Command, Stdio};
+use std::process::{Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::time::{SystemTime, UNIX_EPOCH};
-use tauri::menu::{MenuBuilder, MenuItemBuilder};
 use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
 use tauri::{AppHandle, Emitter, Manager, State, WindowEvent};
 use tauri_plugin_autostart::MacosLauncher;
@@ -29,11 +72,43 @@ use std::os::unix::process::CommandExt as UnixCommandExt;
 type HmacSha256 = Hmac<Sha256>;
 
 const LOG_CAP: usize = 300;
+/// Initial size of the exec-host socket's read and write buffers.
+const SOCKET_BUFFER_BYTES: usize = 8 * 1024;
+/// Longest message accepted on the exec-host socket.
+const MAX_SOCKET_FRAME_BYTES: usize = 16 * 1024 * 1024;
+/// Window over which node log lines are collected into one webview event.
+const NODE_LOG_BATCH_MS: u64 = 50;
+/// Log lines captured into a crash report.
+const CRASH_LOG_TAIL: usize = 100;
+const VERSION_PROBE_TIMEOUT_MS: u64 = 10_000;
+/// Longest the startup gateway connection waits for the node host spawn.
+const NODE_SPAWN_WAIT_MS: u64 = 1_500;
+const DISCOVERY_PROBE_TIMEOUT_MS: u64 = 3_000;
+
+/// Oldest openclaw CLI release whose `node run` flags this app relies on.
+const MIN_OPENCLAW_VERSION: &str = "2026.1.0";
+/// First release whose node host resolves `OPENCLAW_GATEWAY_PASSWORD`
+/// through the shared gateway auth resolver (`resolveNodeHostGatewayCredentials`
+/// in node-host/runner.ts, #39241); older ones only take `--password`.
+const GATEWAY_PASSWORD_ENV_VERSION: &str = "2026.3.7";
 const HMAC_MAX_DRIFT_MS: u64 = 60_000;
 const APPROVAL_TIMEOUT_MS: u64 = 120_000;
+const APPROVAL_SWEEP_INTERVAL_MS: u64 = 5_000;
+const PROCESS_POLL_INTERVAL_MS: u64 = 2_000;
 
 #[cfg(target_os = "windows")]
 const CREATE_NO_WINDOW: u32 = 0x08000000;
+#[cfg(target_os = "windows")]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+#[cfg(target_os = "windows")]
+const BELOW_NORMAL_PRIORITY_CLASS: u32 = 0x00004000;
+/// Nice value for the node host when `low_priority` is enabled.
+#[cfg(unix)]
+const LOW_PRIORITY_NICE: libc::c_int = 10;
+
+/// How long a node host gets to exit after a graceful stop request before
+/// it is forcibly killed.
+const NODE_STOP_GRACE_MS: u64 = 5_000;
 
 #[cfg(target_os = "windows")]
 const OPENCLAW_BIN_NAMES: &[&str] = &["openclaw.cmd", "openclaw.ps1", "openclaw.exe"];
@@ -194,18 +269,289 @@ struct NodeClientConfig {
     use_exec_host: bool,
     #[serde(default = "default_true")]
     exec_host_fallback: bool,
+    /// Keep the exec-host token in the OS credential store and write only a
+    /// `tokenRef` to exec-approvals.json. The CLI reads it back from the
+    /// Keychain or Secret Service; on Windows only the node host this app
+    /// starts gets it (in `OPENCLAW_EXEC_HOST_TOKEN`).
+    #[serde(default)]
+    exec_host_token_in_keychain: bool,
     gateway_token: Option<String>,
     gateway_password: Option<String>,
     #[serde(default)]
     install_path: Option<String>,
     #[serde(default = "default_true")]
     use_bundled_runtime: bool,
+    #[serde(default)]
+    use_managed_node: bool,
+    /// A local openclaw checkout to run instead of any installed CLI, for
+    /// testing the app against a working tree. Its built entry script runs
+    /// under the system node.
+    #[serde(default)]
+    dev_root: Option<String>,
+    /// Extra environment variables for the `openclaw node run` child
+    /// (proxy vars, debug flags, NODE_OPTIONS, ...). Applied last.
+    #[serde(default)]
+    extra_env: HashMap<String, String>,
+    /// Extra arguments appended to `openclaw node run`, for CLI flags the
+    /// app doesn't model yet.
+    #[serde(default)]
+    extra_args: Vec<String>,
+    /// What to do with a node host left running by a previous app instance:
+    /// "terminate" (default) or "adopt".
+    #[serde(default = "default_orphan_policy")]
+    orphan_policy: String,
+    /// Run the node host at below-normal CPU priority so background agents
+    /// don't compete with foreground work.
+    #[serde(default)]
+    low_priority: bool,
+    /// Restart the node host when the gateway reports it offline for this
+    /// many minutes while the process is still alive. 0 disables.
+    #[serde(default = "default_watchdog_offline_minutes")]
+    watchdog_offline_minutes: u32,
+    /// Run the node host as this (low-privilege) account instead of the
+    /// desktop user. Unix uses `sudo -n`, so it needs a NOPASSWD + SETENV
+    /// sudoers rule for the openclaw binary; Windows logs on with
+    /// `run_as_password`.
+    #[serde(default)]
+    run_as_user: Option<String>,
+    /// Password of `run_as_user`, for the Windows logon.
+    #[serde(default)]
+    run_as_password: Option<String>,
+    /// Fixed delay before auto-starting the node host at app launch.
+    #[serde(default)]
+    auto_start_delay_secs: u32,
+    /// Hold auto-start until the gateway host:port accepts TCP connections
+    /// (network up, gateway listening), for at most `auto_start_max_wait_secs`.
+    #[serde(default = "default_true")]
+    auto_start_wait_for_gateway: bool,
+    #[serde(default = "default_auto_start_max_wait_secs")]
+    auto_start_max_wait_secs: u32,
+    /// When launched at login (autostart passes `--minimized`), keep the
+    /// window hidden in the tray instead of minimizing it.
+    #[serde(default)]
+    start_minimized_to_tray: bool,
+    /// Post a desktop notification with Allow / Deny buttons for each exec
+    /// approval instead of opening the approval popup.
+    #[serde(default = "default_true")]
+    approval_notifications: bool,
+    /// Working directory for `openclaw node run` (relative workspace paths
+    /// resolve against it). Defaults to the home directory so tray autostart
+    /// and terminal launches behave the same.
+    #[serde(default)]
+    working_dir: Option<String>,
+    /// PEM bundle of extra CA certificates trusted for the gateway
+    /// connection (forwarded as NODE_EXTRA_CA_CERTS).
+    #[serde(default)]
+    tls_ca_path: Option<String>,
+    /// Client certificate and key (PEM) for gateways that require mutual TLS.
+    #[serde(default)]
+    tls_client_cert_path: Option<String>,
+    #[serde(default)]
+    tls_client_key_path: Option<String>,
+    /// Disable gateway certificate verification entirely. Prefer
+    /// `tls_fingerprint` or `tls_ca_path`; this is a last resort for testing.
+    #[serde(default)]
+    tls_insecure_skip_verify: bool,
+    /// Restart the app-owned node host when a saved config change affects it.
+    /// When off, the UI is told a restart is pending and asks first.
+    #[serde(default = "default_true")]
+    auto_restart_on_config_change: bool,
+    /// Keychain service holding `gateway_token`/`gateway_password`. When set,
+    /// those fields are left out of node-client.json.
+    #[serde(default)]
+    keychain_ref: Option<String>,
+    /// Encrypt node-client.json with a key held in the OS keychain.
+    #[serde(default)]
+    encrypt_at_rest: bool,
+    /// Mirror gateway port, TLS and credential changes into the CLI's
+    /// openclaw.json.
+    #[serde(default)]
+    sync_openclaw_config: bool,
+    /// Report node client health (status transitions, errors) to the
+    /// gateway as presence beacons (see `gateway_report`).
+    #[serde(default)]
+    gateway_reporting: bool,
+    /// Opt-in endpoint that crash reports are POSTed to on the next launch
+    /// (see `crash_report`).
+    #[serde(default)]
+    crash_report_upload_url: Option<String>,
+    /// App release channel: "stable" or "beta" (see `app_update`).
+    #[serde(default = "default_update_channel")]
+    update_channel: String,
+    /// Hours between background app update checks; 0 turns them off.
+    #[serde(default = "default_update_check_interval_hours")]
+    update_check_interval_hours: u64,
+    /// Language for tray labels, notifications and app log annotations, as a
+    /// BCP 47 tag ("de", "fr-CA"); unset follows the OS (see `i18n`).
+    #[serde(default)]
+    locale: Option<String>,
+    /// Serve the token-protected localhost HTTP API (see `local_api`).
+    #[serde(default)]
+    local_api_enabled: bool,
+    #[serde(default = "default_local_api_port")]
+    local_api_port: u16,
+    /// Bearer token for the local API; generated on first enable.
+    #[serde(default)]
+    local_api_token: Option<String>,
+    /// Publish node, gateway and approval state to an MQTT broker (see `mqtt`).
+    #[serde(default)]
+    mqtt_enabled: bool,
+    #[serde(default)]
+    mqtt_host: Option<String>,
+    #[serde(default = "default_mqtt_port")]
+    mqtt_port: u16,
+    #[serde(default)]
+    mqtt_username: Option<String>,
+    #[serde(default)]
+    mqtt_password: Option<String>,
+    #[serde(default = "default_mqtt_topic_prefix")]
+    mqtt_topic_prefix: String,
+    /// Also publish Home Assistant discovery configs.
+    #[serde(default = "default_true")]
+    mqtt_ha_discovery: bool,
+    /// Scripts run on app events, keyed by event name (see `hooks`).
+    #[serde(default)]
+    event_hooks: HashMap<String, String>,
+    /// Let the gateway request shell sessions on this machine; each one still
+    /// needs approval (see `terminal`).
+    #[serde(default)]
+    remote_terminal_enabled: bool,
+    /// Allow pushing and pulling clipboard contents over the gateway
+    /// connection (see `clipboard_sync`). Always user-triggered.
+    #[serde(default)]
+    clipboard_sync_enabled: bool,
+    /// Clipboard kinds that may be exchanged: "text", "image".
+    #[serde(default = "default_clipboard_sync_types")]
+    clipboard_sync_types: Vec<String>,
+    #[serde(default = "default_clipboard_sync_max_bytes")]
+    clipboard_sync_max_bytes: u64,
+    /// Send host capacity metrics to the gateway (see `metrics`).
+    #[serde(default)]
+    metrics_reporting: bool,
+    #[serde(default = "default_metrics_interval_secs")]
+    metrics_interval_secs: u64,
+    /// Metric groups to send: "cpu", "memory", "disk", "node".
+    #[serde(default = "default_metrics_reported")]
+    metrics_reported: Vec<String>,
+    /// Slack incoming webhook and Discord webhook notified of approvals and
+    /// node crashes (see `notifier`).
+    #[serde(default)]
+    slack_webhook_url: Option<String>,
+    #[serde(default)]
+    discord_webhook_url: Option<String>,
+    /// Events sent to the webhooks: "approval-pending", "approval-resolved",
+    /// "node-crashed".
+    #[serde(default = "default_notifier_events")]
+    notifier_events: Vec<String>,
+    /// Message template per event, overriding the built-in one.
+    #[serde(default)]
+    notifier_templates: HashMap<String, String>,
+    /// Also send node host output to the system log (Linux only): "off",
+    /// "journald" or "syslog" (see `log_forward`).
+    #[serde(default = "default_log_forwarding")]
+    log_forwarding: String,
+    /// Syslog receiver as `host:port` (UDP); empty uses the local `/dev/log`.
+    #[serde(default)]
+    log_forward_syslog_address: Option<String>,
+    /// Write approvals, executed commands, exec policy changes and token
+    /// rotations to the Windows Event Log (see `event_log`).
+    #[serde(default)]
+    windows_event_log: bool,
+    /// OTLP/HTTP collector that receives traces of gateway RPCs and exec
+    /// requests, e.g. `http://localhost:4318` (see `telemetry`).
+    #[serde(default)]
+    otlp_endpoint: Option<String>,
+    /// Extra headers sent to the collector, e.g. an API key.
+    #[serde(default)]
+    otlp_headers: HashMap<String, String>,
+    /// Sentry-compatible DSN that receives handled errors, scrubbed of
+    /// secrets (see `error_report`). Unset disables reporting.
+    #[serde(default)]
+    error_report_dsn: Option<String>,
+    /// ntfy topic URL (e.g. `https://ntfy.sh/my-topic`) that pending
+    /// approvals are pushed to, and its access token if the topic is
+    /// protected (see `notifier`).
+    #[serde(default)]
+    ntfy_topic_url: Option<String>,
+    #[serde(default)]
+    ntfy_token: Option<String>,
+    /// Pushover user key and application token for approval pushes.
+    #[serde(default)]
+    pushover_user_key: Option<String>,
+    #[serde(default)]
+    pushover_app_token: Option<String>,
+    /// Link opened from an approval push, with `{id}` replaced by the
+    /// approval id. Unset links to the gateway's Control UI.
+    #[serde(default)]
+    push_approval_link: Option<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_orphan_policy() -> String {
+    "terminate".to_string()
+}
+
+fn default_watchdog_offline_minutes() -> u32 {
+    5
+}
+
+fn default_auto_start_max_wait_secs() -> u32 {
+    120
+}
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+fn default_log_forwarding() -> String {
+    "off".to_string()
+}
+
+fn default_update_check_interval_hours() -> u64 {
+    24
+}
+
+fn default_local_api_port() -> u16 {
+    18790
+}
+
+fn default_mqtt_port() -> u16 {
+    1883
+}
+
+fn default_mqtt_topic_prefix() -> String {
+    "openclaw/node-client".to_string()
+}
+
+fn default_clipboard_sync_types() -> Vec<String> {
+    vec!["text".to_string()]
+}
+
+fn default_clipboard_sync_max_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_metrics_interval_secs() -> u64 {
+    60
+}
+
+fn default_notifier_events() -> Vec<String> {
+    notifier::NOTIFIER_EVENTS
+        .iter()
+        .map(|event| event.to_string())
+        .collect()
+}
+
+fn default_metrics_reported() -> Vec<String> {
+    metrics::METRIC_GROUPS
+        .iter()
+        .map(|group| group.to_string())
+        .collect()
+}
+
 impl Default for NodeClientConfig {
     fn default() -> Self {
         Self {
@@ -218,10 +564,72 @@ impl Default for NodeClientConfig {
             auto_start_node: true,
             use_exec_host: false,
             exec_host_fallback: true,
+            exec_host_token_in_keychain: false,
             gateway_token: None,
             gateway_password: None,
             install_path: None,
             use_bundled_runtime: true,
+            use_managed_node: false,
+            dev_root: None,
+            extra_env: HashMap::new(),
+            extra_args: Vec::new(),
+            orphan_policy: default_orphan_policy(),
+            low_priority: false,
+            watchdog_offline_minutes: default_watchdog_offline_minutes(),
+            run_as_user: None,
+            run_as_password: None,
+            auto_start_delay_secs: 0,
+            auto_start_wait_for_gateway: true,
+            auto_start_max_wait_secs: default_auto_start_max_wait_secs(),
+            start_minimized_to_tray: false,
+            approval_notifications: true,
+            working_dir: None,
+            tls_ca_path: None,
+            tls_client_cert_path: None,
+            tls_client_key_path: None,
+            tls_insecure_skip_verify: false,
+            auto_restart_on_config_change: true,
+            keychain_ref: None,
+            encrypt_at_rest: false,
+            sync_openclaw_config: false,
+            gateway_reporting: false,
+            crash_report_upload_url: None,
+            update_channel: default_update_channel(),
+            update_check_interval_hours: default_update_check_interval_hours(),
+            locale: None,
+            local_api_enabled: false,
+            local_api_port: default_local_api_port(),
+            local_api_token: None,
+            mqtt_enabled: false,
+            mqtt_host: None,
+            mqtt_port: default_mqtt_port(),
+            mqtt_username: None,
+            mqtt_password: None,
+            mqtt_topic_prefix: default_mqtt_topic_prefix(),
+            mqtt_ha_discovery: true,
+            event_hooks: HashMap::new(),
+            remote_terminal_enabled: false,
+            clipboard_sync_enabled: false,
+            clipboard_sync_types: default_clipboard_sync_types(),
+            clipboard_sync_max_bytes: default_clipboard_sync_max_bytes(),
+            metrics_reporting: false,
+            metrics_interval_secs: default_metrics_interval_secs(),
+            metrics_reported: default_metrics_reported(),
+            slack_webhook_url: None,
+            discord_webhook_url: None,
+            notifier_events: default_notifier_events(),
+            notifier_templates: HashMap::new(),
+            log_forwarding: default_log_forwarding(),
+            log_forward_syslog_address: None,
+            windows_event_log: false,
+            otlp_endpoint: None,
+            otlp_headers: HashMap::new(),
+            error_report_dsn: None,
+            ntfy_topic_url: None,
+            ntfy_token: None,
+            pushover_user_key: None,
+            pushover_app_token: None,
+            push_approval_link: None,
         }
     }
 }
@@ -231,15 +639,72 @@ impl NodeClientConfig {
         let scheme = if self.tls { "wss" } else { "ws" };
         format!("{}://{}:{}", scheme, self.host, self.port)
     }
+
+    /// Check the TLS settings before they are handed to the node host, so a
+    /// typo surfaces as a start error instead of an opaque handshake failure.
+    fn validate_tls_options(&self) -> Result<(), String> {
+        if let Some(fp) = non_empty(&self.tls_fingerprint) {
+            if normalize_fingerprint(fp).is_none() {
+                return Err(format!(
+                    "TLS fingerprint {} is not a SHA-256 hex fingerprint (64 hex digits, colons allowed)",
+                    fp
+                ));
+            }
+        }
+        let files = [
+            ("TLS CA bundle", &self.tls_ca_path),
+            ("TLS client certificate", &self.tls_client_cert_path),
+            ("TLS client key", &self.tls_client_key_path),
+        ];
+        for (label, path) in files {
+            if let Some(path) = non_empty(path) {
+                if !Path::new(path).is_file() {
+                    return Err(format!("{} {} does not exist", label, path));
+                }
+            }
+        }
+        match (
+            non_empty(&self.tls_client_cert_path),
+            non_empty(&self.tls_client_key_path),
+        ) {
+            (Some(_), None) => {
+                Err("TLS client certificate is set without a client key".to_string())
+            }
+            (None, Some(_)) => {
+                Err("TLS client key is set without a client certificate".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+/// Lowercase hex of a SHA-256 fingerprint written as `sha256:AA:BB:...`,
+/// plain hex, or anything in between. `None` if it isn't 64 hex digits.
+fn normalize_fingerprint(fp: &str) -> Option<String> {
+    let lower = fp.trim().to_ascii_lowercase();
+    let body = lower
+        .strip_prefix("sha256")
+        .or_else(|| lower.strip_prefix("sha-256"))
+        .unwrap_or(&lower);
+    let hex: String = body
+        .chars()
+        .filter(|c| *c != ':' && !c.is_whitespace())
+        .collect();
+    (hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit())).then_some(hex)
 }
 
 // ---------------------------------------------------------------------------
 // Node status
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 enum NodeStatus {
+    #[default]
     Stopped,
     Starting,
     Running,
@@ -265,12 +730,212 @@ impl NodeStatus {
 // Runtime state
 // ---------------------------------------------------------------------------
 
+/// One captured log line. `stream` is "stdout" / "stderr" for node host
+/// output and "app" for messages from the app itself.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LogEntry {
+    ts_ms: u64,
+    stream: String,
+    level: String,
+    message: String,
+    /// Number of consecutive identical lines collapsed into this entry
+    repeat: u32,
+}
+
+impl LogEntry {
+    fn new(stream: &str, message: String) -> Self {
+        Self {
+            ts_ms: now_ms(),
+            stream: stream.to_string(),
+            level: infer_log_level(stream, &message).to_string(),
+            message,
+            repeat: 1,
+        }
+    }
+
+    /// Legacy single-string form ("[stderr] ..."), kept for `logs` in the
+    /// status response and crash reports.
+    fn render(&self) -> String {
+        let line = if self.stream == "app" {
+            self.message.clone()
+        } else {
+            format!("[{}] {}", self.stream, self.message)
+        };
+        if self.repeat > 1 {
+            format!("{} (x{})", line, self.repeat)
+        } else {
+            line
+        }
+    }
+}
+
+/// The most recent `LOG_CAP` log entries. Storage is allocated once up
+/// front; past capacity a push overwrites the oldest entry in place.
+struct LogRing {
+    entries: Vec<LogEntry>,
+    /// Slot of the oldest entry once the ring is full
+    head: usize,
+}
+
+impl Default for LogRing {
+    fn default() -> Self {
+        Self {
+            entries: Vec::with_capacity(LOG_CAP),
+            head: 0,
+        }
+    }
+}
+
+impl LogRing {
+    fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn push(&mut self, entry: LogEntry) {
+        if self.entries.len() < LOG_CAP {
+            self.entries.push(entry);
+        } else {
+            self.entries[self.head] = entry;
+            self.head = (self.head + 1) % LOG_CAP;
+        }
+    }
+
+    fn newest_slot(&self) -> Option<usize> {
+        if self.entries.is_empty() {
+            None
+        } else if self.entries.len() < LOG_CAP {
+            Some(self.entries.len() - 1)
+        } else {
+            Some((self.head + LOG_CAP - 1) % LOG_CAP)
+        }
+    }
+
+    /// Push `entry`, or fold it into the newest entry when it repeats that
+    /// one, so a spamming child can't flush the buffer. Returns the entry as
+    /// stored; the UI replaces its last row when `repeat` > 1.
+    fn push_collapsing(&mut self, entry: LogEntry) -> LogEntry {
+        match self.back_mut() {
+            Some(last) if last.stream == entry.stream && last.message == entry.message => {
+                last.repeat += 1;
+                last.ts_ms = entry.ts_ms;
+                last.clone()
+            }
+            _ => {
+                self.push(entry.clone());
+                entry
+            }
+        }
+    }
+
+    fn back(&self) -> Option<&LogEntry> {
+        self.newest_slot().map(|slot| &self.entries[slot])
+    }
+
+    fn back_mut(&mut self) -> Option<&mut LogEntry> {
+        self.newest_slot().map(|slot| &mut self.entries[slot])
+    }
+
+    /// Entries oldest first.
+    fn iter(&self) -> impl DoubleEndedIterator<Item = &LogEntry> {
+        let (newer, older) = self.entries.split_at(self.head);
+        older.iter().chain(newer)
+    }
+}
+
+fn infer_log_level(stream: &str, message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("fatal") || lower.contains("error") || lower.contains("failed") {
+        "error"
+    } else if lower.contains("warn") {
+        "warn"
+    } else if lower.contains("debug") {
+        "debug"
+    } else if stream == "stderr" {
+        "warn"
+    } else {
+        "info"
+    }
+}
+
 #[derive(Default)]
 struct RuntimeState {
-    child: Option<Child>,
-    logs: VecDeque<String>,
+    child: Option<tokio::process::Child>,
+    logs: LogRing,
     last_error: Option<String>,
     node_status: Option<NodeStatus>,
+    started_at_ms: Option<u64>,
+    // Node host left over from a previous app instance that we monitor by pid
+    // (no pipes, so no logs)
+    adopted_pid: Option<u32>,
+    // An OS service (`openclaw node install`) owns the node host; the app only
+    // monitors it
+    service_managed: bool,
+    service_pid: Option<u32>,
+    // Whether the running child was started at below-normal priority
+    low_priority: bool,
+    // Account the running child was started as (`run_as_user`)
+    run_as_user: Option<String>,
+    // Node id reported by the node host after it connected
+    node_id: Option<String>,
+    // Crash detected by refresh_process_state but not yet written or emitted
+    unreported_crash: Option<NodeCrashReport>,
+    // Published copy of the status fields for readers that shouldn't take
+    // this lock (see `AppState::node_status`)
+    status_tx: tokio::sync::watch::Sender<NodeStatusSnapshot>,
+}
+
+impl RuntimeState {
+    /// Publish the status fields to `AppState::node_status`. Call after
+    /// changing any of them.
+    fn publish_status(&self) {
+        let running = self.child.is_some()
+            || self.adopted_pid.is_some()
+            || (self.service_managed && self.service_pid.is_some());
+        let snapshot = NodeStatusSnapshot {
+            running,
+            status: self.node_status.clone().unwrap_or(if running {
+                NodeStatus::Running
+            } else {
+                NodeStatus::Stopped
+            }),
+            last_error: self.last_error.clone(),
+            service_managed: self.service_managed,
+            low_priority: running && self.low_priority,
+            node_id: self.node_id.clone(),
+        };
+        self.status_tx.send_if_modified(|current| {
+            if *current == snapshot {
+                return false;
+            }
+            *current = snapshot;
+            true
+        });
+    }
+}
+
+/// Node host status as of the last change to `RuntimeState`.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct NodeStatusSnapshot {
+    running: bool,
+    status: NodeStatus,
+    last_error: Option<String>,
+    service_managed: bool,
+    low_priority: bool,
+    /// Node id reported by the node host; callers fall back to the config.
+    node_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NodeCrashReport {
+    exit_code: Option<i32>,
+    exit_status: String,
+    started_at_ms: Option<u64>,
+    exited_at_ms: u64,
+    log_tail: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report_path: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -293,9 +958,8 @@ struct ApprovalPreview {
 struct PendingApproval {
     id: String,
     preview: ApprovalPreview,
-    #[allow(dead_code)]
     expires_at_ms: u64,
-    tx: std::sync::mpsc::SyncSender<String>,
+    tx: tokio::sync::mpsc::Sender<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -305,7 +969,36 @@ struct PendingApproval {
 struct AppState {
     config: Mutex<NodeClientConfig>,
     runtime: Mutex<RuntimeState>,
-    pending_approvals: Mutex<Vec<PendingApproval>>,
+    // Status published by `RuntimeState::publish_status`, so status polls
+    // never wait on the runtime lock
+    node_status: tokio::sync::watch::Receiver<NodeStatusSnapshot>,
+    // A watch channel rather than a mutex: read from sync commands and async
+    // tasks without blocking the runtime, and never poisoned
+    pending_approvals: tokio::sync::watch::Sender<Vec<PendingApproval>>,
+    openclaw_version: Mutex<Option<OpenclawVersionInfo>>,
+    // Lines for the node host's stdin, written by the task that owns the
+    // pipe (see `spawn_stdin_writer`)
+    node_stdin: Mutex<Option<tokio::sync::mpsc::UnboundedSender<String>>>,
+    // Exec-host socket token, redacted from captured logs
+    exec_host_token: String,
+    // Token for companion CLI control commands (see `control`), redacted
+    // from captured logs like the exec-host token
+    control_token: String,
+    // Secrets from an import the user hasn't saved yet; the webview only
+    // ever sees them masked
+    staged_secrets: Mutex<Option<credentials::GatewaySecrets>>,
+    // Log entries waiting to be emitted to the webview (see `emit_node_log`)
+    node_log_batch: Mutex<Vec<LogEntry>>,
+    // Set while the in-memory config has changes not yet on disk; cleared by
+    // whichever write lands first (see `run_config_writer`)
+    config_dirty: tokio::sync::watch::Sender<bool>,
+    // Notified whenever `apply_config` swaps in a config that differs, for
+    // background tasks that follow settings without polling
+    config_changed: tokio::sync::watch::Sender<()>,
+    // Held across each config write, across a direct save and the swap that
+    // goes with it, and across `update_config`'s read-modify-swap, so a
+    // queued save or another update never lands in between
+    config_io: Mutex<()>,
 }
 
 // ---------------------------------------------------------------------------
@@ -320,6 +1013,21 @@ struct NodeClientStatus {
     gateway_url: String,
     last_error: Option<String>,
     logs: Vec<String>,
+    log_entries: Vec<LogEntry>,
+    openclaw_version: Option<OpenclawVersionInfo>,
+    service_managed: bool,
+    low_priority: bool,
+    node_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenclawVersionInfo {
+    bin_path: String,
+    version: Option<String>,
+    raw: String,
+    min_supported: String,
+    outdated: bool,
 }
 
 // ---------------------------------------------------------------------------
@@ -329,8 +1037,6 @@ struct NodeClientStatus {
 #[derive(Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct ExecEnvelope {
-    #[serde(rename = "type")]
-    msg_type: String,
     #[allow(dead_code)]
     id: Option<String>,
     nonce: Option<String>,
@@ -386,8 +1092,6 @@ struct ExecErrorPayload {
 
 #[derive(Deserialize)]
 struct ApprovalRequestEnvelope {
-    #[serde(rename = "type")]
-    msg_type: String,
     #[allow(dead_code)]
     token: Option<String>,
     id: Option<String>,
@@ -404,6 +1108,10 @@ struct ExecApprovalsSocket {
     path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     token: Option<String>,
+    /// Keychain reference used instead of `token` when the token is kept in
+    /// the OS credential store.
+    #[serde(rename = "tokenRef", default, skip_serializing_if = "Option::is_none")]
+    token_ref: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -488,6 +1196,9 @@ fn openclaw_dir() -> Result<PathBuf, String> {
 }
 
 fn config_path() -> Result<PathBuf, String> {
+    if let Some(path) = cli::config_override() {
+        return Ok(path);
+    }
     let dir = openclaw_dir()?;
     let new_path = dir.join("node-client.json");
     if !new_path.exists() {
@@ -499,27 +1210,74 @@ fn config_path() -> Result<PathBuf, String> {
     Ok(new_path)
 }
 
+fn logs_dir() -> Result<PathBuf, String> {
+    Ok(openclaw_dir()?.join("logs"))
+}
+
 fn exec_approvals_path() -> Result<PathBuf, String> {
     Ok(openclaw_dir()?.join("exec-approvals.json"))
 }
 
-fn exec_host_socket_path() -> String {
+/// Where the exec host listens. Fails on Unix when no private directory is
+/// available for the socket, in which case the exec host doesn't start.
+fn exec_host_socket_path() -> Result<String, String> {
     #[cfg(target_os = "windows")]
     {
-        r"\\.\pipe\openclaw-exec-host".to_string()
+        Ok(r"\\.\pipe\openclaw-exec-host".to_string())
     }
     #[cfg(not(target_os = "windows"))]
     {
-        let base = BaseDirs::new().map(|b| b.home_dir().to_path_buf());
-        match base {
-            Some(home) => home
-                .join(".openclaw")
-                .join("exec-approvals.sock")
-                .to_string_lossy()
-                .to_string(),
-            None => "/tmp/openclaw-exec-approvals.sock".to_string(),
+        // Resolved once so the bound socket and the path registered in
+        // exec-approvals.json always agree
+        static SOCKET_PATH: std::sync::OnceLock<Result<String, String>> =
+            std::sync::OnceLock::new();
+        SOCKET_PATH
+            .get_or_init(|| {
+                resolve_exec_host_socket_dir()
+                    .map(|dir| dir.join("exec-approvals.sock").to_string_lossy().to_string())
+            })
+            .clone()
+    }
+}
+
+/// The first of `$XDG_RUNTIME_DIR/openclaw/`, `~/.openclaw/` and
+/// `<temp>/openclaw-<uid>/` that is (or can be made) a private directory.
+#[cfg(not(target_os = "windows"))]
+fn resolve_exec_host_socket_dir() -> Result<PathBuf, String> {
+    let runtime_dir = std::env::var_os("XDG_RUNTIME_DIR")
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| PathBuf::from(dir).join("openclaw"));
+    let home_dir = BaseDirs::new().map(|b| b.home_dir().join(".openclaw"));
+    let uid = unsafe { libc::getuid() };
+    let temp_dir = std::env::temp_dir().join(format!("openclaw-{}", uid));
+    let mut rejected = Vec::new();
+    for dir in runtime_dir.into_iter().chain(home_dir).chain(Some(temp_dir)) {
+        if ensure_private_dir(&dir) {
+            return Ok(dir);
         }
+        rejected.push(dir.display().to_string());
+    }
+    Err(format!(
+        "no private directory for the exec-host socket ({} are not directories \
+         owned by this user with mode 0700)",
+        rejected.join(", ")
+    ))
+}
+
+/// Create `dir` (mode 0700) or tighten an existing one. Fails for anything
+/// that isn't a real directory owned by this user, e.g. one planted in a
+/// shared temp dir.
+#[cfg(not(target_os = "windows"))]
+fn ensure_private_dir(dir: &Path) -> bool {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+    let _ = fs::DirBuilder::new().recursive(true).mode(0o700).create(dir);
+    let Ok(meta) = fs::symlink_metadata(dir) else {
+        return false;
+    };
+    if !meta.is_dir() || meta.uid() != unsafe { libc::getuid() } {
+        return false;
     }
+    fs::set_permissions(dir, fs::Permissions::from_mode(0o700)).is_ok()
 }
 
 // ---------------------------------------------------------------------------
@@ -580,7 +1338,7 @@ fn try_import_from_openclaw_config() -> Option<NodeClientConfig> {
     let oc: OpenClawConfig = serde_json5::from_str(&raw).ok()?;
     let gw = oc.gateway?;
 
-    let mut cfg = NodeClientConfig::default();
+    let mut cfg = default_config();
     if let Some(port) = gw.port {
         cfg.port = port;
     }
@@ -623,60 +1381,398 @@ fn try_import_from_openclaw_config() -> Option<NodeClientConfig> {
     Some(cfg)
 }
 
+/// Admin-provided defaults merged beneath the user config, so IT can
+/// pre-provision gateway host and TLS policy.
+fn system_config_path() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    {
+        std::env::var_os("ProgramData")
+            .map(|dir| PathBuf::from(dir).join("openclaw").join("node-client.json"))
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        Some(PathBuf::from("/etc/openclaw/node-client.json"))
+    }
+}
+
+/// Hand-edited configs may use JSON5 (comments, trailing commas) or TOML;
+/// the app always writes canonical JSON back on the next save.
+fn parse_config_object(raw: &str) -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let value = match serde_json::from_str::<serde_json::Value>(raw) {
+        Ok(value) => value,
+        Err(json_err) => serde_json5::from_str(raw)
+            .or_else(|_| toml::from_str(raw))
+            .map_err(|_| format!("not valid JSON, JSON5 or TOML: {}", json_err))?,
+    };
+    match value {
+        serde_json::Value::Object(map) => Ok(map),
+        _ => Err("config must be an object".to_string()),
+    }
+}
+
+fn system_config_layer() -> Option<serde_json::Map<String, serde_json::Value>> {
+    let path = system_config_path()?;
+    let raw = fs::read_to_string(&path).ok()?;
+    match parse_config_object(&raw) {
+        Ok(layer) => Some(layer),
+        Err(err) => {
+            eprintln!("ignoring system defaults {}: {}", path.display(), err);
+            None
+        }
+    }
+}
+
+/// Built-in defaults with the system layer applied on top.
+fn layered_defaults() -> serde_json::Map<String, serde_json::Value> {
+    let mut merged = match serde_json::to_value(NodeClientConfig::default()) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    merged.extend(system_config_layer().unwrap_or_default());
+    merged
+}
+
+fn default_config() -> NodeClientConfig {
+    serde_json::from_value(serde_json::Value::Object(layered_defaults())).unwrap_or_default()
+}
+
+fn deserialize_config(raw: &str) -> Result<NodeClientConfig, String> {
+    let mut merged = layered_defaults();
+    merged.extend(parse_config_object(raw)?);
+    serde_json::from_value(serde_json::Value::Object(merged)).map_err(|err| err.to_string())
+}
+
+/// Parse node-client.json contents, filling in keychain-held secrets.
+fn parse_config(raw: &str) -> Result<NodeClientConfig, String> {
+    let unsealed = credentials::unseal_config(raw)?;
+    let raw = unsealed.as_deref().unwrap_or(raw);
+    let mut config = deserialize_config(raw)?;
+    credentials::load_secrets(&mut config);
+    Ok(config)
+}
+
 fn load_config() -> NodeClientConfig {
     let path = match config_path() {
         Ok(path) => path,
-        Err(_) => return try_import_from_openclaw_config().unwrap_or_default(),
+        Err(_) => return try_import_from_openclaw_config().unwrap_or_else(default_config),
+    };
+    let mut config = match fs::read_to_string(&path) {
+        Ok(raw) => parse_config(&raw).unwrap_or_else(|err| {
+            // Keep the unreadable file (e.g. sealed with a key this session
+            // can't reach) so the next save doesn't destroy it
+            eprintln!("failed to load {}: {}", path.display(), err);
+            let _ = fs::copy(&path, path.with_extension("json.unreadable"));
+            default_config()
+        }),
+        Err(_) => return try_import_from_openclaw_config().unwrap_or_else(default_config),
     };
-    match fs::read_to_string(&path) {
-        Ok(raw) => serde_json::from_str(&raw).unwrap_or_default(),
-        Err(_) => try_import_from_openclaw_config().unwrap_or_default(),
+    if config.keychain_ref.is_none()
+        && (config.gateway_token.is_some() || config.gateway_password.is_some())
+    {
+        // One-time migration of plaintext secrets into the keychain
+        if let Err(err) = save_config(&mut config) {
+            eprintln!("failed to migrate gateway credentials: {}", err);
+        }
     }
+    config
 }
 
-fn save_config(config: &NodeClientConfig) -> Result<(), String> {
+/// Write the config to disk. Updates `keychain_ref` to where the gateway
+/// secrets actually ended up.
+fn save_config(config: &mut NodeClientConfig) -> Result<(), String> {
     let path = config_path()?;
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|err| err.to_string())?;
     }
-    let payload = serde_json::to_string_pretty(config).map_err(|err| err.to_string())?;
+    let on_disk = credentials::prepare_for_disk(config);
+    let mut value = serde_json::to_value(&on_disk).map_err(|err| err.to_string())?;
+    // With a system layer present, only write what the user overrides so
+    // later changes to the admin defaults still take effect
+    if let (Some(_), serde_json::Value::Object(map)) = (system_config_layer(), &mut value) {
+        let defaults = layered_defaults();
+        map.retain(|key, value| defaults.get(key) != Some(value));
+    }
+    let mut payload = serde_json::to_string_pretty(&value).map_err(|err| err.to_string())?;
+    if on_disk.encrypt_at_rest {
+        match credentials::seal_config(&payload) {
+            Ok(sealed) => payload = sealed,
+            Err(err) => eprintln!("config encryption unavailable ({}); writing plain JSON", err),
+        }
+    }
 
     // Atomic write: temp file + rename (matches exec-approvals pattern)
     let tmp_path = path.with_extension("json.tmp");
     fs::write(&tmp_path, format!("{}\n", payload)).map_err(|err| err.to_string())?;
+    config_backup::backup_before_write(&path);
     fs::rename(&tmp_path, &path).map_err(|err| err.to_string())?;
 
     restrict_file_permissions(&path);
+    config.keychain_ref = on_disk.keychain_ref;
     Ok(())
 }
 
-/// Restrict a file to owner-only access (contains secrets).
-fn restrict_file_permissions(path: &Path) {
-    #[cfg(target_os = "windows")]
-    {
-        // Windows: files in %USERPROFILE%\.openclaw\ inherit user-private ACLs
-        // from the profile directory. Explicit ACL manipulation via icacls is
-        // fragile (domain-join, empty USERNAME, console flash). Parent directory
-        // inheritance provides sufficient protection.
-        let _ = path;
-    }
+/// Quiet period before a queued config save is written, so the burst of
+/// updates around a node start ends up as one write.
+const CONFIG_SAVE_DEBOUNCE_MS: u64 = 200;
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+/// Ask the writer task to persist the in-memory config.
+fn queue_config_save(app: &AppHandle) {
+    app.state::<AppState>().config_dirty.send_replace(true);
+}
+
+/// Whether the in-memory config has changes still waiting to be written.
+fn config_save_pending(app: &AppHandle) -> bool {
+    *app.state::<AppState>().config_dirty.borrow()
+}
+
+/// Write the in-memory config now if a queued save hasn't landed yet.
+fn flush_config(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let _io = state.config_io.lock().map_err(|err| err.to_string())?;
+    if !state.config_dirty.send_replace(false) {
+        return Ok(());
+    }
+    let mut config = state.config.lock().map_err(|err| err.to_string())?.clone();
+    save_config(&mut config)?;
+    if let Ok(mut current) = state.config.lock() {
+        current.keychain_ref = config.keychain_ref;
     }
+    Ok(())
 }
 
-/// Recover files whose ACLs were corrupted by the old `restrict_file_permissions`
-/// implementation (which stripped all inherited ACEs and then failed the grant).
-/// Resets the file's ACL to inherit from the parent directory.
+/// Persist queued config saves off the command threads, coalescing saves
+/// that arrive within `CONFIG_SAVE_DEBOUNCE_MS` of each other.
+async fn run_config_writer(app: AppHandle) {
+    let mut dirty = app.state::<AppState>().config_dirty.subscribe();
+    loop {
+        if dirty.wait_for(|dirty| *dirty).await.is_err() {
+            return;
+        }
+        while let Ok(Ok(())) = tokio::time::timeout(
+            std::time::Duration::from_millis(CONFIG_SAVE_DEBOUNCE_MS),
+            dirty.changed(),
+        )
+        .await
+        {}
+        let write_app = app.clone();
+        match tauri::async_runtime::spawn_blocking(move || flush_config(&write_app)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => push_log_line(&app, format!("config save failed: {}", err)),
+            Err(err) => push_log_line(&app, format!("config save failed: {}", err)),
+        }
+    }
+}
+
+/// Write `config` to disk and swap it in, for saves the user asked for. Any
+/// queued save is superseded.
+fn save_and_apply_config(
+    app: &AppHandle,
+    mut config: NodeClientConfig,
+    allow_restart: bool,
+) -> Result<ConfigChangeImpact, String> {
+    let state = app.state::<AppState>();
+    let _io = state.config_io.lock().map_err(|err| err.to_string())?;
+    save_config(&mut config)?;
+    state.config_dirty.send_replace(false);
+    apply_config(app, config, allow_restart)
+}
+
+// ---------------------------------------------------------------------------
+// Config change impact
+// ---------------------------------------------------------------------------
+
+/// Config keys (camelCase, as serialized) that are baked into the running
+/// `openclaw node run` child at spawn time.
+const NODE_RESTART_KEYS: &[&str] = &[
+    "host",
+    "port",
+    "tls",
+    "tlsFingerprint",
+    "tlsCaPath",
+    "tlsClientCertPath",
+    "tlsClientKeyPath",
+    "tlsInsecureSkipVerify",
+    "nodeId",
+    "displayName",
+    "useExecHost",
+    "execHostFallback",
+    "gatewayToken",
+    "gatewayPassword",
+    "installPath",
+    "useBundledRuntime",
+    "useManagedNode",
+    "devRoot",
+    "extraEnv",
+    "extraArgs",
+    "lowPriority",
+    "runAsUser",
+    "workingDir",
+];
+
+/// Config keys used by the app's own gateway connection.
+const GATEWAY_RECONNECT_KEYS: &[&str] = &[
+    "host",
+    "port",
+    "tls",
+    "gatewayToken",
+    "gatewayPassword",
+    "nodeId",
+    "displayName",
+];
+
+/// Credentials whose change is recorded as a token rotation.
+const ROTATED_TOKEN_KEYS: &[&str] = &[
+    "gatewayToken",
+    "gatewayPassword",
+    "localApiToken",
+    "mqttPassword",
+];
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigChangeImpact {
+    changed_fields: Vec<String>,
+    /// New values of the changed fields, so open views can patch their copy
+    /// without refetching.
+    values: serde_json::Map<String, serde_json::Value>,
+    /// The running node host uses a changed field.
+    restart_node: bool,
+    /// The gateway connection uses a changed field; the UI owns that
+    /// connection and reconnects when it sees this.
+    reconnect_gateway: bool,
+    /// A node restart was started automatically.
+    restarting: bool,
+}
+
+fn config_change_impact(old: &NodeClientConfig, new: &NodeClientConfig) -> ConfigChangeImpact {
+    let (Ok(serde_json::Value::Object(old)), Ok(serde_json::Value::Object(new))) =
+        (serde_json::to_value(old), serde_json::to_value(new))
+    else {
+        return ConfigChangeImpact::default();
+    };
+    let values: serde_json::Map<String, serde_json::Value> = new
+        .into_iter()
+        .filter(|(key, value)| old.get(key.as_str()) != Some(value))
+        .collect();
+    let changed_fields: Vec<String> = values.keys().cloned().collect();
+    let mut values = values;
+    credentials::redact_secret_values(&mut values);
+    let touches = |keys: &[&str]| changed_fields.iter().any(|f| keys.contains(&f.as_str()));
+    ConfigChangeImpact {
+        restart_node: touches(NODE_RESTART_KEYS),
+        reconnect_gateway: touches(GATEWAY_RECONNECT_KEYS),
+        restarting: false,
+        changed_fields,
+        values,
+    }
+}
+
+/// Swap in a new in-memory config and emit `config-changed` describing what
+/// it affects. With `allow_restart` a running node host is restarted to pick
+/// up the change; otherwise the UI is left to ask for it.
+fn apply_config(
+    app: &AppHandle,
+    config: NodeClientConfig,
+    allow_restart: bool,
+) -> Result<ConfigChangeImpact, String> {
+    let state = app.state::<AppState>();
+    let new_node_id = config.node_id.clone();
+    let mut impact = {
+        let mut current = state.config.lock().map_err(|err| err.to_string())?;
+        let impact = config_change_impact(&current, &config);
+        i18n::set_locale(config.locale.as_deref());
+        *current = config;
+        impact
+    };
+    if impact.changed_fields.is_empty() {
+        return Ok(impact);
+    }
+    state.config_changed.send_replace(());
+    for field in &impact.changed_fields {
+        if ROTATED_TOKEN_KEYS.contains(&field.as_str()) {
+            event_log::record(app, event_log::AuditEvent::TokenRotated { token: field });
+        }
+    }
+
+    // Only an app-owned child can be restarted; a service-managed node
+    // picks up changes on its own reinstall.
+    let (child_running, running_node_id) = state
+        .runtime
+        .lock()
+        .map(|runtime| (runtime.child.is_some(), runtime.node_id.clone()))
+        .unwrap_or((false, None));
+    // Saving the id the running node settled on doesn't need a restart
+    let id_in_effect = running_node_id.is_some() && running_node_id == new_node_id;
+    impact.restart_node = child_running
+        && impact.changed_fields.iter().any(|field| {
+            NODE_RESTART_KEYS.contains(&field.as_str()) && !(id_in_effect && field == "nodeId")
+        });
+    if impact.restart_node && allow_restart {
+        impact.restarting = true;
+        push_log_line(
+            app,
+            format!(
+                "config changed ({}); restarting node host",
+                impact.changed_fields.join(", ")
+            ),
+        );
+        let restart_app = app.clone();
+        std::thread::spawn(move || {
+            if let Err(err) = restart_node_internal(&restart_app) {
+                push_log_line(&restart_app, format!("config restart failed: {}", err));
+            }
+        });
+    }
+    let _ = app.emit("config-changed", &impact);
+    Ok(impact)
+}
+
+/// Change the in-memory config, emit `config-changed`, and queue a save, for
+/// changes the app makes on its own (detected install path, assigned node
+/// id). Never restarts the node host. `config_io` is held from the read to
+/// the swap so concurrent updates and saves can't drop each other's changes.
+fn update_config(
+    app: &AppHandle,
+    update: impl FnOnce(&mut NodeClientConfig),
+) -> Result<ConfigChangeImpact, String> {
+    let state = app.state::<AppState>();
+    let _io = state.config_io.lock().map_err(|err| err.to_string())?;
+    let mut config = state.config.lock().map_err(|err| err.to_string())?.clone();
+    update(&mut config);
+    let impact = apply_config(app, config, false)?;
+    if !impact.changed_fields.is_empty() {
+        queue_config_save(app);
+    }
+    Ok(impact)
+}
+
+/// Restrict a file to owner-only access (contains secrets).
+fn restrict_file_permissions(path: &Path) {
+    #[cfg(target_os = "windows")]
+    {
+        // Windows: files in %USERPROFILE%\.openclaw\ inherit user-private ACLs
+        // from the profile directory. Explicit ACL manipulation via icacls is
+        // fragile (domain-join, empty USERNAME, console flash). Parent directory
+        // inheritance provides sufficient protection.
+        let _ = path;
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+}
+
+/// Recover files whose ACLs were corrupted by the old `restrict_file_permissions`
+/// implementation (which stripped all inherited ACEs and then failed the grant).
+/// Resets the file's ACL to inherit from the parent directory.
 #[cfg(target_os = "windows")]
 fn try_recover_file_acls(path: &Path) {
     if !path.exists() {
         return;
     }
-    if fs::read(path).is_ok() {
+    if fs::File::open(path).is_ok() {
         return; // File readable, no recovery needed
     }
     // File exists but is unreadable — reset ACLs to inherit from parent
@@ -698,6 +1794,7 @@ fn merge_exec_approvals_socket(
     file_path: &Path,
     socket_path: &str,
     token: &str,
+    token_in_keychain: bool,
 ) -> Result<(), String> {
     if let Some(parent) = file_path.parent() {
         fs::create_dir_all(parent).map_err(|e| e.to_string())?;
@@ -722,9 +1819,24 @@ fn merge_exec_approvals_socket(
         }
     };
 
+    let (token, token_ref) = if token_in_keychain {
+        match credentials::store_exec_host_token(token) {
+            Ok(reference) => (None, Some(reference)),
+            Err(err) => {
+                eprintln!(
+                    "keychain unavailable ({}); writing the exec-host token to exec-approvals.json",
+                    err
+                );
+                (Some(token.to_string()), None)
+            }
+        }
+    } else {
+        (Some(token.to_string()), None)
+    };
     file.socket = Some(ExecApprovalsSocket {
         path: Some(socket_path.to_string()),
-        token: Some(token.to_string()),
+        token,
+        token_ref,
     });
 
     let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
@@ -732,6 +1844,7 @@ fn merge_exec_approvals_socket(
     // Atomic write: temp file + rename
     let tmp_path = file_path.with_extension("json.tmp");
     fs::write(&tmp_path, format!("{}\n", json)).map_err(|e| e.to_string())?;
+    config_backup::backup_before_write(file_path);
     fs::rename(&tmp_path, file_path).map_err(|e| e.to_string())?;
 
     // Restrict to owner-only; file contains the shared exec-host token
@@ -754,14 +1867,19 @@ fn clear_exec_approvals_socket(file_path: &Path) -> Result<(), String> {
             extra: HashMap::new(),
         });
 
+    if file.socket.as_ref().is_some_and(|s| s.token_ref.is_some()) {
+        credentials::clear_exec_host_token();
+    }
     file.socket = Some(ExecApprovalsSocket {
         path: None,
         token: None,
+        token_ref: None,
     });
 
     let json = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
     let tmp_path = file_path.with_extension("json.tmp");
     fs::write(&tmp_path, format!("{}\n", json)).map_err(|e| e.to_string())?;
+    config_backup::backup_before_write(file_path);
     fs::rename(&tmp_path, file_path).map_err(|e| e.to_string())?;
     Ok(())
 }
@@ -796,6 +1914,7 @@ fn write_exec_approvals_file(file: &ExecApprovalsFile) -> Result<(), String> {
     let json = serde_json::to_string_pretty(file).map_err(|e| e.to_string())?;
     let tmp_path = path.with_extension("json.tmp");
     fs::write(&tmp_path, format!("{}\n", json)).map_err(|e| e.to_string())?;
+    config_backup::backup_before_write(&path);
     fs::rename(&tmp_path, &path).map_err(|e| e.to_string())?;
     restrict_file_permissions(&path);
     Ok(())
@@ -814,17 +1933,26 @@ fn get_exec_policy() -> Result<ExecPolicyConfig, String> {
 
 #[tauri::command]
 fn set_exec_policy(
+    app: AppHandle,
     security: Option<String>,
     ask: Option<String>,
     ask_fallback: Option<String>,
 ) -> Result<(), String> {
     let mut file = read_exec_approvals_file()?;
     let mut defaults = file.defaults.unwrap_or_default();
+    let change = format!(
+        "security={} ask={} askFallback={}",
+        security.as_deref().unwrap_or("default"),
+        ask.as_deref().unwrap_or("default"),
+        ask_fallback.as_deref().unwrap_or("default")
+    );
     defaults.security = security;
     defaults.ask = ask;
     defaults.ask_fallback = ask_fallback;
     file.defaults = Some(defaults);
-    write_exec_approvals_file(&file)
+    write_exec_approvals_file(&file)?;
+    event_log::record(&app, event_log::AuditEvent::PolicyChanged { change });
+    Ok(())
 }
 
 #[tauri::command]
@@ -836,7 +1964,7 @@ fn get_exec_allowlist() -> Result<Vec<AllowlistEntry>, String> {
 }
 
 #[tauri::command]
-fn add_allowlist_entry(pattern: String) -> Result<(), String> {
+fn add_allowlist_entry(app: AppHandle, pattern: String) -> Result<(), String> {
     let trimmed = pattern.trim().to_string();
     if trimmed.is_empty() {
         return Err("pattern cannot be empty".to_string());
@@ -851,6 +1979,7 @@ fn add_allowlist_entry(pattern: String) -> Result<(), String> {
         return Ok(());
     }
 
+    let change = format!("allowlist entry added: {}", trimmed);
     allowlist.push(AllowlistEntry {
         pattern: trimmed,
         last_used_at: None,
@@ -859,11 +1988,13 @@ fn add_allowlist_entry(pattern: String) -> Result<(), String> {
     agent.allowlist = Some(allowlist);
     agents.insert(DEFAULT_AGENT_ID.to_string(), agent);
     file.agents = Some(agents);
-    write_exec_approvals_file(&file)
+    write_exec_approvals_file(&file)?;
+    event_log::record(&app, event_log::AuditEvent::PolicyChanged { change });
+    Ok(())
 }
 
 #[tauri::command]
-fn remove_allowlist_entry(pattern: String) -> Result<(), String> {
+fn remove_allowlist_entry(app: AppHandle, pattern: String) -> Result<(), String> {
     let mut file = read_exec_approvals_file()?;
     let mut agents = file.agents.unwrap_or_default();
     let mut agent = match agents.remove(DEFAULT_AGENT_ID) {
@@ -878,7 +2009,14 @@ fn remove_allowlist_entry(pattern: String) -> Result<(), String> {
     agent.allowlist = if filtered.is_empty() { None } else { Some(filtered) };
     agents.insert(DEFAULT_AGENT_ID.to_string(), agent);
     file.agents = Some(agents);
-    write_exec_approvals_file(&file)
+    write_exec_approvals_file(&file)?;
+    event_log::record(
+        &app,
+        event_log::AuditEvent::PolicyChanged {
+            change: format!("allowlist entry removed: {}", pattern),
+        },
+    );
+    Ok(())
 }
 
 // ---------------------------------------------------------------------------
@@ -896,45 +2034,244 @@ fn validate_hmac(token: &str, nonce: &str, ts: u64, request_json: &str, expected
     computed == expected
 }
 
+/// Compare two secrets without returning early on the first differing byte.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
 // ---------------------------------------------------------------------------
 // Logging / process state
 // ---------------------------------------------------------------------------
 
 fn push_log_line(app: &AppHandle, line: impl Into<String>) {
-    let text = line.into();
+    push_log_entry(app, LogEntry::new("app", line.into()));
+}
+
+fn push_log_entry(app: &AppHandle, mut entry: LogEntry) {
     {
         let state = app.state::<AppState>();
-        if let Ok(mut runtime) = state.runtime.lock() {
-            if runtime.logs.len() >= LOG_CAP {
-                runtime.logs.pop_front();
+        let mut secrets = vec![state.exec_host_token.clone(), state.control_token.clone()];
+        #[cfg(target_os = "linux")]
+        let mut forward_target = None;
+        if let Ok(config) = state.config.lock() {
+            secrets.extend(config_secrets(&config));
+            #[cfg(target_os = "linux")]
+            if entry.stream != "app" {
+                forward_target = log_forward::Target::from_config(&config);
             }
-            runtime.logs.push_back(text.clone());
+        }
+        entry.message = redact_secrets(&entry.message, &secrets);
+        #[cfg(target_os = "linux")]
+        if let Some(target) = &forward_target {
+            log_forward::forward(target, &entry);
+        }
+        if let Ok(mut runtime) = state.runtime.lock() {
+            entry = runtime.logs.push_collapsing(entry);
         };
     }
-    let _ = app.emit("node-log", text);
+    emit_node_log(app, entry);
+}
+
+/// Queue `entry` for the webview. Entries are sent as one `node-log` array
+/// per `NODE_LOG_BATCH_MS` so a verbose CLI doesn't flood the IPC bridge;
+/// an error flushes the batch at once.
+fn emit_node_log(app: &AppHandle, entry: LogEntry) {
+    let state = app.state::<AppState>();
+    let Ok(mut batch) = state.node_log_batch.lock() else {
+        return;
+    };
+    let flush_now = entry.level == "error";
+    let first = batch.is_empty();
+    queue_log_entry(&mut batch, entry);
+    if flush_now {
+        // Emitted under the lock so batches reach the webview in order
+        let _ = app.emit("node-log", std::mem::take(&mut *batch));
+    } else if first {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(NODE_LOG_BATCH_MS)).await;
+            let state = app.state::<AppState>();
+            if let Ok(mut batch) = state.node_log_batch.lock() {
+                if !batch.is_empty() {
+                    let _ = app.emit("node-log", std::mem::take(&mut *batch));
+                }
+            };
+        });
+    }
+}
+
+/// Add `entry` to a pending `node-log` batch. A repeat replaces the queued
+/// entry it updates.
+fn queue_log_entry(batch: &mut Vec<LogEntry>, entry: LogEntry) {
+    match batch.last_mut() {
+        Some(last)
+            if entry.repeat > 1 && last.stream == entry.stream && last.message == entry.message =>
+        {
+            *last = entry;
+        }
+        _ => batch.push(entry),
+    }
+}
+
+/// Keys whose values are masked wherever they appear as `key=value` or
+/// `"key": "value"` in captured output.
+const CREDENTIAL_KEYS: &[&str] = &[
+    "token",
+    "password",
+    "passwd",
+    "secret",
+    "apikey",
+    "api_key",
+    "api-key",
+    "authorization",
+];
+const REDACTED: &str = "***";
+/// Shorter configured secrets are too likely to collide with normal text.
+const MIN_REDACT_LEN: usize = 6;
+
+/// Configured credentials, masked wherever they appear in captured output.
+fn config_secrets(config: &NodeClientConfig) -> Vec<String> {
+    let mut config = config.clone();
+    let headers = std::mem::take(&mut config.otlp_headers);
+    credentials::SECRET_FIELDS
+        .iter()
+        .filter_map(|field| (field.get)(&mut config).take())
+        .chain(headers.into_values())
+        .collect()
+}
+
+/// Mask known secrets and common credential patterns before a line is
+/// stored or emitted (the CLI can echo tokens back in errors and debug output).
+fn redact_secrets(line: &str, secrets: &[String]) -> String {
+    let mut text = line.to_string();
+    for secret in secrets {
+        if secret.len() >= MIN_REDACT_LEN {
+            text = text.replace(secret.as_str(), REDACTED);
+        }
+    }
+    redact_credential_patterns(&text)
+}
+
+fn redact_credential_patterns(line: &str) -> String {
+    let lower = line.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let is_quote_or_space = |b: u8| b == b'"' || b == b'\'' || b == b' ';
+    let value_end = |start: usize| {
+        bytes[start..]
+            .iter()
+            .position(|b| b.is_ascii_whitespace() || b"\"',;&}".contains(b))
+            .map_or(bytes.len(), |offset| start + offset)
+    };
+
+    let mut ranges = Vec::new();
+    for key in CREDENTIAL_KEYS {
+        for (idx, _) in lower.match_indices(key) {
+            let mut pos = idx + key.len();
+            while pos < bytes.len() && is_quote_or_space(bytes[pos]) {
+                pos += 1;
+            }
+            if pos >= bytes.len() || (bytes[pos] != b'=' && bytes[pos] != b':') {
+                continue;
+            }
+            pos += 1;
+            while pos < bytes.len() && is_quote_or_space(bytes[pos]) {
+                pos += 1;
+            }
+            let end = value_end(pos);
+            if end > pos {
+                ranges.push((pos, end));
+            }
+        }
+    }
+    for (idx, _) in lower.match_indices("bearer ") {
+        let start = idx + "bearer ".len();
+        let end = value_end(start);
+        if end > start {
+            ranges.push((start, end));
+        }
+    }
+    if ranges.is_empty() {
+        return line.to_string();
+    }
+
+    ranges.sort_unstable();
+    let mut out = String::with_capacity(line.len());
+    let mut copied = 0;
+    for (start, end) in ranges {
+        if start < copied {
+            copied = copied.max(end);
+            continue;
+        }
+        out.push_str(&line[copied..start]);
+        out.push_str(REDACTED);
+        copied = end;
+    }
+    out.push_str(&line[copied..]);
+    out
 }
 
+/// Read a node host stream line by line. Each line is processed before the
+/// next is read, so a slow consumer backs up the pipe rather than memory.
 fn spawn_log_reader<R>(app: AppHandle, reader: R, stream_name: &'static str)
 where
-    R: Read + Send + 'static,
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
 {
-    std::thread::spawn(move || {
-        let buffered = BufReader::new(reader);
-        for line in buffered.lines() {
-            match line {
-                Ok(text) => {
-                    // Parse node status from log lines
-                    update_node_status_from_log(&app, &text);
-                    push_log_line(&app, format!("[{}] {}", stream_name, text));
-                }
-                Err(_) => break,
-            }
+    tauri::async_runtime::spawn(async move {
+        let mut lines = tokio::io::BufReader::new(reader).lines();
+        while let Ok(Some(text)) = lines.next_line().await {
+            // Parse node status from log lines
+            update_node_status_from_log(&app, &text);
+            push_log_entry(&app, LogEntry::new(stream_name, text));
         }
         // Pipe closed — child likely exited; detect exit and emit status change
         check_and_emit_child_exit(&app);
     });
 }
 
+/// Own the node host's stdin on a task and write the lines sent to it.
+/// Dropping the sender closes the pipe.
+fn spawn_stdin_writer(
+    app: AppHandle,
+    mut stdin: tokio::process::ChildStdin,
+) -> tokio::sync::mpsc::UnboundedSender<String> {
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    tauri::async_runtime::spawn(async move {
+        while let Some(line) = rx.recv().await {
+            let written = async {
+                stdin.write_all(format!("{}\n", line).as_bytes()).await?;
+                stdin.flush().await
+            };
+            if let Err(err) = written.await {
+                push_log_line(&app, format!("failed to write to node host stdin: {}", err));
+                break;
+            }
+        }
+    });
+    tx
+}
+
+/// Poll for node host exits the log readers can't see (an adopted node host
+/// has no pipes, a service-run one is only watched by pid) and report them.
+async fn run_process_monitor(app: AppHandle) {
+    let mut tick =
+        tokio::time::interval(std::time::Duration::from_millis(PROCESS_POLL_INTERVAL_MS));
+    loop {
+        tick.tick().await;
+        let exit = {
+            let state = app.state::<AppState>();
+            let Ok(mut runtime) = state.runtime.lock() else {
+                continue;
+            };
+            let (_, exit_log) = refresh_process_state(&mut runtime);
+            exit_log.map(|log| (log, runtime.node_status.clone().unwrap_or_default()))
+        };
+        if let Some((exit_log, status)) = exit {
+            report_node_exit(&app, exit_log);
+            let _ = app.emit("node-status-changed", status.as_str());
+        }
+    }
+}
+
 /// Called when a log reader reaches EOF (child likely exited).
 /// Detects exit via refresh_process_state and emits the updated status event.
 fn check_and_emit_child_exit(app: &AppHandle) {
@@ -952,7 +2289,7 @@ fn check_and_emit_child_exit(app: &AppHandle) {
     };
     // Push log outside the lock (push_log_line re-locks)
     if let Some(exit_log) = exit_log {
-        push_log_line(app, exit_log);
+        report_node_exit(app, exit_log);
     }
     if let Some(status) = status_str {
         let _ = app.emit("node-status-changed", &status);
@@ -988,15 +2325,163 @@ fn update_node_status_from_log(app: &AppHandle, line: &str) {
 
     if let Some(status) = new_status {
         let state = app.state::<AppState>();
+        let mut was_starting = false;
         if let Ok(mut runtime) = state.runtime.lock() {
+            was_starting = runtime.node_status == Some(NodeStatus::Starting);
             runtime.node_status = Some(status.clone());
+            runtime.publish_status();
         }
+        let connected = status == NodeStatus::Running;
         let _ = app.emit("node-status-changed", status.as_str());
+        if connected {
+            if was_starting {
+                emit_start_stage(app, "connected", None);
+            }
+            record_assigned_node_id(app);
+        }
+    }
+}
+
+/// Pick up the node id the node host settled on (it persists it to
+/// ~/.openclaw/node.json) once it connects. Unless the user pinned an id,
+/// it is written back to the app config so reconnects keep the same identity.
+fn record_assigned_node_id(app: &AppHandle) {
+    let Some(node_id) = openclaw_dir()
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("node.json")).ok())
+        .and_then(|raw| serde_json::from_str::<OpenClawNodeJson>(&raw).ok())
+        .and_then(|node| node.node_id)
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+    else {
+        return;
+    };
+
+    let state = app.state::<AppState>();
+    let changed = match state.runtime.lock() {
+        Ok(mut runtime) => {
+            let changed = runtime.node_id.as_deref() != Some(node_id.as_str());
+            runtime.node_id = Some(node_id.clone());
+            runtime.publish_status();
+            changed
+        }
+        Err(_) => return,
+    };
+    if !changed {
+        return;
+    }
+    let _ = app.emit("node-id-assigned", &node_id);
+
+    let pinned = state.config.lock().map_or(true, |config| {
+        config
+            .node_id
+            .as_deref()
+            .is_some_and(|id| !id.trim().is_empty())
+    });
+    if pinned {
+        return;
+    }
+    match update_config(app, |config| config.node_id = Some(node_id.clone())) {
+        Ok(_) => push_log_line(app, format!("saved assigned node id {}", node_id)),
+        Err(err) => push_log_line(app, format!("failed to save node id: {}", err)),
+    }
+}
+
+/// Persist a crash report under ~/.openclaw/logs/node-client-crashes/ so
+/// exits that happen while the window is closed can still be diagnosed.
+fn write_crash_report(report: &NodeCrashReport) -> Result<PathBuf, String> {
+    let dir = logs_dir()?.join("node-client-crashes");
+    fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("crash-{}.json", report.exited_at_ms));
+    let json = serde_json::to_string_pretty(report).map_err(|e| e.to_string())?;
+    fs::write(&path, format!("{}\n", json)).map_err(|e| e.to_string())?;
+    restrict_file_permissions(&path);
+    compress_older_crash_reports(&dir, &path);
+    Ok(path)
+}
+
+/// Gzip the node crash reports other than `latest`. They are kept for log
+/// exports but rarely read, and a crash loop can write hundreds.
+fn compress_older_crash_reports(dir: &Path, latest: &Path) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path == latest || path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        if let Err(err) = gzip_file(&path) {
+            eprintln!("failed to compress {}: {}", path.display(), err);
+        }
+    }
+}
+
+/// Replace `path` with `<path>.gz`.
+fn gzip_file(path: &Path) -> std::io::Result<()> {
+    let mut gz_name = path.as_os_str().to_os_string();
+    gz_name.push(".gz");
+    let gz_path = PathBuf::from(gz_name);
+    let tmp_path = gz_path.with_extension("gz.tmp");
+    let mut input = fs::File::open(path)?;
+    let mut encoder =
+        flate2::write::GzEncoder::new(fs::File::create(&tmp_path)?, flate2::Compression::default());
+    std::io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+    fs::rename(&tmp_path, &gz_path)?;
+    restrict_file_permissions(&gz_path);
+    fs::remove_file(path)
+}
+
+/// Log a node host exit detected by `refresh_process_state` and, for
+/// crashes, write the report to disk and emit `node-crashed` with it. Call
+/// without the runtime lock held.
+fn report_node_exit(app: &AppHandle, exit_log: String) {
+    push_log_line(app, exit_log);
+    let crash = {
+        let state = app.state::<AppState>();
+        let Ok(mut runtime) = state.runtime.lock() else {
+            return;
+        };
+        runtime.unreported_crash.take()
+    };
+    if let Some(mut crash) = crash {
+        match write_crash_report(&crash) {
+            Ok(path) => {
+                push_log_line(app, format!("crash report written to {}", path.display()));
+                crash.report_path = Some(path.to_string_lossy().to_string());
+            }
+            Err(err) => eprintln!("failed to write crash report: {}", err),
+        }
+        let _ = app.emit("node-crashed", &crash);
     }
 }
 
+/// Check whether the node host is still running, recording an exit. Returns
+/// whether it runs and, for a new exit, the line to log about it.
 fn refresh_process_state(runtime: &mut RuntimeState) -> (bool, Option<String>) {
+    let result = poll_node_exit(runtime);
+    runtime.publish_status();
+    result
+}
+
+fn poll_node_exit(runtime: &mut RuntimeState) -> (bool, Option<String>) {
     let Some(child) = runtime.child.as_mut() else {
+        if let Some(pid) = runtime.adopted_pid {
+            if process_alive(pid) {
+                return (true, None);
+            }
+            runtime.adopted_pid = None;
+            runtime.node_status = Some(NodeStatus::Stopped);
+            remove_node_pid_file();
+            return (
+                false,
+                Some(i18n::tf("node.adopted_exited", &[("pid", &pid)])),
+            );
+        }
+        if runtime.service_managed {
+            // The service manager restarts it; the monitor picks up the new pid
+            return (runtime.service_pid.is_some_and(process_alive), None);
+        }
         return (false, None);
     };
 
@@ -1004,13 +2489,29 @@ fn refresh_process_state(runtime: &mut RuntimeState) -> (bool, Option<String>) {
         Ok(Some(status)) => {
             runtime.child = None;
             runtime.node_status = Some(NodeStatus::Stopped);
+            remove_node_pid_file();
             if status.success() {
                 runtime.last_error = None;
-                (false, Some("node host exited cleanly".to_string()))
+                (false, Some(i18n::t("node.exited_cleanly").to_string()))
             } else {
-                let msg = format!("node host exited with status {}", status);
+                let msg = i18n::tf("node.exited_with_status", &[("status", &status)]);
                 runtime.last_error = Some(msg.clone());
                 runtime.node_status = Some(NodeStatus::Error);
+                let report = NodeCrashReport {
+                    exit_code: status.code(),
+                    exit_status: status.to_string(),
+                    started_at_ms: runtime.started_at_ms.take(),
+                    exited_at_ms: now_ms(),
+                    log_tail: runtime
+                        .logs
+                        .iter()
+                        .skip(runtime.logs.len().saturating_sub(CRASH_LOG_TAIL))
+                        .map(LogEntry::render)
+                        .collect(),
+                    report_path: None,
+                };
+                // Written by report_node_exit once the runtime lock is released
+                runtime.unreported_crash = Some(report);
                 (false, Some(msg))
             }
         }
@@ -1036,38 +2537,88 @@ struct DiscoveryResult {
     bin_path: String,
     bin_name: String,
     method: String,
+    /// Set when `bin_path` is a version-manager shim or symlink: the
+    /// concrete binary it forwards to, and the dir of the `node` it runs on.
+    real_bin_path: Option<String>,
+    real_bin_dir: Option<String>,
+    /// Parsed from `--version` while validating the candidate.
+    version: Option<String>,
+    /// Candidates passed over because `--version` failed.
+    skipped: Vec<SkippedCandidate>,
+    /// Other working installs that lost to this one on version.
+    alternatives: Vec<InstallCandidate>,
+    /// How each discovery tier fared, in priority order.
+    tiers: Vec<DiscoveryTier>,
 }
 
-fn search_path_string(path_str: &str, method: &str) -> Option<DiscoveryResult> {
-    for dir in path_str.split(PATH_SEP) {
-        let dir = dir.trim();
-        if dir.is_empty() {
-            continue;
-        }
-        let dir_path = std::path::Path::new(dir);
-        for &name in OPENCLAW_BIN_NAMES {
-            let candidate = dir_path.join(name);
-            if candidate.is_file() {
-                return Some(DiscoveryResult {
-                    bin_dir: dir.to_string(),
-                    bin_path: candidate.to_string_lossy().to_string(),
-                    bin_name: name.to_string(),
-                    method: method.to_string(),
-                });
-            }
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SkippedCandidate {
+    bin_path: String,
+    method: String,
+    reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InstallCandidate {
+    bin_dir: String,
+    bin_path: String,
+    bin_name: String,
+    method: String,
+    real_bin_path: Option<String>,
+    version: Option<String>,
+    /// Why `--version` failed; such candidates can't be selected.
+    error: Option<String>,
+}
+
+/// Run `--version` on a candidate so broken shims and stale symlinks are
+/// rejected during discovery instead of surfacing later as a spawn failure.
+fn probe_candidate(dir: &Path, name: &str, method: &str) -> Result<DiscoveryResult, SkippedCandidate> {
+    let bin_path = dir.join(name).to_string_lossy().to_string();
+    match probe_openclaw_version(&bin_path, DISCOVERY_PROBE_TIMEOUT_MS) {
+        Ok(raw) => {
+            let resolved = shims::resolve(Path::new(&bin_path));
+            Ok(DiscoveryResult {
+                bin_dir: dir.to_string_lossy().to_string(),
+                bin_path,
+                bin_name: name.to_string(),
+                method: method.to_string(),
+                real_bin_path: resolved
+                    .as_ref()
+                    .map(|shim| shim.real_path.to_string_lossy().to_string()),
+                real_bin_dir: resolved
+                    .and_then(|shim| shim.node_dir)
+                    .map(|dir| dir.to_string_lossy().to_string()),
+                version: parse_version_output(&raw),
+                skipped: Vec::new(),
+                alternatives: Vec::new(),
+                tiers: Vec::new(),
+            })
         }
+        Err(reason) => Err(SkippedCandidate {
+            bin_path,
+            method: method.to_string(),
+            reason,
+        }),
     }
-    None
+}
+
+fn split_path_string(path_str: &str) -> impl Iterator<Item = PathBuf> + '_ {
+    path_str
+        .split(PATH_SEP)
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
 }
 
 #[cfg(not(target_os = "windows"))]
-fn find_nvm_bin(home: &std::path::Path) -> Option<std::path::PathBuf> {
+fn find_nvm_bin(nvm_dir: &std::path::Path) -> Option<std::path::PathBuf> {
     // Try reading the default alias file (e.g. "v20.11.0" or "lts/iron")
-    let alias_path = home.join(".nvm").join("alias").join("default");
+    let alias_path = nvm_dir.join("alias").join("default");
     if let Ok(version) = fs::read_to_string(&alias_path) {
         let version = version.trim().to_string();
-        let bin = home
-            .join(".nvm")
+        let bin = nvm_dir
             .join("versions")
             .join("node")
             .join(&version)
@@ -1076,11 +2627,10 @@ fn find_nvm_bin(home: &std::path::Path) -> Option<std::path::PathBuf> {
             return Some(bin);
         }
         // Resolve one level of indirection (e.g. "lts/iron" -> another alias file)
-        let resolved_path = home.join(".nvm").join("alias").join(&version);
+        let resolved_path = nvm_dir.join("alias").join(&version);
         if let Ok(resolved) = fs::read_to_string(&resolved_path) {
             let resolved = resolved.trim().to_string();
-            let bin = home
-                .join(".nvm")
+            let bin = nvm_dir
                 .join("versions")
                 .join("node")
                 .join(&resolved)
@@ -1091,7 +2641,7 @@ fn find_nvm_bin(home: &std::path::Path) -> Option<std::path::PathBuf> {
         }
     }
     // Fallback: scan and pick the lexicographically latest version
-    let versions_dir = home.join(".nvm").join("versions").join("node");
+    let versions_dir = nvm_dir.join("versions").join("node");
     let mut entries: Vec<_> = fs::read_dir(&versions_dir)
         .ok()?
         .filter_map(|e| e.ok())
@@ -1121,46 +2671,239 @@ fn find_nvm_windows_bin(nvm_root: &std::path::Path) -> Option<std::path::PathBuf
     None
 }
 
-fn discover_via_well_known_dirs() -> Option<DiscoveryResult> {
-    let home = BaseDirs::new().map(|b| b.home_dir().to_path_buf());
+/// Numeric sort key for version directory names like "v20.11.0" or "18.19.1".
+fn version_sort_key(name: &str) -> Vec<u64> {
+    name.trim_start_matches('v')
+        .split(['.', '-', '+'])
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
 
-    #[cfg(not(target_os = "windows"))]
-    let candidates: Vec<std::path::PathBuf> = {
-        let mut dirs = vec![
-            std::path::PathBuf::from("/home/linuxbrew/.linuxbrew/bin"),
-            std::path::PathBuf::from("/opt/homebrew/bin"),
-        ];
-        if let Some(ref h) = home {
-            if let Some(nvm_bin) = find_nvm_bin(h) {
-                dirs.push(nvm_bin);
-            }
-            dirs.push(h.join(".volta").join("bin"));
-            dirs.push(
-                h.join(".local")
-                    .join("share")
-                    .join("fnm")
-                    .join("aliases")
-                    .join("default")
-                    .join("bin"),
-            );
-            dirs.push(h.join(".local").join("share").join("pnpm"));
-            dirs.push(h.join(".bun").join("bin"));
-            dirs.push(h.join(".local").join("bin"));
+/// The Node install under `versions_dir`: the pinned version (exact, or a
+/// prefix like "20") when it is installed, else the newest one.
+fn find_managed_node_version(versions_dir: &Path, pinned: Option<&str>) -> Option<PathBuf> {
+    let mut entries: Vec<(String, PathBuf)> = fs::read_dir(versions_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        .collect();
+    entries.sort_by_key(|(name, _)| std::cmp::Reverse(version_sort_key(name)));
+    let pinned = pinned
+        .map(|p| p.trim().trim_start_matches('v'))
+        .filter(|p| !p.is_empty());
+    if let Some(pinned) = pinned {
+        let prefix = format!("{}.", pinned);
+        let matched = entries.iter().find(|(name, _)| {
+            let name = name.trim_start_matches('v');
+            name == pinned || name.starts_with(&prefix)
+        });
+        if let Some((_, path)) = matched {
+            return Some(path.clone());
         }
-        dirs.push(std::path::PathBuf::from("/usr/local/bin"));
-        dirs.push(std::path::PathBuf::from("/usr/bin"));
-        dirs
-    };
-
-    #[cfg(target_os = "windows")]
-    let candidates: Vec<std::path::PathBuf> = {
-        let mut dirs: Vec<std::path::PathBuf> = vec![];
+    }
+    entries.into_iter().next().map(|(_, path)| path)
+}
 
-        // npm global
-        if let Ok(appdata) = std::env::var("APPDATA") {
-            dirs.push(std::path::PathBuf::from(&appdata).join("npm"));
+/// Version pinned for `tool` in an asdf-style `.tool-versions` file.
+fn tool_versions_pin(path: &Path, tool: &str) -> Option<String> {
+    fs::read_to_string(path).ok()?.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        if parts.next()? != tool {
+            return None;
         }
-
+        parts.next().map(|version| version.to_string())
+    })
+}
+
+/// Node version from the `[tools]` table of mise's global config.toml.
+fn mise_node_pin(config_dir: &Path) -> Option<String> {
+    let raw = fs::read_to_string(config_dir.join("config.toml")).ok()?;
+    let mut in_tools = false;
+    for line in raw.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_tools = line == "[tools]";
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if in_tools && key.trim().trim_matches('"') == "node" {
+            // `node = "20"` or `node = ["20", "18"]`; the first entry is the default
+            let first = value.trim().trim_start_matches('[').split(',').next()?;
+            let version = first.trim().trim_matches(|c| c == '"' || c == '\'' || c == ']');
+            return Some(version.to_string()).filter(|v| !v.is_empty());
+        }
+    }
+    None
+}
+
+/// Bin dirs for the asdf, mise, nodenv and nvs layouts. Each manager's
+/// concrete install comes before its shims dir, so discovery reports the
+/// real binary rather than a shim that picks a Node version at runtime.
+fn version_manager_bin_dirs(home: &Path) -> Vec<PathBuf> {
+    let env_dir = |var: &str| {
+        std::env::var_os(var)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+    // Windows installs keep node.exe and global npm bins at the install root
+    let install_bin = |dir: PathBuf| if cfg!(windows) { dir } else { dir.join("bin") };
+    #[cfg(not(target_os = "windows"))]
+    let data_home = home.join(".local").join("share");
+    #[cfg(target_os = "windows")]
+    let data_home = env_dir("LOCALAPPDATA").unwrap_or_else(|| home.join("AppData").join("Local"));
+    let global_tool_versions = home.join(".tool-versions");
+    let mut dirs = Vec::new();
+
+    // asdf (no native Windows support)
+    #[cfg(not(target_os = "windows"))]
+    {
+        let asdf = env_dir("ASDF_DATA_DIR").unwrap_or_else(|| home.join(".asdf"));
+        let pin = tool_versions_pin(&global_tool_versions, "nodejs");
+        let installs = asdf.join("installs").join("nodejs");
+        if let Some(install) = find_managed_node_version(&installs, pin.as_deref()) {
+            dirs.push(install_bin(install));
+        }
+        dirs.push(asdf.join("shims"));
+    }
+
+    // mise: global config.toml first, then the asdf-compatible .tool-versions
+    let mise = env_dir("MISE_DATA_DIR").unwrap_or_else(|| data_home.join("mise"));
+    let mise_config =
+        env_dir("MISE_CONFIG_DIR").unwrap_or_else(|| home.join(".config").join("mise"));
+    let pin = mise_node_pin(&mise_config)
+        .or_else(|| tool_versions_pin(&global_tool_versions, "node"))
+        .or_else(|| tool_versions_pin(&global_tool_versions, "nodejs"));
+    let installs = mise.join("installs").join("node");
+    if let Some(install) = find_managed_node_version(&installs, pin.as_deref()) {
+        dirs.push(install_bin(install));
+    }
+    dirs.push(mise.join("shims"));
+
+    // nodenv (nodenv-win uses the same layout)
+    let nodenv = env_dir("NODENV_ROOT").unwrap_or_else(|| home.join(".nodenv"));
+    let pin = fs::read_to_string(nodenv.join("version")).ok();
+    if let Some(install) = find_managed_node_version(&nodenv.join("versions"), pin.as_deref()) {
+        dirs.push(install_bin(install));
+    }
+    dirs.push(nodenv.join("shims"));
+
+    // nvs: `default` links to node/<version>/<arch>
+    #[cfg(not(target_os = "windows"))]
+    let nvs = env_dir("NVS_HOME").unwrap_or_else(|| home.join(".nvs"));
+    #[cfg(target_os = "windows")]
+    let nvs = env_dir("NVS_HOME").unwrap_or_else(|| data_home.join("nvs"));
+    dirs.push(install_bin(nvs.join("default")));
+
+    dirs
+}
+
+/// Homebrew prefixes, native one first: /opt/homebrew on Apple Silicon,
+/// /usr/local on Intel. HOMEBREW_PREFIX wins when set.
+#[cfg(target_os = "macos")]
+fn homebrew_prefixes() -> Vec<PathBuf> {
+    let mut prefixes: Vec<PathBuf> = std::env::var_os("HOMEBREW_PREFIX")
+        .filter(|prefix| !prefix.is_empty())
+        .map(PathBuf::from)
+        .into_iter()
+        .collect();
+    let native = if cfg!(target_arch = "aarch64") {
+        ["/opt/homebrew", "/usr/local"]
+    } else {
+        ["/usr/local", "/opt/homebrew"]
+    };
+    for prefix in native.map(PathBuf::from) {
+        if !prefixes.contains(&prefix) {
+            prefixes.push(prefix);
+        }
+    }
+    prefixes
+}
+
+/// macOS install dirs: Homebrew bins (plus keg-only node@N formulas, which
+/// brew doesn't link into bin), MacPorts, and the ~/Library locations fnm
+/// and pnpm default to.
+#[cfg(target_os = "macos")]
+fn macos_bin_dirs(home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    for prefix in homebrew_prefixes() {
+        dirs.push(prefix.join("bin"));
+        let mut kegs: Vec<(String, PathBuf)> = fs::read_dir(prefix.join("opt"))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+            .filter(|(name, _)| name.starts_with("node@"))
+            .collect();
+        kegs.sort_by_key(|(name, _)| {
+            std::cmp::Reverse(version_sort_key(name.trim_start_matches("node@")))
+        });
+        dirs.extend(kegs.into_iter().map(|(_, keg)| keg.join("bin")));
+    }
+    dirs.push(PathBuf::from("/opt/local/bin"));
+    if let Some(home) = home {
+        dirs.push(
+            home.join("Library")
+                .join("Application Support")
+                .join("fnm")
+                .join("aliases")
+                .join("default")
+                .join("bin"),
+        );
+        dirs.push(home.join("Library").join("pnpm"));
+    }
+    dirs
+}
+
+fn well_known_dirs() -> Vec<PathBuf> {
+    let home = BaseDirs::new().map(|b| b.home_dir().to_path_buf());
+
+    #[cfg(not(target_os = "windows"))]
+    let candidates: Vec<std::path::PathBuf> = {
+        #[cfg(target_os = "macos")]
+        let mut dirs = macos_bin_dirs(home.as_deref());
+        #[cfg(not(target_os = "macos"))]
+        let mut dirs = vec![std::path::PathBuf::from("/home/linuxbrew/.linuxbrew/bin")];
+        if let Some(ref h) = home {
+            // NVM_DIR is usually only set in shell rc files, but honour it
+            // when the app was started from a terminal
+            let nvm_dir = std::env::var_os("NVM_DIR")
+                .filter(|dir| !dir.is_empty())
+                .map(PathBuf::from)
+                .unwrap_or_else(|| h.join(".nvm"));
+            if let Some(nvm_bin) = find_nvm_bin(&nvm_dir) {
+                dirs.push(nvm_bin);
+            }
+            dirs.extend(version_manager_bin_dirs(h));
+            dirs.push(h.join(".volta").join("bin"));
+            dirs.push(
+                h.join(".local")
+                    .join("share")
+                    .join("fnm")
+                    .join("aliases")
+                    .join("default")
+                    .join("bin"),
+            );
+            dirs.push(h.join(".local").join("share").join("pnpm"));
+            dirs.push(h.join(".bun").join("bin"));
+            dirs.push(h.join(".local").join("bin"));
+        }
+        dirs.push(std::path::PathBuf::from("/usr/local/bin"));
+        dirs.push(std::path::PathBuf::from("/usr/bin"));
+        dirs
+    };
+
+    #[cfg(target_os = "windows")]
+    let candidates: Vec<std::path::PathBuf> = {
+        let mut dirs: Vec<std::path::PathBuf> = vec![];
+
+        // npm global
+        if let Ok(appdata) = std::env::var("APPDATA") {
+            dirs.push(std::path::PathBuf::from(&appdata).join("npm"));
+        }
+
         // fnm: active multishell path first, then scan multishells dir, then alias fallback
         if let Ok(multishell) = std::env::var("FNM_MULTISHELL_PATH") {
             dirs.push(std::path::PathBuf::from(multishell));
@@ -1217,6 +2960,11 @@ fn discover_via_well_known_dirs() -> Option<DiscoveryResult> {
             );
         }
 
+        // mise, nodenv-win and nvs
+        if let Some(ref h) = home {
+            dirs.extend(version_manager_bin_dirs(h));
+        }
+
         // Scoop: SCOOP env var first, then home fallback
         if let Ok(scoop) = std::env::var("SCOOP") {
             dirs.push(std::path::PathBuf::from(scoop).join("shims"));
@@ -1244,41 +2992,69 @@ fn discover_via_well_known_dirs() -> Option<DiscoveryResult> {
         dirs
     };
 
-    for dir in &candidates {
-        if dir.is_dir() {
-            for &name in OPENCLAW_BIN_NAMES {
-                let candidate = dir.join(name);
-                if candidate.is_file() {
-                    return Some(DiscoveryResult {
-                        bin_dir: dir.to_string_lossy().to_string(),
-                        bin_path: candidate.to_string_lossy().to_string(),
-                        bin_name: name.to_string(),
-                        method: "well-known-dirs".to_string(),
-                    });
-                }
-            }
+    candidates
+}
+
+#[cfg(not(target_os = "windows"))]
+const LOGIN_PATH_MARKER: &str = "__OPENCLAW_LOGIN_PATH__";
+
+/// How long a login shell PATH read is reused.
+const LOGIN_PATH_CACHE_SECS: u64 = 60;
+
+static LOGIN_PATH_CACHE: Mutex<Option<(std::time::Instant, Option<String>)>> = Mutex::new(None);
+
+/// The user's PATH as a fresh login would see it, read at most once per
+/// `LOGIN_PATH_CACHE_SECS`.
+fn login_shell_path() -> Option<String> {
+    // Held while reading, so a caller racing the startup prewarm waits for
+    // its result instead of starting a second shell
+    let mut cache = LOGIN_PATH_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some((read_at, path)) = cache.as_ref() {
+        if read_at.elapsed() < std::time::Duration::from_secs(LOGIN_PATH_CACHE_SECS) {
+            return path.clone();
         }
     }
-    None
+    let path = read_login_shell_path();
+    *cache = Some((std::time::Instant::now(), path.clone()));
+    path
 }
 
-fn discover_via_login_shell_path() -> Option<DiscoveryResult> {
+fn read_login_shell_path() -> Option<String> {
     #[cfg(not(target_os = "windows"))]
     {
-        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        // macOS apps launched from Finder get launchd's environment, which
+        // may lack SHELL; zsh is the default there. Most Mac setups put
+        // brew shellenv / nvm in ~/.zshrc, which only interactive shells read.
+        #[cfg(target_os = "macos")]
+        let (default_shell, flags) = ("/bin/zsh", ["-i", "-l", "-c"]);
+        #[cfg(not(target_os = "macos"))]
+        let (default_shell, flags) = ("/bin/sh", ["-l", "-c"]);
+        let shell = std::env::var("SHELL")
+            .ok()
+            .filter(|shell| !shell.is_empty())
+            .unwrap_or_else(|| default_shell.to_string());
         let output = Command::new(&shell)
-            .args(["-l", "-c", "echo $PATH"])
+            .args(flags)
+            .arg(format!("echo {}; echo $PATH", LOGIN_PATH_MARKER))
             .stdout(Stdio::piped())
             .stderr(Stdio::null())
             .stdin(Stdio::null())
             .output()
             .ok()?;
-        let path_str = String::from_utf8_lossy(&output.stdout);
-        let path_str = path_str.trim();
+        // Rc files may print banners; PATH is the line after the marker
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let path_str = stdout
+            .lines()
+            .skip_while(|line| line.trim() != LOGIN_PATH_MARKER)
+            .nth(1)?
+            .trim()
+            .to_string();
         if path_str.is_empty() {
             return None;
         }
-        search_path_string(path_str, "login-shell")
+        Some(path_str)
     }
     #[cfg(target_os = "windows")]
     {
@@ -1322,131 +3098,1650 @@ fn discover_via_login_shell_path() -> Option<DiscoveryResult> {
         if combined == ";" {
             return None;
         }
-        search_path_string(&combined, "registry-path")
+        Some(combined)
     }
 }
 
-fn discover_via_process_path() -> Option<DiscoveryResult> {
-    let path_str = std::env::var("PATH").unwrap_or_default();
-    if path_str.is_empty() {
-        return None;
+/// Method name for the login-shell tier; Windows reads PATH from the registry.
+#[cfg(not(target_os = "windows"))]
+const LOGIN_SHELL_METHOD: &str = "login-shell";
+#[cfg(target_os = "windows")]
+const LOGIN_SHELL_METHOD: &str = "registry-path";
+
+/// Per-tier budgets. A tier that overruns is reported as timed out and the
+/// result comes from the tiers that did finish.
+const LOGIN_SHELL_TIER_TIMEOUT_MS: u64 = 5_000;
+#[cfg(not(target_os = "windows"))]
+const SHELL_PROFILE_TIER_TIMEOUT_MS: u64 = 10_000;
+const WELL_KNOWN_TIER_TIMEOUT_MS: u64 = 10_000;
+const PROCESS_PATH_TIER_TIMEOUT_MS: u64 = 10_000;
+#[cfg(target_os = "windows")]
+const WSL_TIER_TIMEOUT_MS: u64 = 15_000;
+
+type TierResults = Vec<Result<DiscoveryResult, SkippedCandidate>>;
+type TierProbe = fn() -> TierRun;
+
+struct TierSpec {
+    method: &'static str,
+    timeout_ms: u64,
+    /// Only consulted when no other tier finds a working install.
+    fallback: bool,
+    probe: TierProbe,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscoveryTier {
+    method: String,
+    /// "found", "not-found", "timed-out", or "not-needed" for a fallback
+    /// tier that wasn't waited for because another tier had an answer.
+    status: String,
+    elapsed_ms: Option<u64>,
+}
+
+/// One directory or binary looked at during discovery, for the report.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscoveryCheck {
+    dir: String,
+    bin_path: Option<String>,
+    /// "found", "dir-missing", "missing", "not-executable" or "version-failed".
+    outcome: String,
+    detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscoveryReportTier {
+    #[serde(flatten)]
+    tier: DiscoveryTier,
+    checks: Vec<DiscoveryCheck>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DiscoveryReport {
+    /// The binary auto-detect would pick.
+    selected: Option<String>,
+    tiers: Vec<DiscoveryReportTier>,
+}
+
+#[derive(Default)]
+struct TierRun {
+    results: TierResults,
+    checks: Vec<DiscoveryCheck>,
+}
+
+struct TierOutcome {
+    tier: DiscoveryTier,
+    fallback: bool,
+    results: TierResults,
+    checks: Vec<DiscoveryCheck>,
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(path).is_ok_and(|meta| meta.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(target_os = "windows")]
+    {
+        path.is_file()
     }
-    search_path_string(&path_str, "process-path")
 }
 
-fn discover_openclaw_binary() -> Option<DiscoveryResult> {
-    discover_via_login_shell_path()
-        .or_else(|| discover_via_well_known_dirs())
-        .or_else(|| discover_via_process_path())
+/// Probe every openclaw binary in `dirs`. Every directory and name looked at
+/// is recorded.
+fn probe_dirs(dirs: Vec<PathBuf>, method: &str) -> TierRun {
+    let mut seen: Vec<PathBuf> = Vec::new();
+    let mut run = TierRun::default();
+    for dir in dirs {
+        if seen.contains(&dir) {
+            continue;
+        }
+        let dir_label = dir.to_string_lossy().to_string();
+        if !dir.is_dir() {
+            run.checks.push(DiscoveryCheck {
+                dir: dir_label,
+                bin_path: None,
+                outcome: "dir-missing".to_string(),
+                detail: None,
+            });
+            seen.push(dir);
+            continue;
+        }
+        for name in OPENCLAW_BIN_NAMES {
+            let candidate = dir.join(name);
+            let bin_path = candidate.to_string_lossy().to_string();
+            if !candidate.is_file() {
+                run.checks.push(DiscoveryCheck {
+                    dir: dir_label.clone(),
+                    bin_path: Some(bin_path),
+                    outcome: "missing".to_string(),
+                    detail: None,
+                });
+                continue;
+            }
+            if !is_executable(&candidate) {
+                run.checks.push(DiscoveryCheck {
+                    dir: dir_label.clone(),
+                    bin_path: Some(bin_path.clone()),
+                    outcome: "not-executable".to_string(),
+                    detail: None,
+                });
+                run.results.push(Err(SkippedCandidate {
+                    bin_path,
+                    method: method.to_string(),
+                    reason: "not executable".to_string(),
+                }));
+                continue;
+            }
+            let result = probe_candidate(&dir, name, method);
+            let (outcome, detail) = match &result {
+                Ok(found) => ("found", found.version.clone()),
+                Err(skipped) => ("version-failed", Some(skipped.reason.clone())),
+            };
+            run.checks.push(DiscoveryCheck {
+                dir: dir_label.clone(),
+                bin_path: Some(bin_path),
+                outcome: outcome.to_string(),
+                detail,
+            });
+            run.results.push(result);
+        }
+        seen.push(dir);
+    }
+    run
 }
 
-/// Resolve the openclaw binary path and its parent directory.
-/// Returns (bin_path, bin_dir). bin_dir is empty when falling back to bare "openclaw".
-fn resolve_openclaw_bin(config: &NodeClientConfig, app: &AppHandle) -> Result<(String, String), String> {
-    // Tier 0: bundled CLI code in app resources + system node
-    if config.use_bundled_runtime {
-        if let Ok(res_dir) = app.path().resource_dir() {
-            let mjs = res_dir.join("openclaw").join("openclaw.mjs");
-            if mjs.is_file() {
-                // Find system node binary via which/where
-                let node_name = if cfg!(windows) { "node.exe" } else { "node" };
-                let which_cmd = if cfg!(windows) { "where" } else { "which" };
-                if let Ok(output) = std::process::Command::new(which_cmd)
-                    .arg(node_name)
-                    .output()
-                {
-                    let node_path = String::from_utf8_lossy(&output.stdout)
-                        .lines()
-                        .next()
-                        .unwrap_or("")
-                        .trim()
-                        .to_string();
-                    if !node_path.is_empty() && Path::new(&node_path).is_file() {
-                        let sentinel = format!("{}::{}", node_path, mjs.display());
-                        return Ok((sentinel, res_dir.to_string_lossy().to_string()));
-                    }
-                }
+/// Discovery tiers in priority order: login shell PATH, PATH read from
+/// shell profile files (Unix), well-known install dirs, process PATH, then
+/// WSL on Windows as a fallback.
+fn discovery_tiers() -> Vec<TierSpec> {
+    #[allow(unused_mut)]
+    let mut tiers = vec![
+        TierSpec {
+            method: LOGIN_SHELL_METHOD,
+            timeout_ms: LOGIN_SHELL_TIER_TIMEOUT_MS,
+            fallback: false,
+            probe: || {
+                let dirs = login_shell_path()
+                    .map(|path_str| split_path_string(&path_str).collect())
+                    .unwrap_or_default();
+                probe_dirs(dirs, LOGIN_SHELL_METHOD)
+            },
+        },
+        // Same dirs without running rc code, for shells that hang or print
+        // banners; still answers when the login shell times out
+        #[cfg(not(target_os = "windows"))]
+        TierSpec {
+            method: "shell-profile",
+            timeout_ms: SHELL_PROFILE_TIER_TIMEOUT_MS,
+            fallback: false,
+            probe: || {
+                let dirs = BaseDirs::new()
+                    .map(|base| shell_profile::path_dirs(base.home_dir()))
+                    .unwrap_or_default();
+                probe_dirs(dirs, "shell-profile")
+            },
+        },
+        TierSpec {
+            method: "well-known-dirs",
+            timeout_ms: WELL_KNOWN_TIER_TIMEOUT_MS,
+            fallback: false,
+            probe: || probe_dirs(well_known_dirs(), "well-known-dirs"),
+        },
+        TierSpec {
+            method: "process-path",
+            timeout_ms: PROCESS_PATH_TIER_TIMEOUT_MS,
+            fallback: false,
+            probe: || {
+                let process_path = std::env::var("PATH").unwrap_or_default();
+                probe_dirs(split_path_string(&process_path).collect(), "process-path")
+            },
+        },
+    ];
+    // Running under WSL loses the exec host, so a native install always wins
+    #[cfg(target_os = "windows")]
+    tiers.push(TierSpec {
+        method: "wsl",
+        timeout_ms: WSL_TIER_TIMEOUT_MS,
+        fallback: true,
+        probe: || {
+            let found = discover_in_wsl();
+            let check = DiscoveryCheck {
+                dir: "wsl.exe".to_string(),
+                bin_path: found.as_ref().map(|found| found.bin_path.clone()),
+                outcome: if found.is_some() { "found" } else { "missing" }.to_string(),
+                detail: found.as_ref().and_then(|found| found.version.clone()),
+            };
+            TierRun {
+                results: found.into_iter().map(Ok).collect(),
+                checks: vec![check],
             }
+        },
+    });
+    tiers
+}
+
+/// Run all tiers at once on the async runtime's blocking pool, each against
+/// its own timeout. Unless `wait_for_fallback` is set, stop waiting once
+/// every regular tier has finished or timed out and one of them found a
+/// working install. Tiers still running when we stop keep going in the
+/// background and their results are dropped.
+fn run_discovery_tiers(wait_for_fallback: bool) -> Vec<TierOutcome> {
+    let tiers = discovery_tiers();
+    let started = std::time::Instant::now();
+    let (tx, rx) = std::sync::mpsc::channel();
+    for (index, spec) in tiers.iter().enumerate() {
+        let tx = tx.clone();
+        let probe = spec.probe;
+        tauri::async_runtime::spawn_blocking(move || {
+            let run = probe();
+            let _ = tx.send((index, run, started.elapsed()));
+        });
+    }
+    drop(tx);
+
+    let budget = |index: usize| std::time::Duration::from_millis(tiers[index].timeout_ms);
+    let mut finished: Vec<Option<(TierRun, std::time::Duration)>> =
+        tiers.iter().map(|_| None).collect();
+    loop {
+        let now = started.elapsed();
+        let pending = |index: usize| finished[index].is_none() && now < budget(index);
+        let any_pending = (0..tiers.len()).any(pending);
+        let decided = if wait_for_fallback {
+            !any_pending
+        } else {
+            let regular = |index: &usize| !tiers[*index].fallback;
+            let regular_pending = (0..tiers.len()).filter(regular).any(pending);
+            let regular_hit = (0..tiers.len()).filter(regular).any(|index| {
+                finished[index]
+                    .as_ref()
+                    .is_some_and(|(run, _)| run.results.iter().any(Result::is_ok))
+            });
+            !any_pending || (!regular_pending && regular_hit)
+        };
+        if decided {
+            break;
+        }
+        let wait = (0..tiers.len())
+            .filter(|&index| pending(index))
+            .map(|index| budget(index).saturating_sub(now))
+            .min()
+            .unwrap_or_default();
+        match rx.recv_timeout(wait) {
+            Ok((index, run, elapsed)) => finished[index] = Some((run, elapsed)),
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {}
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
         }
     }
-    // 1. Explicit install_path takes priority; verify binary exists there
-    if let Some(dir) = &config.install_path {
-        if !dir.is_empty() {
-            let dir_path = std::path::Path::new(dir.as_str());
-            for &name in OPENCLAW_BIN_NAMES {
-                let candidate = dir_path.join(name);
-                if candidate.is_file() {
-                    return Ok((candidate.to_string_lossy().to_string(), dir.clone()));
+
+    let now = started.elapsed();
+    tiers
+        .iter()
+        .zip(finished)
+        .map(|(spec, outcome)| {
+            let (status, elapsed_ms, run) = match outcome {
+                Some((run, elapsed)) => {
+                    let status = if run.results.iter().any(Result::is_ok) {
+                        "found"
+                    } else {
+                        "not-found"
+                    };
+                    (status, Some(elapsed.as_millis() as u64), run)
                 }
+                None if now.as_millis() as u64 >= spec.timeout_ms => {
+                    ("timed-out", Some(spec.timeout_ms), TierRun::default())
+                }
+                None => ("not-needed", None, TierRun::default()),
+            };
+            TierOutcome {
+                tier: DiscoveryTier {
+                    method: spec.method.to_string(),
+                    status: status.to_string(),
+                    elapsed_ms,
+                },
+                fallback: spec.fallback,
+                results: run.results,
+                checks: run.checks,
+            }
+        })
+        .collect()
+}
+
+/// Order two `--version` results: numeric components first, then a release
+/// beats a pre-release of the same version. Unknown versions sort lowest.
+fn compare_versions(a: Option<&str>, b: Option<&str>) -> std::cmp::Ordering {
+    let (Some(a), Some(b)) = (a, b) else {
+        return a.is_some().cmp(&b.is_some());
+    };
+    let mut a_parts = version_components(a);
+    let mut b_parts = version_components(b);
+    let len = a_parts.len().max(b_parts.len());
+    a_parts.resize(len, 0);
+    b_parts.resize(len, 0);
+    a_parts
+        .cmp(&b_parts)
+        .then_with(|| (!a.contains('-')).cmp(&!b.contains('-')))
+}
+
+fn install_candidate(found: &DiscoveryResult) -> InstallCandidate {
+    InstallCandidate {
+        bin_dir: found.bin_dir.clone(),
+        bin_path: found.bin_path.clone(),
+        bin_name: found.bin_name.clone(),
+        method: found.method.clone(),
+        real_bin_path: found.real_bin_path.clone(),
+        version: found.version.clone(),
+        error: None,
+    }
+}
+
+/// The newest working install across the regular tiers (tier order breaks
+/// ties; fallback tiers only count when those found nothing), with the
+/// others as alternatives, anything that failed `--version`, and how each
+/// tier fared.
+fn choose_install(outcomes: &[TierOutcome]) -> Option<DiscoveryResult> {
+    let mut skipped: Vec<SkippedCandidate> = Vec::new();
+    let mut working: Vec<DiscoveryResult> = Vec::new();
+    for outcome in outcomes {
+        for candidate in &outcome.results {
+            match candidate {
+                // The same binary often turns up on several PATHs
+                Ok(found) if working.iter().any(|seen| seen.bin_path == found.bin_path) => {}
+                Ok(found) => working.push(found.clone()),
+                Err(candidate)
+                    if skipped
+                        .iter()
+                        .any(|seen| seen.bin_path == candidate.bin_path) => {}
+                Err(candidate) => skipped.push(candidate.clone()),
             }
-            // install_path set but binary missing there — fall through to discovery
         }
     }
-    // 2. Auto-discover via login shell PATH, well-known dirs, or process PATH
-    if let Some(result) = discover_openclaw_binary() {
-        return Ok((result.bin_path, result.bin_dir));
+    let is_fallback = |found: &DiscoveryResult| {
+        outcomes
+            .iter()
+            .any(|outcome| outcome.fallback && outcome.tier.method == found.method)
+    };
+    let has_regular = working.iter().any(|found| !is_fallback(found));
+    let mut best: Option<usize> = None;
+    for (index, found) in working.iter().enumerate() {
+        if has_regular && is_fallback(found) {
+            continue;
+        }
+        let newer = best.is_none_or(|best| {
+            compare_versions(found.version.as_deref(), working[best].version.as_deref()).is_gt()
+        });
+        if newer {
+            best = Some(index);
+        }
     }
-    // 3. Last resort: bare name (relies on the child process PATH)
-    Ok(("openclaw".to_string(), String::new()))
+    let mut chosen = working.remove(best?);
+    chosen.alternatives = working.iter().map(install_candidate).collect();
+    chosen.skipped = skipped;
+    chosen.tiers = outcomes
+        .iter()
+        .map(|outcome| outcome.tier.clone())
+        .collect();
+    Some(chosen)
 }
 
-// ---------------------------------------------------------------------------
-// Node process management
-// ---------------------------------------------------------------------------
-
-fn start_node_internal(app: &AppHandle) -> Result<(), String> {
-    {
-        let state = app.state::<AppState>();
-        let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
-        let (running, maybe_exit_log) = refresh_process_state(&mut runtime);
-        if let Some(exit_log) = maybe_exit_log {
-            drop(runtime);
-            push_log_line(app, exit_log);
-            let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
-            if runtime.child.is_some() {
-                return Ok(());
-            }
-            let (running_again, _) = refresh_process_state(&mut runtime);
-            if running_again {
-                return Ok(());
-            }
-        } else if running {
-            return Ok(());
+fn discover_openclaw_binary() -> Option<DiscoveryResult> {
+    let outcomes = run_discovery_tiers(false);
+    for outcome in &outcomes {
+        if outcome.tier.status == "timed-out" {
+            eprintln!("openclaw discovery tier {} timed out", outcome.tier.method);
         }
     }
+    let chosen = choose_install(&outcomes);
+    for candidate in chosen.iter().flat_map(|found| &found.skipped) {
+        eprintln!(
+            "skipping openclaw candidate {}: {}",
+            candidate.bin_path, candidate.reason
+        );
+    }
+    chosen
+}
 
-    // Set status to starting
-    {
-        let state = app.state::<AppState>();
-        if let Ok(mut runtime) = state.runtime.lock() {
-            runtime.node_status = Some(NodeStatus::Starting);
+/// Last tier on Windows: an openclaw that only exists inside WSL.
+#[cfg(target_os = "windows")]
+fn discover_in_wsl() -> Option<DiscoveryResult> {
+    let (bin_path, bin_dir) = wsl::discover(VERSION_PROBE_TIMEOUT_MS)?;
+    let version = probe_openclaw_version(&bin_path, VERSION_PROBE_TIMEOUT_MS)
+        .ok()
+        .and_then(|raw| parse_version_output(&raw));
+    Some(DiscoveryResult {
+        bin_dir,
+        bin_path,
+        bin_name: "openclaw".to_string(),
+        method: "wsl".to_string(),
+        real_bin_path: None,
+        real_bin_dir: None,
+        version,
+        skipped: Vec::new(),
+        alternatives: Vec::new(),
+        tiers: Vec::new(),
+    })
+}
+
+/// Every openclaw install across all tiers, including ones that fail
+/// `--version`, so the user can choose instead of taking the first hit.
+/// A binary reported by several tiers keeps the first.
+fn list_openclaw_installs() -> Vec<InstallCandidate> {
+    let mut installs: Vec<InstallCandidate> = Vec::new();
+    let candidates = run_discovery_tiers(true)
+        .into_iter()
+        .flat_map(|outcome| outcome.results);
+    for candidate in candidates {
+        let install = match candidate {
+            Ok(found) => install_candidate(&found),
+            Err(skipped) => {
+                let bin_path = Path::new(&skipped.bin_path);
+                InstallCandidate {
+                    bin_dir: bin_path
+                        .parent()
+                        .map(|dir| dir.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    bin_name: bin_path
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                    bin_path: skipped.bin_path,
+                    method: skipped.method,
+                    real_bin_path: None,
+                    version: None,
+                    error: Some(skipped.reason),
+                }
+            }
         };
+        if !installs
+            .iter()
+            .any(|seen| seen.bin_path == install.bin_path)
+        {
+            installs.push(install);
+        }
     }
-    let _ = app.emit("node-status-changed", NodeStatus::Starting.as_str());
+    installs
+}
 
-    let config = {
-        let state = app.state::<AppState>();
-        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
-        cfg
-    };
+/// Everything discovery looked at, tier by tier, for "can't find my
+/// install" reports. All tiers run to completion (or their timeout).
+fn discovery_report() -> DiscoveryReport {
+    let outcomes = run_discovery_tiers(true);
+    let selected = choose_install(&outcomes).map(|found| found.bin_path);
+    DiscoveryReport {
+        selected,
+        tiers: outcomes
+            .into_iter()
+            .map(|outcome| DiscoveryReportTier {
+                tier: outcome.tier,
+                checks: outcome.checks,
+            })
+            .collect(),
+    }
+}
 
-    let (openclaw_bin, bin_dir) = resolve_openclaw_bin(&config, app)?;
-    push_log_line(app, format!("using openclaw binary: {}", openclaw_bin));
-    // Sentinel "node_path::mjs_path" means bundled runtime: run `node openclaw.mjs ...`
-    let mut command = if openclaw_bin.contains("::") {
-        let mut parts = openclaw_bin.splitn(2, "::");
-        let node = parts
+/// Find the system `node` binary via which/where.
+fn find_system_node() -> Option<String> {
+    let node_name = if cfg!(windows) { "node.exe" } else { "node" };
+    let which_cmd = if cfg!(windows) { "where" } else { "which" };
+    let output = Command::new(which_cmd).arg(node_name).output().ok()?;
+    let node_path = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_string();
+    if !node_path.is_empty() && Path::new(&node_path).is_file() {
+        return Some(node_path);
+    }
+    // Under launchd PATH is only /usr/bin:/bin:/usr/sbin:/sbin
+    #[cfg(target_os = "macos")]
+    for prefix in homebrew_prefixes() {
+        let node = prefix.join("bin").join("node");
+        if node.is_file() {
+            return Some(node.to_string_lossy().to_string());
+        }
+    }
+    None
+}
+
+/// launchd's default PATH, which apps opened from Finder or the Dock inherit.
+#[cfg(target_os = "macos")]
+const LAUNCHD_DEFAULT_PATH: &[&str] = &["/usr/bin", "/bin", "/usr/sbin", "/sbin"];
+
+/// Under launchd's bare PATH the node host can't find git, brew-installed
+/// CLIs or anything else the user's shell normally provides; append the
+/// login shell's PATH in that case.
+#[cfg(target_os = "macos")]
+fn launchd_path_fixup(path: String) -> String {
+    let bare = split_path_string(&path)
+        .all(|dir| LAUNCHD_DEFAULT_PATH.iter().any(|default| dir == Path::new(default)));
+    if !bare {
+        return path;
+    }
+    let Some(login_path) = login_shell_path() else {
+        return path;
+    };
+    let mut dirs: Vec<PathBuf> = split_path_string(&path).collect();
+    for dir in split_path_string(&login_path) {
+        if !dirs.contains(&dir) {
+            dirs.push(dir);
+        }
+    }
+    dirs.iter()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join(PATH_SEP)
+}
+
+/// The `node` used to run JS entrypoints: the managed runtime when enabled
+/// and installed, otherwise the system one.
+fn resolve_node_binary(config: &NodeClientConfig, app: &AppHandle) -> Option<String> {
+    if config.use_managed_node {
+        if let Some(node) = managed_node::installed_node_path(app) {
+            return Some(node);
+        }
+    }
+    find_system_node()
+}
+
+/// Node.js version openclaw.mjs refuses to start below; Bun and Deno report
+/// the Node version they emulate in `process.versions.node`.
+const BUNDLED_MIN_NODE_VERSION: &str = "22.12";
+
+/// Bun or Deno to run the bundled openclaw.mjs when there is no Node.js:
+/// the first one found whose Node compatibility level passes the CLI's own
+/// version gate. Each rejected runtime is logged with the reason.
+fn find_alt_js_runtime(app: &AppHandle) -> Option<String> {
+    let home = BaseDirs::new().map(|b| b.home_dir().to_path_buf());
+    for (name, home_bin) in [("bun", ".bun"), ("deno", ".deno")] {
+        let runtime = find_executable(name).or_else(|| {
+            let bin = home.as_ref()?.join(home_bin).join("bin");
+            EXECUTABLE_SUFFIXES
+                .iter()
+                .map(|suffix| bin.join(format!("{}{}", name, suffix)))
+                .find(|candidate| candidate.is_file())
+        });
+        let Some(runtime) = runtime else {
+            continue;
+        };
+        let runtime = runtime.to_string_lossy().to_string();
+        match alt_runtime_node_version(&runtime, name) {
+            Some(version) if !version_older_than(&version, BUNDLED_MIN_NODE_VERSION) => {
+                return Some(runtime);
+            }
+            Some(version) => push_log_line(
+                app,
+                format!(
+                    "{} skipped: emulates Node.js {}, openclaw needs {}+",
+                    runtime, version, BUNDLED_MIN_NODE_VERSION
+                ),
+            ),
+            None => push_log_line(
+                app,
+                format!("{} skipped: could not read its Node.js compatibility version", runtime),
+            ),
+        }
+    }
+    None
+}
+
+/// The `process.versions.node` a Bun or Deno binary reports.
+fn alt_runtime_node_version(runtime: &str, name: &str) -> Option<String> {
+    let script = "console.log(process.versions.node)";
+    let mut command = Command::new(runtime);
+    if name == "deno" {
+        command.args(["eval", script]);
+    } else {
+        command.args(["-e", script]);
+    }
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    #[cfg(target_os = "windows")]
+    command.creation_flags(CREATE_NO_WINDOW);
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    version
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit())
+        .then_some(version)
+}
+
+/// The built entry script of a checkout at `root`: the CLI package's dist
+/// output in a workspace layout, else the root launcher once `dist/` exists.
+fn dev_build_entry(root: &Path) -> Option<PathBuf> {
+    let package_entry = root
+        .join("packages")
+        .join("cli")
+        .join("dist")
+        .join("openclaw.mjs");
+    if package_entry.is_file() {
+        return Some(package_entry);
+    }
+    let root_entry = root.join("openclaw.mjs");
+    (root_entry.is_file() && root.join("dist").is_dir()).then_some(root_entry)
+}
+
+/// Whether `openclaw_bin` is the dev-build sentinel for `config.dev_root`.
+fn is_dev_build(config: &NodeClientConfig, openclaw_bin: &str) -> bool {
+    let Some(root) = non_empty(&config.dev_root) else {
+        return false;
+    };
+    openclaw_bin
+        .split_once("::")
+        .is_some_and(|(_, mjs)| Path::new(mjs).starts_with(root))
+}
+
+/// Resolve the openclaw binary path and its parent directory.
+/// Returns (bin_path, bin_dir, node_dir). bin_dir is empty when falling back to
+/// bare "openclaw". When the binary found is a version-manager shim, bin_path
+/// is the concrete install it forwards to and node_dir holds that install's
+/// `node`, to go first on the child PATH.
+fn resolve_openclaw_bin(
+    config: &NodeClientConfig,
+    app: &AppHandle,
+) -> Result<(String, String, Option<String>), String> {
+    // Dev build: a configured checkout wins over everything else, run as
+    // `node <entry>.mjs` like the bundled runtime
+    if let Some(root) = non_empty(&config.dev_root) {
+        match dev_build_entry(Path::new(root)) {
+            Some(mjs) => {
+                if let Some(node_path) = resolve_node_binary(config, app) {
+                    let sentinel = format!("{}::{}", node_path, mjs.display());
+                    return Ok((sentinel, root.to_string(), None));
+                }
+                push_log_line(app, "dev build skipped: no Node.js found");
+            }
+            None => push_log_line(
+                app,
+                format!(
+                    "dev build skipped: no built openclaw.mjs under {} (build the checkout first)",
+                    root
+                ),
+            ),
+        }
+    }
+    // Tier 0: bundled CLI code (self-updated copy in app data, else app
+    // resources) + system node, or Bun/Deno when there is no node
+    if config.use_bundled_runtime {
+        if let Some(bundle_dir) = runtime_update::active_bundle_dir(app) {
+            let mjs = bundle_dir.join("openclaw.mjs");
+            let runtime = resolve_node_binary(config, app).or_else(|| find_alt_js_runtime(app));
+            if let Some(node_path) = runtime {
+                let sentinel = format!("{}::{}", node_path, mjs.display());
+                let bin_dir = bundle_dir.parent().unwrap_or(&bundle_dir);
+                return Ok((sentinel, bin_dir.to_string_lossy().to_string(), None));
+            }
+            push_log_line(
+                app,
+                "bundled runtime skipped: no Node.js, Bun or Deno found (enable the managed \
+                 Node.js runtime to download one)",
+            );
+        }
+    }
+    // 1. Explicit install_path takes priority; verify binary exists there
+    if let Some(dir) = &config.install_path {
+        if !dir.is_empty() {
+            let dir_path = std::path::Path::new(dir.as_str());
+            // A \\wsl$ or \\wsl.localhost directory: run it inside that distro
+            if let Some((Some(distro), linux_dir)) = wsl::to_wsl_path(dir) {
+                if dir_path.join("openclaw").is_file() {
+                    let linux_bin = format!("{}/openclaw", linux_dir.trim_end_matches('/'));
+                    return Ok((
+                        wsl::encode_bin(Some(&distro), &linux_bin),
+                        dir.clone(),
+                        None,
+                    ));
+                }
+            }
+            for &name in OPENCLAW_BIN_NAMES {
+                let candidate = dir_path.join(name);
+                if candidate.is_file() {
+                    let Some(resolved) = shims::resolve(&candidate) else {
+                        return Ok((candidate.to_string_lossy().to_string(), dir.clone(), None));
+                    };
+                    return Ok((
+                        resolved.real_path.to_string_lossy().to_string(),
+                        dir.clone(),
+                        resolved
+                            .node_dir
+                            .map(|node_dir| node_dir.to_string_lossy().to_string()),
+                    ));
+                }
+            }
+            // install_path set but binary missing there — fall through to discovery
+        }
+    }
+    // 2. Auto-discover via login shell PATH, well-known dirs, or process PATH
+    if let Some(result) = discover_openclaw_binary() {
+        let bin_path = result.real_bin_path.unwrap_or(result.bin_path);
+        return Ok((bin_path, result.bin_dir, result.real_bin_dir));
+    }
+    // 3. Last resort: bare name (relies on the child process PATH)
+    Ok(("openclaw".to_string(), String::new(), None))
+}
+
+/// Build a `Command` for a resolved openclaw binary. The bundled runtime
+/// sentinel "node_path::mjs_path" expands to `node openclaw.mjs` (or
+/// `bun openclaw.mjs`, `deno run -A openclaw.mjs`).
+fn openclaw_command(openclaw_bin: &str) -> Result<Command, String> {
+    if let Some(bin) = wsl::parse_bin(openclaw_bin) {
+        Ok(wsl::command(&bin, None))
+    } else if openclaw_bin.contains("::") {
+        let mut parts = openclaw_bin.splitn(2, "::");
+        let node = parts
             .next()
             .ok_or_else(|| "Invalid bundled runtime sentinel: missing node path".to_string())?;
         let mjs = parts
             .next()
             .ok_or_else(|| "Invalid bundled runtime sentinel: missing entry script path".to_string())?;
         let mut c = Command::new(node);
+        let is_deno = Path::new(node)
+            .file_stem()
+            .is_some_and(|stem| stem.eq_ignore_ascii_case("deno"));
+        if is_deno {
+            // Node-compat mode with the same unrestricted access node has
+            c.args(["run", "-A"]);
+        }
         c.arg(mjs);
-        c
+        Ok(c)
+    } else {
+        Ok(Command::new(openclaw_bin))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// CLI version detection
+// ---------------------------------------------------------------------------
+
+/// Run `<openclaw> --version` and return its trimmed stdout.
+/// The CLI prints e.g. "OpenClaw 2026.3.14 (abc1234)".
+fn probe_openclaw_version(openclaw_bin: &str, timeout_ms: u64) -> Result<String, String> {
+    let mut command = openclaw_command(openclaw_bin)?;
+
+    #[cfg(target_os = "linux")]
+    sanitize_appimage_env(&mut command);
+
+    command
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+
+    #[cfg(target_os = "windows")]
+    {
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|err| format!("failed to run `openclaw --version`: {}", err))?;
+    let deadline =
+        std::time::Instant::now() + std::time::Duration::from_millis(timeout_ms);
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                let mut out = String::new();
+                if let Some(mut stdout) = child.stdout.take() {
+                    let _ = stdout.read_to_string(&mut out);
+                }
+                if !status.success() {
+                    return Err(format!("`openclaw --version` exited with status {}", status));
+                }
+                return Ok(out.trim().to_string());
+            }
+            Ok(None) => {
+                if std::time::Instant::now() >= deadline {
+                    let _ = child.kill();
+                    let _ = child.wait();
+                    return Err("`openclaw --version` timed out".to_string());
+                }
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            Err(err) => return Err(format!("failed to inspect `openclaw --version`: {}", err)),
+        }
+    }
+}
+
+/// Extract the first version-looking token ("2026.3.14", "v1.2.3-beta.1").
+fn parse_version_output(raw: &str) -> Option<String> {
+    raw.split_whitespace()
+        .map(|token| token.trim_start_matches('v'))
+        .find(|token| {
+            token.contains('.')
+                && token.chars().next().is_some_and(|c| c.is_ascii_digit())
+        })
+        .map(|token| token.to_string())
+}
+
+/// Numeric `major.minor.patch` components; pre-release suffixes are ignored.
+fn version_components(version: &str) -> Vec<u64> {
+    version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("")
+        .split('.')
+        .map(|part| part.parse::<u64>().unwrap_or(0))
+        .collect()
+}
+
+fn version_older_than(version: &str, minimum: &str) -> bool {
+    let mut a = version_components(version);
+    let mut b = version_components(minimum);
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    a < b
+}
+
+fn detect_openclaw_version(openclaw_bin: &str) -> Result<OpenclawVersionInfo, String> {
+    let raw = probe_openclaw_version(openclaw_bin, VERSION_PROBE_TIMEOUT_MS)?;
+    let version = parse_version_output(&raw);
+    let outdated = version
+        .as_deref()
+        .map(|v| version_older_than(v, MIN_OPENCLAW_VERSION))
+        .unwrap_or(false);
+    Ok(OpenclawVersionInfo {
+        bin_path: openclaw_bin.to_string(),
+        version,
+        raw,
+        min_supported: MIN_OPENCLAW_VERSION.to_string(),
+        outdated,
+    })
+}
+
+/// Return the cached version for `openclaw_bin`, probing the binary when the
+/// cache is empty, stale (different binary), or `refresh` is set.
+fn cached_openclaw_version(
+    app: &AppHandle,
+    openclaw_bin: &str,
+    refresh: bool,
+) -> Result<OpenclawVersionInfo, String> {
+    let state = app.state::<AppState>();
+    if !refresh {
+        let cached = state
+            .openclaw_version
+            .lock()
+            .map_err(|err| err.to_string())?
+            .clone();
+        if let Some(info) = cached.filter(|info| info.bin_path == openclaw_bin) {
+            return Ok(info);
+        }
+    }
+    let info = detect_openclaw_version(openclaw_bin)?;
+    if let Ok(mut cached) = state.openclaw_version.lock() {
+        *cached = Some(info.clone());
+    }
+    if info.outdated {
+        push_log_line(
+            app,
+            format!(
+                "Warning: openclaw {} is older than the minimum supported version {}. \
+                 Update with: npm install -g openclaw@latest",
+                info.version.as_deref().unwrap_or("unknown"),
+                MIN_OPENCLAW_VERSION
+            ),
+        );
+    }
+    Ok(info)
+}
+
+// ---------------------------------------------------------------------------
+// CLI installation
+// ---------------------------------------------------------------------------
+
+#[cfg(target_os = "windows")]
+const EXECUTABLE_SUFFIXES: &[&str] = &[".cmd", ".exe", ""];
+#[cfg(not(target_os = "windows"))]
+const EXECUTABLE_SUFFIXES: &[&str] = &[""];
+
+/// Locate a package-manager executable: process PATH first, then next to the
+/// system `node` (npm ships alongside it), then Volta's home.
+fn find_executable(name: &str) -> Option<PathBuf> {
+    let mut dirs: Vec<PathBuf> = std::env::var("PATH")
+        .unwrap_or_default()
+        .split(PATH_SEP)
+        .filter(|d| !d.trim().is_empty())
+        .map(PathBuf::from)
+        .collect();
+    if let Some(node) = find_system_node() {
+        if let Some(node_dir) = Path::new(&node).parent() {
+            dirs.push(node_dir.to_path_buf());
+        }
+    }
+    if let Ok(volta_home) = std::env::var("VOLTA_HOME") {
+        dirs.push(PathBuf::from(volta_home).join("bin"));
+    }
+    if let Some(home) = BaseDirs::new().map(|b| b.home_dir().to_path_buf()) {
+        dirs.push(home.join(".volta").join("bin"));
+    }
+    dirs.iter().find_map(|dir| {
+        EXECUTABLE_SUFFIXES
+            .iter()
+            .map(|suffix| dir.join(format!("{}{}", name, suffix)))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+/// Pick the package manager that owns the user's global installs: Volta when
+/// it manages node, else npm, else pnpm. Returns (program, args, label).
+fn detect_install_command() -> Option<(PathBuf, Vec<&'static str>, &'static str)> {
+    let volta_managed = std::env::var("VOLTA_HOME").is_ok()
+        || find_system_node().is_some_and(|n| n.to_lowercase().contains("volta"));
+    if volta_managed {
+        if let Some(volta) = find_executable("volta") {
+            return Some((volta, vec!["install", "openclaw@latest"], "volta"));
+        }
+    }
+    if let Some(npm) = find_executable("npm") {
+        return Some((npm, vec!["install", "-g", "openclaw@latest"], "npm"));
+    }
+    if let Some(pnpm) = find_executable("pnpm") {
+        return Some((pnpm, vec!["add", "-g", "openclaw@latest"], "pnpm"));
+    }
+    None
+}
+
+fn spawn_install_output_reader<R>(
+    app: AppHandle,
+    reader: R,
+    stream_name: &'static str,
+) -> tauri::async_runtime::JoinHandle<()>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tauri::async_runtime::spawn(async move {
+        let mut lines = tokio::io::BufReader::new(reader).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            let _ = app.emit(
+                "openclaw-install-progress",
+                serde_json::json!({ "stream": stream_name, "line": line }),
+            );
+            push_log_line(&app, format!("[install] {}", line));
+        }
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Orphaned node host detection
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NodePidFile {
+    pid: u32,
+    started_at_ms: u64,
+    bin: String,
+}
+
+fn node_pid_file_path() -> Result<PathBuf, String> {
+    Ok(openclaw_dir()?.join("node-client-node.pid"))
+}
+
+fn write_node_pid_file(pid: u32, bin: &str) {
+    let Ok(path) = node_pid_file_path() else {
+        return;
+    };
+    let record = NodePidFile {
+        pid,
+        started_at_ms: now_ms(),
+        bin: bin.to_string(),
+    };
+    if let Ok(json) = serde_json::to_string(&record) {
+        let _ = fs::write(&path, json);
+    }
+}
+
+fn remove_node_pid_file() {
+    if let Ok(path) = node_pid_file_path() {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn process_alive(pid: u32) -> bool {
+    let rc = unsafe { libc::kill(pid as i32, 0) };
+    rc == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(target_os = "windows")]
+fn process_alive(pid: u32) -> bool {
+    use windows_sys::Win32::Foundation::{CloseHandle, STILL_ACTIVE};
+    use windows_sys::Win32::System::Threading::{
+        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION,
+    };
+    if let Some(alive) = run_as::logon_child_alive(pid) {
+        return alive;
+    }
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return false;
+        }
+        let mut code: u32 = 0;
+        let ok = GetExitCodeProcess(handle, &mut code) != 0;
+        CloseHandle(handle);
+        ok && code == STILL_ACTIVE as u32
+    }
+}
+
+/// Arguments of a running process, used to make sure a recorded pid still
+/// belongs to `openclaw node run` and wasn't reused by something else.
+fn process_argv(pid: u32) -> Option<Vec<String>> {
+    #[cfg(target_os = "linux")]
+    {
+        let raw = fs::read(format!("/proc/{}/cmdline", pid)).ok()?;
+        Some(
+            raw.split(|b| *b == 0)
+                .filter(|part| !part.is_empty())
+                .map(|part| String::from_utf8_lossy(part).to_string())
+                .collect(),
+        )
+    }
+    #[cfg(target_os = "macos")]
+    {
+        let output = Command::new("ps")
+            .args(["-o", "command=", "-p", &pid.to_string()])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+            .ok()?;
+        Some(split_command_line(&String::from_utf8_lossy(&output.stdout)))
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let query = format!(
+            "(Get-CimInstance Win32_Process -Filter 'ProcessId={}').CommandLine",
+            pid
+        );
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &query])
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .ok()?;
+        Some(split_command_line(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// Split a command line as `ps` or Win32_Process reports it into arguments;
+/// double quotes group, and are dropped.
+#[cfg(not(target_os = "linux"))]
+fn split_command_line(line: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut in_arg = false;
+    for c in line.trim().chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_arg = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        args.push(current);
+    }
+    args
+}
+
+/// Whether `argv` runs `openclaw node run`: the openclaw entry point (the
+/// binary, its shim, or openclaw.mjs under node) directly followed by
+/// `node run`. A process that merely mentions both, like an editor with
+/// the repo open, doesn't count.
+fn looks_like_node_host(argv: &[String]) -> bool {
+    let is_entry_point = |arg: &str| {
+        let name = arg.rsplit(['/', '\\']).next().unwrap_or(arg).to_ascii_lowercase();
+        matches!(
+            name.as_str(),
+            "openclaw" | "openclaw.mjs" | "openclaw.cmd" | "openclaw.exe"
+        )
+    };
+    argv.windows(3)
+        .any(|args| is_entry_point(&args[0]) && args[1] == "node" && args[2] == "run")
+}
+
+/// Ask a process we don't own a `Child` for to exit, escalating to a hard
+/// kill after the grace period.
+fn terminate_pid(pid: u32) {
+    #[cfg(not(target_os = "windows"))]
+    let signalled = unsafe { libc::kill(pid as i32, libc::SIGTERM) == 0 };
+    #[cfg(target_os = "windows")]
+    let signalled = send_ctrl_break(pid);
+
+    if signalled {
+        let deadline =
+            std::time::Instant::now() + std::time::Duration::from_millis(NODE_STOP_GRACE_MS);
+        while std::time::Instant::now() < deadline {
+            if !process_alive(pid) {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    unsafe {
+        libc::kill(pid as i32, libc::SIGKILL);
+    }
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::Foundation::CloseHandle;
+        use windows_sys::Win32::System::Threading::{
+            OpenProcess, TerminateProcess, PROCESS_TERMINATE,
+        };
+        // Started as another account: this user can't open it by pid
+        if run_as::terminate_logon_child(pid) {
+            return;
+        }
+        unsafe {
+            let handle = OpenProcess(PROCESS_TERMINATE, 0, pid);
+            if !handle.is_null() {
+                TerminateProcess(handle, 1);
+                CloseHandle(handle);
+            }
+        }
+    }
+}
+
+/// On startup, find a node host left running by a previous (crashed) app
+/// instance via the pid file and adopt or terminate it per `orphan_policy`,
+/// so auto-start doesn't spawn a duplicate.
+fn handle_orphaned_node(app: &AppHandle) {
+    let Ok(path) = node_pid_file_path() else {
+        return;
+    };
+    let Some(record) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_str::<NodePidFile>(&raw).ok())
+    else {
+        return;
+    };
+    let is_orphan = process_alive(record.pid)
+        && process_argv(record.pid)
+            .map(|argv| looks_like_node_host(&argv))
+            .unwrap_or(false);
+    if !is_orphan {
+        remove_node_pid_file();
+        return;
+    }
+
+    let policy = {
+        let state = app.state::<AppState>();
+        let policy = state
+            .config
+            .lock()
+            .map(|cfg| cfg.orphan_policy.clone())
+            .unwrap_or_else(|_| default_orphan_policy());
+        policy
+    };
+    if policy == "adopt" {
+        {
+            let state = app.state::<AppState>();
+            if let Ok(mut runtime) = state.runtime.lock() {
+                runtime.adopted_pid = Some(record.pid);
+                runtime.started_at_ms = Some(record.started_at_ms);
+                runtime.node_status = Some(NodeStatus::Running);
+                runtime.publish_status();
+            };
+        }
+        push_log_line(
+            app,
+            format!(
+                "adopted node host left running by a previous instance (pid {}); \
+                 its output is not captured until it is restarted",
+                record.pid
+            ),
+        );
+        let _ = app.emit("node-status-changed", NodeStatus::Running.as_str());
     } else {
-        Command::new(&openclaw_bin)
+        push_log_line(
+            app,
+            format!("terminating orphaned node host (pid {})", record.pid),
+        );
+        terminate_pid(record.pid);
+        remove_node_pid_file();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Auto-start
+// ---------------------------------------------------------------------------
+
+const GATEWAY_PROBE_INTERVAL_MS: u64 = 2_000;
+const GATEWAY_PROBE_CONNECT_TIMEOUT_MS: u64 = 3_000;
+
+/// Whether `host:port` resolves and accepts a TCP connection.
+fn gateway_reachable(host: &str, port: u16) -> bool {
+    use std::net::{TcpStream, ToSocketAddrs};
+    let Ok(addrs) = (host, port).to_socket_addrs() else {
+        return false;
     };
+    let timeout = std::time::Duration::from_millis(GATEWAY_PROBE_CONNECT_TIMEOUT_MS);
+    addrs
+        .into_iter()
+        .any(|addr| TcpStream::connect_timeout(&addr, timeout).is_ok())
+}
+
+/// Run the configured auto-start delay and, if enabled, block until the
+/// gateway is reachable or the max wait expires. At login Wi-Fi is often
+/// still connecting, and starting immediately just burns node host retries.
+fn wait_for_auto_start(app: &AppHandle, config: &NodeClientConfig) {
+    if config.auto_start_delay_secs > 0 {
+        push_log_line(
+            app,
+            format!("delaying auto-start by {}s", config.auto_start_delay_secs),
+        );
+        std::thread::sleep(std::time::Duration::from_secs(u64::from(
+            config.auto_start_delay_secs,
+        )));
+    }
+    if !config.auto_start_wait_for_gateway {
+        return;
+    }
+
+    let deadline = std::time::Instant::now()
+        + std::time::Duration::from_secs(u64::from(config.auto_start_max_wait_secs));
+    let mut logged = false;
+    while !gateway_reachable(&config.host, config.port) {
+        if std::time::Instant::now() >= deadline {
+            push_log_line(
+                app,
+                format!(
+                    "gateway {}:{} still unreachable after {}s; starting node host anyway",
+                    config.host, config.port, config.auto_start_max_wait_secs
+                ),
+            );
+            return;
+        }
+        if !logged {
+            push_log_line(
+                app,
+                format!(
+                    "waiting for gateway {}:{} to become reachable before auto-start",
+                    config.host, config.port
+                ),
+            );
+            logged = true;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(GATEWAY_PROBE_INTERVAL_MS));
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Pre-flight checks
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreflightIssue {
+    /// "gateway-unreachable" | "node-host-already-running" | "tls-fingerprint-changed"
+    code: String,
+    message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pids: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PreflightReport {
+    ok: bool,
+    gateway_reachable: bool,
+    issues: Vec<PreflightIssue>,
+}
+
+/// Pids of `openclaw node run` processes this app doesn't own (another app
+/// instance, a manual terminal run, a leftover service).
+fn find_foreign_node_hosts(exclude: &[u32]) -> Vec<u32> {
+    #[cfg(target_os = "linux")]
+    let pids: Vec<u32> = fs::read_dir("/proc")
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+                .filter(|pid| {
+                    process_argv(*pid)
+                        .map(|argv| looks_like_node_host(&argv))
+                        .unwrap_or(false)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    #[cfg(target_os = "macos")]
+    let pids: Vec<u32> = Command::new("ps")
+        .args(["-axo", "pid=,command="])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let (pid, cmd) = line.trim_start().split_once(' ')?;
+                    looks_like_node_host(&split_command_line(cmd))
+                        .then(|| pid.parse::<u32>().ok())?
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    #[cfg(target_os = "windows")]
+    let pids: Vec<u32> = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-CimInstance Win32_Process | Where-Object { $_.ProcessId -ne $PID -and \
+             $_.CommandLine -like '*openclaw*' } | \
+             ForEach-Object { \"$($_.ProcessId) $($_.CommandLine)\" }",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .creation_flags(CREATE_NO_WINDOW)
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| {
+                    let (pid, cmd) = line.trim_start().split_once(' ')?;
+                    looks_like_node_host(&split_command_line(cmd))
+                        .then(|| pid.parse::<u32>().ok())?
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let own_pid = std::process::id();
+    pids.into_iter()
+        .filter(|pid| *pid != own_pid && !exclude.contains(pid))
+        .collect()
+}
+
+/// Checks run before spawning `openclaw node run`, so conflicts surface as a
+/// structured error rather than cryptic child log lines. An unreachable
+/// gateway only counts when `require_gateway` is set; otherwise (auto-start,
+/// restarts) it is logged and the node host keeps retrying.
+fn run_preflight(app: &AppHandle, config: &NodeClientConfig, require_gateway: bool) -> PreflightReport {
+    let mut issues = Vec::new();
+
+    let reachable = gateway_reachable(&config.host, config.port);
+    if !reachable && !require_gateway {
+        push_log_line(
+            app,
+            format!(
+                "warning: gateway {}:{} is not reachable yet; the node host will keep retrying",
+                config.host, config.port
+            ),
+        );
+    }
+    if !reachable && require_gateway {
+        issues.push(PreflightIssue {
+            code: "gateway-unreachable".to_string(),
+            message: format!(
+                "gateway {}:{} is not reachable (is it running, and is the host/port correct?)",
+                config.host, config.port
+            ),
+            pids: Vec::new(),
+        });
+    }
+
+    // An explicit fingerprint or skip-verify takes precedence over the
+    // trust-on-first-use store
+    if reachable
+        && config.tls
+        && non_empty(&config.tls_fingerprint).is_none()
+        && !config.tls_insecure_skip_verify
+    {
+        match tls_trust::check_gateway(app, &config.host, config.port) {
+            Ok(tls_trust::TrustCheck::Changed { expected, actual }) => {
+                issues.push(PreflightIssue {
+                    code: "tls-fingerprint-changed".to_string(),
+                    message: format!(
+                        "gateway {}:{} presented a different TLS certificate (sha256 {}, \
+                         previously {}); if the certificate was rotated, forget the known \
+                         host and start again",
+                        config.host, config.port, actual, expected
+                    ),
+                    pids: Vec::new(),
+                });
+            }
+            Ok(_) => {}
+            Err(err) => push_log_line(app, format!("TLS fingerprint check skipped: {}", err)),
+        }
+    }
+
+    let tracked: Vec<u32> = {
+        let state = app.state::<AppState>();
+        let tracked = state
+            .runtime
+            .lock()
+            .map(|runtime| {
+                runtime
+                    .child
+                    .as_ref()
+                    .and_then(tokio::process::Child::id)
+                    .into_iter()
+                    .chain(runtime.adopted_pid)
+                    .chain(runtime.service_pid)
+                    .collect()
+            })
+            .unwrap_or_default();
+        tracked
+    };
+    let foreign = find_foreign_node_hosts(&tracked);
+    if !foreign.is_empty() {
+        issues.push(PreflightIssue {
+            code: "node-host-already-running".to_string(),
+            message: format!(
+                "another openclaw node host is already running (pid {}); stop it first so \
+                 two hosts don't connect as the same node",
+                foreign
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            pids: foreign,
+        });
+    }
+
+    PreflightReport {
+        ok: issues.is_empty(),
+        gateway_reachable: reachable,
+        issues,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Node process management
+// ---------------------------------------------------------------------------
+
+/// Emit a `node-start-progress` event so the UI can show where a start is
+/// ("resolving-binary", "spawning", "waiting-for-gateway", "connected").
+fn emit_start_stage(app: &AppHandle, stage: &str, detail: Option<String>) {
+    let _ = app.emit(
+        "node-start-progress",
+        serde_json::json!({ "stage": stage, "detail": detail }),
+    );
+}
+
+fn start_node_internal(app: &AppHandle) -> Result<(), String> {
+    start_node_checked(app, true)
+}
+
+fn start_node_checked(app: &AppHandle, require_gateway: bool) -> Result<(), String> {
+    let mut stage = "preparing";
+    let result = start_node_staged(app, &mut stage, require_gateway);
+    if let Err(err) = &result {
+        let _ = app.emit(
+            "node-start-progress",
+            serde_json::json!({ "stage": "failed", "failedStage": stage, "detail": err }),
+        );
+    }
+    result
+}
+
+fn start_node_staged(
+    app: &AppHandle,
+    stage: &mut &'static str,
+    require_gateway: bool,
+) -> Result<(), String> {
+    {
+        let state = app.state::<AppState>();
+        let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+        if runtime.service_managed {
+            return Err(i18n::t("node.service_managed").to_string());
+        }
+        let (running, maybe_exit_log) = refresh_process_state(&mut runtime);
+        if let Some(exit_log) = maybe_exit_log {
+            drop(runtime);
+            report_node_exit(app, exit_log);
+            let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+            if runtime.child.is_some() {
+                return Ok(());
+            }
+            let (running_again, _) = refresh_process_state(&mut runtime);
+            if running_again {
+                return Ok(());
+            }
+        } else if running {
+            return Ok(());
+        }
+    }
+
+    // Set status to starting
+    {
+        let state = app.state::<AppState>();
+        if let Ok(mut runtime) = state.runtime.lock() {
+            runtime.node_status = Some(NodeStatus::Starting);
+            runtime.publish_status();
+        };
+    }
+    let _ = app.emit("node-status-changed", NodeStatus::Starting.as_str());
+
+    let config = {
+        let state = app.state::<AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+
+    *stage = "preflight";
+    emit_start_stage(app, stage, None);
+    let preflight = run_preflight(app, &config, require_gateway);
+    if !preflight.ok {
+        let message = preflight
+            .issues
+            .iter()
+            .map(|issue| issue.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        let _ = app.emit("node-preflight-failed", &preflight);
+        let state = app.state::<AppState>();
+        if let Ok(mut runtime) = state.runtime.lock() {
+            runtime.node_status = Some(NodeStatus::Error);
+            runtime.last_error = Some(message.clone());
+            runtime.publish_status();
+        };
+        let _ = app.emit("node-status-changed", NodeStatus::Error.as_str());
+        return Err(format!("pre-flight check failed: {}", message));
+    }
+
+    *stage = "resolving-binary";
+    emit_start_stage(app, stage, None);
+    let (openclaw_bin, bin_dir, node_dir) = resolve_openclaw_bin(&config, app)?;
+    push_log_line(app, format!("using openclaw binary: {}", openclaw_bin));
+    let dev_build = is_dev_build(&config, &openclaw_bin);
+    if dev_build {
+        push_log_line(app, "running a development build; skipping integrity check");
+    }
+    if let Some((_, mjs)) = openclaw_bin.split_once("::").filter(|_| !dev_build) {
+        let bundle_dir = Path::new(mjs).parent().unwrap_or(Path::new("."));
+        match runtime_update::verify_bundle_integrity(bundle_dir) {
+            Ok(true) => {}
+            // Only debug builds accept a bundle without a manifest
+            Ok(false) => push_log_line(
+                app,
+                "bundled runtime has no checksum manifest (debug build); skipping integrity check",
+            ),
+            Err(err) => {
+                let _ = app.emit("bundled-runtime-integrity-failed", &err);
+                let state = app.state::<AppState>();
+                if let Ok(mut runtime) = state.runtime.lock() {
+                    runtime.node_status = Some(NodeStatus::Error);
+                    runtime.last_error = Some(err.message.clone());
+                    runtime.publish_status();
+                };
+                let _ = app.emit("node-status-changed", NodeStatus::Error.as_str());
+                return Err(format!("refusing to run bundled runtime: {}", err.message));
+            }
+        }
+    }
+    let working_dir = config
+        .working_dir
+        .as_deref()
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty());
+    if let Some(dir) = working_dir {
+        if !Path::new(dir).is_dir() {
+            return Err(format!("working directory {} does not exist", dir));
+        }
+    }
+
+    // Sentinel "node_path::mjs_path" means bundled runtime: run `node openclaw.mjs ...`
+    let run_as_user = config
+        .run_as_user
+        .as_deref()
+        .map(str::trim)
+        .filter(|user| !user.is_empty());
+    let wsl_bin = wsl::parse_bin(&openclaw_bin);
+    let mut command = match &wsl_bin {
+        Some(bin) => {
+            // Linux side of the working dir, else the distro user's home
+            let cwd = working_dir
+                .and_then(wsl::to_wsl_path)
+                .map(|(_, path)| path)
+                .unwrap_or_else(|| "~".to_string());
+            push_log_line(
+                app,
+                format!(
+                    "running node host inside WSL ({}) in {}",
+                    bin.distro.unwrap_or("default distro"),
+                    cwd
+                ),
+            );
+            if config.use_exec_host {
+                push_log_line(
+                    app,
+                    "the exec host pipe is not reachable from WSL; commands run inside the distro",
+                );
+            }
+            wsl::command(bin, Some(&cwd))
+        }
+        None => openclaw_command(&openclaw_bin)?,
+    };
+    let run_as_user = run_as_user.filter(|_| wsl_bin.is_none());
 
     // Sanitize AppImage env vars before any other env modifications
     #[cfg(target_os = "linux")]
@@ -1459,18 +4754,40 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
         .arg(config.host.clone())
         .arg("--port")
         .arg(config.port.to_string())
-        .stdin(Stdio::null())
+        .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
+    config.validate_tls_options()?;
     if config.tls {
         command.arg("--tls");
     }
+    if let Some(ca) = non_empty(&config.tls_ca_path) {
+        command.env("NODE_EXTRA_CA_CERTS", ca);
+    }
+    if let (Some(cert), Some(key)) = (
+        non_empty(&config.tls_client_cert_path),
+        non_empty(&config.tls_client_key_path),
+    ) {
+        command.env("OPENCLAW_GATEWAY_TLS_CERT", cert);
+        command.env("OPENCLAW_GATEWAY_TLS_KEY", key);
+    }
+    if config.tls_insecure_skip_verify {
+        push_log_line(app, "warning: gateway TLS certificate verification is disabled");
+        // Read by the gateway client for its own connection only;
+        // NODE_TLS_REJECT_UNAUTHORIZED would turn verification off for every
+        // HTTPS request the node host makes
+        command.env("OPENCLAW_GATEWAY_TLS_INSECURE", "1");
+    }
     if let Some(fp) = config.tls_fingerprint.as_ref() {
         let trimmed = fp.trim();
         if !trimmed.is_empty() {
             command.arg("--tls-fingerprint").arg(trimmed);
         }
+    } else if config.tls && !config.tls_insecure_skip_verify {
+        if let Some(fp) = tls_trust::trusted_fingerprint(&config.host, config.port) {
+            command.arg("--tls-fingerprint").arg(fp);
+        }
     }
     if let Some(node_id) = config.node_id.as_ref() {
         let trimmed = node_id.trim();
@@ -1485,12 +4802,50 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
         }
     }
 
-    // Inject exec-host env var if configured
-    if config.use_exec_host {
+    for arg in &config.extra_args {
+        if !arg.trim().is_empty() {
+            command.arg(arg);
+        }
+    }
+
+    match working_dir {
+        // wsl.exe gets the directory through --cd instead
+        _ if wsl_bin.is_some() => {}
+        Some(dir) => {
+            command.current_dir(dir);
+        }
+        // The other account starts in its own profile (Windows) or / (sudo)
+        None if run_as_user.is_some() => {}
+        None => {
+            if let Some(home) = BaseDirs::new().map(|b| b.home_dir().to_path_buf()) {
+                command.current_dir(home);
+            }
+        }
+    }
+
+    // Inject exec-host env vars if configured. The socket path and token are
+    // passed explicitly so the node host never has to find them through an
+    // exec-approvals.json of its own.
+    if config.use_exec_host && run_as_user.is_some() {
+        // The socket directory is private to the desktop account, and routing
+        // commands back to it would undo the isolation anyway
+        push_log_line(
+            app,
+            "the exec host socket is private to this account; commands run as the node host \
+             account under its own exec approvals",
+        );
+    } else if config.use_exec_host {
         command.env("OPENCLAW_NODE_EXEC_HOST", "app");
         if !config.exec_host_fallback {
             command.env("OPENCLAW_NODE_EXEC_FALLBACK", "0");
         }
+        if let (None, Ok(socket_path)) = (&wsl_bin, exec_host_socket_path()) {
+            command.env("OPENCLAW_EXEC_HOST_SOCKET", socket_path);
+            command.env(
+                "OPENCLAW_EXEC_HOST_TOKEN",
+                app.state::<AppState>().exec_host_token.as_str(),
+            );
+        }
     }
     if let Some(ref token) = config.gateway_token {
         if !token.is_empty() {
@@ -1499,13 +4854,39 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
     }
     if let Some(ref password) = config.gateway_password {
         if !password.is_empty() {
-            command.arg("--password").arg(password);
+            // Not in argv, where any local process can read it
+            command.env("OPENCLAW_GATEWAY_PASSWORD", password);
+            // Probes the CLI the first time (starts run off the main thread);
+            // if that fails the password goes both ways so the node host
+            // still authenticates
+            let version = cached_openclaw_version(app, &openclaw_bin, false)
+                .ok()
+                .and_then(|info| info.version);
+            let reads_env = version.as_deref().is_some_and(|version| {
+                !version_older_than(version, GATEWAY_PASSWORD_ENV_VERSION)
+            });
+            if !reads_env {
+                push_log_line(
+                    app,
+                    format!(
+                        "Warning: openclaw {} may only accept the gateway password on its \
+                         command line, where other local processes can see it. Update with: \
+                         npm install -g openclaw@latest",
+                        version.as_deref().unwrap_or("(unknown version)")
+                    ),
+                );
+                command.arg("--password").arg(password);
+            }
         }
     }
 
     // Suppress Node.js DEP0040 punycode deprecation warning (from transitive deps)
     {
-        let existing = std::env::var("NODE_OPTIONS").unwrap_or_default();
+        let existing = config
+            .extra_env
+            .get("NODE_OPTIONS")
+            .cloned()
+            .unwrap_or_else(|| std::env::var("NODE_OPTIONS").unwrap_or_default());
         let flag = "--disable-warning=DEP0040";
         let node_opts = if existing.is_empty() {
             flag.to_string()
@@ -1515,10 +4896,42 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
         command.env("NODE_OPTIONS", node_opts);
     }
 
-    // Prepend discovered bin_dir to child PATH so co-located `node` is findable
-    if !bin_dir.is_empty() {
-        let current_path = std::env::var("PATH").unwrap_or_default();
-        command.env("PATH", format!("{}{}{}", bin_dir, PATH_SEP, current_path));
+    // Prepend discovered bin_dir to child PATH so co-located `node` is findable,
+    // ahead of it the `node` a resolved shim would have picked; the managed
+    // runtime goes first so `#!/usr/bin/env node` shims use it too
+    {
+        let mut path = std::env::var("PATH").unwrap_or_default();
+        #[cfg(target_os = "macos")]
+        {
+            path = launchd_path_fixup(path);
+        }
+        if !bin_dir.is_empty() {
+            path = format!("{}{}{}", bin_dir, PATH_SEP, path);
+        }
+        if let Some(ref node_dir) = node_dir {
+            path = format!("{}{}{}", node_dir, PATH_SEP, path);
+        }
+        if config.use_managed_node {
+            if let Some(node_dir) = managed_node::installed_node_path(app)
+                .and_then(|n| Path::new(&n).parent().map(|d| d.to_string_lossy().to_string()))
+            {
+                path = format!("{}{}{}", node_dir, PATH_SEP, path);
+            }
+        }
+        command.env("PATH", path);
+    }
+
+    // User-configured env overrides win over everything above except the
+    // merged NODE_OPTIONS
+    for (key, value) in &config.extra_env {
+        let key = key.trim();
+        if key.is_empty() || key.contains('=') || key.contains('\0') || key == "NODE_OPTIONS" {
+            continue;
+        }
+        command.env(key, value);
+    }
+    if wsl_bin.is_some() {
+        wsl::forward_env(&mut command);
     }
 
     // Auto-save the discovered install path when it differs from the stored one
@@ -1526,18 +4939,45 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
     if !bin_dir.is_empty() && !openclaw_bin.contains("::") {
         let current = config.install_path.clone().unwrap_or_default();
         if current != bin_dir {
-            let state = app.state::<AppState>();
-            if let Ok(mut cfg) = state.config.lock() {
-                cfg.install_path = Some(bin_dir.clone());
-                let _ = save_config(&cfg);
-            }
+            let _ = update_config(app, |cfg| cfg.install_path = Some(bin_dir.clone()));
             let _ = app.emit("install-path-detected", bin_dir.clone());
         }
     }
 
+    // Wrapped last so sudo sees the final program, args and env
+    #[cfg(not(target_os = "windows"))]
+    if let Some(user) = run_as_user {
+        push_log_line(app, format!("running node host as user {} via sudo", user));
+        command = run_as::sudo_command(&command, user);
+        command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+    }
+    // Own process group, so a hard stop also reaches sudo's child and
+    // whatever a shim started
+    #[cfg(unix)]
+    command.process_group(0);
+
+    // Own process group so a CTRL_BREAK_EVENT can target the child (and any
+    // node.exe under an openclaw.cmd shim) without touching this process.
     #[cfg(target_os = "windows")]
     {
-        command.creation_flags(CREATE_NO_WINDOW);
+        let mut flags = CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP;
+        if config.low_priority {
+            flags |= BELOW_NORMAL_PRIORITY_CLASS;
+        }
+        command.creation_flags(flags);
+    }
+    // Inherited by everything the node host spawns
+    #[cfg(unix)]
+    if config.low_priority {
+        unsafe {
+            command.pre_exec(|| {
+                libc::setpriority(libc::PRIO_PROCESS, 0, LOW_PRIORITY_NICE);
+                Ok(())
+            });
+        }
     }
 
     // Auto-SIGTERM child when parent dies (crash, OOM kill, etc.)
@@ -1549,28 +4989,95 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
         });
     }
 
-    let mut child = command
-        .spawn()
-        .map_err(|err| format!("failed to start `openclaw node run`: {}", err))?;
+    *stage = "spawning";
+    emit_start_stage(app, stage, Some(openclaw_bin.clone()));
+    // The child's pipes are registered with the async runtime, which this
+    // (sync) caller may not be running on
+    #[cfg(target_os = "windows")]
+    let logon_pid = match run_as_user {
+        Some(user) => {
+            push_log_line(
+                app,
+                format!("running node host as user {}; its output is not captured", user),
+            );
+            let password = config.run_as_password.as_deref().unwrap_or_default();
+            Some(run_as::spawn_with_logon(&command, user, password, config.low_priority)?)
+        }
+        None => None,
+    };
+    #[cfg(not(target_os = "windows"))]
+    let logon_pid: Option<u32> = None;
 
-    if let Some(stdout) = child.stdout.take() {
-        spawn_log_reader(app.clone(), stdout, "stdout");
-    }
-    if let Some(stderr) = child.stderr.take() {
-        spawn_log_reader(app.clone(), stderr, "stderr");
-    }
+    if let Some(pid) = logon_pid {
+        // No pipes: watched by pid like an adopted node host
+        let state = app.state::<AppState>();
+        let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+        write_node_pid_file(pid, &openclaw_bin);
+        runtime.adopted_pid = Some(pid);
+        runtime.low_priority = config.low_priority;
+        runtime.last_error = None;
+        runtime.started_at_ms = Some(now_ms());
+        runtime.publish_status();
+    } else {
+        let runtime_handle = tauri::async_runtime::handle();
+        let _runtime_guard = runtime_handle.inner().enter();
+        let mut child = tokio::process::Command::from(command).spawn().map_err(|err| {
+            let message = format!("failed to start `openclaw node run`: {}", err);
+            error_report::capture(
+                "node.spawn",
+                &message,
+                serde_json::json!({
+                    "bin": openclaw_bin,
+                    "errorKind": format!("{:?}", err.kind()),
+                }),
+            );
+            message
+        })?;
+
+        if let Some(stdout) = child.stdout.take() {
+            spawn_log_reader(app.clone(), stdout, "stdout");
+        }
+        if let Some(stderr) = child.stderr.take() {
+            spawn_log_reader(app.clone(), stderr, "stderr");
+        }
 
-    {
         let state = app.state::<AppState>();
+        if let Ok(mut stdin) = state.node_stdin.lock() {
+            *stdin = child
+                .stdin
+                .take()
+                .map(|pipe| spawn_stdin_writer(app.clone(), pipe));
+        }
         let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+        if let Some(pid) = child.id() {
+            write_node_pid_file(pid, &openclaw_bin);
+        }
         runtime.child = Some(child);
+        runtime.low_priority = config.low_priority;
+        runtime.run_as_user = run_as_user.map(str::to_string);
         runtime.last_error = None;
+        runtime.started_at_ms = Some(now_ms());
+        runtime.publish_status();
     }
 
     push_log_line(
         app,
         format!("started node host for gateway {}", config.gateway_url()),
     );
+    *stage = "waiting-for-gateway";
+    emit_start_stage(app, stage, Some(config.gateway_url()));
+
+    // Populate the CLI version cache off the start path so get_status can
+    // report it (and warn about outdated installs) without blocking.
+    {
+        let app_clone = app.clone();
+        let bin = openclaw_bin.clone();
+        std::thread::spawn(move || {
+            if let Err(err) = cached_openclaw_version(&app_clone, &bin, false) {
+                push_log_line(&app_clone, format!("openclaw version check failed: {}", err));
+            }
+        });
+    }
 
     // Fallback: if the child is still alive after 5 s and status is still
     // "Starting", the process likely connected (older CLI builds don't emit a
@@ -1581,20 +5088,29 @@ fn start_node_internal(app: &AppHandle) -> Result<(), String> {
         std::thread::spawn(move || {
             std::thread::sleep(std::time::Duration::from_secs(5));
             let state = app_clone.state::<AppState>();
-            let should_emit = {
+            let (should_emit, exit_log) = {
                 let Ok(mut runtime) = state.runtime.lock() else {
                     return;
                 };
-                let (running, _) = refresh_process_state(&mut runtime);
+                let (running, exit_log) = refresh_process_state(&mut runtime);
                 if running && runtime.node_status == Some(NodeStatus::Starting) {
                     runtime.node_status = Some(NodeStatus::Running);
-                    true
+                    runtime.publish_status();
+                    (true, exit_log)
                 } else {
-                    false
+                    (false, exit_log)
                 }
             };
+            if let Some(exit_log) = exit_log {
+                report_node_exit(&app_clone, exit_log);
+            }
             if should_emit {
                 let _ = app_clone.emit("node-status-changed", NodeStatus::Running.as_str());
+                emit_start_stage(
+                    &app_clone,
+                    "connected",
+                    Some("assumed: process still running after 5s".to_string()),
+                );
             }
         });
     }
@@ -1606,10 +5122,17 @@ fn stop_node_internal(app: &AppHandle) -> Result<(), String> {
     let mut maybe_child = {
         let state = app.state::<AppState>();
         let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+        if runtime.service_managed {
+            return Err(
+                "node host is managed by the installed OS service; stop it with the \
+                 service manager or uninstall the service"
+                    .to_string(),
+            );
+        }
         let (running, maybe_exit_log) = refresh_process_state(&mut runtime);
         if let Some(exit_log) = maybe_exit_log {
             drop(runtime);
-            push_log_line(app, exit_log);
+            report_node_exit(app, exit_log);
             let state = app.state::<AppState>();
             let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
             let (running_again, _) = refresh_process_state(&mut runtime);
@@ -1625,64 +5148,156 @@ fn stop_node_internal(app: &AppHandle) -> Result<(), String> {
         }
     };
 
+    let (adopted_pid, child_user) = {
+        let state = app.state::<AppState>();
+        let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+        (runtime.adopted_pid.take(), runtime.run_as_user.take())
+    };
+    if let Some(pid) = adopted_pid {
+        terminate_pid(pid);
+        push_log_line(app, format!("stopped adopted node host (pid {})", pid));
+    }
+
+    // Close the child's stdin so nothing can write to a stopping process
+    if let Ok(mut stdin) = app.state::<AppState>().node_stdin.lock() {
+        *stdin = None;
+    }
+
+    // A failed kill is reported only after the runtime state is cleared, so
+    // the UI never keeps showing a child we no longer hold
+    let mut stop_error = None;
     if let Some(child) = maybe_child.as_mut() {
+        // Graceful shutdown: SIGTERM / CTRL_BREAK first, escalate to a hard
+        // kill after the grace period. No pid means it has already exited.
         #[cfg(not(target_os = "windows"))]
-        {
-            // Graceful shutdown: SIGTERM first, escalate to SIGKILL after 5s
-            let pid = child.id() as i32;
-            unsafe {
-                libc::kill(pid, libc::SIGTERM);
-            }
-            let deadline =
-                std::time::Instant::now() + std::time::Duration::from_secs(5);
-            loop {
-                match child.try_wait() {
-                    Ok(Some(_)) => break,
-                    Ok(None) => {
-                        if std::time::Instant::now() >= deadline {
-                            let _ = child.kill();
-                            let _ = child.wait();
-                            break;
+        let signalled = child
+            .id()
+            .is_some_and(|pid| unsafe { libc::kill(pid as i32, libc::SIGTERM) == 0 });
+        #[cfg(target_os = "windows")]
+        let signalled = child.id().is_some_and(send_ctrl_break);
+
+        let grace = if signalled { NODE_STOP_GRACE_MS } else { 0 };
+        let deadline = std::time::Instant::now() + std::time::Duration::from_millis(grace);
+        loop {
+            match child.try_wait() {
+                Ok(Some(_)) => break,
+                Ok(None) => {
+                    if std::time::Instant::now() >= deadline {
+                        match force_kill_child(child, child_user.as_deref()) {
+                            Ok(()) => wait_for_killed_child(child),
+                            Err(err) => {
+                                stop_error = Some(format!("failed to stop node host: {}", err))
+                            }
                         }
-                        std::thread::sleep(std::time::Duration::from_millis(100));
-                    }
-                    Err(_) => {
-                        let _ = child.kill();
-                        let _ = child.wait();
                         break;
                     }
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                }
+                Err(_) => {
+                    let _ = child.start_kill();
+                    wait_for_killed_child(child);
+                    break;
                 }
             }
         }
-        #[cfg(target_os = "windows")]
-        {
-            child
-                .kill()
-                .map_err(|err| format!("failed to stop node host: {}", err))?;
-            let _ = child.wait();
+        if stop_error.is_none() {
+            push_log_line(app, i18n::t("node.stopped"));
         }
-        push_log_line(app, "stopped node host process");
     }
+    remove_node_pid_file();
 
+    let status = if stop_error.is_some() {
+        NodeStatus::Error
+    } else {
+        NodeStatus::Stopped
+    };
     {
         let state = app.state::<AppState>();
         if let Ok(mut runtime) = state.runtime.lock() {
-            runtime.node_status = Some(NodeStatus::Stopped);
+            runtime.node_status = Some(status.clone());
+            if stop_error.is_some() {
+                runtime.last_error = stop_error.clone();
+            }
+            runtime.publish_status();
         };
     }
-    let _ = app.emit("node-status-changed", NodeStatus::Stopped.as_str());
-    Ok(())
+    let _ = app.emit("node-status-changed", status.as_str());
+    match stop_error {
+        Some(err) => Err(err),
+        None => Ok(()),
+    }
+}
+
+/// Hard-kill the node host: on Unix its whole process group, so sudo's
+/// child and processes started by a shim go too.
+fn force_kill_child(
+    child: &mut tokio::process::Child,
+    run_as_user: Option<&str>,
+) -> std::io::Result<()> {
+    #[cfg(not(target_os = "windows"))]
+    if let Some(pid) = child.id() {
+        if run_as::kill_process_group(pid, run_as_user) {
+            return Ok(());
+        }
+    }
+    #[cfg(target_os = "windows")]
+    let _ = run_as_user;
+    child.start_kill()
+}
+
+/// Deliver CTRL_BREAK_EVENT to the child's process group (Node maps it to
+/// SIGBREAK and runs the CLI's shutdown handlers).
+///
+/// The app is a GUI-subsystem process without a console of its own, so it
+/// temporarily attaches to the child's hidden console to send the event,
+/// ignoring the event itself while attached. Returns `false` if the event
+/// could not be delivered, in which case the caller should kill immediately.
+#[cfg(target_os = "windows")]
+fn send_ctrl_break(pid: u32) -> bool {
+    use windows_sys::Win32::System::Console::{
+        AttachConsole, FreeConsole, GenerateConsoleCtrlEvent, SetConsoleCtrlHandler,
+        CTRL_BREAK_EVENT,
+    };
+    unsafe {
+        FreeConsole();
+        if AttachConsole(pid) == 0 {
+            return false;
+        }
+        SetConsoleCtrlHandler(None, 1);
+        let sent = GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, pid) != 0;
+        FreeConsole();
+        SetConsoleCtrlHandler(None, 0);
+        sent
+    }
+}
+
+/// Give a killed child a moment to be reaped; the runtime reaps it later
+/// if it takes longer.
+fn wait_for_killed_child(child: &mut tokio::process::Child) {
+    for _ in 0..20 {
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
 }
 
+/// Restarts (from the UI, a config change or the watchdog) don't require a
+/// reachable gateway: the node host was already running against it.
 fn restart_node_internal(app: &AppHandle) -> Result<(), String> {
     stop_node_internal(app)?;
-    start_node_internal(app)
+    start_node_checked(app, false)
 }
 
 // ---------------------------------------------------------------------------
 // Command execution (for exec-host)
 // ---------------------------------------------------------------------------
 
+/// Output kept per stream of an exec-host command.
+const EXEC_OUTPUT_MAX_BYTES: usize = 4 * 1024 * 1024;
+/// How long to keep reading a command's pipes after it exits.
+const EXEC_OUTPUT_GRACE_MS: u64 = 500;
+
 async fn run_exec_command(
     argv: Vec<String>,
     cwd: Option<String>,
@@ -1750,9 +5365,11 @@ async fn run_exec_command(
         }
     };
 
-    // Take stdout/stderr handles before waiting so we can read them on timeout
-    let stdout_handle = child.stdout.take();
-    let stderr_handle = child.stderr.take();
+    // Drain both pipes while waiting: a child that fills the OS pipe buffer
+    // blocks on write and would never exit otherwise
+    let (stop_readers, stop) = tokio::sync::watch::channel(false);
+    let stdout_reader = drain_pipe(child.stdout.take(), stop.clone());
+    let stderr_reader = drain_pipe(child.stderr.take(), stop);
 
     let timeout = std::time::Duration::from_millis(
         timeout_ms
@@ -1760,58 +5377,84 @@ async fn run_exec_command(
             .unwrap_or(120_000),
     );
 
-    match tokio::time::timeout(timeout, child.wait()).await {
-        Ok(Ok(status)) => {
-            let stdout = if let Some(mut h) = stdout_handle {
-                let mut buf = Vec::new();
-                let _ = h.read_to_end(&mut buf).await;
-                String::from_utf8_lossy(&buf).to_string()
-            } else {
-                String::new()
-            };
-            let stderr = if let Some(mut h) = stderr_handle {
-                let mut buf = Vec::new();
-                let _ = h.read_to_end(&mut buf).await;
-                String::from_utf8_lossy(&buf).to_string()
-            } else {
-                String::new()
-            };
-            ExecHostRunResult {
-                exit_code: status.code(),
-                timed_out: false,
-                success: status.success(),
-                stdout,
-                stderr,
-                error: None,
-            }
-        }
-        Ok(Err(e)) => {
-            // wait() failed — kill defensively
+    let finished = tokio::time::timeout(timeout, child.wait()).await;
+    // Output still in the pipes gets a short grace period once the command
+    // exits; a background process that inherited them can keep them open
+    // indefinitely, so after that the output read so far is returned
+    let grace = match finished {
+        Ok(Ok(_)) => std::time::Duration::from_millis(EXEC_OUTPUT_GRACE_MS),
+        _ => {
             let _ = child.kill().await;
             let _ = child.wait().await;
-            ExecHostRunResult {
-                exit_code: None,
-                timed_out: false,
-                success: false,
-                stdout: String::new(),
-                stderr: String::new(),
-                error: Some(format!("wait error: {}", e)),
-            }
+            std::time::Duration::ZERO
         }
-        Err(_) => {
-            // Timeout — explicitly kill the process so it doesn't run forever
-            let _ = child.kill().await;
-            let _ = child.wait().await;
-            ExecHostRunResult {
-                exit_code: None,
-                timed_out: true,
-                success: false,
-                stdout: String::new(),
-                stderr: String::new(),
-                error: Some("command timed out".to_string()),
+    };
+    let stopper = tokio::spawn(async move {
+        tokio::time::sleep(grace).await;
+        let _ = stop_readers.send(true);
+    });
+    let stdout = String::from_utf8_lossy(&stdout_reader.await.unwrap_or_default()).to_string();
+    let stderr = String::from_utf8_lossy(&stderr_reader.await.unwrap_or_default()).to_string();
+    stopper.abort();
+
+    match finished {
+        Ok(Ok(status)) => ExecHostRunResult {
+            exit_code: status.code(),
+            timed_out: false,
+            success: status.success(),
+            stdout,
+            stderr,
+            error: None,
+        },
+        Ok(Err(e)) => ExecHostRunResult {
+            exit_code: None,
+            timed_out: false,
+            success: false,
+            stdout,
+            stderr,
+            error: Some(format!("wait error: {}", e)),
+        },
+        Err(_) => ExecHostRunResult {
+            exit_code: None,
+            timed_out: true,
+            success: false,
+            stdout,
+            stderr,
+            error: Some("command timed out".to_string()),
+        },
+    }
+}
+
+/// Read a child's pipe on its own task until it closes or `stop` is set.
+/// Past `EXEC_OUTPUT_MAX_BYTES` the output is read and dropped, so the
+/// child never blocks on a full pipe.
+fn drain_pipe<R>(
+    pipe: Option<R>,
+    mut stop: tokio::sync::watch::Receiver<bool>,
+) -> tokio::task::JoinHandle<Vec<u8>>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut buf = Vec::new();
+        let Some(mut pipe) = pipe else {
+            return buf;
+        };
+        let mut chunk = [0u8; 8192];
+        loop {
+            tokio::select! {
+                read = pipe.read(&mut chunk) => match read {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => {
+                        let room = EXEC_OUTPUT_MAX_BYTES.saturating_sub(buf.len());
+                        buf.extend_from_slice(&chunk[..n.min(room)]);
+                    }
+                },
+                _ = stop.changed() => break,
             }
         }
-    }
+        buf
+    })
 }
 
 fn make_error_response(code: &str, message: &str) -> String {
@@ -1841,46 +5484,109 @@ fn make_success_response(result: ExecHostRunResult) -> String {
 // Socket handler — processes a single connection
 // ---------------------------------------------------------------------------
 
+/// Messages are newline-delimited JSON, one response line per request line.
 async fn handle_socket_connection<S>(stream: S, app: AppHandle, token: String)
 where
     S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin + Send + 'static,
 {
-    let (reader, mut writer) = tokio::io::split(stream);
-    let mut lines = tokio::io::BufReader::new(reader).lines();
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    // Both buffers live as long as the connection, so a run of small
+    // requests reuses their allocations
+    let mut input = BytesMut::with_capacity(SOCKET_BUFFER_BYTES);
+    let mut output = BytesMut::with_capacity(SOCKET_BUFFER_BYTES);
 
-    while let Ok(Some(line)) = lines.next_line().await {
-        let trimmed = line.trim().to_string();
-        if trimmed.is_empty() {
-            continue;
+    loop {
+        let response = match next_socket_frame(&mut reader, &mut input).await {
+            Ok(Some(frame)) => {
+                let frame = frame.trim_ascii();
+                if frame.is_empty() {
+                    continue;
+                }
+                process_socket_frame(frame, &app, &token).await
+            }
+            Ok(None) => break,
+            Err(err) if err.kind() == std::io::ErrorKind::InvalidData => {
+                // Answer, then drop the connection: the rest of the stream
+                // can't be framed
+                make_error_response("frame-too-large", &err.to_string())
+            }
+            Err(_) => break,
+        };
+        output.clear();
+        output.extend_from_slice(response.as_bytes());
+        output.put_u8(b'\n');
+        if writer.write_all(&output).await.is_err() {
+            break;
         }
-
-        let response = process_socket_line(&trimmed, &app, &token).await;
-        let out = format!("{}\n", response);
-        if writer.write_all(out.as_bytes()).await.is_err() {
+        if input.len() > MAX_SOCKET_FRAME_BYTES {
             break;
         }
     }
 }
 
-async fn process_socket_line(line: &str, app: &AppHandle, token: &str) -> String {
-    // Try parsing as exec envelope first
-    if let Ok(envelope) = serde_json::from_str::<ExecEnvelope>(line) {
-        if envelope.msg_type == "exec" {
-            return handle_exec_message(envelope, app, token).await;
+/// The next line from `reader`, without its newline; `None` at end of
+/// stream. Bytes past the line stay in `buf` for the next call.
+async fn next_socket_frame<R>(reader: &mut R, buf: &mut BytesMut) -> std::io::Result<Option<Bytes>>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut scanned = 0;
+    loop {
+        if let Some(end) = buf[scanned..].iter().position(|&b| b == b'\n') {
+            let mut frame = buf.split_to(scanned + end + 1);
+            frame.truncate(scanned + end);
+            return Ok(Some(frame.freeze()));
         }
-    }
-
-    // Try parsing as approval request envelope
-    if let Ok(envelope) = serde_json::from_str::<ApprovalRequestEnvelope>(line) {
-        if envelope.msg_type == "request" {
-            return handle_approval_request(envelope, app, token).await;
+        scanned = buf.len();
+        if scanned > MAX_SOCKET_FRAME_BYTES {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("message exceeds {} bytes", MAX_SOCKET_FRAME_BYTES),
+            ));
+        }
+        if reader.read_buf(buf).await? == 0 {
+            // A last line without a newline still counts
+            return Ok((!buf.is_empty()).then(|| buf.split().freeze()));
         }
     }
+}
+
+/// Just the `type` field, to pick the envelope to parse the frame as.
+#[derive(Deserialize)]
+struct SocketMessageType<'a> {
+    #[serde(rename = "type", borrow, default)]
+    msg_type: Option<Cow<'a, str>>,
+}
 
-    make_error_response("unknown-type", "unrecognized message type")
+async fn process_socket_frame(frame: &[u8], app: &AppHandle, token: &str) -> String {
+    let msg_type = serde_json::from_slice::<SocketMessageType>(frame)
+        .ok()
+        .and_then(|message| message.msg_type);
+    let invalid = |err: serde_json::Error| {
+        make_error_response("bad-request", &format!("invalid message: {}", err))
+    };
+    match msg_type.as_deref() {
+        Some("exec") => match serde_json::from_slice::<ExecEnvelope>(frame) {
+            Ok(envelope) => handle_exec_message(envelope, app, token).await,
+            Err(err) => invalid(err),
+        },
+        Some("request") => match serde_json::from_slice::<ApprovalRequestEnvelope>(frame) {
+            Ok(envelope) => handle_approval_request(envelope, app, token).await,
+            Err(err) => invalid(err),
+        },
+        // Companion CLI commands (`openclaw-node-client status`, ...)
+        Some("control") => match serde_json::from_slice::<control::ControlRequest>(frame) {
+            Ok(request) => control::handle(request, app).await,
+            Err(err) => invalid(err),
+        },
+        _ => make_error_response("unknown-type", "unrecognized message type"),
+    }
 }
 
 async fn handle_exec_message(envelope: ExecEnvelope, app: &AppHandle, token: &str) -> String {
+    // Rejected requests return early and leave the span recorded as failed
+    let mut span = telemetry::Span::new("exec.request", telemetry::SpanKind::Server);
+
     // Validate required fields
     let nonce = match envelope.nonce {
         Some(ref n) if !n.is_empty() => n.as_str(),
@@ -1920,17 +5626,34 @@ async fn handle_exec_message(envelope: ExecEnvelope, app: &AppHandle, token: &st
         Ok(r) => r,
         Err(e) => return make_error_response("bad-request", &format!("invalid requestJson: {}", e)),
     };
+    if let Some(program) = request.command.first() {
+        span.attr("exec.program", program.clone());
+    }
+    if let Some(agent_id) = &request.agent_id {
+        span.attr("exec.agent_id", agent_id.clone());
+    }
 
     // If approval_decision is provided, run directly
     if let Some(ref decision) = request.approval_decision {
         if decision == "allow-once" || decision == "allow-always" {
-            let result = run_exec_command(
+            event_log::record(
+                app,
+                event_log::AuditEvent::CommandExecuted {
+                    argv: &request.command,
+                    cwd: request.cwd.as_deref(),
+                    approval: decision,
+                },
+            );
+            span.attr("exec.approval", decision.clone());
+            let result = run_exec_traced(
+                &span,
                 request.command,
                 request.cwd,
                 request.env,
                 request.timeout_ms,
             )
             .await;
+            span.end();
             return make_success_response(result);
         }
     }
@@ -1954,69 +5677,161 @@ async fn handle_exec_message(envelope: ExecEnvelope, app: &AppHandle, token: &st
         expires_at_ms: expires,
     };
 
-    let (tx, rx) = std::sync::mpsc::sync_channel::<String>(1);
+    let approval_span = span.child("exec.approval");
+    let decision = await_approval_decision(app, preview).await;
+    approval_span.end();
+    span.attr("exec.approval", decision.clone());
+
+    if decision == "deny" {
+        span.fail("denied by user");
+        span.end();
+        return make_error_response("denied", "execution denied by user");
+    }
+
+    // Run the command
+    event_log::record(
+        app,
+        event_log::AuditEvent::CommandExecuted {
+            argv: &request.command,
+            cwd: request.cwd.as_deref(),
+            approval: &decision,
+        },
+    );
+    let result = run_exec_traced(
+        &span,
+        request.command,
+        request.cwd,
+        request.env,
+        request.timeout_ms,
+    )
+    .await;
+    span.end();
+    make_success_response(result)
+}
+
+/// `run_exec_command` under an `exec.run` span of `parent`.
+async fn run_exec_traced(
+    parent: &telemetry::Span,
+    argv: Vec<String>,
+    cwd: Option<String>,
+    env: Option<HashMap<String, String>>,
+    timeout_ms: Option<i64>,
+) -> ExecHostRunResult {
+    let mut span = parent.child("exec.run");
+    let result = run_exec_command(argv, cwd, env, timeout_ms).await;
+    if let Some(code) = result.exit_code {
+        span.attr("process.exit.code", code);
+    }
+    span.attr("exec.timed_out", result.timed_out);
+    if let Some(error) = &result.error {
+        span.fail(error.clone());
+    }
+    span.end();
+    result
+}
+
+/// Queue `preview` for the user and block until they decide; "deny" once
+/// `APPROVAL_TIMEOUT_MS` passes. Shared by exec-host requests and remote
+/// terminal sessions.
+async fn await_approval_decision(app: &AppHandle, preview: ApprovalPreview) -> String {
+    let id = preview.id.clone();
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<String>(1);
 
     let pending = PendingApproval {
-        id: approval_id.clone(),
+        id: id.clone(),
         preview: preview.clone(),
-        expires_at_ms: expires,
+        expires_at_ms: preview.expires_at_ms,
         tx,
     };
 
     // Add to pending and emit event
-    {
-        let state = app.state::<AppState>();
-        if let Ok(mut approvals) = state.pending_approvals.lock() {
-            approvals.push(pending);
-        };
-    }
+    app.state::<AppState>()
+        .pending_approvals
+        .send_modify(|approvals| approvals.push(pending));
     let _ = app.emit("approval-pending", &preview);
 
-    // Surface the window so the user sees the approval prompt
-    if let Some(window) = app.get_webview_window("main") {
-        if !window.is_visible().unwrap_or(true) {
-            let _ = window.show();
-            let _ = window.set_focus();
-        }
+    // Prefer an actionable notification; otherwise pop up the approval
+    // window rather than raising the main window
+    if !approval_notify::notify(app, &preview) {
+        approval_window::show(app);
     }
 
     // Wait for decision with timeout
     let timeout_duration = std::time::Duration::from_millis(APPROVAL_TIMEOUT_MS);
-    let decision = match rx.recv_timeout(timeout_duration) {
-        Ok(d) => d,
-        Err(_) => "deny".to_string(),
+    let decision = match tokio::time::timeout(timeout_duration, rx.recv()).await {
+        Ok(Some(d)) => d,
+        _ => "deny".to_string(),
     };
 
-    // Remove from pending
-    {
-        let state = app.state::<AppState>();
-        if let Ok(mut approvals) = state.pending_approvals.lock() {
-            approvals.retain(|a| a.id != approval_id);
-        };
-    }
-
-    // Emit resolved event
-    let _ = app.emit(
-        "approval-resolved",
-        serde_json::json!({
-            "id": approval_id,
-            "decision": decision,
-        }),
+    // Remove from pending; if the sweeper got there first it has already
+    // announced the expiry
+    let was_pending = app
+        .state::<AppState>()
+        .pending_approvals
+        .send_if_modified(|approvals| {
+            let before = approvals.len();
+            approvals.retain(|a| a.id != id);
+            approvals.len() != before
+        });
+    approval_window::close_if_idle(app);
+    event_log::record(
+        app,
+        event_log::AuditEvent::ApprovalDecision {
+            preview: &preview,
+            decision: &decision,
+        },
     );
 
-    if decision == "deny" {
-        return make_error_response("denied", "execution denied by user");
+    // Emit resolved event
+    if was_pending {
+        let _ = app.emit(
+            "approval-resolved",
+            serde_json::json!({
+                "id": id,
+                "decision": decision,
+            }),
+        );
     }
+    decision
+}
 
-    // Run the command
-    let result = run_exec_command(
-        request.command,
-        request.cwd,
-        request.env,
-        request.timeout_ms,
-    )
-    .await;
-    make_success_response(result)
+/// Drop pending approvals past their expiry whose waiter never cleaned up
+/// (e.g. its connection task was cancelled), resolving each as an expired
+/// deny so open views and notifications clear it.
+async fn run_approval_sweeper(app: AppHandle) {
+    let mut tick =
+        tokio::time::interval(std::time::Duration::from_millis(APPROVAL_SWEEP_INTERVAL_MS));
+    loop {
+        tick.tick().await;
+        let now = now_ms();
+        let mut expired = Vec::new();
+        app.state::<AppState>()
+            .pending_approvals
+            .send_if_modified(|approvals| {
+                approvals.retain(|a| {
+                    if a.expires_at_ms > now {
+                        return true;
+                    }
+                    expired.push(a.id.clone());
+                    false
+                });
+                !expired.is_empty()
+            });
+        if expired.is_empty() {
+            continue;
+        }
+        approval_window::close_if_idle(&app);
+        for id in expired {
+            let _ = app.emit(
+                "approval-resolved",
+                serde_json::json!({
+                    "id": id,
+                    "decision": "deny",
+                    "expired": true,
+                }),
+            );
+        }
+    }
 }
 
 async fn handle_approval_request(
@@ -2069,62 +5884,18 @@ async fn handle_approval_request(
         .unwrap_or_default();
 
     let expires = now_ms() + APPROVAL_TIMEOUT_MS;
-    let preview = ApprovalPreview {
-        id: req_id.clone(),
-        raw_command: Some(command),
-        argv: command_argv,
-        cwd,
-        env_keys,
-        agent_id,
-        session_key,
-        expires_at_ms: expires,
-    };
-
-    let (tx, rx) = std::sync::mpsc::sync_channel::<String>(1);
-
-    let pending = PendingApproval {
-        id: req_id.clone(),
-        preview: preview.clone(),
-        expires_at_ms: expires,
-        tx,
-    };
-
-    {
-        let state = app.state::<AppState>();
-        if let Ok(mut approvals) = state.pending_approvals.lock() {
-            approvals.push(pending);
-        };
-    }
-    let _ = app.emit("approval-pending", &preview);
-
-    // Surface the window so the user sees the approval prompt
-    if let Some(window) = app.get_webview_window("main") {
-        if !window.is_visible().unwrap_or(true) {
-            let _ = window.show();
-            let _ = window.set_focus();
-        }
-    }
-
-    let timeout_duration = std::time::Duration::from_millis(APPROVAL_TIMEOUT_MS);
-    let decision = match rx.recv_timeout(timeout_duration) {
-        Ok(d) => d,
-        Err(_) => "deny".to_string(),
+    let preview = ApprovalPreview {
+        id: req_id.clone(),
+        raw_command: Some(command),
+        argv: command_argv,
+        cwd,
+        env_keys,
+        agent_id,
+        session_key,
+        expires_at_ms: expires,
     };
 
-    {
-        let state = app.state::<AppState>();
-        if let Ok(mut approvals) = state.pending_approvals.lock() {
-            approvals.retain(|a| a.id != req_id);
-        };
-    }
-
-    let _ = app.emit(
-        "approval-resolved",
-        serde_json::json!({
-            "id": req_id,
-            "decision": decision,
-        }),
-    );
+    let decision = await_approval_decision(app, preview).await;
 
     serde_json::to_string(&serde_json::json!({
         "type": "decision",
@@ -2173,6 +5944,11 @@ async fn start_exec_host_server(app: AppHandle, token: String) {
             Ok(s) => s,
             Err(e) => {
                 eprintln!("failed to create named pipe: {}", e);
+                error_report::capture(
+                    "exec_host.socket",
+                    &format!("failed to create named pipe: {}", e),
+                    serde_json::json!({ "pipe": pipe_name }),
+                );
                 tokio::time::sleep(std::time::Duration::from_secs(1)).await;
                 continue;
             }
@@ -2180,6 +5956,11 @@ async fn start_exec_host_server(app: AppHandle, token: String) {
 
         if let Err(e) = server.connect().await {
             eprintln!("named pipe connect error: {}", e);
+            error_report::capture(
+                "exec_host.socket",
+                &format!("named pipe connect error: {}", e),
+                serde_json::json!({ "pipe": pipe_name }),
+            );
             continue;
         }
 
@@ -2193,7 +5974,25 @@ async fn start_exec_host_server(app: AppHandle, token: String) {
 
 #[cfg(not(target_os = "windows"))]
 async fn start_exec_host_server(app: AppHandle, token: String) {
-    let sock_path = exec_host_socket_path();
+    let sock_path = match exec_host_socket_path() {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("exec host not started: {}", e);
+            error_report::capture(
+                "exec_host.socket",
+                &format!("exec host not started: {}", e),
+                serde_json::json!({}),
+            );
+            return;
+        }
+    };
+    // The socket is created with the umask's mode and only narrowed after
+    // bind; it must sit in a directory nobody else can enter until then.
+    // Checked again here in case the directory changed since it was resolved.
+    if !Path::new(&sock_path).parent().is_some_and(ensure_private_dir) {
+        eprintln!("exec host not started: {} is not in a private directory", sock_path);
+        return;
+    }
 
     // Remove stale socket file
     let _ = std::fs::remove_file(&sock_path);
@@ -2202,9 +6001,20 @@ async fn start_exec_host_server(app: AppHandle, token: String) {
         Ok(l) => l,
         Err(e) => {
             eprintln!("failed to bind unix socket at {}: {}", sock_path, e);
+            error_report::capture(
+                "exec_host.socket",
+                &format!("failed to bind unix socket: {}", e),
+                serde_json::json!({ "socket": sock_path }),
+            );
             return;
         }
     };
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Err(e) = fs::set_permissions(&sock_path, fs::Permissions::from_mode(0o600)) {
+            eprintln!("failed to restrict {}: {}", sock_path, e);
+        }
+    }
 
     loop {
         match listener.accept().await {
@@ -2217,6 +6027,11 @@ async fn start_exec_host_server(app: AppHandle, token: String) {
             }
             Err(e) => {
                 eprintln!("unix socket accept error: {}", e);
+                error_report::capture(
+                    "exec_host.socket",
+                    &format!("unix socket accept error: {}", e),
+                    serde_json::json!({ "socket": sock_path }),
+                );
             }
         }
     }
@@ -2231,103 +6046,209 @@ fn get_config(state: State<'_, AppState>) -> Result<NodeClientConfig, String> {
     state
         .config
         .lock()
-        .map(|config| config.clone())
+        .map(|config| credentials::redact_config_secrets(&config))
         .map_err(|err| err.to_string())
 }
 
+/// Put the real gateway secrets back into a config from the webview, which
+/// only ever has placeholders for them.
+fn with_stored_secrets(
+    app: &AppHandle,
+    mut config: NodeClientConfig,
+) -> Result<NodeClientConfig, String> {
+    let state = app.state::<AppState>();
+    let stored = state.config.lock().map_err(|err| err.to_string())?.clone();
+    let staged = state
+        .staged_secrets
+        .lock()
+        .map_err(|err| err.to_string())?
+        .clone();
+    credentials::restore_secrets(&mut config, &stored, staged.as_ref());
+    Ok(config)
+}
+
+/// The unmasked value of a secret field (any key in
+/// `credentials::SECRET_FIELDS`, e.g. `gatewayToken` or `runAsPassword`), for
+/// an explicit "show" action in the UI.
 #[tauri::command]
-fn set_config(state: State<'_, AppState>, config: NodeClientConfig) -> Result<(), String> {
-    save_config(&config)?;
-    let mut current = state.config.lock().map_err(|err| err.to_string())?;
-    *current = config;
-    Ok(())
+fn reveal_secret(app: AppHandle, field: String) -> Result<Option<String>, String> {
+    let secret = credentials::SECRET_FIELDS
+        .iter()
+        .find(|secret| secret.key == field)
+        .ok_or_else(|| format!("{} is not a secret field", field))?;
+    let mut config = NodeClientConfig::default();
+    *(secret.get)(&mut config) = Some(credentials::SECRET_PLACEHOLDER.to_string());
+    let mut config = with_stored_secrets(&app, config)?;
+    Ok((secret.get)(&mut config).take())
 }
 
 #[tauri::command]
-fn get_status(app: AppHandle, state: State<'_, AppState>) -> Result<NodeClientStatus, String> {
-    let (running, node_status) = {
-        let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
-        let (running, maybe_exit_log) = refresh_process_state(&mut runtime);
-        if let Some(exit_log) = maybe_exit_log {
-            let current_status = runtime.node_status.clone();
-            drop(runtime);
-            push_log_line(&app, exit_log);
-            let mut runtime = state.runtime.lock().map_err(|err| err.to_string())?;
-            let (running_again, _) = refresh_process_state(&mut runtime);
-            (
-                running_again,
-                runtime
-                    .node_status
-                    .clone()
-                    .or(current_status)
-                    .unwrap_or(NodeStatus::Stopped),
-            )
-        } else {
-            (
-                running,
-                runtime
-                    .node_status
-                    .clone()
-                    .unwrap_or(if running {
-                        NodeStatus::Running
-                    } else {
-                        NodeStatus::Stopped
-                    }),
-            )
-        }
-    };
+async fn set_config(
+    app: AppHandle,
+    config: NodeClientConfig,
+    validate: Option<bool>,
+) -> Result<ConfigChangeImpact, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        set_config_blocking(app, config, validate.unwrap_or(false))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
 
-    let config = state.config.lock().map_err(|err| err.to_string())?.clone();
-    let runtime = state.runtime.lock().map_err(|err| err.to_string())?;
+/// With `validate`, the gateway settings are probed first and nothing is
+/// saved if the check fails.
+fn set_config_blocking(
+    app: AppHandle,
+    mut config: NodeClientConfig,
+    validate: bool,
+) -> Result<ConfigChangeImpact, String> {
+    config = with_stored_secrets(&app, config)?;
+    if validate {
+        gateway_probe::validate_before_save(&app, &config)?;
+    }
+    let allow_restart = config.auto_restart_on_config_change;
+    let synced = config.clone();
+    let impact = save_and_apply_config(&app, config, allow_restart)?;
+    if let Ok(mut staged) = app.state::<AppState>().staged_secrets.lock() {
+        *staged = None;
+    }
+    match openclaw_sync::sync_gateway_settings(&synced, &impact) {
+        Ok(keys) if !keys.is_empty() => push_log_line(
+            &app,
+            format!("updated openclaw.json gateway settings ({})", keys.join(", ")),
+        ),
+        Ok(_) => {}
+        Err(err) => push_log_line(&app, format!("openclaw.json sync failed: {}", err)),
+    }
+    Ok(impact)
+}
+
+/// Exits are picked up by the log readers and `run_process_monitor`, so
+/// this only reads published state.
+#[tauri::command]
+fn get_status(state: State<'_, AppState>) -> Result<NodeClientStatus, String> {
+    let node = state.node_status.borrow().clone();
+    let (gateway_url, config_node_id) = {
+        let config = state.config.lock().map_err(|err| err.to_string())?;
+        (config.gateway_url(), config.node_id.clone())
+    };
+    let openclaw_version = state
+        .openclaw_version
+        .lock()
+        .map_err(|err| err.to_string())?
+        .clone();
+    let log_entries: Vec<LogEntry> = state
+        .runtime
+        .lock()
+        .map_err(|err| err.to_string())?
+        .logs
+        .iter()
+        .cloned()
+        .collect();
 
     Ok(NodeClientStatus {
-        running,
-        status: node_status.as_str().to_string(),
-        gateway_url: config.gateway_url(),
-        last_error: runtime.last_error.clone(),
-        logs: runtime.logs.iter().cloned().collect(),
+        running: node.running,
+        status: node.status.as_str().to_string(),
+        gateway_url,
+        last_error: node.last_error,
+        logs: log_entries.iter().map(LogEntry::render).collect(),
+        log_entries,
+        openclaw_version,
+        service_managed: node.service_managed,
+        low_priority: node.low_priority,
+        node_id: node.node_id.or(config_node_id),
     })
 }
 
 #[tauri::command]
-fn start_node(app: AppHandle) -> Result<(), String> {
-    start_node_internal(&app)
+fn get_openclaw_version(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    refresh: Option<bool>,
+) -> Result<OpenclawVersionInfo, String> {
+    let config = state.config.lock().map_err(|err| err.to_string())?.clone();
+    let (openclaw_bin, _, _) = resolve_openclaw_bin(&config, &app)?;
+    cached_openclaw_version(&app, &openclaw_bin, refresh.unwrap_or(false))
+}
+
+#[tauri::command]
+async fn preflight_node_start(app: AppHandle) -> Result<PreflightReport, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = {
+            let state = app.state::<AppState>();
+            let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+            cfg
+        };
+        Ok(run_preflight(&app, &config, true))
+    })
+    .await
+    .map_err(|err| err.to_string())?
 }
 
+// Async so the pre-flight scan and stop grace period run on a blocking
+// thread instead of the main (UI) thread sync commands are called on
+
 #[tauri::command]
-fn stop_node(app: AppHandle) -> Result<(), String> {
-    stop_node_internal(&app)
+async fn start_node(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || start_node_internal(&app))
+        .await
+        .map_err(|err| err.to_string())?
 }
 
 #[tauri::command]
-fn restart_node(app: AppHandle) -> Result<(), String> {
-    restart_node_internal(&app)
+async fn stop_node(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || stop_node_internal(&app))
+        .await
+        .map_err(|err| err.to_string())?
 }
 
 #[tauri::command]
-fn get_pending_approvals(state: State<'_, AppState>) -> Result<Vec<ApprovalPreview>, String> {
-    let approvals = state
-        .pending_approvals
-        .lock()
-        .map_err(|err| err.to_string())?;
-    Ok(approvals.iter().map(|a| a.preview.clone()).collect())
+async fn restart_node(app: AppHandle) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || restart_node_internal(&app))
+        .await
+        .map_err(|err| err.to_string())?
 }
 
+/// Write a line to the node host's stdin, for CLI prompts (pairing codes,
+/// confirmations) that would otherwise wait forever.
 #[tauri::command]
-fn decide_approval(
-    _app: AppHandle,
+fn send_node_input(
+    app: AppHandle,
     state: State<'_, AppState>,
-    id: String,
-    decision: String,
+    line: String,
 ) -> Result<(), String> {
+    let line = line.trim_end_matches(['\r', '\n']).to_string();
+    let bytes = line.len();
+    let mut guard = state.node_stdin.lock().map_err(|err| err.to_string())?;
+    let stdin = guard
+        .as_ref()
+        .ok_or_else(|| "node host is not running".to_string())?;
+    if stdin.send(line).is_err() {
+        // The writer stopped after a failed write (logged there)
+        *guard = None;
+        return Err("node host stdin is closed".to_string());
+    }
+    drop(guard);
+    // Never the text itself: prompts ask for pairing codes and passwords
+    push_log_line(&app, format!("[stdin] sent {} bytes", bytes));
+    Ok(())
+}
+
+#[tauri::command]
+fn get_pending_approvals(state: State<'_, AppState>) -> Result<Vec<ApprovalPreview>, String> {
+    let approvals = state.pending_approvals.borrow();
+    Ok(approvals.iter().map(|a| a.preview.clone()).collect())
+}
+
+/// Hand a decision to the exec-host request waiting on approval `id`.
+/// Shared by the UI and the tray's approvals submenu.
+fn send_approval_decision(app: &AppHandle, id: &str, decision: &str) -> Result<(), String> {
     if decision != "deny" && decision != "allow-once" && decision != "allow-always" {
         return Err(format!("invalid decision: {}", decision));
     }
 
-    let approvals = state
-        .pending_approvals
-        .lock()
-        .map_err(|err| err.to_string())?;
+    let state = app.state::<AppState>();
+    let approvals = state.pending_approvals.borrow();
 
     let pending = approvals
         .iter()
@@ -2336,12 +6257,59 @@ fn decide_approval(
 
     pending
         .tx
-        .try_send(decision)
+        .try_send(decision.to_string())
         .map_err(|err| format!("failed to send decision: {}", err))?;
 
     Ok(())
 }
 
+#[tauri::command]
+fn decide_approval(app: AppHandle, id: String, decision: String) -> Result<(), String> {
+    send_approval_decision(&app, &id, &decision)
+}
+
+/// Show `dir` in Explorer / Finder / the XDG file manager, creating it first
+/// so a fresh install still opens somewhere.
+fn open_in_file_manager(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    #[cfg(target_os = "windows")]
+    let opener = "explorer";
+    #[cfg(target_os = "macos")]
+    let opener = "open";
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let opener = "xdg-open";
+    let mut child = Command::new(opener)
+        .arg(dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|err| format!("failed to run {}: {}", opener, err))?;
+    // explorer.exe exits non-zero even on success, so only reap the child
+    std::thread::spawn(move || {
+        let _ = child.wait();
+    });
+    Ok(())
+}
+
+fn config_dir() -> Result<PathBuf, String> {
+    let path = config_path()?;
+    Ok(path
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or(openclaw_dir()?))
+}
+
+#[tauri::command]
+fn open_config_dir() -> Result<(), String> {
+    open_in_file_manager(&config_dir()?)
+}
+
+#[tauri::command]
+fn open_logs_dir() -> Result<(), String> {
+    open_in_file_manager(&logs_dir()?)
+}
+
 #[tauri::command]
 fn enable_autostart(app: AppHandle) -> Result<(), String> {
     use tauri_plugin_autostart::ManagerExt;
@@ -2373,25 +6341,112 @@ fn get_install_path(state: State<'_, AppState>) -> Result<Option<String>, String
 }
 
 #[tauri::command]
-fn set_install_path(state: State<'_, AppState>, path: Option<String>) -> Result<(), String> {
-    let mut config = state.config.lock().map_err(|err| err.to_string())?;
-    config.install_path = path;
-    save_config(&config)?;
+fn set_install_path(app: AppHandle, path: Option<String>) -> Result<(), String> {
+    update_config(&app, |config| config.install_path = path)?;
     Ok(())
 }
 
 #[tauri::command]
-fn import_openclaw_config() -> Option<NodeClientConfig> {
-    try_import_from_openclaw_config()
+fn import_openclaw_config(state: State<'_, AppState>) -> Option<NodeClientConfig> {
+    let imported = try_import_from_openclaw_config()?;
+    if let Ok(mut staged) = state.staged_secrets.lock() {
+        *staged = Some(credentials::GatewaySecrets {
+            token: imported.gateway_token.clone(),
+            password: imported.gateway_password.clone(),
+        });
+    }
+    Some(credentials::redact_config_secrets(&imported))
 }
 
 #[tauri::command]
-fn detect_install_path(state: State<'_, AppState>) -> Result<Option<DiscoveryResult>, String> {
+fn detect_install_path(app: AppHandle) -> Result<Option<DiscoveryResult>, String> {
     let result = discover_openclaw_binary();
     if let Some(ref discovery) = result {
-        let mut config = state.config.lock().map_err(|err| err.to_string())?;
-        config.install_path = Some(discovery.bin_dir.clone());
-        save_config(&config)?;
+        update_config(&app, |config| {
+            config.install_path = Some(discovery.bin_dir.clone())
+        })?;
+    }
+    Ok(result)
+}
+
+#[tauri::command]
+async fn list_install_candidates() -> Result<Vec<InstallCandidate>, String> {
+    tauri::async_runtime::spawn_blocking(list_openclaw_installs)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+async fn get_discovery_report() -> Result<DiscoveryReport, String> {
+    tauri::async_runtime::spawn_blocking(discovery_report)
+        .await
+        .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+async fn install_openclaw(app: AppHandle) -> Result<Option<DiscoveryResult>, String> {
+    let (program, args, manager) = detect_install_command()
+        .ok_or_else(|| "no npm, pnpm, or volta found; install Node.js first".to_string())?;
+    push_log_line(
+        &app,
+        format!("installing openclaw via {}: {} {}", manager, program.display(), args.join(" ")),
+    );
+    let _ = app.emit(
+        "openclaw-install-progress",
+        serde_json::json!({
+            "stream": "status",
+            "line": format!("running {} {}", manager, args.join(" ")),
+        }),
+    );
+
+    let mut cmd = tokio::process::Command::new(&program);
+    cmd.args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    #[cfg(target_os = "linux")]
+    sanitize_appimage_env_tokio(&mut cmd);
+    #[cfg(target_os = "windows")]
+    {
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|err| format!("failed to run {}: {}", manager, err))?;
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(spawn_install_output_reader(app.clone(), stdout, "stdout"));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(spawn_install_output_reader(app.clone(), stderr, "stderr"));
+    }
+    let status = child
+        .wait()
+        .await
+        .map_err(|err| format!("failed to wait for {}: {}", manager, err))?;
+    for reader in readers {
+        let _ = reader.await;
+    }
+    if !status.success() {
+        return Err(format!("{} exited with status {}", manager, status));
+    }
+
+    // Re-run discovery so the fresh install is picked up immediately
+    let result = tauri::async_runtime::spawn_blocking(discover_openclaw_binary)
+        .await
+        .map_err(|err| err.to_string())?;
+    if let Some(ref discovery) = result {
+        update_config(&app, |config| {
+            config.install_path = Some(discovery.bin_dir.clone())
+        })?;
+        let _ = app.emit("install-path-detected", discovery.bin_dir.clone());
+        push_log_line(&app, format!("openclaw installed at {}", discovery.bin_path));
+    } else {
+        push_log_line(&app, "openclaw installed, but discovery could not locate the binary");
+    }
+    if let Ok(mut cached) = app.state::<AppState>().openclaw_version.lock() {
+        *cached = None;
     }
     Ok(result)
 }
@@ -2400,43 +6455,44 @@ fn detect_install_path(state: State<'_, AppState>) -> Result<Option<DiscoveryRes
 fn get_device_id(app: AppHandle) -> Result<String, String> {
     let data_dir = app.path().app_data_dir()
         .map_err(|e| format!("failed to get data dir: {}", e))?;
-    let identity = gateway::load_or_create_device_identity(&data_dir)?;
+    let identity = app
+        .state::<Arc<gateway::GatewayState>>()
+        .device_identity(&data_dir)?;
     Ok(identity.device_id)
 }
 
+fn configured_gateway_url(app: &AppHandle) -> Result<String, String> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().map_err(|err| err.to_string())?;
+    Ok(config.gateway_url())
+}
+
+/// Tray shortcut for values needed when approving pairing on the gateway
+/// side (device id, gateway URL).
+fn copy_to_clipboard(app: &AppHandle, what: &str, text: Result<String, String>) {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    let result = text.and_then(|text| {
+        app.clipboard()
+            .write_text(text)
+            .map_err(|err| err.to_string())
+    });
+    if let Err(err) = result {
+        push_log_line(app, format!("failed to copy {}: {}", what, err));
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tray
 // ---------------------------------------------------------------------------
 
 fn setup_tray(app: &tauri::App) -> Result<(), String> {
-    let show = MenuItemBuilder::new("Open")
-        .id("show")
-        .build(app)
-        .map_err(|err| err.to_string())?;
-    let start = MenuItemBuilder::new("Start Node Host")
-        .id("start")
-        .build(app)
-        .map_err(|err| err.to_string())?;
-    let stop = MenuItemBuilder::new("Stop Node Host")
-        .id("stop")
-        .build(app)
-        .map_err(|err| err.to_string())?;
-    let restart = MenuItemBuilder::new("Restart Node Host")
-        .id("restart")
-        .build(app)
-        .map_err(|err| err.to_string())?;
-    let quit = MenuItemBuilder::new("Quit")
-        .id("quit")
-        .build(app)
-        .map_err(|err| err.to_string())?;
+    let menu = tray_status::build_menu(app.handle(), &[], &[]).map_err(|err| err.to_string())?;
 
-    let menu = MenuBuilder::new(app)
-        .items(&[&show, &start, &stop, &restart, &quit])
-        .build()
-        .map_err(|err| err.to_string())?;
-
-    TrayIconBuilder::new()
-        .menu(&menu)
+    let mut tray = TrayIconBuilder::with_id(tray_status::TRAY_ID);
+    if let Some(icon) = app.default_window_icon() {
+        tray = tray.icon(icon.clone());
+    }
+    tray.menu(&menu)
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| match event.id.as_ref() {
             "show" => {
@@ -2445,24 +6501,45 @@ fn setup_tray(app: &tauri::App) -> Result<(), String> {
                     let _ = window.set_focus();
                 }
             }
+            // Off the main thread, which the tray menu runs on
             "start" => {
-                let _ = start_node_internal(app);
+                let app = app.clone();
+                tauri::async_runtime::spawn_blocking(move || start_node_internal(&app));
             }
             "stop" => {
-                let _ = stop_node_internal(app);
+                let app = app.clone();
+                tauri::async_runtime::spawn_blocking(move || stop_node_internal(&app));
             }
             "restart" => {
-                let _ = restart_node_internal(app);
+                let app = app.clone();
+                tauri::async_runtime::spawn_blocking(move || restart_node_internal(&app));
             }
             "quit" => {
                 // Clean up exec-approvals socket registration
                 if let Ok(path) = exec_approvals_path() {
                     let _ = clear_exec_approvals_socket(&path);
                 }
+                control::remove_token();
                 let _ = stop_node_internal(app);
                 app.exit(0);
             }
-            _ => {}
+            "open-config-dir" => {
+                if let Err(err) = config_dir().and_then(|dir| open_in_file_manager(&dir)) {
+                    push_log_line(app, format!("failed to open config folder: {}", err));
+                }
+            }
+            "copy-device-id" => copy_to_clipboard(app, "device ID", get_device_id(app.clone())),
+            "copy-gateway-url" => {
+                copy_to_clipboard(app, "gateway URL", configured_gateway_url(app))
+            }
+            "open-logs-dir" => {
+                if let Err(err) = logs_dir().and_then(|dir| open_in_file_manager(&dir)) {
+                    push_log_line(app, format!("failed to open logs folder: {}", err));
+                }
+            }
+            other => {
+                tray_status::handle_menu_event(app, other);
+            }
         })
         .on_tray_icon_event(|tray, event| {
             if let TrayIconEvent::Click {
@@ -2498,6 +6575,19 @@ fn main() {
         std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
     }
 
+    let launch = match cli::parse_args() {
+        cli::Launch::Run(options) => options,
+        cli::Launch::Control(command) => {
+            let (message, code) = control::run(&command);
+            cli::exit_with(&message, code)
+        }
+        cli::Launch::Exit { message, code } => cli::exit_with(&message, code),
+    };
+    if let Err(err) = cli::apply_config_override(&launch) {
+        cli::exit_with(&format!("error: {}", err), 2);
+    }
+    crash_report::install();
+
     // Recover config files whose ACLs were corrupted by a previous version's
     // broken icacls invocation (stripped all ACEs, then failed the grant).
     #[cfg(target_os = "windows")]
@@ -2511,30 +6601,53 @@ fn main() {
     }
 
     let config = load_config();
+    i18n::set_locale(config.locale.as_deref());
     let approval_token = generate_token();
+    let control_token = generate_token();
+    let runtime = RuntimeState::default();
 
     let builder = tauri::Builder::default()
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_dialog::init())
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(app_update::plugin())
         .plugin(tauri_plugin_autostart::init(
             MacosLauncher::LaunchAgent,
-            None,
+            Some(vec!["--minimized"]),
         ))
         .manage(AppState {
             config: Mutex::new(config.clone()),
-            runtime: Mutex::new(RuntimeState::default()),
-            pending_approvals: Mutex::new(Vec::new()),
+            node_status: runtime.status_tx.subscribe(),
+            runtime: Mutex::new(runtime),
+            pending_approvals: tokio::sync::watch::Sender::new(Vec::new()),
+            openclaw_version: Mutex::new(None),
+            node_stdin: Mutex::new(None),
+            exec_host_token: approval_token.clone(),
+            control_token: control_token.clone(),
+            staged_secrets: Mutex::new(None),
+            node_log_batch: Mutex::new(Vec::new()),
+            config_dirty: tokio::sync::watch::Sender::new(false),
+            config_changed: tokio::sync::watch::Sender::new(()),
+            config_io: Mutex::new(()),
         })
         .manage(Arc::new(gateway::GatewayState::new()))
+        .manage(tunnel::TunnelState::default())
+        .manage(terminal::TerminalState::default())
+        .manage(clipboard_sync::ClipboardOfferState::default())
         .invoke_handler(tauri::generate_handler![
             get_config,
             set_config,
             get_status,
+            get_openclaw_version,
+            preflight_node_start,
             start_node,
             stop_node,
             restart_node,
+            send_node_input,
             get_pending_approvals,
             decide_approval,
+            open_config_dir,
+            open_logs_dir,
             enable_autostart,
             disable_autostart,
             is_autostart_enabled,
@@ -2542,6 +6655,9 @@ fn main() {
             set_install_path,
             import_openclaw_config,
             detect_install_path,
+            list_install_candidates,
+            get_discovery_report,
+            install_openclaw,
             get_exec_policy,
             set_exec_policy,
             get_exec_allowlist,
@@ -2551,12 +6667,80 @@ fn main() {
             gateway::gateway_disconnect,
             gateway::gateway_status,
             gateway::gateway_rpc,
-            get_device_id
+            file_transfer::gateway_send_file,
+            file_transfer::gateway_fetch_file,
+            tunnel::open_tunnel,
+            tunnel::list_tunnels,
+            tunnel::close_tunnel,
+            terminal::list_terminal_sessions,
+            terminal::close_terminal_session,
+            clipboard_sync::push_clipboard,
+            clipboard_sync::pull_clipboard,
+            clipboard_sync::get_clipboard_offer,
+            clipboard_sync::resolve_clipboard_offer,
+            metrics::get_node_metrics,
+            notifier::test_notifier,
+            error_report::send_test_error_report,
+            get_device_id,
+            managed_node::get_managed_node_status,
+            managed_node::install_managed_node,
+            node_service::get_node_service_status,
+            node_service::install_node_service,
+            node_service::uninstall_node_service,
+            log_export::export_logs,
+            settings_bundle::export_settings_bundle,
+            settings_bundle::import_settings_bundle,
+            tls_trust::get_known_hosts,
+            tls_trust::forget_known_host,
+            config_backup::list_config_backups,
+            config_backup::rollback_config,
+            crash_report::list_crash_reports,
+            crash_report::clear_crash_reports,
+            crash_report::open_crash_dir,
+            openclaw_sync::apply_openclaw_config_update,
+            reveal_secret,
+            gateway_probe::validate_gateway_settings,
+            gateway_probe::run_connectivity_check,
+            runtime_update::check_bundled_runtime_update,
+            runtime_update::update_bundled_runtime,
+            app_update::check_for_app_update,
+            app_update::install_app_update
         ])
         .setup(move |app| {
+            // First, so errors from the rest of startup can be reported
+            error_report::register(app.handle());
+
+            // Reading the login shell's PATH can take seconds (nvm, conda in
+            // rc files); start it now so auto-start discovery finds it cached
+            let auto_start = config.auto_start_node || launch.start_node;
+            if auto_start {
+                std::thread::spawn(|| {
+                    let _ = login_shell_path();
+                });
+            }
+
             setup_tray(app)?;
 
+            // Login entries written by older versions have no --minimized
+            // flag; re-register so they pick it up.
+            {
+                use tauri_plugin_autostart::ManagerExt;
+                if app.autolaunch().is_enabled().unwrap_or(false) {
+                    let _ = app.autolaunch().enable();
+                }
+            }
+
+            // The window is created hidden so a tray-only launch never
+            // flashes it on screen.
             if let Some(window) = app.get_webview_window("main") {
+                let tray_only =
+                    launch.headless || (launch.minimized && config.start_minimized_to_tray);
+                if !tray_only {
+                    let _ = window.show();
+                    if launch.minimized {
+                        let _ = window.minimize();
+                    }
+                }
                 let window_handle = window.clone();
                 window.on_window_event(move |event| {
                     if let WindowEvent::CloseRequested { api, .. } = event {
@@ -2567,16 +6751,35 @@ fn main() {
             }
 
             // Register socket in exec-approvals.json
-            let socket_path = exec_host_socket_path();
             let token_for_socket = approval_token.clone();
-            if let Ok(approvals_path) = exec_approvals_path() {
-                if let Err(e) =
-                    merge_exec_approvals_socket(&approvals_path, &socket_path, &token_for_socket)
-                {
+            let token_in_keychain = app
+                .state::<AppState>()
+                .config
+                .lock()
+                .map(|c| c.exec_host_token_in_keychain)
+                .unwrap_or(false);
+            if let (Ok(approvals_path), Ok(socket_path)) =
+                (exec_approvals_path(), exec_host_socket_path())
+            {
+                if let Err(e) = merge_exec_approvals_socket(
+                    &approvals_path,
+                    &socket_path,
+                    &token_for_socket,
+                    token_in_keychain,
+                ) {
                     eprintln!("failed to register exec-approvals socket: {}", e);
+                } else {
+                    event_log::record(
+                        app.handle(),
+                        event_log::AuditEvent::TokenRotated { token: "execHost" },
+                    );
                 }
             }
 
+            if let Err(e) = control::write_token(&control_token) {
+                eprintln!("failed to write the control token: {}", e);
+            }
+
             // Start exec-host socket server
             let app_handle = app.handle().clone();
             let token_for_server = approval_token.clone();
@@ -2585,12 +6788,35 @@ fn main() {
                 start_exec_host_server(app_handle, token_for_server).await;
             });
 
-            // Auto-start node if configured
-            if config.auto_start_node {
-                if let Err(err) = start_node_internal(&app.handle()) {
-                    push_log_line(&app.handle(), format!("auto-start failed: {}", err));
-                }
+            handle_orphaned_node(app.handle());
+
+            // Auto-start node if configured, unless an OS service runs it.
+            // Detecting the service shells out to the CLI, so keep it off the
+            // setup path.
+            let service_app = app.handle().clone();
+            // Set once the node host is spawned or won't be; the gateway
+            // connection below waits on it instead of a fixed delay
+            let (node_spawned_tx, mut node_spawned_rx) = tokio::sync::watch::channel(false);
+            if !auto_start {
+                let _ = node_spawned_tx.send(true);
             }
+            std::thread::spawn(move || {
+                let service_managed = node_service::detect_and_monitor(&service_app);
+                if auto_start && !service_managed {
+                    let config = {
+                        let state = service_app.state::<AppState>();
+                        let cfg = state.config.lock().ok().map(|cfg| cfg.clone());
+                        cfg
+                    };
+                    if let Some(config) = config {
+                        wait_for_auto_start(&service_app, &config);
+                    }
+                    if let Err(err) = start_node_checked(&service_app, false) {
+                        push_log_line(&service_app, format!("auto-start failed: {}", err));
+                    }
+                }
+                let _ = node_spawned_tx.send(true);
+            });
 
             // Auto-connect to gateway WebSocket
             {
@@ -2607,8 +6833,13 @@ fn main() {
                     .unwrap_or_else(|_| std::path::PathBuf::from("."));
                 let gw_attempt = gw_state.begin_attempt();
                 tauri::async_runtime::spawn(async move {
-                    // Short delay to let the node process start first
-                    tokio::time::sleep(tokio::time::Duration::from_millis(1500)).await;
+                    // Let the node process start first, but never hold the
+                    // connection for a slow service check or network wait
+                    let _ = tokio::time::timeout(
+                        tokio::time::Duration::from_millis(NODE_SPAWN_WAIT_MS),
+                        node_spawned_rx.wait_for(|spawned| *spawned),
+                    )
+                    .await;
                     gateway::run_gateway_connection(
                         gw_app,
                         gw_state,
@@ -2623,6 +6854,29 @@ fn main() {
                 });
             }
 
+            tauri::async_runtime::spawn(run_config_writer(app.handle().clone()));
+            tauri::async_runtime::spawn(run_approval_sweeper(app.handle().clone()));
+            tauri::async_runtime::spawn(run_process_monitor(app.handle().clone()));
+            tauri::async_runtime::spawn(watchdog::run_watchdog(app.handle().clone()));
+            tauri::async_runtime::spawn(tray_status::run_tray_status(app.handle().clone()));
+            tauri::async_runtime::spawn(local_api::run_local_api(app.handle().clone()));
+            tauri::async_runtime::spawn(mqtt::run_mqtt(app.handle().clone()));
+            tauri::async_runtime::spawn(gateway_report::run_gateway_report(
+                app.handle().clone(),
+            ));
+            tauri::async_runtime::spawn(metrics::run_metrics_report(app.handle().clone()));
+            tauri::async_runtime::spawn(telemetry::run_exporter(app.handle().clone()));
+            tauri::async_runtime::spawn(crash_report::upload_pending(app.handle().clone()));
+            tauri::async_runtime::spawn(app_update::run_update_checks(app.handle().clone()));
+            #[cfg(target_os = "linux")]
+            tauri::async_runtime::spawn(dbus_service::run_dbus(app.handle().clone()));
+            hooks::register(app.handle());
+            notifier::register(app.handle());
+
+            if let Err(err) = config_watch::watch_config_file(app.handle().clone()) {
+                eprintln!("config file watcher unavailable: {}", err);
+            }
+
             Ok(())
         });
 
@@ -2640,9 +6894,200 @@ fn main() {
             // are cleaned up regardless of how the app exits (WM force-close,
             // SIGTERM, runtime panic, etc.). Both functions are idempotent.
             let _ = stop_node_internal(app_handle);
+            if let Err(err) = flush_config(app_handle) {
+                eprintln!("failed to save config on exit: {}", err);
+            }
             if let Ok(path) = exec_approvals_path() {
                 let _ = clear_exec_approvals_socket(&path);
             }
+            control::remove_token();
+            crash_report::mark_clean_exit();
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_credential_assignments_and_bearer_tokens() {
+        assert_eq!(
+            redact_credential_patterns("connect failed: token=abc123 host=gw"),
+            "connect failed: token=*** host=gw"
+        );
+        assert_eq!(
+            redact_credential_patterns(r#"{"password": "hunter2", "user": "me"}"#),
+            r#"{"password": "***", "user": "me"}"#
+        );
+        assert_eq!(
+            redact_credential_patterns("retrying with Bearer eyJhbGciOi.x.y"),
+            "retrying with Bearer ***"
+        );
+        assert_eq!(
+            redact_credential_patterns("Authorization: Bearer eyJhbGciOi.x.y"),
+            "Authorization: *** ***"
+        );
+        assert_eq!(
+            redact_credential_patterns("API_KEY: sk-live-1; next"),
+            "API_KEY: ***; next"
+        );
+    }
+
+    #[test]
+    fn leaves_lines_without_credentials_alone() {
+        let line = "node host connected to gateway 127.0.0.1:18789";
+        assert_eq!(redact_credential_patterns(line), line);
+        // The key alone, without a value, is not a credential
+        assert_eq!(redact_credential_patterns("token expired"), "token expired");
+        assert_eq!(redact_credential_patterns("password:"), "password:");
+    }
+
+    #[test]
+    fn matches_node_hosts_by_argv() {
+        let argv = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+        assert!(looks_like_node_host(&argv(&[
+            "/usr/bin/node",
+            "/usr/lib/node_modules/openclaw/openclaw.mjs",
+            "node",
+            "run",
+            "--host",
+            "gw",
+        ])));
+        assert!(looks_like_node_host(&argv(&["openclaw", "node", "run"])));
+        assert!(!looks_like_node_host(&argv(&["vim", "openclaw/docs/node run.md"])));
+        assert!(!looks_like_node_host(&argv(&["openclaw", "node", "install"])));
+        assert!(!looks_like_node_host(&argv(&["openclaw-node-client", "--start-node"])));
+    }
+
+    #[test]
+    fn compares_versions_numerically() {
+        assert!(version_older_than("2026.2.9", "2026.2.22"));
+        assert!(!version_older_than("2026.2.22", "2026.2.22"));
+        assert!(!version_older_than("2026.10.1", "2026.2.22"));
+        assert!(version_older_than("2025.12.31", "2026.1.1"));
+    }
+
+    #[test]
+    fn version_comparison_ignores_suffixes_and_pads_components() {
+        assert!(!version_older_than("2026.2.22-beta.1", "2026.2.22"));
+        assert!(!version_older_than("2026.3", "2026.3.0"));
+        assert!(version_older_than("2026.3", "2026.3.1"));
+        assert!(!version_older_than("2026.3.1+abc", "2026.3"));
+    }
+
+    #[test]
+    fn parses_json_json5_and_toml_configs() {
+        let json = parse_config_object(r#"{"host": "gw", "port": 18789}"#).unwrap();
+        assert_eq!(json["host"], "gw");
+        assert_eq!(json["port"], 18789);
+
+        let json5 = parse_config_object("{\n  // gateway\n  host: 'gw',\n  port: 18789,\n}").unwrap();
+        assert_eq!(json5["host"], "gw");
+        assert_eq!(json5["port"], 18789);
+
+        let toml = parse_config_object("host = \"gw\"\nport = 18789\n").unwrap();
+        assert_eq!(toml["host"], "gw");
+        assert_eq!(toml["port"], 18789);
+    }
+
+    #[test]
+    fn rejects_configs_that_are_not_objects() {
+        assert_eq!(
+            parse_config_object("[1, 2]").unwrap_err(),
+            "config must be an object"
+        );
+        assert!(parse_config_object("host = ")
+            .unwrap_err()
+            .starts_with("not valid JSON, JSON5 or TOML"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn returns_exec_output_when_a_background_process_keeps_the_pipes_open() {
+        let started = std::time::Instant::now();
+        let result = run_exec_command(
+            vec!["sh".into(), "-c".into(), "sleep 5 & echo done; exit 3".into()],
+            None,
+            None,
+            Some(20_000),
+        )
+        .await;
+        assert!(started.elapsed() < std::time::Duration::from_secs(4));
+        assert!(!result.timed_out);
+        assert_eq!(result.exit_code, Some(3));
+        assert_eq!(result.stdout, "done\n");
+    }
+
+    #[test]
+    fn compares_secrets_in_full() {
+        assert!(constant_time_eq(b"Bearer abc", b"Bearer abc"));
+        assert!(!constant_time_eq(b"Bearer abc", b"Bearer abd"));
+        assert!(!constant_time_eq(b"Bearer abc", b"Bearer ab"));
+        assert!(!constant_time_eq(b"", b"Bearer abc"));
+    }
+
+    #[test]
+    fn masks_and_restores_every_secret_field() {
+        let mut stored = NodeClientConfig::default();
+        for field in credentials::SECRET_FIELDS {
+            *(field.get)(&mut stored) = Some(format!("{}-value", field.key));
+        }
+        stored
+            .otlp_headers
+            .insert("x-api-key".to_string(), "collector-key".to_string());
+        let mut masked = credentials::redact_config_secrets(&stored);
+        let values = serde_json::to_value(&masked).unwrap();
+        for field in credentials::SECRET_FIELDS {
+            assert_eq!(values[field.key], credentials::SECRET_PLACEHOLDER);
+        }
+        assert_eq!(values["otlpHeaders"]["x-api-key"], credentials::SECRET_PLACEHOLDER);
+        credentials::restore_secrets(&mut masked, &stored, None);
+        assert_eq!(masked.otlp_headers["x-api-key"], "collector-key");
+        for field in credentials::SECRET_FIELDS {
+            assert_eq!(
+                (field.get)(&mut masked).as_deref(),
+                Some(format!("{}-value", field.key).as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn collapses_repeated_log_lines() {
+        let mut logs = LogRing::default();
+        let mut batch = Vec::new();
+        for message in ["gateway closed", "gateway closed", "gateway closed", "reconnected"] {
+            let entry = logs.push_collapsing(LogEntry::new("stderr", message.to_string()));
+            queue_log_entry(&mut batch, entry);
+        }
+        // Same text on another stream is a different line
+        let entry = logs.push_collapsing(LogEntry::new("stdout", "reconnected".to_string()));
+        queue_log_entry(&mut batch, entry);
+
+        let rendered: Vec<String> = logs.iter().map(LogEntry::render).collect();
+        assert_eq!(
+            rendered,
+            [
+                "[stderr] gateway closed (x3)",
+                "[stderr] reconnected",
+                "[stdout] reconnected"
+            ]
+        );
+        let repeats: Vec<u32> = batch.iter().map(|entry| entry.repeat).collect();
+        assert_eq!(repeats, [3, 1, 1]);
+    }
+
+    #[test]
+    fn collapses_repeats_after_the_log_ring_wraps() {
+        let mut logs = LogRing::default();
+        for i in 0..LOG_CAP {
+            logs.push_collapsing(LogEntry::new("stdout", format!("line {}", i)));
+        }
+        logs.push_collapsing(LogEntry::new("stdout", "spam".to_string()));
+        logs.push_collapsing(LogEntry::new("stdout", "spam".to_string()));
+        assert_eq!(logs.len(), LOG_CAP);
+        assert_eq!(logs.iter().next().unwrap().message, "line 1");
+        assert_eq!(logs.back().unwrap().message, "spam");
+        assert_eq!(logs.back().unwrap().repeat, 2);
+    }
+}
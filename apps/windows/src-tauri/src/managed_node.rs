@@ -0,0 +1,207 @@
+// Managed Node.js runtime.
+//
+// When no system Node.js is available the bundled CLI cannot run. In managed
+// mode the app downloads a pinned official Node.js build into
+// `<app data>/runtime/<dist name>/`, verifies it against the release's
+// SHASUMS256.txt, and uses it both for the bundled `openclaw.mjs` entrypoint
+// and (via PATH) for npm-installed CLI shims.
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// Pinned Node.js release (current LTS line supported by the CLI).
+pub const MANAGED_NODE_VERSION: &str = "22.14.0";
+const NODE_DIST_BASE: &str = "https://nodejs.org/dist";
+const DOWNLOAD_TIMEOUT_SECS: u64 = 600;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManagedNodeStatus {
+    version: String,
+    installed: bool,
+    node_path: Option<String>,
+    supported: bool,
+}
+
+/// `node-v<ver>-<os>-<arch>` for this platform, or `None` when nodejs.org
+/// publishes no build for it.
+fn dist_name() -> Option<String> {
+    let os = match std::env::consts::OS {
+        "linux" => "linux",
+        "macos" => "darwin",
+        "windows" => "win",
+        _ => return None,
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "x64",
+        "aarch64" => "arm64",
+        _ => return None,
+    };
+    Some(format!("node-v{}-{}-{}", MANAGED_NODE_VERSION, os, arch))
+}
+
+fn archive_name(dist: &str) -> String {
+    if cfg!(windows) {
+        format!("{}.zip", dist)
+    } else {
+        format!("{}.tar.gz", dist)
+    }
+}
+
+fn install_dir(app: &AppHandle) -> Option<PathBuf> {
+    let dist = dist_name()?;
+    Some(app.path().app_data_dir().ok()?.join("runtime").join(dist))
+}
+
+fn node_binary_in(dir: &Path) -> PathBuf {
+    if cfg!(windows) {
+        dir.join("node.exe")
+    } else {
+        dir.join("bin").join("node")
+    }
+}
+
+/// Path of the managed `node` binary, if it has been installed.
+pub fn installed_node_path(app: &AppHandle) -> Option<String> {
+    let node = node_binary_in(&install_dir(app)?);
+    node.is_file().then(|| node.to_string_lossy().to_string())
+}
+
+fn emit_progress(app: &AppHandle, stage: &str, detail: Option<String>) {
+    let _ = app.emit(
+        "managed-node-progress",
+        serde_json::json!({ "stage": stage, "detail": detail }),
+    );
+}
+
+fn expected_sha256(shasums: &str, archive: &str) -> Option<String> {
+    shasums.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        let hash = parts.next()?;
+        (parts.next()? == archive).then(|| hash.to_lowercase())
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+fn extract_archive(bytes: &[u8], dest: &Path) -> Result<(), String> {
+    let decoder = flate2::read::GzDecoder::new(bytes);
+    tar::Archive::new(decoder)
+        .unpack(dest)
+        .map_err(|e| format!("failed to extract Node.js archive: {}", e))
+}
+
+#[cfg(target_os = "windows")]
+fn extract_archive(bytes: &[u8], dest: &Path) -> Result<(), String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("invalid Node.js archive: {}", e))?;
+    archive
+        .extract(dest)
+        .map_err(|e| format!("failed to extract Node.js archive: {}", e))
+}
+
+async fn download(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    client
+        .get(url)
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|e| format!("download of {} failed: {}", url, e))?
+        .bytes()
+        .await
+        .map(|b| b.to_vec())
+        .map_err(|e| format!("download of {} failed: {}", url, e))
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn get_managed_node_status(app: AppHandle) -> ManagedNodeStatus {
+    let node_path = installed_node_path(&app);
+    ManagedNodeStatus {
+        version: MANAGED_NODE_VERSION.to_string(),
+        installed: node_path.is_some(),
+        node_path,
+        supported: dist_name().is_some(),
+    }
+}
+
+#[tauri::command]
+pub async fn install_managed_node(app: AppHandle) -> Result<ManagedNodeStatus, String> {
+    let dist = dist_name().ok_or_else(|| {
+        format!(
+            "no official Node.js build for {}-{}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })?;
+    let target = install_dir(&app).ok_or_else(|| "failed to get data dir".to_string())?;
+    if node_binary_in(&target).is_file() {
+        return Ok(get_managed_node_status(app));
+    }
+
+    let archive = archive_name(&dist);
+    let release_url = format!("{}/v{}", NODE_DIST_BASE, MANAGED_NODE_VERSION);
+    let client = reqwest::Client::new();
+
+    emit_progress(&app, "downloading", Some(archive.clone()));
+    let shasums = download(&client, &format!("{}/SHASUMS256.txt", release_url)).await?;
+    let shasums = String::from_utf8_lossy(&shasums).to_string();
+    let expected = expected_sha256(&shasums, &archive)
+        .ok_or_else(|| format!("{} is not listed in SHASUMS256.txt", archive))?;
+    let bytes = download(&client, &format!("{}/{}", release_url, archive)).await?;
+
+    emit_progress(&app, "verifying", None);
+    let actual = hex::encode(Sha256::digest(&bytes));
+    if actual != expected {
+        let msg = format!(
+            "Node.js archive checksum mismatch (expected {}, got {})",
+            expected, actual
+        );
+        emit_progress(&app, "failed", Some(msg.clone()));
+        return Err(msg);
+    }
+
+    emit_progress(&app, "extracting", None);
+    let runtime_root = target
+        .parent()
+        .map(Path::to_path_buf)
+        .ok_or_else(|| "invalid runtime dir".to_string())?;
+    let staging = runtime_root.join(format!("{}.staging", dist));
+    let _ = fs::remove_dir_all(&staging);
+    fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+    let staging_clone = staging.clone();
+    let extracted = tauri::async_runtime::spawn_blocking(move || {
+        extract_archive(&bytes, &staging_clone)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+    // Archives contain a single top-level `<dist>/` directory
+    let result = extracted.and_then(|_| {
+        let unpacked = staging.join(&dist);
+        if !node_binary_in(&unpacked).is_file() {
+            return Err("Node.js archive did not contain a node binary".to_string());
+        }
+        let _ = fs::remove_dir_all(&target);
+        fs::rename(&unpacked, &target).map_err(|e| e.to_string())
+    });
+    let _ = fs::remove_dir_all(&staging);
+    if let Err(err) = result {
+        emit_progress(&app, "failed", Some(err.clone()));
+        return Err(err);
+    }
+
+    crate::push_log_line(
+        &app,
+        format!("managed Node.js v{} installed at {}", MANAGED_NODE_VERSION, target.display()),
+    );
+    emit_progress(&app, "done", Some(MANAGED_NODE_VERSION.to_string()));
+    Ok(get_managed_node_status(app))
+}
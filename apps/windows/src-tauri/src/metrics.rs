@@ -0,0 +1,254 @@
+// Host capacity metrics for the gateway dashboard.
+//
+// With `metrics_reporting` on, the app samples this machine every
+// `metrics_interval_secs` and sends the groups listed in `metrics_reported`
+// to the gateway as a `node.metrics` RPC:
+//
+//   cpu     overall usage and core count
+//   memory  total / used / available bytes
+//   disk    total and free bytes of the volume holding the home directory
+//   node    CPU and memory of the node host and everything it spawned
+//
+// Gateways that don't advertise `node.metrics` are skipped silently.
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use sysinfo::{Disks, Pid, ProcessesToUpdate, System};
+use tauri::{AppHandle, Manager};
+
+use crate::gateway::{self, GatewayState};
+use crate::AppState;
+
+const METHOD: &str = "node.metrics";
+const POLL_INTERVAL_SECS: u64 = 5;
+const MIN_INTERVAL_SECS: u64 = 10;
+pub const METRIC_GROUPS: &[&str] = &["cpu", "memory", "disk", "node"];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuMetrics {
+    usage_percent: f32,
+    cores: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MemoryMetrics {
+    total_bytes: u64,
+    used_bytes: u64,
+    available_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskMetrics {
+    mount_point: String,
+    total_bytes: u64,
+    free_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeProcessMetrics {
+    pid: u32,
+    /// Summed over the node host and its descendants; 100 = one full core.
+    cpu_percent: f32,
+    memory_bytes: u64,
+    process_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeMetrics {
+    ts_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cpu: Option<CpuMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    memory: Option<MemoryMetrics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    disk: Option<DiskMetrics>,
+    /// Absent when the group is off or no node host is running.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    node: Option<NodeProcessMetrics>,
+}
+
+struct Settings {
+    enabled: bool,
+    interval_secs: u64,
+    groups: Vec<String>,
+}
+
+fn settings(app: &AppHandle) -> Option<Settings> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().ok()?;
+    Some(Settings {
+        enabled: config.metrics_reporting,
+        interval_secs: config.metrics_interval_secs.max(MIN_INTERVAL_SECS),
+        groups: config.metrics_reported.clone(),
+    })
+}
+
+/// Pid of the node host the app runs or monitors, if any.
+fn node_pid(app: &AppHandle) -> Option<u32> {
+    let state = app.state::<AppState>();
+    let runtime = state.runtime.lock().ok()?;
+    runtime
+        .child
+        .as_ref()
+        .and_then(|child| child.id())
+        .or(runtime.adopted_pid)
+        .or(runtime.service_pid)
+}
+
+/// The volume holding `path`: the disk with the longest matching mount point.
+fn disk_for(path: &Path) -> Option<DiskMetrics> {
+    let disks = Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| DiskMetrics {
+            mount_point: disk.mount_point().to_string_lossy().to_string(),
+            total_bytes: disk.total_space(),
+            free_bytes: disk.available_space(),
+        })
+}
+
+fn node_usage(system: &System, pid: u32) -> Option<NodeProcessMetrics> {
+    let root = Pid::from_u32(pid);
+    system.process(root)?;
+    // Walk down from the node host; exec'd commands are its descendants
+    let mut tree: HashSet<Pid> = HashSet::from([root]);
+    loop {
+        let before = tree.len();
+        for (pid, process) in system.processes() {
+            if process
+                .parent()
+                .is_some_and(|parent| tree.contains(&parent))
+            {
+                tree.insert(*pid);
+            }
+        }
+        if tree.len() == before {
+            break;
+        }
+    }
+    let processes: Vec<_> = tree.iter().filter_map(|pid| system.process(*pid)).collect();
+    Some(NodeProcessMetrics {
+        pid,
+        cpu_percent: processes.iter().map(|process| process.cpu_usage()).sum(),
+        memory_bytes: processes.iter().map(|process| process.memory()).sum(),
+        process_count: processes.len(),
+    })
+}
+
+/// Refresh `system` and take a sample of the requested groups. CPU figures
+/// are averaged since the previous refresh of the same `System`.
+fn sample(app: &AppHandle, system: &mut System, groups: &[String]) -> NodeMetrics {
+    let wants = |group: &str| groups.iter().any(|name| name == group);
+    let node_pid = if wants("node") { node_pid(app) } else { None };
+    if wants("cpu") {
+        system.refresh_cpu_usage();
+    }
+    if wants("memory") {
+        system.refresh_memory();
+    }
+    if node_pid.is_some() {
+        system.refresh_processes(ProcessesToUpdate::All, true);
+    }
+    NodeMetrics {
+        ts_ms: crate::now_ms(),
+        cpu: wants("cpu").then(|| CpuMetrics {
+            usage_percent: system.global_cpu_usage(),
+            cores: system.cpus().len(),
+        }),
+        memory: wants("memory").then(|| MemoryMetrics {
+            total_bytes: system.total_memory(),
+            used_bytes: system.used_memory(),
+            available_bytes: system.available_memory(),
+        }),
+        disk: if wants("disk") {
+            directories::BaseDirs::new().and_then(|base| disk_for(base.home_dir()))
+        } else {
+            None
+        },
+        node: node_pid.and_then(|pid| node_usage(system, pid)),
+    }
+}
+
+async fn report(app: &AppHandle, gateway: &GatewayState, metrics: &NodeMetrics) {
+    let mut params = match serde_json::to_value(metrics) {
+        Ok(params) => params,
+        Err(_) => return,
+    };
+    params["instanceId"] = serde_json::json!(crate::get_device_id(app.clone()).ok());
+    params["platform"] = serde_json::json!(std::env::consts::OS);
+    match gateway::request(gateway, METHOD.to_string(), Some(params)).await {
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) | Err(err) => eprintln!("metrics report failed: {}", err),
+    }
+}
+
+/// Report on the configured interval while enabled and connected.
+pub async fn run_metrics_report(app: AppHandle) {
+    let gateway = app.state::<Arc<GatewayState>>().inner().clone();
+    let mut system = System::new();
+    let mut last_report: Option<std::time::Instant> = None;
+    let mut primed = false;
+    loop {
+        tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECS)).await;
+        let Some(settings) = settings(&app) else {
+            continue;
+        };
+        if !settings.enabled
+            || gateway.get_status().state != "connected"
+            || !gateway.supports(METHOD)
+        {
+            last_report = None;
+            primed = false;
+            continue;
+        }
+        if !primed {
+            // CPU usage is a difference between two refreshes; take the
+            // first one a poll ahead of the first report
+            sample(&app, &mut system, &settings.groups);
+            primed = true;
+            continue;
+        }
+        let due = last_report
+            .is_none_or(|at| at.elapsed() >= Duration::from_secs(settings.interval_secs));
+        if !due {
+            continue;
+        }
+        last_report = Some(std::time::Instant::now());
+        let metrics = sample(&app, &mut system, &settings.groups);
+        report(&app, &gateway, &metrics).await;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// A fresh sample of every metric group, as it would be reported.
+#[tauri::command]
+pub async fn get_node_metrics(app: AppHandle) -> Result<NodeMetrics, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut system = System::new();
+        // CPU usage is a difference between two refreshes
+        system.refresh_cpu_usage();
+        system.refresh_processes(ProcessesToUpdate::All, true);
+        std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        let groups: Vec<String> = METRIC_GROUPS
+            .iter()
+            .map(|group| group.to_string())
+            .collect();
+        sample(&app, &mut system, &groups)
+    })
+    .await
+    .map_err(|err| err.to_string())
+}
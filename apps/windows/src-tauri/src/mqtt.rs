@@ -0,0 +1,267 @@
+// Optional MQTT publishing of node client state.
+//
+// Smart-home setups (Home Assistant, Node-RED) want to show whether the
+// node host is up and react to approvals. With `mqtt_enabled` the app
+// connects to the configured broker and publishes, under `mqtt_topic_prefix`:
+//
+//   <prefix>/availability        "online" / "offline" (retained, last will)
+//   <prefix>/node                node status, e.g. "running" (retained)
+//   <prefix>/gateway             gateway connection state (retained)
+//   <prefix>/approvals/pending   number of pending approvals (retained)
+//   <prefix>/approvals/event     {"event": "pending" | "resolved", ...}
+//
+// With `mqtt_ha_discovery` it also publishes Home Assistant discovery
+// configs so the three states show up as sensors without YAML.
+
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS};
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, EventId, Listener, Manager};
+use tokio::sync::mpsc;
+
+use crate::gateway::GatewayState;
+use crate::AppState;
+
+const CONFIG_POLL_INTERVAL_SECS: u64 = 2;
+const STATE_POLL_INTERVAL_MS: u64 = 1_000;
+const KEEP_ALIVE_SECS: u64 = 30;
+const RECONNECT_DELAY_SECS: u64 = 5;
+const HA_DISCOVERY_PREFIX: &str = "homeassistant";
+
+/// Broker settings; the session is restarted whenever these change.
+#[derive(Debug, Clone, PartialEq)]
+struct MqttSettings {
+    host: String,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+    prefix: String,
+    ha_discovery: bool,
+}
+
+fn wanted(app: &AppHandle) -> Option<MqttSettings> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().ok()?;
+    if !config.mqtt_enabled {
+        return None;
+    }
+    let host = crate::non_empty(&config.mqtt_host)?.to_string();
+    let prefix = config.mqtt_topic_prefix.trim().trim_end_matches('/');
+    Some(MqttSettings {
+        host,
+        port: config.mqtt_port,
+        username: crate::non_empty(&config.mqtt_username).map(str::to_string),
+        password: crate::non_empty(&config.mqtt_password).map(str::to_string),
+        prefix: if prefix.is_empty() {
+            crate::default_mqtt_topic_prefix()
+        } else {
+            prefix.to_string()
+        },
+        ha_discovery: config.mqtt_ha_discovery,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Published {
+    node: String,
+    gateway: String,
+    pending: usize,
+}
+
+fn current_state(app: &AppHandle) -> Published {
+    let state = app.state::<AppState>();
+    let node = state.node_status.borrow().status.as_str().to_string();
+    let pending = state.pending_approvals.borrow().len();
+    Published {
+        node,
+        gateway: app.state::<Arc<GatewayState>>().get_status().state,
+        pending,
+    }
+}
+
+/// Stable id for discovery `unique_id`s, from the gateway device identity.
+fn device_id(app: &AppHandle) -> String {
+    crate::get_device_id(app.clone())
+        .map(|id| id.chars().take(12).collect())
+        .unwrap_or_else(|_| "node".to_string())
+}
+
+fn publish_discovery(client: &AsyncClient, settings: &MqttSettings, device: &str) {
+    let device_info = json!({
+        "identifiers": [format!("openclaw_node_client_{}", device)],
+        "name": "OpenClaw Node Client",
+        "manufacturer": "OpenClaw",
+        "sw_version": env!("CARGO_PKG_VERSION"),
+    });
+    let sensors = [
+        ("node", "Node status", "mdi:server"),
+        ("gateway", "Gateway", "mdi:lan-connect"),
+        ("approvals/pending", "Pending approvals", "mdi:shield-alert"),
+    ];
+    for (topic, name, icon) in sensors {
+        let object_id = topic.replace('/', "_");
+        let config = json!({
+            "name": name,
+            "icon": icon,
+            "unique_id": format!("openclaw_{}_{}", device, object_id),
+            "state_topic": format!("{}/{}", settings.prefix, topic),
+            "availability_topic": format!("{}/availability", settings.prefix),
+            "device": device_info,
+        });
+        let _ = client.try_publish(
+            format!(
+                "{}/sensor/openclaw_{}/{}/config",
+                HA_DISCOVERY_PREFIX, device, object_id
+            ),
+            QoS::AtLeastOnce,
+            true,
+            config.to_string(),
+        );
+    }
+}
+
+/// Removes the approval event listeners when the session ends or is aborted.
+struct Listeners {
+    app: AppHandle,
+    ids: Vec<EventId>,
+}
+
+impl Drop for Listeners {
+    fn drop(&mut self) {
+        for id in self.ids.drain(..) {
+            self.app.unlisten(id);
+        }
+    }
+}
+
+/// One broker connection: publish state changes and approval events until
+/// the task is aborted. Dropping the connection without a DISCONNECT makes
+/// the broker publish the "offline" last will.
+async fn run_session(app: AppHandle, settings: MqttSettings) {
+    let device = device_id(&app);
+    let availability = format!("{}/availability", settings.prefix);
+    let broker = format!("{}:{}", settings.host, settings.port);
+    let mut options = MqttOptions::new(
+        format!("openclaw-node-client-{}", device),
+        settings.host.clone(),
+        settings.port,
+    );
+    options.set_keep_alive(Duration::from_secs(KEEP_ALIVE_SECS));
+    options.set_last_will(LastWill::new(
+        &availability,
+        "offline",
+        QoS::AtLeastOnce,
+        true,
+    ));
+    if let Some(username) = &settings.username {
+        options.set_credentials(username, settings.password.clone().unwrap_or_default());
+    }
+    let (client, mut eventloop) = AsyncClient::new(options, 32);
+
+    let (events_tx, mut events_rx) = mpsc::unbounded_channel::<Value>();
+    let _listeners = Listeners {
+        app: app.clone(),
+        ids: ["approval-pending", "approval-resolved"]
+            .into_iter()
+            .map(|name| {
+                let tx = events_tx.clone();
+                let kind = name.trim_start_matches("approval-");
+                app.listen(name, move |event| {
+                    let payload =
+                        serde_json::from_str::<Value>(event.payload()).unwrap_or(Value::Null);
+                    let _ = tx.send(json!({ "event": kind, "approval": payload }));
+                })
+            })
+            .collect(),
+    };
+
+    let mut tick = tokio::time::interval(Duration::from_millis(STATE_POLL_INTERVAL_MS));
+    let mut shown: Option<Published> = None;
+    let mut failing = false;
+    loop {
+        tokio::select! {
+            polled = eventloop.poll() => match polled {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    failing = false;
+                    crate::push_log_line(&app, format!("MQTT connected to {}", broker));
+                    // Retained state may have been replaced by the last will
+                    let _ = client.try_publish(&availability, QoS::AtLeastOnce, true, "online");
+                    if settings.ha_discovery {
+                        publish_discovery(&client, &settings, &device);
+                    }
+                    shown = None;
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    // Log once per outage; the next poll reconnects
+                    if !failing {
+                        crate::push_log_line(
+                            &app,
+                            format!("MQTT connection to {} failed: {}", broker, err),
+                        );
+                        failing = true;
+                    }
+                    tokio::time::sleep(Duration::from_secs(RECONNECT_DELAY_SECS)).await;
+                }
+            },
+            Some(event) = events_rx.recv() => {
+                let _ = client.try_publish(
+                    format!("{}/approvals/event", settings.prefix),
+                    QoS::AtLeastOnce,
+                    false,
+                    event.to_string(),
+                );
+            }
+            _ = tick.tick() => {
+                if failing {
+                    continue;
+                }
+                let next = current_state(&app);
+                let topics = [
+                    ("node", &next.node, shown.as_ref().map(|s| &s.node)),
+                    ("gateway", &next.gateway, shown.as_ref().map(|s| &s.gateway)),
+                ];
+                for (topic, value, before) in topics {
+                    if before != Some(value) {
+                        let _ = client.try_publish(
+                            format!("{}/{}", settings.prefix, topic),
+                            QoS::AtLeastOnce,
+                            true,
+                            value.as_str(),
+                        );
+                    }
+                }
+                if shown.as_ref().map(|s| s.pending) != Some(next.pending) {
+                    let _ = client.try_publish(
+                        format!("{}/approvals/pending", settings.prefix),
+                        QoS::AtLeastOnce,
+                        true,
+                        next.pending.to_string(),
+                    );
+                }
+                shown = Some(next);
+            }
+        }
+    }
+}
+
+/// Connect, reconnect or disconnect as the MQTT settings change.
+pub async fn run_mqtt(app: AppHandle) {
+    let mut running: Option<(MqttSettings, JoinHandle<()>)> = None;
+    loop {
+        let next = wanted(&app);
+        if running.as_ref().map(|(settings, _)| settings) != next.as_ref() {
+            if let Some((_, handle)) = running.take() {
+                handle.abort();
+            }
+            if let Some(settings) = next {
+                let handle =
+                    tauri::async_runtime::spawn(run_session(app.clone(), settings.clone()));
+                running = Some((settings, handle));
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(CONFIG_POLL_INTERVAL_SECS)).await;
+    }
+}
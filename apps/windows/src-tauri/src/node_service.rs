@@ -0,0 +1,282 @@
+// OS service integration for the node host.
+//
+// `openclaw node install` already knows how to register the node host as a
+// systemd user unit, launchd agent, or Windows scheduled task, so the app
+// drives the CLI (`--json`) instead of writing service definitions itself.
+// While a service is installed the app stops spawning its own child and only
+// monitors the externally-managed process.
+
+use serde::Serialize;
+use std::process::Stdio;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+use crate::{AppState, NodeStatus};
+
+const SERVICE_POLL_INTERVAL_SECS: u64 = 30;
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeServiceStatus {
+    installed: bool,
+    running: bool,
+    label: Option<String>,
+    pid: Option<u32>,
+    detail: Option<String>,
+}
+
+/// Run `openclaw node <args> --json` and parse the JSON object it prints.
+fn run_node_cli(app: &AppHandle, args: &[String]) -> Result<serde_json::Value, String> {
+    let config = {
+        let state = app.state::<AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    let (openclaw_bin, _, _) = crate::resolve_openclaw_bin(&config, app)?;
+    let mut command = crate::openclaw_command(&openclaw_bin)?;
+
+    #[cfg(target_os = "linux")]
+    crate::sanitize_appimage_env(&mut command);
+
+    command
+        .arg("node")
+        .args(args)
+        .arg("--json")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    if let Some(ref token) = config.gateway_token {
+        if !token.is_empty() {
+            command.env("OPENCLAW_GATEWAY_TOKEN", token);
+        }
+    }
+    if let Some(ref password) = config.gateway_password {
+        if !password.is_empty() {
+            command.env("OPENCLAW_GATEWAY_PASSWORD", password);
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        command.creation_flags(crate::CREATE_NO_WINDOW);
+    }
+
+    let subcommand = args.first().map(String::as_str).unwrap_or_default();
+    let output = command
+        .output()
+        .map_err(|err| format!("failed to run `openclaw node {}`: {}", subcommand, err))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Warnings can precede the JSON payload
+    let parsed = stdout
+        .find('{')
+        .and_then(|start| serde_json::from_str::<serde_json::Value>(&stdout[start..]).ok());
+    match parsed {
+        Some(value) if output.status.success() => Ok(value),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let message = parsed
+                .as_ref()
+                .and_then(|value| value.get("error").or_else(|| value.get("message")))
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| stderr.trim().to_string());
+            Err(format!(
+                "`openclaw node {}` failed ({}): {}",
+                subcommand, output.status, message
+            ))
+        }
+    }
+}
+
+fn query_service_status(app: &AppHandle) -> Result<NodeServiceStatus, String> {
+    let payload = run_node_cli(app, &["status".to_string()])?;
+    let service = payload.get("service").cloned().unwrap_or_default();
+    let runtime = service.get("runtime").cloned().unwrap_or_default();
+    let pid = runtime
+        .get("pid")
+        .and_then(|value| value.as_u64())
+        .map(|pid| pid as u32);
+    let runtime_status = runtime.get("status").and_then(|value| value.as_str());
+    Ok(NodeServiceStatus {
+        installed: service
+            .get("loaded")
+            .and_then(|value| value.as_bool())
+            .unwrap_or(false),
+        running: runtime_status == Some("running") || pid.is_some_and(crate::process_alive),
+        label: service
+            .get("label")
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
+        pid,
+        detail: runtime
+            .get("detail")
+            .and_then(|value| value.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// Record the service state in `RuntimeState` and emit a status change when
+/// the externally-managed node starts or stops.
+fn apply_service_status(app: &AppHandle, status: &NodeServiceStatus) {
+    let changed = {
+        let state = app.state::<AppState>();
+        let Ok(mut runtime) = state.runtime.lock() else {
+            return;
+        };
+        let was_running = runtime.service_managed && runtime.service_pid.is_some();
+        runtime.service_managed = status.installed;
+        runtime.service_pid = if status.running { status.pid } else { None };
+        if status.installed && runtime.child.is_none() {
+            runtime.node_status = Some(if status.running {
+                NodeStatus::Running
+            } else {
+                NodeStatus::Stopped
+            });
+        }
+        runtime.publish_status();
+        status.installed && was_running != runtime.service_pid.is_some()
+    };
+    if changed {
+        let node_status = if status.running {
+            NodeStatus::Running
+        } else {
+            NodeStatus::Stopped
+        };
+        let _ = app.emit("node-status-changed", node_status.as_str());
+    }
+}
+
+/// Check for an installed service and keep polling it in the background.
+/// Returns whether the node host is managed by an OS service, so startup can
+/// skip spawning its own child.
+pub fn detect_and_monitor(app: &AppHandle) -> bool {
+    let managed = match query_service_status(app) {
+        Ok(status) => {
+            apply_service_status(app, &status);
+            if status.installed {
+                crate::push_log_line(
+                    app,
+                    format!(
+                        "node host is managed by OS service {}; monitoring only",
+                        status.label.as_deref().unwrap_or("openclaw-node")
+                    ),
+                );
+            }
+            status.installed
+        }
+        Err(_) => false,
+    };
+
+    let app = app.clone();
+    std::thread::spawn(move || loop {
+        std::thread::sleep(Duration::from_secs(SERVICE_POLL_INTERVAL_SECS));
+        let managed = {
+            let state = app.state::<AppState>();
+            let managed = state
+                .runtime
+                .lock()
+                .map(|runtime| runtime.service_managed)
+                .unwrap_or(false);
+            managed
+        };
+        if managed {
+            if let Ok(status) = query_service_status(&app) {
+                apply_service_status(&app, &status);
+            }
+        }
+    });
+
+    managed
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn get_node_service_status(app: AppHandle) -> Result<NodeServiceStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let status = query_service_status(&app)?;
+        apply_service_status(&app, &status);
+        Ok(status)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub async fn install_node_service(app: AppHandle) -> Result<NodeServiceStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let config = {
+            let state = app.state::<AppState>();
+            let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+            cfg
+        };
+        // The service takes over from the app-owned child
+        let service_managed = {
+            let state = app.state::<AppState>();
+            let managed = state
+                .runtime
+                .lock()
+                .map(|runtime| runtime.service_managed)
+                .unwrap_or(false);
+            managed
+        };
+        if !service_managed {
+            crate::stop_node_internal(&app)?;
+        }
+
+        let mut args = vec![
+            "install".to_string(),
+            "--force".to_string(),
+            "--host".to_string(),
+            config.host.clone(),
+            "--port".to_string(),
+            config.port.to_string(),
+        ];
+        if config.tls {
+            args.push("--tls".to_string());
+        }
+        let optional = [
+            ("--tls-fingerprint", &config.tls_fingerprint),
+            ("--node-id", &config.node_id),
+            ("--display-name", &config.display_name),
+        ];
+        for (flag, value) in optional {
+            if let Some(value) = value.as_deref().map(str::trim).filter(|v| !v.is_empty()) {
+                args.push(flag.to_string());
+                args.push(value.to_string());
+            }
+        }
+        run_node_cli(&app, &args)?;
+
+        let status = query_service_status(&app)?;
+        apply_service_status(&app, &status);
+        crate::push_log_line(
+            &app,
+            format!(
+                "installed node host service {}",
+                status.label.as_deref().unwrap_or("openclaw-node")
+            ),
+        );
+        Ok(status)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+#[tauri::command]
+pub async fn uninstall_node_service(app: AppHandle) -> Result<NodeServiceStatus, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        run_node_cli(&app, &["uninstall".to_string()])?;
+        let status = query_service_status(&app).unwrap_or_default();
+        apply_service_status(&app, &status);
+        crate::push_log_line(&app, "uninstalled node host service");
+        Ok(status)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
@@ -0,0 +1,636 @@
+// Slack, Discord and mobile push notifications.
+//
+// Posts approval-pending, approval-resolved and node-crashed events to a
+// Slack incoming webhook and/or a Discord webhook, formatted for each:
+// Slack gets mrkdwn with `&<>` escaped, Discord gets plain markdown with
+// mentions disabled, and both are cut to the service's message limit.
+//
+// Pending approvals are also pushed to phones through an ntfy topic and/or
+// Pushover, as plain text at high priority with a link to where the
+// approval can be handled: `push_approval_link` with `{id}` filled in, or
+// the gateway's Control UI when that isn't set (and the gateway isn't on
+// loopback, which a phone can't reach).
+//
+// Messages come from a template per event (`notifier_templates` overrides
+// the defaults below) with `{placeholder}` values filled from the event.
+// Commands leave the machine, so configured secrets and credential-looking
+// arguments in them are masked first.
+// Each webhook is rate limited: at most one post every
+// `MIN_SPACING_MILLIS` and `MAX_PER_MINUTE` a minute; anything over that is
+// dropped and counted in the next message. A 429 is retried once after the
+// service's Retry-After.
+
+use serde_json::{json, Value};
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Listener, Manager};
+use tokio::sync::mpsc;
+
+use crate::AppState;
+
+/// Events a notification can be sent for.
+pub const NOTIFIER_EVENTS: &[&str] = &["approval-pending", "approval-resolved", "node-crashed"];
+
+const DEFAULT_TEMPLATES: &[(&str, &str)] = &[
+    (
+        "approval-pending",
+        "Approval needed on {host}: {command}\nAgent: {agent} · Directory: {cwd}",
+    ),
+    (
+        "approval-resolved",
+        "Approval on {host} {decision}: {command}",
+    ),
+    (
+        "node-crashed",
+        "Node host on {host} went down ({exitStatus})\n{logTail}",
+    ),
+];
+
+const MIN_SPACING_MILLIS: u64 = 1_000;
+const MAX_PER_MINUTE: usize = 20;
+const LOG_TAIL_LINES: usize = 5;
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+const CONNECT_TIMEOUT_SECS: u64 = 5;
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+const PUSHOVER_API: &str = "https://api.pushover.net/1/messages.json";
+const PUSH_TITLE: &str = "OpenClaw approval needed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Service {
+    Slack,
+    Discord,
+    Ntfy,
+    Pushover,
+}
+
+impl Service {
+    fn name(self) -> &'static str {
+        match self {
+            Service::Slack => "Slack",
+            Service::Discord => "Discord",
+            Service::Ntfy => "ntfy",
+            Service::Pushover => "Pushover",
+        }
+    }
+
+    /// Push services only get pending approvals.
+    fn is_push(self) -> bool {
+        matches!(self, Service::Ntfy | Service::Pushover)
+    }
+
+    /// Longest text the service accepts in one message.
+    fn max_chars(self) -> usize {
+        match self {
+            Service::Slack => 3_000,
+            Service::Discord => 2_000,
+            Service::Ntfy => 4_000,
+            Service::Pushover => 1_024,
+        }
+    }
+
+    fn escape(self, text: &str) -> String {
+        match self {
+            Service::Slack => text
+                .replace('&', "&amp;")
+                .replace('<', "&lt;")
+                .replace('>', "&gt;"),
+            Service::Discord | Service::Ntfy | Service::Pushover => text.to_string(),
+        }
+    }
+
+    /// Inline code, or a code block for multi-line text. Push messages are
+    /// shown as plain text, so they get the text as is.
+    fn code(self, text: &str) -> String {
+        if self.is_push() {
+            return text.trim_end().to_string();
+        }
+        let text = self.escape(&text.replace('`', "'"));
+        if text.contains('\n') {
+            format!("```\n{}\n```", text.trim_end())
+        } else {
+            format!("`{}`", text)
+        }
+    }
+
+    fn body(self, endpoint: &Endpoint, text: &str, link: Option<&str>) -> Value {
+        match self {
+            Service::Slack => json!({
+                "text": text,
+                "blocks": [{ "type": "section", "text": { "type": "mrkdwn", "text": text } }],
+            }),
+            Service::Discord => json!({
+                "content": text,
+                "allowed_mentions": { "parse": [] },
+            }),
+            Service::Ntfy => {
+                let mut body = json!({
+                    "topic": endpoint.topic,
+                    "title": PUSH_TITLE,
+                    "message": text,
+                    "priority": 4,
+                    "tags": ["lock"],
+                });
+                if let Some(link) = link {
+                    body["click"] = json!(link);
+                    body["actions"] = json!([{ "action": "view", "label": "Review", "url": link }]);
+                }
+                body
+            }
+            Service::Pushover => {
+                let mut body = json!({
+                    "token": endpoint.token,
+                    "user": endpoint.user,
+                    "title": PUSH_TITLE,
+                    "message": text,
+                    "priority": 1,
+                });
+                if let Some(link) = link {
+                    body["url"] = json!(link);
+                    body["url_title"] = json!("Review approval");
+                }
+                body
+            }
+        }
+    }
+}
+
+/// Where one service's messages go.
+struct Endpoint {
+    service: Service,
+    url: String,
+    /// ntfy topic; the URL is then the server's publish endpoint.
+    topic: Option<String>,
+    /// ntfy access token or Pushover application token.
+    token: Option<String>,
+    /// Pushover user or group key.
+    user: Option<String>,
+}
+
+impl Endpoint {
+    fn webhook(service: Service, url: &str) -> Self {
+        Endpoint {
+            service,
+            url: url.to_string(),
+            topic: None,
+            token: None,
+            user: None,
+        }
+    }
+
+    /// `topic_url` is the full topic URL, e.g. `https://ntfy.sh/my-topic`;
+    /// JSON is published to the server root with the topic in the body.
+    fn ntfy(topic_url: &str, token: Option<&str>) -> Option<Self> {
+        let (server, topic) = topic_url.trim().trim_end_matches('/').rsplit_once('/')?;
+        if topic.is_empty() || !server.contains("://") || server.ends_with('/') {
+            return None;
+        }
+        Some(Endpoint {
+            service: Service::Ntfy,
+            url: server.to_string(),
+            topic: Some(topic.to_string()),
+            token: token.map(str::to_string),
+            user: None,
+        })
+    }
+
+    fn pushover(user: &str, token: &str) -> Self {
+        Endpoint {
+            service: Service::Pushover,
+            url: PUSHOVER_API.to_string(),
+            topic: None,
+            token: Some(token.to_string()),
+            user: Some(user.to_string()),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Limiter {
+    sent: VecDeque<Instant>,
+    suppressed: usize,
+}
+
+impl Limiter {
+    /// Whether a post is allowed now; counts it as suppressed otherwise.
+    fn admit(&mut self) -> bool {
+        let now = Instant::now();
+        while self
+            .sent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= Duration::from_secs(60))
+        {
+            self.sent.pop_front();
+        }
+        if self.sent.len() >= MAX_PER_MINUTE {
+            self.suppressed += 1;
+            return false;
+        }
+        true
+    }
+
+    async fn wait_for_spacing(&self) {
+        if let Some(last) = self.sent.back() {
+            let spacing = Duration::from_millis(MIN_SPACING_MILLIS);
+            let elapsed = last.elapsed();
+            if elapsed < spacing {
+                tokio::time::sleep(spacing - elapsed).await;
+            }
+        }
+    }
+}
+
+struct Targets {
+    endpoints: Vec<Endpoint>,
+    events: Vec<String>,
+    templates: HashMap<String, String>,
+    /// Approval link with `{id}` still to fill in.
+    approval_link: Option<String>,
+}
+
+fn targets(app: &AppHandle) -> Option<Targets> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().ok()?;
+    let mut endpoints: Vec<Endpoint> = [
+        (Service::Slack, &config.slack_webhook_url),
+        (Service::Discord, &config.discord_webhook_url),
+    ]
+    .into_iter()
+    .filter_map(|(service, url)| crate::non_empty(url).map(|url| Endpoint::webhook(service, url)))
+    .collect();
+    if let Some(topic_url) = crate::non_empty(&config.ntfy_topic_url) {
+        match Endpoint::ntfy(topic_url, crate::non_empty(&config.ntfy_token)) {
+            Some(endpoint) => endpoints.push(endpoint),
+            None => eprintln!("ntfy topic URL {} has no topic", topic_url),
+        }
+    }
+    if let (Some(user), Some(token)) = (
+        crate::non_empty(&config.pushover_user_key),
+        crate::non_empty(&config.pushover_app_token),
+    ) {
+        endpoints.push(Endpoint::pushover(user, token));
+    }
+    if endpoints.is_empty() {
+        return None;
+    }
+    let approval_link = match crate::non_empty(&config.push_approval_link) {
+        Some(link) => Some(link.to_string()),
+        None if is_loopback(&config.host) => None,
+        None => Some(format!(
+            "{}://{}:{}/",
+            if config.tls { "https" } else { "http" },
+            config.host,
+            config.port
+        )),
+    };
+    Some(Targets {
+        endpoints,
+        events: config.notifier_events.clone(),
+        templates: config.notifier_templates.clone(),
+        approval_link,
+    })
+}
+
+fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_matches(|c| c == '[' || c == ']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// `link` with the approval id filled in.
+fn approval_link(link: &str, payload: &Value) -> Option<String> {
+    if !link.contains("{id}") {
+        return Some(link.to_string());
+    }
+    let id = field(payload, "id")?;
+    Some(link.replace(
+        "{id}",
+        &url::form_urlencoded::byte_serialize(id.as_bytes()).collect::<String>(),
+    ))
+}
+
+fn template<'a>(templates: &'a HashMap<String, String>, event: &str) -> &'a str {
+    templates
+        .get(event)
+        .map(String::as_str)
+        .filter(|template| !template.trim().is_empty())
+        .or_else(|| {
+            DEFAULT_TEMPLATES
+                .iter()
+                .find(|(name, _)| *name == event)
+                .map(|(_, template)| *template)
+        })
+        .unwrap_or("{event} on {host}")
+}
+
+fn field(payload: &Value, key: &str) -> Option<String> {
+    match payload.get(key)? {
+        Value::String(text) if !text.is_empty() => Some(text.clone()),
+        Value::Null | Value::String(_) => None,
+        other => Some(other.to_string()),
+    }
+}
+
+/// Placeholder values for `event`, formatted for `service`. `command` is
+/// the approval's already redacted command.
+fn values(
+    service: Service,
+    event: &str,
+    payload: &Value,
+    host: &str,
+    command: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let missing = || "—".to_string();
+    let text = |key: &str| {
+        field(payload, key)
+            .map(|value| service.escape(&value))
+            .unwrap_or_else(missing)
+    };
+    let mut values = vec![("event", event.to_string()), ("host", service.escape(host))];
+    match event {
+        "approval-pending" | "approval-resolved" => {
+            values.extend([
+                ("id", text("id")),
+                (
+                    "command",
+                    command
+                        .map(|command| service.code(command))
+                        .unwrap_or_else(missing),
+                ),
+                ("agent", text("agentId")),
+                ("cwd", text("cwd")),
+                ("decision", text("decision")),
+            ]);
+        }
+        "node-crashed" => {
+            let tail: Vec<&str> = payload
+                .get("logTail")
+                .and_then(Value::as_array)
+                .map(|lines| lines.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let tail = &tail[tail.len().saturating_sub(LOG_TAIL_LINES)..];
+            values.extend([
+                ("exitStatus", text("exitStatus")),
+                ("exitCode", text("exitCode")),
+                (
+                    "logTail",
+                    if tail.is_empty() {
+                        String::new()
+                    } else {
+                        service.code(&format!("{}\n", tail.join("\n")))
+                    },
+                ),
+            ]);
+        }
+        _ => {}
+    }
+    values
+}
+
+/// The approval's command with `secrets` and credential-looking
+/// assignments masked.
+fn approval_command(payload: &Value, secrets: &[String]) -> Option<String> {
+    let command = field(payload, "rawCommand").or_else(|| {
+        let argv: Vec<&str> = payload
+            .get("argv")?
+            .as_array()?
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        (!argv.is_empty()).then(|| argv.join(" "))
+    })?;
+    Some(crate::redact_credential_patterns(&crate::redact_secrets(
+        &command, secrets,
+    )))
+}
+
+fn secrets(app: &AppHandle) -> Vec<String> {
+    let state = app.state::<AppState>();
+    let mut secrets = vec![state.exec_host_token.clone(), state.control_token.clone()];
+    if let Ok(config) = state.config.lock() {
+        secrets.extend(crate::config_secrets(&config));
+    }
+    secrets
+}
+
+fn render(
+    service: Service,
+    template: &str,
+    values: &[(&str, String)],
+    suppressed: usize,
+) -> String {
+    // One pass over the template, so placeholders inside values stay as-is
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            values
+                .iter()
+                .find(|(key, _)| *key == &after[..end])
+                .map(|(_, value)| (value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                text.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    if suppressed > 0 {
+        text.push_str(&format!(
+            "\n_({} earlier notification{} dropped by rate limiting)_",
+            suppressed,
+            if suppressed == 1 { "" } else { "s" }
+        ));
+    }
+    if text.chars().count() > service.max_chars() {
+        text = text.chars().take(service.max_chars() - 1).collect();
+        text.push('…');
+    }
+    text
+}
+
+async fn post(
+    client: &reqwest::Client,
+    endpoint: &Endpoint,
+    text: &str,
+    link: Option<&str>,
+) -> Result<(), String> {
+    let body = endpoint.service.body(endpoint, text, link);
+    for attempt in 0..2 {
+        let mut request = client.post(&endpoint.url).json(&body);
+        if endpoint.service == Service::Ntfy {
+            if let Some(token) = &endpoint.token {
+                request = request.bearer_auth(token);
+            }
+        }
+        let response = request.send().await.map_err(|err| err.to_string())?;
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS && attempt == 0 {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .unwrap_or(1.0)
+                .clamp(0.0, MAX_RETRY_AFTER_SECS as f64);
+            tokio::time::sleep(Duration::from_secs_f64(retry_after)).await;
+            continue;
+        }
+        return response
+            .error_for_status()
+            .map(|_| ())
+            .map_err(|err| err.to_string());
+    }
+    Err("rate limited".to_string())
+}
+
+/// HTTP client for webhook and push posts. A service that stops answering
+/// mustn't stall the notification queue.
+fn http_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(CONNECT_TIMEOUT_SECS))
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .unwrap_or_default()
+}
+
+/// Post notifications one at a time so rate limits hold across events.
+async fn run_notifier(app: AppHandle, mut events: mpsc::UnboundedReceiver<(String, Value)>) {
+    let client = http_client();
+    let mut limiters: HashMap<Service, Limiter> = HashMap::new();
+    let mut commands: HashMap<String, String> = HashMap::new();
+    while let Some((event, payload)) = events.recv().await {
+        // Resolutions name the command remembered from the pending event
+        let command = if event.starts_with("approval-") {
+            field(&payload, "id")
+                .and_then(|id| commands.get(&id).cloned())
+                .or_else(|| approval_command(&payload, &secrets(&app)))
+        } else {
+            None
+        };
+        if event == "approval-pending" {
+            if let (Some(id), Some(command)) = (field(&payload, "id"), &command) {
+                commands.insert(id, command.clone());
+            }
+        }
+        let targets = targets(&app).filter(|targets| targets.events.contains(&event));
+        if let Some(targets) = targets {
+            let host = crate::gateway_report::host_label(&app);
+            let link = targets
+                .approval_link
+                .as_deref()
+                .and_then(|link| approval_link(link, &payload));
+            for endpoint in &targets.endpoints {
+                let service = endpoint.service;
+                if service.is_push() && event != "approval-pending" {
+                    continue;
+                }
+                let limiter = limiters.entry(service).or_default();
+                if !limiter.admit() {
+                    continue;
+                }
+                limiter.wait_for_spacing().await;
+                let values = values(service, &event, &payload, &host, command.as_deref());
+                let text = render(
+                    service,
+                    template(&targets.templates, &event),
+                    &values,
+                    limiter.suppressed,
+                );
+                limiter.sent.push_back(Instant::now());
+                let link = link.as_deref().filter(|_| service.is_push());
+                match post(&client, endpoint, &text, link).await {
+                    Ok(()) => limiter.suppressed = 0,
+                    Err(err) => crate::push_log_line(
+                        &app,
+                        format!("{} notification failed: {}", service.name(), err),
+                    ),
+                }
+            }
+        }
+        if event == "approval-resolved" {
+            if let Some(id) = field(&payload, "id") {
+                commands.remove(&id);
+            }
+        }
+    }
+}
+
+/// Listen for the notifiable events and start the posting task.
+pub fn register(app: &AppHandle) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    for &event in NOTIFIER_EVENTS {
+        let tx = tx.clone();
+        app.listen(event, move |fired| {
+            let payload = serde_json::from_str::<Value>(fired.payload()).unwrap_or(Value::Null);
+            let _ = tx.send((event.to_string(), payload));
+        });
+    }
+    tauri::async_runtime::spawn(run_notifier(app.clone(), rx));
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Send a test message to the configured `service` ("slack", "discord",
+/// "ntfy" or "pushover").
+#[tauri::command]
+pub async fn test_notifier(app: AppHandle, service: String) -> Result<(), String> {
+    let service = match service.as_str() {
+        "slack" => Service::Slack,
+        "discord" => Service::Discord,
+        "ntfy" => Service::Ntfy,
+        "pushover" => Service::Pushover,
+        other => return Err(format!("unknown notifier {}", other)),
+    };
+    let (endpoint, link) = targets(&app)
+        .and_then(|targets| {
+            let link = targets.approval_link;
+            targets
+                .endpoints
+                .into_iter()
+                .find(|endpoint| endpoint.service == service)
+                .map(|endpoint| (endpoint, link))
+        })
+        .ok_or_else(|| format!("{} is not configured", service.name()))?;
+    let host = crate::gateway_report::host_label(&app);
+    let text = format!(
+        "Test notification from the OpenClaw node client on {}",
+        service.escape(&host)
+    );
+    let link = link
+        .map(|link| link.replace("{id}", "test"))
+        .filter(|_| service.is_push());
+    post(&http_client(), &endpoint, &text, link.as_deref()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masks_secrets_in_approval_commands() {
+        let secrets = vec!["gw-secret-token-123".to_string()];
+        let payload = json!({
+            "rawCommand": "curl -H 'x: gw-secret-token-123' https://example.com --data password=hunter2"
+        });
+        assert_eq!(
+            approval_command(&payload, &secrets).as_deref(),
+            Some("curl -H 'x: ***' https://example.com --data password=***")
+        );
+        let payload = json!({ "argv": ["deploy", "--api-key=abc123"] });
+        assert_eq!(
+            approval_command(&payload, &secrets).as_deref(),
+            Some("deploy --api-key=***")
+        );
+    }
+}
@@ -0,0 +1,233 @@
+// Keep gateway settings in step with the CLI's openclaw.json.
+//
+// The app imports the gateway section of openclaw.json once; after that the
+// two drift. With `syncOpenclawConfig` enabled, saving a change to the port,
+// TLS or credentials in the app updates the matching keys in openclaw.json.
+// Only keys the user changed are written, and credentials held as SecretRefs
+// in openclaw.json are never replaced with plaintext.
+//
+// In the other direction, edits made by the CLI are detected by the config
+// watcher and offered to the user through `openclaw-config-updated` rather
+// than applied silently.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{AppState, ConfigChangeImpact, NodeClientConfig};
+
+/// Config keys (camelCase, as serialized) mirrored into openclaw.json.
+const SYNCED_KEYS: &[&str] = &[
+    "port",
+    "tls",
+    "gatewayToken",
+    "gatewayPassword",
+    "tlsFingerprint",
+];
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenclawConfigUpdate {
+    changed_fields: Vec<String>,
+    /// New values from openclaw.json, keyed like `NodeClientConfig`, with
+    /// credentials masked.
+    values: Map<String, Value>,
+    #[serde(skip)]
+    unmasked: Map<String, Value>,
+}
+
+pub fn openclaw_config_path() -> Result<PathBuf, String> {
+    Ok(crate::openclaw_dir()?.join("openclaw.json"))
+}
+
+/// Walk to (creating as needed) the object at `path` under `root`. Returns
+/// `None` when a key on the way holds something other than an object.
+fn object_at<'a>(
+    root: &'a mut Map<String, Value>,
+    path: &[&str],
+) -> Option<&'a mut Map<String, Value>> {
+    let mut current = root;
+    for key in path {
+        current = current
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()?;
+    }
+    Some(current)
+}
+
+/// Set a credential unless openclaw.json holds a SecretRef for it.
+fn set_secret(parent: &mut Map<String, Value>, key: &str, value: Option<&str>) -> bool {
+    if parent.get(key).is_some_and(Value::is_object) {
+        return false;
+    }
+    match value {
+        Some(secret) => parent.insert(key.to_string(), Value::String(secret.to_string())),
+        None => parent.remove(key),
+    };
+    true
+}
+
+/// Mirror the changed gateway settings into openclaw.json. Returns the keys
+/// written; an empty list when syncing is off, nothing relevant changed, or
+/// openclaw.json doesn't exist.
+pub fn sync_gateway_settings(
+    config: &NodeClientConfig,
+    impact: &ConfigChangeImpact,
+) -> Result<Vec<String>, String> {
+    if !config.sync_openclaw_config {
+        return Ok(Vec::new());
+    }
+    // Turning sync on pushes the current values once
+    let just_enabled = impact
+        .changed_fields
+        .iter()
+        .any(|field| field == "syncOpenclawConfig");
+    let keys: Vec<&str> = SYNCED_KEYS
+        .iter()
+        .copied()
+        .filter(|key| just_enabled || impact.changed_fields.iter().any(|field| field == key))
+        .collect();
+    if keys.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let path = openclaw_config_path()?;
+    let Ok(raw) = fs::read_to_string(&path) else {
+        return Ok(Vec::new());
+    };
+    let mut root: Map<String, Value> = serde_json5::from_str(&raw)
+        .map_err(|err| format!("cannot update {}: {}", path.display(), err))?;
+
+    let mut written = Vec::new();
+    for key in keys {
+        let applied = match key {
+            "port" => object_at(&mut root, &["gateway"])
+                .map(|gateway| gateway.insert("port".into(), config.port.into()))
+                .is_some(),
+            "tls" => object_at(&mut root, &["gateway", "tls"])
+                .map(|tls| tls.insert("enabled".into(), config.tls.into()))
+                .is_some(),
+            "gatewayToken" => object_at(&mut root, &["gateway", "auth"]).is_some_and(|auth| {
+                set_secret(auth, "token", crate::non_empty(&config.gateway_token))
+            }),
+            "gatewayPassword" => object_at(&mut root, &["gateway", "auth"]).is_some_and(|auth| {
+                set_secret(auth, "password", crate::non_empty(&config.gateway_password))
+            }),
+            "tlsFingerprint" => object_at(&mut root, &["gateway", "remote"])
+                .map(|remote| match crate::non_empty(&config.tls_fingerprint) {
+                    Some(fp) => remote.insert("tlsFingerprint".into(), fp.into()),
+                    None => remote.remove("tlsFingerprint"),
+                })
+                .is_some(),
+            _ => false,
+        };
+        if applied {
+            written.push(key.to_string());
+        }
+    }
+    if written.is_empty() {
+        return Ok(written);
+    }
+
+    let payload = serde_json::to_string_pretty(&root).map_err(|err| err.to_string())?;
+    // Same backup name the CLI uses for its own writes
+    let _ = fs::copy(&path, path.with_extension("json.bak"));
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, format!("{}\n", payload)).map_err(|err| err.to_string())?;
+    fs::rename(&tmp_path, &path).map_err(|err| err.to_string())?;
+    crate::restrict_file_permissions(&path);
+    Ok(written)
+}
+
+// ---------------------------------------------------------------------------
+// openclaw.json -> app
+// ---------------------------------------------------------------------------
+
+/// The gateway settings present in openclaw.json, keyed like
+/// `NodeClientConfig`. Keys missing from the file are left out rather than
+/// reported as cleared.
+fn openclaw_gateway_values() -> Result<Map<String, Value>, String> {
+    let path = openclaw_config_path()?;
+    let raw = fs::read_to_string(&path).map_err(|err| err.to_string())?;
+    let root: Value = serde_json5::from_str(&raw)
+        .map_err(|err| format!("invalid {}: {}", path.display(), err))?;
+    let gateway = &root["gateway"];
+    let mut values = Map::new();
+    let sources = [
+        ("port", &gateway["port"]),
+        ("tls", &gateway["tls"]["enabled"]),
+        ("gatewayToken", &gateway["auth"]["token"]),
+        ("gatewayPassword", &gateway["auth"]["password"]),
+        ("tlsFingerprint", &gateway["remote"]["tlsFingerprint"]),
+    ];
+    for (key, value) in sources {
+        // SecretRefs resolve in the CLI only; skip anything but plain values
+        if value.is_u64() || value.is_boolean() || value.is_string() {
+            values.insert(key.to_string(), value.clone());
+        }
+    }
+    Ok(values)
+}
+
+/// Settings in openclaw.json that differ from the app's config.
+fn pending_update(app: &AppHandle) -> Result<OpenclawConfigUpdate, String> {
+    let current = {
+        let state = app.state::<AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    let current = serde_json::to_value(&current).map_err(|err| err.to_string())?;
+    let values: Map<String, Value> = openclaw_gateway_values()?
+        .into_iter()
+        .filter(|(key, value)| current.get(key) != Some(value))
+        .collect();
+    let mut masked = values.clone();
+    crate::credentials::redact_secret_values(&mut masked);
+    Ok(OpenclawConfigUpdate {
+        changed_fields: values.keys().cloned().collect(),
+        values: masked,
+        unmasked: values,
+    })
+}
+
+/// Called by the config watcher when openclaw.json changes. Emits
+/// `openclaw-config-updated` when the CLI's gateway settings no longer match.
+pub fn notify_openclaw_config_changed(app: &AppHandle) {
+    match pending_update(app) {
+        Ok(update) if !update.changed_fields.is_empty() => {
+            crate::push_log_line(
+                app,
+                format!(
+                    "openclaw.json gateway settings changed ({})",
+                    update.changed_fields.join(", ")
+                ),
+            );
+            let _ = app.emit("openclaw-config-updated", update);
+        }
+        Ok(_) => {}
+        Err(err) => crate::push_log_line(app, format!("ignoring openclaw.json change: {}", err)),
+    }
+}
+
+/// Take the gateway settings from openclaw.json into the app's config.
+#[tauri::command]
+pub fn apply_openclaw_config_update(app: AppHandle) -> Result<ConfigChangeImpact, String> {
+    let update = pending_update(&app)?;
+    let current = {
+        let state = app.state::<AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    let mut merged = match serde_json::to_value(&current).map_err(|err| err.to_string())? {
+        Value::Object(map) => map,
+        _ => return Err("config did not serialize to an object".to_string()),
+    };
+    merged.extend(update.unmasked);
+    let config: NodeClientConfig =
+        serde_json::from_value(Value::Object(merged)).map_err(|err| err.to_string())?;
+    let allow_restart = config.auto_restart_on_config_change;
+    crate::save_and_apply_config(&app, config, allow_restart)
+}
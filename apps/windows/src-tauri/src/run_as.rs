@@ -0,0 +1,282 @@
+// Running the node host under a dedicated low-privilege account.
+//
+// Unix wraps the finished `openclaw node run` command in `sudo -n -H -u
+// <user>`. Only the variables set explicitly on the command (gateway auth,
+// PATH, TLS paths, extraEnv) are preserved, by name, so nothing else from
+// the desktop session reaches the other account. The child leads its own
+// process group, so a hard stop reaches the node host and not just sudo.
+//
+// Windows starts the node host with CreateProcessWithLogonW using the
+// account's password (`runAsPassword`). That child has no pipes the app can
+// read and is watched by pid like an adopted node host; the process handle
+// is kept so stop works even though the account's DACL doesn't let this
+// user open the process.
+
+#[cfg(not(target_os = "windows"))]
+use std::path::Path;
+#[cfg(not(target_os = "windows"))]
+use std::process::Command;
+
+/// Wrap `target` in `sudo -n -H -u <user>`. `-n` fails fast instead of
+/// prompting for a password nobody can type, and `-H` gives the node host
+/// the account's own home, so its state and exec approvals stay there.
+/// Stdio is not carried over; set it on the returned command.
+#[cfg(not(target_os = "windows"))]
+pub fn sudo_command(target: &Command, user: &str) -> Command {
+    let preserved: Vec<String> = target
+        .get_envs()
+        .filter(|(_, value)| value.is_some())
+        .map(|(key, _)| key.to_string_lossy().to_string())
+        .filter(|key| !key.contains(','))
+        .collect();
+    let mut command = Command::new("sudo");
+    command.args(["-n", "-H"]);
+    if !preserved.is_empty() {
+        command.arg(format!("--preserve-env={}", preserved.join(",")));
+    }
+    command
+        .args(["-u", user, "--"])
+        .arg(target.get_program())
+        .args(target.get_args());
+    for (key, value) in target.get_envs() {
+        match value {
+            Some(value) => command.env(key, value),
+            None => command.env_remove(key),
+        };
+    }
+    // The desktop user's home is usually unreadable for the other account
+    command.current_dir(target.get_current_dir().unwrap_or(Path::new("/")));
+    command
+}
+
+/// SIGKILL the process group led by `pid`. For a node host running as
+/// another account the signal from this user only reaches sudo, so the
+/// group is also killed as that account (needs a sudoers rule for `kill`).
+#[cfg(not(target_os = "windows"))]
+pub fn kill_process_group(pid: u32, user: Option<&str>) -> bool {
+    if let Some(user) = user {
+        let _ = Command::new("sudo")
+            .args(["-n", "-u", user, "--", "kill", "-KILL", "--"])
+            .arg(format!("-{}", pid))
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status();
+    }
+    unsafe { libc::kill(-(pid as i32), libc::SIGKILL) == 0 }
+}
+
+#[cfg(target_os = "windows")]
+pub use logon::{logon_child_alive, spawn_with_logon, terminate_logon_child};
+
+#[cfg(target_os = "windows")]
+mod logon {
+    use std::ffi::OsStr;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+    use std::process::Command;
+    use std::sync::Mutex;
+
+    use windows_sys::Win32::Foundation::{CloseHandle, HANDLE, STILL_ACTIVE};
+    use windows_sys::Win32::System::Threading::{
+        CreateProcessWithLogonW, GetExitCodeProcess, TerminateProcess, BELOW_NORMAL_PRIORITY_CLASS,
+        CREATE_NEW_CONSOLE, CREATE_NEW_PROCESS_GROUP, CREATE_UNICODE_ENVIRONMENT,
+        LOGON_WITH_PROFILE, PROCESS_INFORMATION, STARTF_USESHOWWINDOW, STARTUPINFOW,
+    };
+
+    const SW_HIDE: u16 = 0;
+
+    /// System variables programs expect; everything else comes from the
+    /// command's explicit env. USERPROFILE and APPDATA are left out so the
+    /// node host resolves the account's own profile.
+    const INHERITED_ENV: &[&str] = &[
+        "SystemRoot",
+        "SystemDrive",
+        "windir",
+        "ComSpec",
+        "PATHEXT",
+        "OS",
+        "PROCESSOR_ARCHITECTURE",
+        "NUMBER_OF_PROCESSORS",
+    ];
+
+    /// Pid and process handle (as usize, HANDLE isn't Send) of the node
+    /// host started under another account.
+    static LOGON_CHILD: Mutex<Option<(u32, usize)>> = Mutex::new(None);
+
+    fn wide(value: &OsStr) -> Vec<u16> {
+        value.encode_wide().chain(Some(0)).collect()
+    }
+
+    /// Append `arg` to a command line with the quoting the MSVC runtime
+    /// (and node.exe) parses back.
+    fn push_arg(line: &mut Vec<u16>, arg: &OsStr) {
+        const QUOTE: u16 = b'"' as u16;
+        const BACKSLASH: u16 = b'\\' as u16;
+        if !line.is_empty() {
+            line.push(b' ' as u16);
+        }
+        let arg: Vec<u16> = arg.encode_wide().collect();
+        let plain = !arg.is_empty()
+            && !arg
+                .iter()
+                .any(|&c| c == b' ' as u16 || c == b'\t' as u16 || c == QUOTE);
+        if plain {
+            line.extend(arg);
+            return;
+        }
+        line.push(QUOTE);
+        let mut backslashes = 0;
+        for c in arg {
+            if c == BACKSLASH {
+                backslashes += 1;
+            } else {
+                if c == QUOTE {
+                    line.extend(std::iter::repeat_n(BACKSLASH, backslashes + 1));
+                }
+                backslashes = 0;
+            }
+            line.push(c);
+        }
+        line.extend(std::iter::repeat_n(BACKSLASH, backslashes));
+        line.push(QUOTE);
+    }
+
+    fn environment_block(command: &Command) -> Vec<u16> {
+        let mut vars: Vec<(String, String)> = INHERITED_ENV
+            .iter()
+            .filter_map(|key| std::env::var(key).ok().map(|v| (key.to_string(), v)))
+            .collect();
+        for (key, value) in command.get_envs() {
+            let key = key.to_string_lossy().to_string();
+            vars.retain(|(existing, _)| !existing.eq_ignore_ascii_case(&key));
+            if let Some(value) = value {
+                vars.push((key, value.to_string_lossy().to_string()));
+            }
+        }
+        vars.sort_by_key(|(key, _)| key.to_uppercase());
+        let mut block = Vec::new();
+        for (key, value) in vars {
+            block.extend(format!("{}={}", key, value).encode_utf16());
+            block.push(0);
+        }
+        block.push(0);
+        block
+    }
+
+    /// Start `command` as `user` ("name", "DOMAIN\\name" or a UPN) and
+    /// return its pid.
+    pub fn spawn_with_logon(
+        command: &Command,
+        user: &str,
+        password: &str,
+        low_priority: bool,
+    ) -> Result<u32, String> {
+        if password.is_empty() {
+            return Err(format!(
+                "no password for {}; set runAsPassword to run the node host as another account",
+                user
+            ));
+        }
+        let program = Path::new(command.get_program());
+        let is_script = program
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("cmd") || ext.eq_ignore_ascii_case("bat"));
+        if is_script {
+            return Err(format!(
+                "{} is a script shim; running as another account needs node.exe or the \
+                 bundled runtime",
+                program.display()
+            ));
+        }
+        let (domain, name) = match user.split_once('\\') {
+            Some((domain, name)) => (Some(domain), name),
+            None => (None, user),
+        };
+
+        let mut line = Vec::new();
+        push_arg(&mut line, command.get_program());
+        for arg in command.get_args() {
+            push_arg(&mut line, arg);
+        }
+        line.push(0);
+        let environment = environment_block(command);
+        let name = wide(OsStr::new(name));
+        let domain = domain.map(|d| wide(OsStr::new(d)));
+        let password = wide(OsStr::new(password));
+        let cwd = command.get_current_dir().map(|dir| wide(dir.as_os_str()));
+
+        let mut flags = CREATE_NEW_CONSOLE | CREATE_NEW_PROCESS_GROUP | CREATE_UNICODE_ENVIRONMENT;
+        if low_priority {
+            flags |= BELOW_NORMAL_PRIORITY_CLASS;
+        }
+        unsafe {
+            let mut startup: STARTUPINFOW = std::mem::zeroed();
+            startup.cb = std::mem::size_of::<STARTUPINFOW>() as u32;
+            startup.dwFlags = STARTF_USESHOWWINDOW;
+            startup.wShowWindow = SW_HIDE;
+            let mut info: PROCESS_INFORMATION = std::mem::zeroed();
+            let ok = CreateProcessWithLogonW(
+                name.as_ptr(),
+                domain.as_ref().map_or(std::ptr::null(), |d| d.as_ptr()),
+                password.as_ptr(),
+                LOGON_WITH_PROFILE,
+                std::ptr::null(),
+                line.as_mut_ptr(),
+                flags,
+                environment.as_ptr().cast(),
+                cwd.as_ref().map_or(std::ptr::null(), |c| c.as_ptr()),
+                &startup,
+                &mut info,
+            );
+            if ok == 0 {
+                return Err(format!(
+                    "failed to start the node host as {}: {}",
+                    user,
+                    std::io::Error::last_os_error()
+                ));
+            }
+            CloseHandle(info.hThread);
+            let previous = LOGON_CHILD
+                .lock()
+                .map_err(|err| err.to_string())?
+                .replace((info.dwProcessId, info.hProcess as usize));
+            if let Some((_, handle)) = previous {
+                CloseHandle(handle as HANDLE);
+            }
+            Ok(info.dwProcessId)
+        }
+    }
+
+    /// Whether the logon child `pid` is still running, or None when `pid`
+    /// isn't that child.
+    pub fn logon_child_alive(pid: u32) -> Option<bool> {
+        let guard = LOGON_CHILD.lock().ok()?;
+        let (child_pid, handle) = (*guard)?;
+        if child_pid != pid {
+            return None;
+        }
+        let mut code: u32 = 0;
+        let ok = unsafe { GetExitCodeProcess(handle as HANDLE, &mut code) } != 0;
+        Some(ok && code == STILL_ACTIVE as u32)
+    }
+
+    /// Terminate the logon child `pid` through its process handle. False
+    /// when `pid` isn't that child.
+    pub fn terminate_logon_child(pid: u32) -> bool {
+        let Ok(mut guard) = LOGON_CHILD.lock() else {
+            return false;
+        };
+        match *guard {
+            Some((child_pid, handle)) if child_pid == pid => {
+                unsafe {
+                    TerminateProcess(handle as HANDLE, 1);
+                    CloseHandle(handle as HANDLE);
+                }
+                *guard = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
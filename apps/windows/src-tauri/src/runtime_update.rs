@@ -0,0 +1,654 @@
+// Self-update of the bundled openclaw runtime.
+//
+// The copy of the CLI shipped in app resources is read-only and only changes
+// with an app release. Newer releases are fetched from the npm registry and
+// staged under `<app data>/runtime/openclaw-<version>/`; the active copy is
+// selected by `<app data>/runtime/current.json`, which is only ever replaced
+// atomically (temp file + rename) so the resolver never sees a half-written
+// pointer.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256, Sha512};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+const NPM_LATEST_URL: &str = "https://registry.npmjs.org/openclaw/latest";
+const METADATA_TIMEOUT_SECS: u64 = 15;
+const DOWNLOAD_TIMEOUT_SECS: u64 = 300;
+const NPM_INSTALL_TIMEOUT_SECS: u64 = 600;
+/// Checksum manifest in a bundle directory, sha256sum format
+/// ("<hex>  <relative path>" per line) covering every file in the bundle;
+/// a symlink is listed with the hash of its target path. Release builds ship
+/// one next to `openclaw.mjs` in resources; self-updated bundles get one at
+/// install time.
+const INTEGRITY_MANIFEST: &str = "SHA256SUMS";
+/// Lockfiles `npm ci` installs from, in npm's order of preference.
+const NPM_LOCKFILES: &[&str] = &["npm-shrinkwrap.json", "package-lock.json"];
+
+// ---------------------------------------------------------------------------
+// Types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct NpmDist {
+    tarball: String,
+    integrity: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NpmLatest {
+    version: String,
+    dist: NpmDist,
+}
+
+/// Contents of `runtime/current.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ActiveRuntime {
+    version: String,
+    dir: String,
+    installed_at_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    previous_dir: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundledRuntimeUpdateInfo {
+    current_version: Option<String>,
+    latest_version: String,
+    update_available: bool,
+    /// "resources" (shipped with the app) or "app-data" (self-updated)
+    source: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleIntegrityError {
+    dir: String,
+    file: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageJsonVersion {
+    version: Option<String>,
+}
+
+// ---------------------------------------------------------------------------
+// Paths
+// ---------------------------------------------------------------------------
+
+fn runtime_root(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("runtime"))
+        .map_err(|e| format!("failed to get data dir: {}", e))
+}
+
+fn pointer_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(runtime_root(app)?.join("current.json"))
+}
+
+fn read_pointer(app: &AppHandle) -> Option<ActiveRuntime> {
+    let raw = fs::read_to_string(pointer_path(app).ok()?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+fn write_pointer(app: &AppHandle, pointer: Option<&ActiveRuntime>) -> Result<(), String> {
+    let path = pointer_path(app)?;
+    let Some(pointer) = pointer else {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        };
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(pointer).map_err(|e| e.to_string())?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, format!("{}\n", json)).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, &path).map_err(|e| e.to_string())
+}
+
+fn resources_bundle_dir(app: &AppHandle) -> Option<PathBuf> {
+    let dir = app.path().resource_dir().ok()?.join("openclaw");
+    dir.join("openclaw.mjs").is_file().then_some(dir)
+}
+
+/// Directory containing the `openclaw.mjs` the resolver should launch: the
+/// self-updated copy when `current.json` points at a valid bundle, otherwise
+/// the copy shipped in app resources.
+pub fn active_bundle_dir(app: &AppHandle) -> Option<PathBuf> {
+    if let Some(pointer) = read_pointer(app) {
+        let dir = PathBuf::from(&pointer.dir);
+        if dir.join("openclaw.mjs").is_file() {
+            return Some(dir);
+        }
+    }
+    resources_bundle_dir(app)
+}
+
+fn bundle_version(dir: &Path) -> Option<String> {
+    let raw = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str::<PackageJsonVersion>(&raw).ok()?.version
+}
+
+// ---------------------------------------------------------------------------
+// Integrity
+// ---------------------------------------------------------------------------
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+impl BundleIntegrityError {
+    fn new(dir: &Path, file: Option<&str>, message: String) -> Self {
+        Self {
+            dir: dir.to_string_lossy().to_string(),
+            file: file.map(str::to_string),
+            expected: None,
+            actual: None,
+            message,
+        }
+    }
+}
+
+/// Check the bundle against its manifest: every file must be listed with a
+/// matching hash, and nothing listed may be missing. Returns `Ok(false)`
+/// when a development build has no manifest; release builds treat a
+/// missing manifest as a failure.
+pub fn verify_bundle_integrity(dir: &Path) -> Result<bool, BundleIntegrityError> {
+    let raw = match fs::read_to_string(dir.join(INTEGRITY_MANIFEST)) {
+        Ok(raw) => raw,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && cfg!(debug_assertions) => {
+            return Ok(false)
+        }
+        Err(e) => {
+            let msg = format!("failed to read checksum manifest: {}", e);
+            return Err(BundleIntegrityError::new(dir, None, msg));
+        }
+    };
+
+    let mut expected_hashes = BTreeMap::new();
+    for line in raw.lines().filter(|line| !line.trim().is_empty()) {
+        let Some((expected, file)) = line.split_once(char::is_whitespace) else {
+            let msg = format!("malformed checksum manifest line: {}", line);
+            return Err(BundleIntegrityError::new(dir, None, msg));
+        };
+        // sha256sum marks binary mode with a leading '*'
+        let file = file.trim_start().trim_start_matches('*');
+        if Path::new(file)
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            let msg = format!("invalid path in checksum manifest: {}", file);
+            return Err(BundleIntegrityError::new(dir, Some(file), msg));
+        }
+        expected_hashes.insert(file.to_string(), expected.to_string());
+    }
+    if !expected_hashes.contains_key("openclaw.mjs") {
+        let msg = "checksum manifest does not cover openclaw.mjs".to_string();
+        return Err(BundleIntegrityError::new(dir, Some("openclaw.mjs"), msg));
+    }
+
+    let files = bundle_files(dir).map_err(|msg| BundleIntegrityError::new(dir, None, msg))?;
+    for file in &files {
+        let Some(expected) = expected_hashes.remove(file) else {
+            let msg = format!("bundled runtime contains unlisted file {}", file);
+            return Err(BundleIntegrityError::new(dir, Some(file), msg));
+        };
+        let actual = hash_bundle_entry(dir, file)
+            .map_err(|msg| BundleIntegrityError::new(dir, Some(file), msg))?;
+        if !actual.eq_ignore_ascii_case(&expected) {
+            let msg = format!("bundled runtime file {} has been modified or truncated", file);
+            return Err(BundleIntegrityError {
+                expected: Some(expected),
+                actual: Some(actual),
+                ..BundleIntegrityError::new(dir, Some(file), msg)
+            });
+        }
+    }
+    if let Some(file) = expected_hashes.keys().next() {
+        let msg = format!("bundled runtime file {} is missing", file);
+        return Err(BundleIntegrityError::new(dir, Some(file), msg));
+    }
+    Ok(true)
+}
+
+/// Every file and symlink under `dir` except the manifest, as sorted
+/// `/`-separated relative paths.
+fn bundle_files(dir: &Path) -> Result<Vec<String>, String> {
+    let mut files = Vec::new();
+    let mut pending = vec![PathBuf::new()];
+    while let Some(rel_dir) = pending.pop() {
+        let entries = fs::read_dir(dir.join(&rel_dir))
+            .map_err(|e| format!("failed to list {}: {}", dir.join(&rel_dir).display(), e))?;
+        for entry in entries {
+            let entry = entry.map_err(|e| e.to_string())?;
+            let rel = rel_dir.join(entry.file_name());
+            let file_type = entry.file_type().map_err(|e| e.to_string())?;
+            if file_type.is_dir() {
+                pending.push(rel);
+                continue;
+            }
+            let name = rel
+                .components()
+                .map(|c| c.as_os_str().to_str())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(|| format!("non-UTF-8 file name in bundle: {}", rel.display()))?
+                .join("/");
+            if name != INTEGRITY_MANIFEST {
+                files.push(name);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Manifest hash of one bundle entry: file contents, or a symlink's target.
+fn hash_bundle_entry(dir: &Path, rel: &str) -> Result<String, String> {
+    let path = dir.join(rel);
+    let meta = fs::symlink_metadata(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    if !meta.file_type().is_symlink() {
+        return sha256_file(&path);
+    }
+    let target = fs::read_link(&path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    Ok(hex::encode(Sha256::digest(target.to_string_lossy().as_bytes())))
+}
+
+fn write_integrity_manifest(dir: &Path) -> Result<(), String> {
+    let mut manifest = String::new();
+    for rel in bundle_files(dir)? {
+        manifest.push_str(&format!("{}  {}\n", hash_bundle_entry(dir, &rel)?, rel));
+    }
+    fs::write(dir.join(INTEGRITY_MANIFEST), manifest).map_err(|e| e.to_string())
+}
+
+// ---------------------------------------------------------------------------
+// Update steps
+// ---------------------------------------------------------------------------
+
+fn emit_stage(app: &AppHandle, stage: &str, detail: Option<String>) {
+    let _ = app.emit(
+        "bundled-runtime-update",
+        serde_json::json!({ "stage": stage, "detail": detail }),
+    );
+}
+
+async fn fetch_latest(client: &reqwest::Client) -> Result<NpmLatest, String> {
+    client
+        .get(NPM_LATEST_URL)
+        .timeout(Duration::from_secs(METADATA_TIMEOUT_SECS))
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|e| format!("npm registry request failed: {}", e))?
+        .json::<NpmLatest>()
+        .await
+        .map_err(|e| format!("invalid npm registry response: {}", e))
+}
+
+/// Verify an npm `dist.integrity` SRI string ("sha512-<base64>").
+fn verify_integrity(bytes: &[u8], integrity: &str) -> Result<(), String> {
+    let expected = integrity
+        .split_whitespace()
+        .find_map(|entry| entry.strip_prefix("sha512-"))
+        .ok_or_else(|| format!("unsupported integrity format: {}", integrity))?;
+    let actual = STANDARD.encode(Sha512::digest(bytes));
+    if actual != expected {
+        return Err("downloaded tarball failed sha512 integrity check".to_string());
+    }
+    Ok(())
+}
+
+/// Extract an npm tarball into `dest`, stripping the leading `package/`
+/// component and refusing entries that would escape the destination.
+fn extract_tarball(bytes: &[u8], dest: &Path) -> Result<(), String> {
+    let decoder = flate2::read::GzDecoder::new(bytes);
+    let mut archive = tar::Archive::new(decoder);
+    let entries = archive.entries().map_err(|e| e.to_string())?;
+    for entry in entries {
+        let mut entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path().map_err(|e| e.to_string())?.into_owned();
+        let rel: PathBuf = path.components().skip(1).collect();
+        if rel.as_os_str().is_empty()
+            || rel.components().any(|c| !matches!(c, Component::Normal(_)))
+        {
+            continue;
+        }
+        let target = dest.join(&rel);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        entry.unpack(&target).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Install production dependencies with the npm that ships next to `node`.
+async fn install_production_deps(node_path: &str, dir: &Path) -> Result<(), String> {
+    let npm_name = if cfg!(windows) { "npm.cmd" } else { "npm" };
+    let npm = Path::new(node_path)
+        .parent()
+        .map(|bin| bin.join(npm_name))
+        .filter(|p| p.is_file())
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| npm_name.to_string());
+
+    // Only the pinned dependency tree, and no lifecycle scripts: an update
+    // runs in the background without the user watching
+    if !NPM_LOCKFILES.iter().any(|name| dir.join(name).is_file()) {
+        return Err("package ships no lockfile; refusing an unpinned npm install".to_string());
+    }
+    let mut cmd = tokio::process::Command::new(&npm);
+    cmd.args([
+        "ci",
+        "--omit=dev",
+        "--ignore-scripts",
+        "--no-audit",
+        "--no-fund",
+    ])
+    .current_dir(dir)
+    .stdin(std::process::Stdio::null())
+    .stdout(std::process::Stdio::null())
+    .stderr(std::process::Stdio::piped());
+    #[cfg(target_os = "linux")]
+    crate::sanitize_appimage_env_tokio(&mut cmd);
+    #[cfg(target_os = "windows")]
+    {
+        cmd.creation_flags(crate::CREATE_NO_WINDOW);
+    }
+
+    let output = tokio::time::timeout(
+        Duration::from_secs(NPM_INSTALL_TIMEOUT_SECS),
+        cmd.output(),
+    )
+    .await
+    .map_err(|_| "npm ci timed out".to_string())?
+    .map_err(|e| format!("failed to run {}: {}", npm, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let tail: Vec<&str> = stderr.lines().rev().take(5).collect();
+        return Err(format!(
+            "npm ci exited with status {}: {}",
+            output.status,
+            tail.into_iter().rev().collect::<Vec<_>>().join(" | ")
+        ));
+    }
+    Ok(())
+}
+
+/// Launch the bundle through the regular resolver path and make sure it
+/// reports the expected version.
+fn smoke_test(app: &AppHandle, expected_version: &str) -> Result<(), String> {
+    let mut config = {
+        let state = app.state::<crate::AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    // A configured dev checkout would shadow the bundle under test
+    config.dev_root = None;
+    let (openclaw_bin, _, _) = crate::resolve_openclaw_bin(&config, app)?;
+    let info = crate::cached_openclaw_version(app, &openclaw_bin, true)?;
+    match info.version {
+        Some(ref v) if v == expected_version => Ok(()),
+        other => Err(format!(
+            "new runtime reported version {} (expected {})",
+            other.as_deref().unwrap_or("unknown"),
+            expected_version
+        )),
+    }
+}
+
+fn current_info(app: &AppHandle, latest_version: String) -> BundledRuntimeUpdateInfo {
+    let pointer = read_pointer(app);
+    let (current_version, source) = match active_bundle_dir(app) {
+        Some(dir) => {
+            let source = if pointer.as_ref().is_some_and(|p| Path::new(&p.dir) == dir) {
+                "app-data"
+            } else {
+                "resources"
+            };
+            (bundle_version(&dir), source)
+        }
+        None => (None, "resources"),
+    };
+    let update_available = match current_version.as_deref() {
+        Some(current) => crate::version_older_than(current, &latest_version),
+        None => true,
+    };
+    BundledRuntimeUpdateInfo {
+        current_version,
+        latest_version,
+        update_available,
+        source: source.to_string(),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub async fn check_bundled_runtime_update(
+    app: AppHandle,
+) -> Result<BundledRuntimeUpdateInfo, String> {
+    let client = reqwest::Client::new();
+    let latest = fetch_latest(&client).await?;
+    Ok(current_info(&app, latest.version))
+}
+
+#[tauri::command]
+pub async fn update_bundled_runtime(app: AppHandle) -> Result<BundledRuntimeUpdateInfo, String> {
+    let config = {
+        let state = app.state::<crate::AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    let node_path = crate::resolve_node_binary(&config, &app)
+        .ok_or_else(|| "no Node.js found to run the bundled runtime".to_string())?;
+    if !config.use_bundled_runtime {
+        return Err("bundled runtime is disabled (useBundledRuntime is off)".to_string());
+    }
+
+    emit_stage(&app, "checking", None);
+    let client = reqwest::Client::new();
+    let latest = fetch_latest(&client).await?;
+    let info = current_info(&app, latest.version.clone());
+    if !info.update_available {
+        emit_stage(&app, "up-to-date", info.current_version.clone());
+        return Ok(info);
+    }
+    let integrity = latest
+        .dist
+        .integrity
+        .clone()
+        .ok_or_else(|| "npm registry did not provide an integrity hash".to_string())?;
+
+    emit_stage(&app, "downloading", Some(latest.version.clone()));
+    let bytes = client
+        .get(&latest.dist.tarball)
+        .timeout(Duration::from_secs(DOWNLOAD_TIMEOUT_SECS))
+        .send()
+        .await
+        .and_then(|res| res.error_for_status())
+        .map_err(|e| format!("download failed: {}", e))?
+        .bytes()
+        .await
+        .map_err(|e| format!("download failed: {}", e))?;
+
+    emit_stage(&app, "verifying", None);
+    verify_integrity(&bytes, &integrity)?;
+
+    let root = runtime_root(&app)?;
+    let target_dir = root.join(format!("openclaw-{}", latest.version));
+    let staging_dir = root.join(format!("openclaw-{}.staging", latest.version));
+    let _ = fs::remove_dir_all(&staging_dir);
+    fs::create_dir_all(&staging_dir).map_err(|e| e.to_string())?;
+
+    let staged = async {
+        emit_stage(&app, "extracting", None);
+        let dest = staging_dir.clone();
+        tauri::async_runtime::spawn_blocking(move || extract_tarball(&bytes, &dest))
+            .await
+            .map_err(|e| e.to_string())??;
+        if !staging_dir.join("openclaw.mjs").is_file() {
+            return Err("downloaded package does not contain openclaw.mjs".to_string());
+        }
+        emit_stage(&app, "installing-dependencies", None);
+        install_production_deps(&node_path, &staging_dir).await?;
+        // The tarball was verified above and dependencies come from its
+        // lockfile; record every file so later launches can detect tampering
+        // on disk
+        let dest = staging_dir.clone();
+        tauri::async_runtime::spawn_blocking(move || write_integrity_manifest(&dest))
+            .await
+            .map_err(|e| e.to_string())??;
+        let _ = fs::remove_dir_all(&target_dir);
+        fs::rename(&staging_dir, &target_dir).map_err(|e| e.to_string())
+    }
+    .await;
+    if let Err(err) = staged {
+        let _ = fs::remove_dir_all(&staging_dir);
+        emit_stage(&app, "failed", Some(err.clone()));
+        return Err(err);
+    }
+
+    // Switch the resolver, then prove the new bundle actually runs; restore
+    // the previous pointer if it doesn't.
+    emit_stage(&app, "activating", None);
+    let previous = read_pointer(&app);
+    let next = ActiveRuntime {
+        version: latest.version.clone(),
+        dir: target_dir.to_string_lossy().to_string(),
+        installed_at_ms: crate::now_ms(),
+        previous_dir: previous.as_ref().map(|p| p.dir.clone()),
+    };
+    write_pointer(&app, Some(&next))?;
+    let app_for_test = app.clone();
+    let expected = latest.version.clone();
+    let verified = tauri::async_runtime::spawn_blocking(move || {
+        smoke_test(&app_for_test, &expected)
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+    if let Err(err) = verified {
+        write_pointer(&app, previous.as_ref())?;
+        let _ = fs::remove_dir_all(&target_dir);
+        let msg = format!("bundled runtime update rolled back: {}", err);
+        crate::push_log_line(&app, msg.clone());
+        emit_stage(&app, "rolled-back", Some(err));
+        return Err(msg);
+    }
+
+    // Keep exactly one previous version around; prune anything older.
+    if let Some(old) = previous.and_then(|p| p.previous_dir) {
+        if Path::new(&old) != target_dir {
+            let _ = fs::remove_dir_all(old);
+        }
+    }
+
+    crate::push_log_line(
+        &app,
+        format!(
+            "bundled runtime updated to openclaw {}; restart the node host to use it",
+            latest.version
+        ),
+    );
+    emit_stage(&app, "done", Some(latest.version.clone()));
+    Ok(current_info(&app, latest.version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fresh bundle directory with an entrypoint and one dependency.
+    fn bundle(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "openclaw-bundle-test-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("node_modules/dep")).unwrap();
+        fs::write(dir.join("openclaw.mjs"), "import './node_modules/dep/index.js';").unwrap();
+        fs::write(dir.join("node_modules/dep/index.js"), "export {};").unwrap();
+        dir
+    }
+
+    #[test]
+    fn accepts_an_unmodified_bundle() {
+        let dir = bundle("ok");
+        write_integrity_manifest(&dir).unwrap();
+        assert!(verify_bundle_integrity(&dir).unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_modified_dependencies() {
+        let dir = bundle("modified");
+        write_integrity_manifest(&dir).unwrap();
+        fs::write(dir.join("node_modules/dep/index.js"), "process.exit(1);").unwrap();
+        let err = verify_bundle_integrity(&dir).unwrap_err();
+        assert_eq!(err.file.as_deref(), Some("node_modules/dep/index.js"));
+        assert!(err.expected.is_some() && err.actual.is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_unlisted_and_missing_files() {
+        let dir = bundle("unlisted");
+        write_integrity_manifest(&dir).unwrap();
+        fs::write(dir.join("node_modules/dep/extra.js"), "").unwrap();
+        let err = verify_bundle_integrity(&dir).unwrap_err();
+        assert_eq!(err.file.as_deref(), Some("node_modules/dep/extra.js"));
+
+        fs::remove_file(dir.join("node_modules/dep/extra.js")).unwrap();
+        fs::remove_file(dir.join("node_modules/dep/index.js")).unwrap();
+        let err = verify_bundle_integrity(&dir).unwrap_err();
+        assert_eq!(err.file.as_deref(), Some("node_modules/dep/index.js"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rejects_manifests_that_skip_the_entrypoint_or_escape_the_bundle() {
+        let dir = bundle("paths");
+        fs::write(dir.join(INTEGRITY_MANIFEST), "00  ../outside.js\n").unwrap();
+        let err = verify_bundle_integrity(&dir).unwrap_err();
+        assert_eq!(err.file.as_deref(), Some("../outside.js"));
+
+        fs::write(dir.join(INTEGRITY_MANIFEST), "00  node_modules/dep/index.js\n").unwrap();
+        let err = verify_bundle_integrity(&dir).unwrap_err();
+        assert_eq!(err.file.as_deref(), Some("openclaw.mjs"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_manifest_passes_only_in_debug_builds() {
+        let dir = bundle("no-manifest");
+        let result = verify_bundle_integrity(&dir);
+        if cfg!(debug_assertions) {
+            assert!(!result.unwrap());
+        } else {
+            assert!(result.is_err());
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,273 @@
+// Settings bundle export/import.
+//
+// A bundle is one JSON file carrying the node client config and the exec
+// policy/allowlist, for provisioning more machines. Per-machine fields
+// (install path, node id, working directory) are never exported.
+// Credentials (`credentials::SECRET_FIELDS`) are left out unless a passphrase
+// is given, in which case they are sealed with ChaCha20-Poly1305 under a
+// PBKDF2-derived key.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::credentials::SECRET_FIELDS;
+use crate::{AllowlistEntry, AppState, ConfigChangeImpact, ExecPolicyConfig, NodeClientConfig};
+
+const BUNDLE_FORMAT: &str = "openclaw-node-client-settings";
+const BUNDLE_VERSION: u32 = 1;
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SealedSecrets {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Secret config fields keyed like the config (`gatewayToken`, ...), plus
+/// the collector headers. Bundles from older versions carry only the
+/// gateway token and password.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct BundleSecrets {
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    otlp_headers: HashMap<String, String>,
+    #[serde(flatten)]
+    values: BTreeMap<String, Option<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SettingsBundle {
+    format: String,
+    version: u32,
+    exported_at_ms: u64,
+    app_version: String,
+    config: NodeClientConfig,
+    exec_policy: ExecPolicyConfig,
+    allowlist: Vec<AllowlistEntry>,
+    #[serde(default)]
+    secrets: Option<SealedSecrets>,
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> Key {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    Key::from(key)
+}
+
+fn seal_secrets(secrets: &BundleSecrets, passphrase: &str) -> Result<SealedSecrets, String> {
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+    let plaintext = serde_json::to_vec(secrets).map_err(|err| err.to_string())?;
+    let ciphertext = ChaCha20Poly1305::new(&derive_key(passphrase, &salt))
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|_| "failed to encrypt credentials".to_string())?;
+    Ok(SealedSecrets {
+        salt: STANDARD.encode(salt),
+        nonce: STANDARD.encode(nonce),
+        ciphertext: STANDARD.encode(ciphertext),
+    })
+}
+
+fn open_secrets(sealed: &SealedSecrets, passphrase: &str) -> Result<BundleSecrets, String> {
+    let decode = |value: &str| {
+        STANDARD
+            .decode(value)
+            .map_err(|err| format!("corrupt settings bundle: {}", err))
+    };
+    let salt = decode(&sealed.salt)?;
+    let nonce = decode(&sealed.nonce)?;
+    let ciphertext = decode(&sealed.ciphertext)?;
+    if nonce.len() != 12 {
+        return Err("corrupt settings bundle: bad nonce".to_string());
+    }
+    let plaintext = ChaCha20Poly1305::new(&derive_key(passphrase, &salt))
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "wrong passphrase for settings bundle credentials".to_string())?;
+    serde_json::from_slice(&plaintext).map_err(|err| err.to_string())
+}
+
+fn build_bundle(app: &AppHandle, passphrase: Option<&str>) -> Result<SettingsBundle, String> {
+    let mut config = {
+        let state = app.state::<AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    let mut secrets = BundleSecrets {
+        otlp_headers: std::mem::take(&mut config.otlp_headers),
+        ..BundleSecrets::default()
+    };
+    for field in SECRET_FIELDS {
+        if let Some(value) = (field.get)(&mut config).take() {
+            secrets.values.insert(field.key.to_string(), Some(value));
+        }
+    }
+    config.keychain_ref = None;
+    config.install_path = None;
+    config.node_id = None;
+    config.working_dir = None;
+
+    let sealed = match passphrase {
+        Some(passphrase) if !secrets.values.is_empty() || !secrets.otlp_headers.is_empty() => {
+            Some(seal_secrets(&secrets, passphrase)?)
+        }
+        _ => None,
+    };
+    Ok(SettingsBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        version: BUNDLE_VERSION,
+        exported_at_ms: crate::now_ms(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        config,
+        exec_policy: crate::get_exec_policy()?,
+        allowlist: crate::get_exec_allowlist()?,
+        secrets: sealed,
+    })
+}
+
+fn apply_bundle(
+    app: &AppHandle,
+    bundle: SettingsBundle,
+    passphrase: Option<&str>,
+) -> Result<ConfigChangeImpact, String> {
+    if bundle.format != BUNDLE_FORMAT {
+        return Err("not an OpenClaw node client settings bundle".to_string());
+    }
+    if bundle.version > BUNDLE_VERSION {
+        return Err(format!(
+            "settings bundle version {} is newer than this app supports",
+            bundle.version
+        ));
+    }
+    let mut secrets = match (&bundle.secrets, passphrase) {
+        (Some(sealed), Some(passphrase)) => Some(open_secrets(sealed, passphrase)?),
+        (Some(_), None) => {
+            return Err(
+                "settings bundle contains encrypted credentials; a passphrase is required"
+                    .to_string(),
+            )
+        }
+        (None, _) => None,
+    };
+
+    let mut current = {
+        let state = app.state::<AppState>();
+        let cfg = state.config.lock().map_err(|err| err.to_string())?.clone();
+        cfg
+    };
+    let mut config = bundle.config;
+    // Keep this machine's identity, paths and any credentials the sealed
+    // secrets don't carry
+    config.install_path = current.install_path.take();
+    config.node_id = current.node_id.take();
+    config.working_dir = current.working_dir.take();
+    config.keychain_ref = current.keychain_ref.take();
+    for field in SECRET_FIELDS {
+        let bundled = secrets
+            .as_mut()
+            .and_then(|secrets| secrets.values.remove(field.key));
+        let local = (field.get)(&mut current).take();
+        *(field.get)(&mut config) = bundled.unwrap_or(local);
+    }
+    config.otlp_headers = secrets
+        .as_mut()
+        .map(|secrets| std::mem::take(&mut secrets.otlp_headers))
+        .filter(|headers| !headers.is_empty())
+        .unwrap_or(current.otlp_headers);
+
+    let policy = bundle.exec_policy;
+    crate::set_exec_policy(app.clone(), policy.security, policy.ask, policy.ask_fallback)?;
+    let existing: HashSet<String> = crate::get_exec_allowlist()?
+        .into_iter()
+        .map(|entry| entry.pattern)
+        .collect();
+    for entry in bundle.allowlist {
+        if !existing.contains(&entry.pattern) {
+            crate::add_allowlist_entry(app.clone(), entry.pattern)?;
+        }
+    }
+
+    let allow_restart = config.auto_restart_on_config_change;
+    crate::save_and_apply_config(app, config, allow_restart)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Export settings to a file chosen with the save dialog. Credentials are
+/// included (encrypted) only when `passphrase` is given. Returns the path
+/// written, or `None` when the dialog was cancelled.
+#[tauri::command]
+pub async fn export_settings_bundle(
+    app: AppHandle,
+    passphrase: Option<String>,
+) -> Result<Option<String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let passphrase = passphrase.as_deref().filter(|p| !p.is_empty());
+        let bundle = build_bundle(&app, passphrase)?;
+        let payload = serde_json::to_string_pretty(&bundle).map_err(|err| err.to_string())?;
+        let Some(selected) = app
+            .dialog()
+            .file()
+            .set_title("Export settings")
+            .set_file_name("openclaw-node-settings.json")
+            .add_filter("Settings bundle", &["json"])
+            .blocking_save_file()
+        else {
+            return Ok(None);
+        };
+        let path = selected.into_path().map_err(|err| err.to_string())?;
+        fs::write(&path, format!("{}\n", payload))
+            .map_err(|err| format!("failed to write {}: {}", path.display(), err))?;
+        crate::restrict_file_permissions(&path);
+        crate::push_log_line(&app, format!("exported settings to {}", path.display()));
+        Ok(Some(path.to_string_lossy().to_string()))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
+
+/// Import a settings bundle picked with the open dialog. Returns `None` when
+/// the dialog was cancelled.
+#[tauri::command]
+pub async fn import_settings_bundle(
+    app: AppHandle,
+    passphrase: Option<String>,
+) -> Result<Option<ConfigChangeImpact>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let Some(selected) = app
+            .dialog()
+            .file()
+            .set_title("Import settings")
+            .add_filter("Settings bundle", &["json"])
+            .blocking_pick_file()
+        else {
+            return Ok(None);
+        };
+        let path = selected.into_path().map_err(|err| err.to_string())?;
+        let raw = fs::read_to_string(&path)
+            .map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+        let bundle: SettingsBundle = serde_json::from_str(&raw)
+            .map_err(|err| format!("invalid settings bundle: {}", err))?;
+        let passphrase = passphrase.as_deref().filter(|p| !p.is_empty());
+        let impact = apply_bundle(&app, bundle, passphrase)?;
+        crate::push_log_line(&app, format!("imported settings from {}", path.display()));
+        Ok(Some(impact))
+    })
+    .await
+    .map_err(|err| err.to_string())?
+}
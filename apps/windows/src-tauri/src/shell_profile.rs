@@ -0,0 +1,104 @@
+// PATH entries reconstructed from shell profile files.
+//
+// Spawning `$SHELL -l` is the most faithful way to learn the user's PATH,
+// but it runs arbitrary rc code: banners, slow plugin managers, prompts that
+// wait on a tty. Reading the common profile files and picking out PATH
+// assignments gets most of the same directories with no side effects, and
+// still answers when the login shell times out.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Profile files in the order shells read them, relative to the home dir.
+const PROFILE_FILES: &[&str] = &[
+    ".profile",
+    ".bash_profile",
+    ".bash_login",
+    ".bashrc",
+    ".zshenv",
+    ".zprofile",
+    ".zshrc",
+    ".config/fish/config.fish",
+];
+
+/// Expand `$HOME`, `${HOME}` and a leading `~` in one PATH entry. Entries
+/// that still reference other variables or command substitutions can't be
+/// resolved without running the shell and are dropped.
+fn expand_entry(entry: &str, home: &Path) -> Option<PathBuf> {
+    let entry = entry.trim().trim_matches(|c| c == '"' || c == '\'');
+    if entry.is_empty() {
+        return None;
+    }
+    let home_str = home.to_string_lossy();
+    let expanded = if let Some(rest) = entry.strip_prefix('~') {
+        format!("{}{}", home_str, rest)
+    } else {
+        entry.to_string()
+    };
+    let expanded = expanded
+        .replace("${HOME}", &home_str)
+        .replace("$HOME", &home_str);
+    if expanded.contains('$') || expanded.contains('`') || !expanded.starts_with('/') {
+        return None;
+    }
+    Some(PathBuf::from(expanded))
+}
+
+/// Directories a single profile line adds to PATH.
+fn line_dirs(line: &str, home: &Path) -> Vec<PathBuf> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return Vec::new();
+    }
+    // fish: `fish_add_path dir...` and `set -gx PATH dir... $PATH`
+    if let Some(rest) = line.strip_prefix("fish_add_path ") {
+        return rest
+            .split_whitespace()
+            .filter(|arg| !arg.starts_with('-'))
+            .filter_map(|arg| expand_entry(arg, home))
+            .collect();
+    }
+    if line.starts_with("set ") {
+        let mut words = line
+            .split_whitespace()
+            .skip(1)
+            .skip_while(|w| w.starts_with('-'));
+        if words.next() != Some("PATH") {
+            return Vec::new();
+        }
+        return words.filter_map(|arg| expand_entry(arg, home)).collect();
+    }
+    // sh family: `export PATH=...`, `PATH=...`, `typeset -x PATH=...`
+    let assignment = line
+        .strip_prefix("export ")
+        .or_else(|| line.strip_prefix("typeset -x "))
+        .unwrap_or(line)
+        .trim_start();
+    let Some(value) = assignment.strip_prefix("PATH=") else {
+        return Vec::new();
+    };
+    // Drop a trailing `; export PATH` or comment
+    let value = value.split([';', '#']).next().unwrap_or("").trim();
+    value
+        .trim_matches(|c| c == '"' || c == '\'')
+        .split(':')
+        .filter_map(|entry| expand_entry(entry, home))
+        .collect()
+}
+
+/// PATH directories assigned in the user's profile files, deduped, in the
+/// order the files are read.
+pub fn path_dirs(home: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    for file in PROFILE_FILES {
+        let Ok(raw) = fs::read_to_string(home.join(file)) else {
+            continue;
+        };
+        for dir in raw.lines().flat_map(|line| line_dirs(line, home)) {
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+    }
+    dirs
+}
@@ -0,0 +1,202 @@
+// Resolve version-manager shims to the install they forward to.
+//
+// Volta, asdf, mise, nodenv and scoop put small forwarding executables on
+// PATH that pick the real binary (and with it the Node version) at run time,
+// based on whatever directory or environment the child happens to start in.
+// Discovery follows the shim once so the node host runs the concrete install
+// with that install's `node` first on PATH.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+
+#[cfg(target_os = "windows")]
+const NODE_BIN: &str = "node.exe";
+#[cfg(not(target_os = "windows"))]
+const NODE_BIN: &str = "node";
+
+/// How far above the real binary to look for the `node` it runs under
+/// (e.g. lib/node_modules/openclaw/openclaw.mjs -> bin/node).
+const NODE_SEARCH_DEPTH: usize = 6;
+
+#[derive(Debug, Clone)]
+pub struct ResolvedShim {
+    pub real_path: PathBuf,
+    /// Directory holding the `node` the real binary should run under.
+    pub node_dir: Option<PathBuf>,
+}
+
+fn file_name(path: &Path) -> Option<String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+fn file_stem(path: &Path) -> Option<String> {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+}
+
+/// First line of `<manager> which <name>`, as an existing file.
+fn manager_which(manager: &Path, name: &str) -> Option<PathBuf> {
+    let mut command = Command::new(manager);
+    command
+        .args(["which", name])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null());
+    // Resolve against the global version, not wherever the app was started
+    if let Some(base) = directories::BaseDirs::new() {
+        command.current_dir(base.home_dir());
+    }
+    #[cfg(target_os = "windows")]
+    command.creation_flags(crate::CREATE_NO_WINDOW);
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let path = PathBuf::from(stdout.lines().next()?.trim());
+    path.is_file().then_some(path)
+}
+
+/// The manager's own executable: its root's bin dir, else PATH.
+fn manager_executable(root: &Path, manager: &str) -> PathBuf {
+    let name = if cfg!(windows) {
+        format!("{}.exe", manager)
+    } else {
+        manager.to_string()
+    };
+    let local = root.join("bin").join(&name);
+    if local.is_file() {
+        local
+    } else {
+        PathBuf::from(manager)
+    }
+}
+
+/// asdf, mise and nodenv keep their shims in `<root>/shims`.
+fn resolve_shims_dir(bin_path: &Path, name: &str) -> Option<PathBuf> {
+    let parent = bin_path.parent()?;
+    if !file_name(parent)?.eq_ignore_ascii_case("shims") {
+        return None;
+    }
+    let root = parent.parent()?;
+    let root_name = file_name(root)?.to_ascii_lowercase();
+    let manager = if root_name.contains("asdf") {
+        "asdf"
+    } else if root_name.contains("mise") {
+        "mise"
+    } else if root_name.contains("nodenv") {
+        "nodenv"
+    } else {
+        return None;
+    };
+    manager_which(&manager_executable(root, manager), name)
+}
+
+/// Volta's `bin` entries all forward to volta-shim; `volta which` names the
+/// package binary, and the package's pinned Node lives under tools/image.
+fn resolve_volta(bin_path: &Path, name: &str) -> Option<ResolvedShim> {
+    let parent = bin_path.parent()?;
+    let root = parent.parent()?;
+    if file_name(parent)? != "bin" || !file_name(root)?.to_ascii_lowercase().contains("volta") {
+        return None;
+    }
+    let real_path = manager_which(
+        &root
+            .join("bin")
+            .join(if cfg!(windows) { "volta.exe" } else { "volta" }),
+        name,
+    )
+    .or_else(|| manager_which(Path::new("volta"), name))?;
+    let pinned = fs::read_to_string(
+        root.join("tools")
+            .join("user")
+            .join("packages")
+            .join(format!("{}.json", name)),
+    )
+    .ok()
+    .and_then(|raw| serde_json::from_str::<serde_json::Value>(&raw).ok())
+    .and_then(|package| package["platform"]["node"].as_str().map(str::to_string));
+    let node_dir = pinned
+        .map(|version| {
+            let dir = root.join("tools").join("image").join("node").join(version);
+            if cfg!(windows) {
+                dir
+            } else {
+                dir.join("bin")
+            }
+        })
+        .filter(|dir| dir.join(NODE_BIN).is_file());
+    Some(ResolvedShim {
+        node_dir: node_dir.or_else(|| node_dir_for(&real_path)),
+        real_path,
+    })
+}
+
+/// Scoop shims: `<name>.shim` next to an exe shim (`path = "..."`), or a
+/// `.cmd` shim whose first line is `@rem <target>`.
+fn resolve_scoop(bin_path: &Path) -> Option<PathBuf> {
+    let sidecar = bin_path.with_file_name(format!("{}.shim", file_stem(bin_path)?));
+    let target = if let Ok(raw) = fs::read_to_string(&sidecar) {
+        raw.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "path").then(|| value.trim().trim_matches('"').to_string())
+        })?
+    } else if bin_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("cmd"))
+    {
+        let raw = fs::read_to_string(bin_path).ok()?;
+        raw.lines()
+            .next()?
+            .trim()
+            .strip_prefix("@rem ")?
+            .trim()
+            .to_string()
+    } else {
+        return None;
+    };
+    let target = PathBuf::from(target);
+    target.is_file().then_some(target)
+}
+
+/// Nearest directory at or above `path` with a `node` binary in it or in
+/// its `bin` subdirectory.
+fn node_dir_for(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .take(NODE_SEARCH_DEPTH)
+        .flat_map(|dir| [dir.to_path_buf(), dir.join("bin")])
+        .find(|dir| dir.join(NODE_BIN).is_file())
+}
+
+/// Follow a shim or symlink at `bin_path` to the concrete binary. Returns
+/// `None` when `bin_path` already is the real thing.
+pub fn resolve(bin_path: &Path) -> Option<ResolvedShim> {
+    let name = file_stem(bin_path)?;
+    if let Some(resolved) = resolve_volta(bin_path, &name) {
+        return Some(resolved);
+    }
+    let real_path = resolve_shims_dir(bin_path, &name)
+        .or_else(|| resolve_scoop(bin_path))
+        .or_else(|| {
+            let is_link =
+                fs::symlink_metadata(bin_path).is_ok_and(|meta| meta.file_type().is_symlink());
+            if is_link {
+                fs::canonicalize(bin_path).ok()
+            } else {
+                None
+            }
+        })?;
+    if real_path == bin_path {
+        return None;
+    }
+    Some(ResolvedShim {
+        node_dir: node_dir_for(&real_path),
+        real_path,
+    })
+}
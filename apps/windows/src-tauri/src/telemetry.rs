@@ -0,0 +1,296 @@
+// OpenTelemetry trace export.
+//
+// With `otlp_endpoint` set to an OTLP/HTTP collector (e.g.
+// `http://localhost:4318`), the app records spans for the gateway
+// connection and exec-host requests and POSTs them as OTLP/JSON to
+// `{otlp_endpoint}/v1/traces`, adding `otlp_headers` to each request:
+//
+//   gateway.connect          socket open through hello-ok
+//     gateway.ws_open        TCP / TLS / WebSocket upgrade
+//     gateway.hello          connect request until hello-ok
+//   gateway.rpc <method>     one RPC, request to reply
+//   exec.request             one exec-host request
+//     exec.approval          waiting for the user's decision
+//     exec.run               running the command
+//
+// Spans are batched every `EXPORT_INTERVAL_SECS`; when the collector is
+// down or can't keep up they are dropped rather than queued. With no
+// endpoint configured spans aren't recorded at all.
+
+use rand::RngCore;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+use crate::AppState;
+
+const EXPORT_INTERVAL_SECS: u64 = 5;
+const MAX_BATCH: usize = 512;
+const QUEUE_CAPACITY: usize = 4096;
+const SERVICE_NAME: &str = "openclaw-node-client";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static QUEUE: OnceLock<mpsc::Sender<SpanData>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy)]
+pub enum SpanKind {
+    Internal = 1,
+    Server = 2,
+    Client = 3,
+}
+
+struct SpanData {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_id: Option<[u8; 8]>,
+    name: String,
+    kind: SpanKind,
+    start_ns: u64,
+    end_ns: u64,
+    attributes: Vec<(&'static str, Value)>,
+    error: Option<String>,
+}
+
+/// A span in progress. Exported by `end`; a span dropped without `end`
+/// (an early return) is exported as failed.
+pub struct Span {
+    data: Option<SpanData>,
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos() as u64
+}
+
+fn random_id<const N: usize>() -> [u8; N] {
+    let mut id = [0u8; N];
+    rand::thread_rng().fill_bytes(&mut id);
+    id
+}
+
+impl Span {
+    /// Start a new trace.
+    pub fn new(name: impl Into<String>, kind: SpanKind) -> Self {
+        Self::start(name.into(), kind, random_id(), None)
+    }
+
+    /// Start a span under this one.
+    pub fn child(&self, name: impl Into<String>) -> Self {
+        match &self.data {
+            Some(parent) => Self::start(
+                name.into(),
+                SpanKind::Internal,
+                parent.trace_id,
+                Some(parent.span_id),
+            ),
+            None => Span { data: None },
+        }
+    }
+
+    fn start(name: String, kind: SpanKind, trace_id: [u8; 16], parent_id: Option<[u8; 8]>) -> Self {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return Span { data: None };
+        }
+        Span {
+            data: Some(SpanData {
+                trace_id,
+                span_id: random_id(),
+                parent_id,
+                name,
+                kind,
+                start_ns: now_ns(),
+                end_ns: 0,
+                attributes: Vec::new(),
+                error: None,
+            }),
+        }
+    }
+
+    pub fn attr(&mut self, key: &'static str, value: impl Into<Value>) {
+        if let Some(data) = &mut self.data {
+            data.attributes.push((key, value.into()));
+        }
+    }
+
+    pub fn fail(&mut self, error: impl Into<String>) {
+        if let Some(data) = &mut self.data {
+            data.error = Some(error.into());
+        }
+    }
+
+    pub fn end(mut self) {
+        if let Some(data) = self.data.take() {
+            export(data);
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(mut data) = self.data.take() {
+            data.error
+                .get_or_insert_with(|| "did not complete".to_string());
+            export(data);
+        }
+    }
+}
+
+fn export(mut data: SpanData) {
+    data.end_ns = now_ns();
+    if let Some(queue) = QUEUE.get() {
+        // Full queue: the collector is behind, drop the span
+        let _ = queue.try_send(data);
+    }
+}
+
+fn attribute(key: &str, value: &Value) -> Value {
+    let value = match value {
+        Value::Bool(flag) => json!({ "boolValue": flag }),
+        Value::Number(number) if number.is_i64() || number.is_u64() => {
+            json!({ "intValue": number.to_string() })
+        }
+        Value::Number(number) => json!({ "doubleValue": number.as_f64() }),
+        Value::String(text) => json!({ "stringValue": text }),
+        other => json!({ "stringValue": other.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn span_json(span: &SpanData) -> Value {
+    let mut value = json!({
+        "traceId": hex::encode(span.trace_id),
+        "spanId": hex::encode(span.span_id),
+        "name": span.name,
+        "kind": span.kind as u8,
+        "startTimeUnixNano": span.start_ns.to_string(),
+        "endTimeUnixNano": span.end_ns.to_string(),
+        "attributes": span
+            .attributes
+            .iter()
+            .map(|(key, value)| attribute(key, value))
+            .collect::<Vec<_>>(),
+    });
+    if let Some(parent) = span.parent_id {
+        value["parentSpanId"] = json!(hex::encode(parent));
+    }
+    value["status"] = match &span.error {
+        Some(message) => json!({ "code": 2, "message": message }),
+        None => json!({ "code": 1 }),
+    };
+    value
+}
+
+fn payload(app: &AppHandle, spans: &[SpanData]) -> Value {
+    let resource = [
+        ("service.name", json!(SERVICE_NAME)),
+        (
+            "service.version",
+            json!(app.package_info().version.to_string()),
+        ),
+        (
+            "service.instance.id",
+            json!(crate::get_device_id(app.clone()).unwrap_or_default()),
+        ),
+        ("host.name", json!(crate::gateway_report::host_label(app))),
+        ("os.type", json!(std::env::consts::OS)),
+    ];
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": resource
+                    .iter()
+                    .map(|(key, value)| attribute(key, value))
+                    .collect::<Vec<_>>(),
+            },
+            "scopeSpans": [{
+                "scope": { "name": SERVICE_NAME },
+                "spans": spans.iter().map(span_json).collect::<Vec<_>>(),
+            }],
+        }],
+    })
+}
+
+struct Collector {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+fn collector(app: &AppHandle) -> Option<Collector> {
+    let state = app.state::<AppState>();
+    let config = state.config.lock().ok()?;
+    let endpoint = crate::non_empty(&config.otlp_endpoint)?.trim_end_matches('/');
+    let url = if endpoint.ends_with("/v1/traces") {
+        endpoint.to_string()
+    } else {
+        format!("{}/v1/traces", endpoint)
+    };
+    Some(Collector {
+        url,
+        headers: config
+            .otlp_headers
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect(),
+    })
+}
+
+async fn send(client: &reqwest::Client, collector: &Collector, body: &Value) -> Result<(), String> {
+    let mut request = client.post(&collector.url).json(body);
+    for (key, value) in &collector.headers {
+        request = request.header(key, value);
+    }
+    request
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map(|_| ())
+        .map_err(|err| err.to_string())
+}
+
+/// Collect finished spans and send them to the configured collector.
+pub async fn run_exporter(app: AppHandle) {
+    let (tx, mut rx) = mpsc::channel::<SpanData>(QUEUE_CAPACITY);
+    if QUEUE.set(tx).is_err() {
+        return;
+    }
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()
+        .unwrap_or_default();
+    let mut last_error: Option<String> = None;
+    loop {
+        let collector = collector(&app);
+        ENABLED.store(collector.is_some(), Ordering::Relaxed);
+
+        let mut batch = Vec::new();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(EXPORT_INTERVAL_SECS);
+        while batch.len() < MAX_BATCH {
+            match tokio::time::timeout_at(deadline, rx.recv()).await {
+                Ok(Some(span)) => batch.push(span),
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+        let Some(collector) = collector else {
+            continue;
+        };
+        if batch.is_empty() {
+            continue;
+        }
+        match send(&client, &collector, &payload(&app, &batch)).await {
+            Ok(()) => last_error = None,
+            Err(err) => {
+                // Say it once per outage, not once per batch
+                if last_error.as_deref() != Some(err.as_str()) {
+                    crate::push_log_line(&app, format!("trace export failed: {}", err));
+                }
+                last_error = Some(err);
+            }
+        }
+    }
+}
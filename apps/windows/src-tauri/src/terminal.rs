@@ -0,0 +1,428 @@
+// Remote terminal sessions requested by the gateway.
+//
+// With `remote_terminal_enabled` on, an operator can ask for a shell on this
+// machine without any SSH setup. Each request goes through the same approval
+// prompt as exec-host commands; once allowed, the user's default shell runs
+// in a PTY and its I/O is streamed over the gateway connection:
+//
+//   event terminal.open   {sessionId, cols?, rows?, requestedBy?}
+//   event terminal.input  {sessionId, data}          data, base64
+//   event terminal.resize {sessionId, cols, rows}
+//   event terminal.close  {sessionId}
+//
+//   terminal.started {sessionId, shell}
+//   terminal.data    {sessionId, data}
+//   terminal.exit    {sessionId, code?, error?}      also sent for denials
+//
+// Sessions end when the shell exits, the operator closes them, the user
+// closes them from the app, or the gateway connection drops.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc as std_mpsc, Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::sync::mpsc;
+
+use crate::gateway::{self, GatewayState};
+use crate::i18n::tf;
+use crate::AppState;
+
+const DEFAULT_COLS: u16 = 80;
+const DEFAULT_ROWS: u16 = 24;
+const CHUNK_SIZE: usize = 16 * 1024;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TerminalSessionInfo {
+    id: String,
+    shell: String,
+    requested_by: Option<String>,
+    started_at_ms: u64,
+}
+
+struct Session {
+    info: TerminalSessionInfo,
+    /// Tells this session apart from a later one reusing its id.
+    serial: u64,
+    master: Box<dyn MasterPty + Send>,
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    input: std_mpsc::Sender<Vec<u8>>,
+}
+
+enum Output {
+    Data(Vec<u8>),
+    Exit(Option<u32>),
+}
+
+#[derive(Default)]
+pub struct TerminalState {
+    sessions: Mutex<HashMap<String, Session>>,
+    next_serial: AtomicU64,
+}
+
+impl TerminalState {
+    fn contains(&self, id: &str) -> bool {
+        self.sessions
+            .lock()
+            .map(|sessions| sessions.contains_key(id))
+            .unwrap_or(false)
+    }
+
+    fn kill(&self, id: &str) -> bool {
+        let Ok(mut sessions) = self.sessions.lock() else {
+            return false;
+        };
+        match sessions.get_mut(id) {
+            Some(session) => {
+                let _ = session.killer.kill();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+async fn call(app: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    let state = app.state::<Arc<GatewayState>>().inner().clone();
+    gateway::request(&state, method.to_string(), Some(params))
+        .await?
+        .map_err(|err| format!("{} failed: {}", method, err))
+}
+
+fn enabled(app: &AppHandle) -> bool {
+    app.state::<AppState>()
+        .config
+        .lock()
+        .map(|config| config.remote_terminal_enabled)
+        .unwrap_or(false)
+}
+
+fn home_dir() -> Option<String> {
+    directories::BaseDirs::new().map(|base| base.home_dir().to_string_lossy().to_string())
+}
+
+fn pty_size(payload: &Value) -> PtySize {
+    let dimension = |key: &str, default: u16| {
+        payload
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|value| u16::try_from(value).ok())
+            .filter(|value| *value > 0)
+            .unwrap_or(default)
+    };
+    PtySize {
+        rows: dimension("rows", DEFAULT_ROWS),
+        cols: dimension("cols", DEFAULT_COLS),
+        pixel_width: 0,
+        pixel_height: 0,
+    }
+}
+
+/// Start the user's shell in a PTY; output and the exit status arrive on
+/// the returned channel, which closes once both are done.
+fn spawn_shell(
+    app: &AppHandle,
+    id: &str,
+    requested_by: Option<String>,
+    size: PtySize,
+) -> Result<(TerminalSessionInfo, mpsc::Receiver<Output>), String> {
+    let pair = native_pty_system()
+        .openpty(size)
+        .map_err(|err| format!("failed to open a PTY: {}", err))?;
+    let mut command = CommandBuilder::new_default_prog();
+    let shell = command.get_shell();
+    if let Some(home) = home_dir() {
+        command.cwd(home);
+    }
+    #[cfg(not(target_os = "windows"))]
+    command.env("TERM", "xterm-256color");
+    let mut child = pair
+        .slave
+        .spawn_command(command)
+        .map_err(|err| format!("failed to start {}: {}", shell, err))?;
+    // The shell holds the slave side now; keeping ours open would hide EOF
+    drop(pair.slave);
+
+    let mut reader = pair
+        .master
+        .try_clone_reader()
+        .map_err(|err| err.to_string())?;
+    let mut writer = pair.master.take_writer().map_err(|err| err.to_string())?;
+    let (output_tx, output_rx) = mpsc::channel::<Output>(16);
+    let (input_tx, input_rx) = std_mpsc::channel::<Vec<u8>>();
+
+    let data_tx = output_tx.clone();
+    std::thread::spawn(move || {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        loop {
+            match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(read) => {
+                    if data_tx
+                        .blocking_send(Output::Data(buf[..read].to_vec()))
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    std::thread::spawn(move || {
+        for data in input_rx {
+            if writer.write_all(&data).is_err() {
+                break;
+            }
+        }
+    });
+
+    let info = TerminalSessionInfo {
+        id: id.to_string(),
+        shell,
+        requested_by,
+        started_at_ms: crate::now_ms(),
+    };
+    let terminals = app.state::<TerminalState>();
+    let serial = terminals.next_serial.fetch_add(1, Ordering::Relaxed);
+    let session = Session {
+        info: info.clone(),
+        serial,
+        killer: child.clone_killer(),
+        master: pair.master,
+        input: input_tx,
+    };
+    // Another open with the same id may have been approved meanwhile
+    match terminals
+        .sessions
+        .lock()
+        .map_err(|err| err.to_string())?
+        .entry(id.to_string())
+    {
+        Entry::Occupied(_) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("session {} is already open", id));
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(session);
+        }
+    }
+
+    let waiter_app = app.clone();
+    let waiter_id = id.to_string();
+    std::thread::spawn(move || {
+        let code = child.wait().ok().map(|status| status.exit_code());
+        // Dropping the master ends the reader (ConPTY never reports EOF on
+        // its own) and the input writer
+        if let Ok(mut sessions) = waiter_app.state::<TerminalState>().sessions.lock() {
+            if sessions
+                .get(&waiter_id)
+                .is_some_and(|session| session.serial == serial)
+            {
+                sessions.remove(&waiter_id);
+            }
+        }
+        let _ = output_tx.blocking_send(Output::Exit(code));
+    });
+    Ok((info, output_rx))
+}
+
+/// Ask the user, then run the session until the shell exits.
+async fn open_session(app: AppHandle, payload: Value) {
+    let Some(id) = payload
+        .get("sessionId")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        return;
+    };
+    let exit = |error: String| {
+        let app = app.clone();
+        let id = id.clone();
+        async move {
+            crate::push_log_line(&app, format!("remote terminal {}: {}", id, error));
+            let _ = call(
+                &app,
+                "terminal.exit",
+                json!({ "sessionId": id, "error": error }),
+            )
+            .await;
+        }
+    };
+    if !enabled(&app) {
+        exit("remote terminal sessions are disabled on this machine".to_string()).await;
+        return;
+    }
+    if app.state::<TerminalState>().contains(&id) {
+        exit(format!("session {} is already open", id)).await;
+        return;
+    }
+    let requested_by = payload
+        .get("requestedBy")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+
+    let shell = CommandBuilder::new_default_prog().get_shell();
+    let preview = crate::ApprovalPreview {
+        id: crate::uuid_v4(),
+        raw_command: Some(tf("terminal.approval", &[("shell", &shell)])),
+        argv: vec![shell],
+        cwd: home_dir(),
+        env_keys: Vec::new(),
+        agent_id: requested_by.clone(),
+        session_key: Some(id.clone()),
+        expires_at_ms: crate::now_ms() + crate::APPROVAL_TIMEOUT_MS,
+    };
+    let decision = crate::await_approval_decision(&app, preview).await;
+    // "allow-always" is not remembered for terminals; every session asks
+    if decision == "deny" {
+        exit("denied by user".to_string()).await;
+        return;
+    }
+
+    let (info, mut output) = match spawn_shell(&app, &id, requested_by, pty_size(&payload)) {
+        Ok(started) => started,
+        Err(err) => {
+            exit(err).await;
+            return;
+        }
+    };
+    crate::push_log_line(
+        &app,
+        format!(
+            "remote terminal {} started ({}{})",
+            id,
+            info.shell,
+            info.requested_by
+                .as_deref()
+                .map(|by| format!(", requested by {}", by))
+                .unwrap_or_default()
+        ),
+    );
+    let _ = call(
+        &app,
+        "terminal.started",
+        json!({ "sessionId": id, "shell": info.shell }),
+    )
+    .await;
+
+    let mut code = None;
+    while let Some(message) = output.recv().await {
+        match message {
+            Output::Data(data) => {
+                let params = json!({ "sessionId": id, "data": STANDARD.encode(&data) });
+                if let Err(err) = call(&app, "terminal.data", params).await {
+                    eprintln!("remote terminal {}: {}", id, err);
+                    app.state::<TerminalState>().kill(&id);
+                }
+            }
+            Output::Exit(status) => code = status,
+        }
+    }
+    crate::push_log_line(
+        &app,
+        format!(
+            "remote terminal {} ended (exit code {})",
+            id,
+            code.map(|code| code.to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        ),
+    );
+    let _ = call(
+        &app,
+        "terminal.exit",
+        json!({ "sessionId": id, "code": code }),
+    )
+    .await;
+}
+
+/// Route a `terminal.*` gateway event.
+pub fn dispatch(app: &AppHandle, event: &str, payload: &Value) {
+    if event == "terminal.open" {
+        tauri::async_runtime::spawn(open_session(app.clone(), payload.clone()));
+        return;
+    }
+    let id = payload
+        .get("sessionId")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let terminals = app.state::<TerminalState>();
+    match event {
+        "terminal.input" => {
+            let Ok(data) = STANDARD.decode(
+                payload
+                    .get("data")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default(),
+            ) else {
+                return;
+            };
+            if let Ok(sessions) = terminals.sessions.lock() {
+                if let Some(session) = sessions.get(id) {
+                    let _ = session.input.send(data);
+                }
+            }
+        }
+        "terminal.resize" => {
+            if let Ok(sessions) = terminals.sessions.lock() {
+                if let Some(session) = sessions.get(id) {
+                    let _ = session.master.resize(pty_size(payload));
+                }
+            }
+        }
+        "terminal.close" => {
+            terminals.kill(id);
+        }
+        _ => {}
+    }
+}
+
+/// The gateway connection closed: nobody is attached any more.
+pub fn connection_closed(app: &AppHandle) {
+    let terminals = app.state::<TerminalState>();
+    let Ok(mut sessions) = terminals.sessions.lock() else {
+        return;
+    };
+    for session in sessions.values_mut() {
+        let _ = session.killer.kill();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Remote terminal sessions currently running on this machine.
+#[tauri::command]
+pub fn list_terminal_sessions(
+    terminals: tauri::State<'_, TerminalState>,
+) -> Result<Vec<TerminalSessionInfo>, String> {
+    let mut list: Vec<TerminalSessionInfo> = terminals
+        .sessions
+        .lock()
+        .map_err(|err| err.to_string())?
+        .values()
+        .map(|session| session.info.clone())
+        .collect();
+    list.sort_by_key(|info| info.started_at_ms);
+    Ok(list)
+}
+
+/// End a remote terminal session from this side.
+#[tauri::command]
+pub fn close_terminal_session(
+    terminals: tauri::State<'_, TerminalState>,
+    id: String,
+) -> Result<(), String> {
+    if terminals.kill(&id) {
+        Ok(())
+    } else {
+        Err(format!("no terminal session {}", id))
+    }
+}
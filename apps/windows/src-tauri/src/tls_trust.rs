@@ -0,0 +1,259 @@
+// Trust-on-first-use store for gateway TLS certificates.
+//
+// Without a pinned `tlsFingerprint` the node host would accept whatever
+// certificate the system trust store vouches for. Like SSH known_hosts, the
+// app records the gateway's certificate fingerprint the first time it sees
+// it, pins the node host to it, and refuses to start when it later changes.
+
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::crypto::CryptoProvider;
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{ClientConfig, ClientConnection, DigitallySignedStruct, SignatureScheme, StreamOwned};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownHost {
+    fingerprint: String,
+    first_seen_ms: u64,
+    last_seen_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KnownHostEntry {
+    host: String,
+    #[serde(flatten)]
+    known: KnownHost,
+}
+
+pub enum TrustCheck {
+    Trusted,
+    FirstUse(String),
+    Changed { expected: String, actual: String },
+}
+
+/// Accepts any certificate; the probe only wants to see what the gateway
+/// presents. Handshake signatures are still checked so the peer has to hold
+/// the certificate's key.
+#[derive(Debug)]
+struct CaptureCertificate(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for CaptureCertificate {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Complete a TLS handshake over `sock` without validating the certificate
+/// chain. Returns the stream and the SHA-256 fingerprint (lowercase hex) of
+/// the leaf certificate for the caller to check.
+pub fn handshake_capturing_fingerprint(
+    host: &str,
+    sock: TcpStream,
+) -> Result<(StreamOwned<ClientConnection, TcpStream>, String), String> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+    let config = ClientConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()
+        .map_err(|err| err.to_string())?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(CaptureCertificate(provider)))
+        .with_no_client_auth();
+    let server_name =
+        ServerName::try_from(host.to_string()).map_err(|err| format!("invalid host: {}", err))?;
+    let conn =
+        ClientConnection::new(Arc::new(config), server_name).map_err(|err| err.to_string())?;
+    let mut stream = StreamOwned::new(conn, sock);
+    while stream.conn.is_handshaking() {
+        stream
+            .conn
+            .complete_io(&mut stream.sock)
+            .map_err(|err| format!("TLS handshake with {} failed: {}", host, err))?;
+    }
+    let fingerprint = stream
+        .conn
+        .peer_certificates()
+        .and_then(|certs| certs.first())
+        .map(|cert| hex::encode(Sha256::digest(cert.as_ref())))
+        .ok_or_else(|| "gateway presented no certificate".to_string())?;
+    Ok((stream, fingerprint))
+}
+
+/// SHA-256 fingerprint (lowercase hex) of the gateway's leaf certificate.
+fn probe_fingerprint(host: &str, port: u16) -> Result<String, String> {
+    let addr = (host, port)
+        .to_socket_addrs()
+        .map_err(|err| format!("cannot resolve {}: {}", host, err))?
+        .next()
+        .ok_or_else(|| format!("cannot resolve {}", host))?;
+    let timeout = Duration::from_secs(PROBE_TIMEOUT_SECS);
+    let sock = TcpStream::connect_timeout(&addr, timeout).map_err(|err| err.to_string())?;
+    let _ = sock.set_read_timeout(Some(timeout));
+    let _ = sock.set_write_timeout(Some(timeout));
+    handshake_capturing_fingerprint(host, sock).map(|(_, fingerprint)| fingerprint)
+}
+
+fn known_hosts_path() -> Result<PathBuf, String> {
+    Ok(crate::openclaw_dir()?.join("node-client-known-hosts.json"))
+}
+
+fn load_known_hosts() -> BTreeMap<String, KnownHost> {
+    known_hosts_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default()
+}
+
+fn save_known_hosts(hosts: &BTreeMap<String, KnownHost>) -> Result<(), String> {
+    let path = known_hosts_path()?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    let payload = serde_json::to_string_pretty(hosts).map_err(|err| err.to_string())?;
+    let tmp_path = path.with_extension("json.tmp");
+    fs::write(&tmp_path, format!("{}\n", payload)).map_err(|err| err.to_string())?;
+    fs::rename(&tmp_path, &path).map_err(|err| err.to_string())?;
+    crate::restrict_file_permissions(&path);
+    Ok(())
+}
+
+fn host_key(host: &str, port: u16) -> String {
+    format!("{}:{}", host.trim().to_ascii_lowercase(), port)
+}
+
+/// The recorded fingerprint for a gateway, if it has been seen before.
+pub fn trusted_fingerprint(host: &str, port: u16) -> Option<String> {
+    load_known_hosts()
+        .remove(&host_key(host, port))
+        .map(|known| known.fingerprint)
+}
+
+/// Probe the gateway and compare its certificate with the store, recording
+/// it on first use. Emits `tls-fingerprint-first-use` or
+/// `tls-fingerprint-changed` for the UI.
+pub fn check_gateway(app: &AppHandle, host: &str, port: u16) -> Result<TrustCheck, String> {
+    let actual = probe_fingerprint(host, port)?;
+    let key = host_key(host, port);
+    let mut hosts = load_known_hosts();
+    let now = crate::now_ms();
+    let check = match hosts.get_mut(&key) {
+        Some(known) if known.fingerprint == actual => {
+            known.last_seen_ms = now;
+            TrustCheck::Trusted
+        }
+        Some(known) => TrustCheck::Changed {
+            expected: known.fingerprint.clone(),
+            actual: actual.clone(),
+        },
+        None => {
+            hosts.insert(
+                key.clone(),
+                KnownHost {
+                    fingerprint: actual.clone(),
+                    first_seen_ms: now,
+                    last_seen_ms: now,
+                },
+            );
+            TrustCheck::FirstUse(actual.clone())
+        }
+    };
+    match &check {
+        TrustCheck::Trusted => {
+            let _ = save_known_hosts(&hosts);
+        }
+        TrustCheck::FirstUse(fingerprint) => {
+            save_known_hosts(&hosts)?;
+            crate::push_log_line(
+                app,
+                format!(
+                    "trusting gateway {} on first use (sha256 {})",
+                    key, fingerprint
+                ),
+            );
+            let _ = app.emit(
+                "tls-fingerprint-first-use",
+                serde_json::json!({ "host": key, "fingerprint": fingerprint }),
+            );
+        }
+        TrustCheck::Changed { expected, actual } => {
+            let _ = app.emit(
+                "tls-fingerprint-changed",
+                serde_json::json!({ "host": key, "expected": expected, "actual": actual }),
+            );
+        }
+    }
+    Ok(check)
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn get_known_hosts() -> Vec<KnownHostEntry> {
+    load_known_hosts()
+        .into_iter()
+        .map(|(host, known)| KnownHostEntry { host, known })
+        .collect()
+}
+
+/// Drop a recorded fingerprint, e.g. after a legitimate certificate
+/// rotation; the next start trusts whatever the gateway presents.
+#[tauri::command]
+pub fn forget_known_host(host: String, port: u16) -> Result<(), String> {
+    let mut hosts = load_known_hosts();
+    if hosts.remove(&host_key(&host, port)).is_some() {
+        save_known_hosts(&hosts)?;
+    }
+    Ok(())
+}
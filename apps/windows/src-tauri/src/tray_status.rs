@@ -0,0 +1,346 @@
+// Tray icon that reflects node host and gateway health.
+//
+// The tray icon is the only part of the app most users see. A small status
+// dot drawn over the app icon shows the combined state — running, starting
+// or reconnecting, error, approvals waiting — and the tooltip spells it out,
+// e.g. "Node: running · Gateway: connected · 2 approvals pending". Pending
+// exec approvals also get a tray submenu with Allow once / Deny per entry,
+// and a "Recent Activity" submenu lists the last errors and reconnects.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::image::Image;
+use tauri::menu::{Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem, SubmenuBuilder};
+use tauri::{AppHandle, Manager, Wry};
+
+use crate::gateway::GatewayState;
+use crate::i18n::{self, t, tf};
+use crate::{AppState, LogEntry, NodeStatus};
+
+pub const TRAY_ID: &str = "main";
+
+const TRAY_REFRESH_INTERVAL_MS: u64 = 1_000;
+
+/// Menu item ids for approval decisions: "approval:<decision>:<id>".
+const APPROVAL_MENU_PREFIX: &str = "approval:";
+
+/// Longest command or log line shown in a tray submenu.
+const MENU_LABEL_MAX_CHARS: usize = 48;
+
+/// Log lines kept in the "Recent Activity" submenu.
+const RECENT_ACTIVITY_LINES: usize = 5;
+
+/// Lowercase fragments that make an otherwise ordinary log line worth
+/// showing under "Recent Activity".
+const ACTIVITY_KEYWORDS: &[&str] = &[
+    "warn",
+    "reconnect",
+    "disconnected",
+    "connected to gateway",
+    "watchdog",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum TrayHealth {
+    Stopped,
+    Running,
+    Connecting,
+    Error,
+    ApprovalsPending(usize),
+}
+
+impl TrayHealth {
+    /// RGB of the status dot; `None` leaves the icon as is.
+    fn dot_color(&self) -> Option<[u8; 3]> {
+        match self {
+            TrayHealth::Stopped => None,
+            TrayHealth::Running => Some([0x22, 0xc5, 0x5e]),
+            TrayHealth::Connecting => Some([0xf5, 0x9e, 0x0b]),
+            TrayHealth::Error => Some([0xef, 0x44, 0x44]),
+            TrayHealth::ApprovalsPending(_) => Some([0x3b, 0x82, 0xf6]),
+        }
+    }
+}
+
+/// Everything the tray shows; the icon and tooltip are only touched when
+/// this changes.
+#[derive(Debug, Clone, PartialEq)]
+struct TraySnapshot {
+    health: TrayHealth,
+    node: &'static str,
+    gateway: String,
+    /// (id, command) of each pending approval, oldest first.
+    approvals: Vec<(String, String)>,
+    /// Recent errors, warnings and connection changes, newest first.
+    activity: Vec<String>,
+    /// Language the menu and tooltip are built in.
+    lang: i18n::Lang,
+}
+
+impl TraySnapshot {
+    fn tooltip(&self) -> String {
+        let mut parts = vec![
+            tf("tray.tooltip.node", &[("status", &self.node)]),
+            tf("tray.tooltip.gateway", &[("status", &self.gateway)]),
+        ];
+        match self.approvals.len() {
+            0 => {}
+            1 => parts.push(t("tray.tooltip.one_approval").to_string()),
+            count => parts.push(tf("tray.tooltip.approvals", &[("count", &count)])),
+        }
+        parts.join(" · ")
+    }
+}
+
+/// Combined state, most urgent first: approvals waiting on the user, then
+/// errors, then anything short of a running node on a connected gateway.
+fn snapshot(app: &AppHandle, gateway: String) -> TraySnapshot {
+    let state = app.state::<AppState>();
+    let approvals: Vec<(String, String)> = state
+        .pending_approvals
+        .borrow()
+        .iter()
+        .map(|approval| {
+            let command = approval
+                .preview
+                .raw_command
+                .clone()
+                .unwrap_or_else(|| approval.preview.argv.join(" "));
+            (approval.id.clone(), command)
+        })
+        .collect();
+    let pending = approvals.len();
+    let (node_status, has_child, service_managed, activity) = match state.runtime.lock() {
+        Ok(runtime) => (
+            runtime.node_status.clone(),
+            runtime.child.is_some(),
+            runtime.service_managed,
+            runtime
+                .logs
+                .iter()
+                .rev()
+                .filter(|entry| is_significant(entry))
+                .take(RECENT_ACTIVITY_LINES)
+                .map(LogEntry::render)
+                .collect(),
+        ),
+        Err(_) => (Some(NodeStatus::Error), false, false, Vec::new()),
+    };
+    let node_status = node_status.unwrap_or(if has_child || service_managed {
+        NodeStatus::Starting
+    } else {
+        NodeStatus::Stopped
+    });
+    let health = match node_status {
+        _ if pending > 0 => TrayHealth::ApprovalsPending(pending),
+        NodeStatus::Error => TrayHealth::Error,
+        NodeStatus::Starting | NodeStatus::Reconnecting | NodeStatus::Disconnected => {
+            TrayHealth::Connecting
+        }
+        NodeStatus::Running if gateway == "connected" => TrayHealth::Running,
+        NodeStatus::Running => TrayHealth::Connecting,
+        NodeStatus::Stopped => TrayHealth::Stopped,
+    };
+    TraySnapshot {
+        health,
+        node: node_status.as_str(),
+        gateway,
+        approvals,
+        activity,
+        lang: i18n::lang(),
+    }
+}
+
+fn is_significant(entry: &LogEntry) -> bool {
+    if entry.level == "error" {
+        return true;
+    }
+    let lower = entry.message.to_lowercase();
+    ACTIVITY_KEYWORDS
+        .iter()
+        .any(|keyword| lower.contains(keyword))
+}
+
+/// The app icon with a filled status dot (and a dark ring so it reads on
+/// light and dark panels) in the bottom-right corner.
+fn badge_icon(base: &Image<'_>, color: [u8; 3]) -> Image<'static> {
+    let (width, height) = (base.width(), base.height());
+    let mut rgba = base.rgba().to_vec();
+    let size = width.min(height) as f32;
+    let radius = size * 0.22;
+    let ring = (size * 0.05).max(1.0);
+    let (cx, cy) = (width as f32 - radius - ring, height as f32 - radius - ring);
+    for y in 0..height {
+        for x in 0..width {
+            let dx = x as f32 + 0.5 - cx;
+            let dy = y as f32 + 0.5 - cy;
+            let distance = (dx * dx + dy * dy).sqrt();
+            let pixel = match distance {
+                d if d <= radius => [color[0], color[1], color[2], 0xff],
+                d if d <= radius + ring => [0x11, 0x11, 0x11, 0xff],
+                _ => continue,
+            };
+            let offset = ((y * width + x) * 4) as usize;
+            rgba[offset..offset + 4].copy_from_slice(&pixel);
+        }
+    }
+    Image::new_owned(rgba, width, height)
+}
+
+/// `text` on one line, cut to fit a menu item.
+fn menu_label(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.chars().count() <= MENU_LABEL_MAX_CHARS {
+        return text;
+    }
+    let truncated: String = text.chars().take(MENU_LABEL_MAX_CHARS - 1).collect();
+    format!("{}…", truncated)
+}
+
+/// The tray menu: node host controls, a "Pending Approvals" submenu with
+/// Allow once / Deny for each entry while any are waiting, recent activity,
+/// shortcuts to the config and logs folders, and copy device id / gateway URL.
+pub fn build_menu(
+    app: &AppHandle,
+    approvals: &[(String, String)],
+    activity: &[String],
+) -> tauri::Result<Menu<Wry>> {
+    let mut menu = MenuBuilder::new(app)
+        .item(&MenuItemBuilder::new(t("tray.open")).id("show").build(app)?)
+        .item(
+            &MenuItemBuilder::new(t("tray.start"))
+                .id("start")
+                .build(app)?,
+        )
+        .item(&MenuItemBuilder::new(t("tray.stop")).id("stop").build(app)?)
+        .item(
+            &MenuItemBuilder::new(t("tray.restart"))
+                .id("restart")
+                .build(app)?,
+        );
+    if !approvals.is_empty() {
+        let mut submenu = SubmenuBuilder::new(
+            app,
+            tf("tray.pending_approvals", &[("count", &approvals.len())]),
+        );
+        for (index, (id, command)) in approvals.iter().enumerate() {
+            if index > 0 {
+                submenu = submenu.item(&PredefinedMenuItem::separator(app)?);
+            }
+            submenu = submenu
+                .item(
+                    &MenuItemBuilder::new(menu_label(command))
+                        .enabled(false)
+                        .build(app)?,
+                )
+                .item(
+                    &MenuItemBuilder::new(t("approval.allow_once"))
+                        .id(format!("{}allow-once:{}", APPROVAL_MENU_PREFIX, id))
+                        .build(app)?,
+                )
+                .item(
+                    &MenuItemBuilder::new(t("approval.deny"))
+                        .id(format!("{}deny:{}", APPROVAL_MENU_PREFIX, id))
+                        .build(app)?,
+                );
+        }
+        menu = menu
+            .item(&PredefinedMenuItem::separator(app)?)
+            .item(&submenu.build()?);
+    }
+    let mut recent = SubmenuBuilder::new(app, t("tray.recent_activity"));
+    if activity.is_empty() {
+        recent = recent.item(
+            &MenuItemBuilder::new(t("tray.no_recent_activity"))
+                .enabled(false)
+                .build(app)?,
+        );
+    }
+    for line in activity {
+        recent = recent.item(
+            &MenuItemBuilder::new(menu_label(line))
+                .enabled(false)
+                .build(app)?,
+        );
+    }
+    menu.item(&PredefinedMenuItem::separator(app)?)
+        .item(&recent.build()?)
+        .item(
+            &MenuItemBuilder::new(t("tray.open_config_dir"))
+                .id("open-config-dir")
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::new(t("tray.open_logs_dir"))
+                .id("open-logs-dir")
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::new(t("tray.copy_device_id"))
+                .id("copy-device-id")
+                .build(app)?,
+        )
+        .item(
+            &MenuItemBuilder::new(t("tray.copy_gateway_url"))
+                .id("copy-gateway-url")
+                .build(app)?,
+        )
+        .item(&PredefinedMenuItem::separator(app)?)
+        .item(&MenuItemBuilder::new(t("tray.quit")).id("quit").build(app)?)
+        .build()
+}
+
+/// Handle an "approval:<decision>:<id>" menu item. Returns false for other ids.
+pub fn handle_menu_event(app: &AppHandle, menu_id: &str) -> bool {
+    let Some(rest) = menu_id.strip_prefix(APPROVAL_MENU_PREFIX) else {
+        return false;
+    };
+    if let Some((decision, id)) = rest.split_once(':') {
+        if let Err(err) = crate::send_approval_decision(app, id, decision) {
+            crate::push_log_line(app, format!("tray approval decision failed: {}", err));
+        }
+    }
+    true
+}
+
+fn apply(app: &AppHandle, snapshot: &TraySnapshot, previous: Option<&TraySnapshot>) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    if previous.map(|shown| &shown.health) != Some(&snapshot.health) {
+        if let Some(base) = app.default_window_icon() {
+            let icon = match snapshot.health.dot_color() {
+                Some(color) => badge_icon(base, color),
+                None => Image::new_owned(base.rgba().to_vec(), base.width(), base.height()),
+            };
+            let _ = tray.set_icon(Some(icon));
+        }
+    }
+    let menu_changed = previous.is_none_or(|shown| {
+        shown.approvals != snapshot.approvals
+            || shown.activity != snapshot.activity
+            || shown.lang != snapshot.lang
+    });
+    if menu_changed {
+        match build_menu(app, &snapshot.approvals, &snapshot.activity) {
+            Ok(menu) => {
+                let _ = tray.set_menu(Some(menu));
+            }
+            Err(err) => eprintln!("failed to rebuild tray menu: {}", err),
+        }
+    }
+    let _ = tray.set_tooltip(Some(snapshot.tooltip()));
+}
+
+pub async fn run_tray_status(app: AppHandle) {
+    let mut shown: Option<TraySnapshot> = None;
+    loop {
+        let gateway = app.state::<Arc<GatewayState>>().get_status().state;
+        let next = snapshot(&app, gateway);
+        if shown.as_ref() != Some(&next) {
+            apply(&app, &next, shown.as_ref());
+            shown = Some(next);
+        }
+        tokio::time::sleep(Duration::from_millis(TRAY_REFRESH_INTERVAL_MS)).await;
+    }
+}
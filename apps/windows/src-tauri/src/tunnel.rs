@@ -0,0 +1,542 @@
+// Port forwarding over the gateway connection.
+//
+// A tunnel carries TCP streams over the app's existing gateway WebSocket, so
+// nothing else has to be exposed on either side:
+//
+//   local   listens on 127.0.0.1:<listenPort> here; each connection is
+//           opened by the gateway to <targetHost>:<targetPort>
+//   remote  the gateway listens on <listenPort>; each connection it accepts
+//           is opened here to <targetHost>:<targetPort>
+//
+// Streams are multiplexed by id over these gateway methods and events:
+//
+//   tunnel.open     {tunnelId, streamId, host, port}   (local tunnels)
+//   tunnel.listen   {tunnelId, port} -> {port}          (remote tunnels)
+//   tunnel.unlisten {tunnelId}
+//   tunnel.data     {streamId, data}                    data, base64
+//   tunnel.close    {streamId, error?}                  end of stream
+//
+//   event tunnel.accept {tunnelId, streamId}            (remote tunnels)
+//   event tunnel.data   {streamId, data}
+//   event tunnel.close  {streamId, error?}
+//
+// Outgoing data waits for each `tunnel.data` reply before reading more,
+// which is the stream's only flow control. Incoming data is queued per stream
+// up to `INBOUND_QUEUE` chunks; a stream whose socket falls further behind is
+// closed. Streams die with the gateway connection; local listeners survive
+// it, remote tunnels do not.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use rand::RngCore;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+use crate::gateway::{self, GatewayState};
+
+const CHUNK_SIZE: usize = 32 * 1024;
+const INBOUND_QUEUE: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TunnelInfo {
+    id: String,
+    /// "local" | "remote"
+    direction: String,
+    /// Port listened on: here for local tunnels, on the gateway for remote
+    /// ones.
+    listen_port: u16,
+    target_host: String,
+    target_port: u16,
+    opened_at_ms: u64,
+    active_streams: u64,
+    bytes_sent: u64,
+    bytes_received: u64,
+}
+
+#[derive(Default)]
+struct Counters {
+    active_streams: AtomicU64,
+    bytes_sent: AtomicU64,
+    bytes_received: AtomicU64,
+}
+
+struct Tunnel {
+    info: TunnelInfo,
+    counters: Arc<Counters>,
+    /// Accept loop of a local tunnel.
+    listener: Option<tauri::async_runtime::JoinHandle<()>>,
+}
+
+enum Inbound {
+    Data(Vec<u8>),
+    Close,
+}
+
+struct Stream {
+    tunnel_id: String,
+    tx: mpsc::Sender<Inbound>,
+}
+
+#[derive(Default)]
+pub struct TunnelState {
+    tunnels: Mutex<HashMap<String, Tunnel>>,
+    streams: Mutex<HashMap<String, Stream>>,
+    seq: AtomicU64,
+}
+
+impl TunnelState {
+    fn counters(&self, tunnel_id: &str) -> Option<Arc<Counters>> {
+        self.tunnels
+            .lock()
+            .ok()?
+            .get(tunnel_id)
+            .map(|tunnel| tunnel.counters.clone())
+    }
+
+    fn add_stream(&self, tunnel_id: &str, stream_id: &str) -> mpsc::Receiver<Inbound> {
+        let (tx, rx) = mpsc::channel(INBOUND_QUEUE);
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.insert(
+                stream_id.to_string(),
+                Stream {
+                    tunnel_id: tunnel_id.to_string(),
+                    tx,
+                },
+            );
+        }
+        rx
+    }
+
+    /// Dropping the sender ends the stream's writer half.
+    fn remove_stream(&self, stream_id: &str) {
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.remove(stream_id);
+        }
+    }
+
+    fn remove_streams(&self, keep: impl Fn(&Stream) -> bool) {
+        if let Ok(mut streams) = self.streams.lock() {
+            streams.retain(|_, stream| keep(stream));
+        }
+    }
+
+    /// Queue `message` for the stream's socket. Returns false when the queue
+    /// was full and the stream has been dropped.
+    fn deliver(&self, stream_id: &str, message: Inbound) -> bool {
+        let Ok(mut streams) = self.streams.lock() else {
+            return true;
+        };
+        let Some(stream) = streams.get(stream_id) else {
+            return true;
+        };
+        match stream.tx.try_send(message) {
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                streams.remove(stream_id);
+                false
+            }
+            _ => true,
+        }
+    }
+}
+
+fn random_id() -> String {
+    let mut bytes = [0u8; 8];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+fn gateway_state(app: &AppHandle, method: &str) -> Result<Arc<GatewayState>, String> {
+    let state = app.state::<Arc<GatewayState>>().inner().clone();
+    if state.get_status().state != "connected" {
+        return Err("gateway is not connected".to_string());
+    }
+    if !state.supports(method) {
+        return Err(format!("the gateway does not support tunnels ({})", method));
+    }
+    Ok(state)
+}
+
+async fn call(app: &AppHandle, method: &str, params: Value) -> Result<Value, String> {
+    let state = app.state::<Arc<GatewayState>>().inner().clone();
+    gateway::request(&state, method.to_string(), Some(params))
+        .await?
+        .map_err(|err| format!("{} failed: {}", method, err))
+}
+
+/// Copy bytes both ways between `socket` and the gateway side of
+/// `stream_id` until both halves are closed.
+async fn pump(
+    app: AppHandle,
+    stream_id: String,
+    socket: TcpStream,
+    mut inbound: mpsc::Receiver<Inbound>,
+    counters: Arc<Counters>,
+) {
+    let (mut reader, mut writer) = socket.into_split();
+    counters.active_streams.fetch_add(1, Ordering::Relaxed);
+
+    let upstream = async {
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        let error = loop {
+            let read = match reader.read(&mut buf).await {
+                Ok(0) => break None,
+                Ok(read) => read,
+                Err(err) => break Some(err.to_string()),
+            };
+            let params = json!({ "streamId": stream_id, "data": STANDARD.encode(&buf[..read]) });
+            if let Err(err) = call(&app, "tunnel.data", params).await {
+                // The gateway side is gone; stop the writer half too
+                app.state::<TunnelState>().remove_stream(&stream_id);
+                return Err(err);
+            }
+            counters
+                .bytes_sent
+                .fetch_add(read as u64, Ordering::Relaxed);
+        };
+        call(
+            &app,
+            "tunnel.close",
+            json!({ "streamId": stream_id, "error": error }),
+        )
+        .await
+        .map(|_| ())
+    };
+
+    // Resolves to true when the stream was dropped (tunnel closed, gateway
+    // gone) rather than closed by the other end
+    let downstream = async {
+        let dropped = loop {
+            match inbound.recv().await {
+                Some(Inbound::Data(data)) => {
+                    if writer.write_all(&data).await.is_err() {
+                        break false;
+                    }
+                    counters
+                        .bytes_received
+                        .fetch_add(data.len() as u64, Ordering::Relaxed);
+                }
+                Some(Inbound::Close) => break false,
+                None => break true,
+            }
+        };
+        let _ = writer.shutdown().await;
+        dropped
+    };
+
+    // Each half closes on its own (TCP half-close), except that a dropped
+    // stream also abandons the local read
+    tokio::pin!(upstream, downstream);
+    let result = tokio::select! {
+        result = &mut upstream => {
+            downstream.await;
+            result
+        }
+        dropped = &mut downstream => {
+            if dropped {
+                Ok(())
+            } else {
+                upstream.await
+            }
+        }
+    };
+    if let Err(err) = result {
+        eprintln!("tunnel stream {}: {}", stream_id, err);
+    }
+    app.state::<TunnelState>().remove_stream(&stream_id);
+    counters.active_streams.fetch_sub(1, Ordering::Relaxed);
+}
+
+/// Accept loop of a local tunnel: one gateway stream per connection.
+async fn run_local(
+    app: AppHandle,
+    listener: TcpListener,
+    tunnel_id: String,
+    target_host: String,
+    target_port: u16,
+) {
+    loop {
+        let socket = match listener.accept().await {
+            Ok((socket, _)) => socket,
+            Err(err) => {
+                eprintln!("tunnel {}: accept failed: {}", tunnel_id, err);
+                continue;
+            }
+        };
+        let tunnels = app.state::<TunnelState>();
+        let Some(counters) = tunnels.counters(&tunnel_id) else {
+            return;
+        };
+        let stream_id = format!(
+            "{}-{}",
+            tunnel_id,
+            tunnels.seq.fetch_add(1, Ordering::Relaxed) + 1
+        );
+        // Register first so data that arrives right after the open isn't lost
+        let inbound = tunnels.add_stream(&tunnel_id, &stream_id);
+        let app = app.clone();
+        let tunnel_id = tunnel_id.clone();
+        let target_host = target_host.clone();
+        tauri::async_runtime::spawn(async move {
+            let params = json!({
+                "tunnelId": tunnel_id,
+                "streamId": stream_id,
+                "host": target_host,
+                "port": target_port,
+            });
+            match call(&app, "tunnel.open", params).await {
+                Ok(_) => pump(app, stream_id, socket, inbound, counters).await,
+                Err(err) => {
+                    app.state::<TunnelState>().remove_stream(&stream_id);
+                    crate::push_log_line(&app, format!("tunnel {}: {}", tunnel_id, err));
+                }
+            }
+        });
+    }
+}
+
+/// A connection accepted by the gateway for a remote tunnel.
+async fn accept_remote(app: AppHandle, tunnel_id: String, stream_id: String) {
+    let tunnels = app.state::<TunnelState>();
+    // Only remote tunnels take connections from the gateway
+    let target = tunnels.tunnels.lock().ok().and_then(|tunnels| {
+        tunnels
+            .get(&tunnel_id)
+            .filter(|tunnel| tunnel.info.direction == "remote")
+            .map(|tunnel| {
+                (
+                    tunnel.info.target_host.clone(),
+                    tunnel.info.target_port,
+                    tunnel.counters.clone(),
+                )
+            })
+    });
+    let Some((host, port, counters)) = target else {
+        let params = json!({ "streamId": stream_id, "error": "unknown tunnel" });
+        let _ = call(&app, "tunnel.close", params).await;
+        return;
+    };
+    let inbound = tunnels.add_stream(&tunnel_id, &stream_id);
+    match TcpStream::connect((host.as_str(), port)).await {
+        Ok(socket) => pump(app.clone(), stream_id, socket, inbound, counters).await,
+        Err(err) => {
+            tunnels.remove_stream(&stream_id);
+            let err = format!("connect to {}:{} failed: {}", host, port, err);
+            crate::push_log_line(&app, format!("tunnel {}: {}", tunnel_id, err));
+            let _ = call(
+                &app,
+                "tunnel.close",
+                json!({ "streamId": stream_id, "error": err }),
+            )
+            .await;
+        }
+    }
+}
+
+/// Route a `tunnel.*` gateway event to its stream.
+pub fn dispatch(app: &AppHandle, event: &str, payload: &Value) {
+    let field = |key: &str| {
+        payload
+            .get(key)
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let stream_id = field("streamId");
+    let tunnels = app.state::<TunnelState>();
+    match event {
+        "tunnel.accept" => {
+            tauri::async_runtime::spawn(accept_remote(app.clone(), field("tunnelId"), stream_id));
+        }
+        "tunnel.data" => match STANDARD.decode(field("data")) {
+            Ok(data) => {
+                if !tunnels.deliver(&stream_id, Inbound::Data(data)) {
+                    eprintln!("tunnel stream {}: receive queue full", stream_id);
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let params =
+                            json!({ "streamId": stream_id, "error": "receive queue full" });
+                        let _ = call(&app, "tunnel.close", params).await;
+                    });
+                }
+            }
+            Err(err) => {
+                eprintln!("tunnel stream {}: invalid data: {}", stream_id, err);
+                tunnels.remove_stream(&stream_id);
+            }
+        },
+        "tunnel.close" => {
+            tunnels.deliver(&stream_id, Inbound::Close);
+        }
+        _ => {}
+    }
+}
+
+/// The gateway connection closed: end every stream and drop remote tunnels,
+/// whose listeners lived on the gateway.
+pub fn connection_closed(app: &AppHandle) {
+    let tunnels = app.state::<TunnelState>();
+    tunnels.remove_streams(|_| false);
+    let removed: Vec<String> = match tunnels.tunnels.lock() {
+        Ok(mut map) => {
+            let ids: Vec<String> = map
+                .values()
+                .filter(|tunnel| tunnel.info.direction == "remote")
+                .map(|tunnel| tunnel.info.id.clone())
+                .collect();
+            for id in &ids {
+                map.remove(id);
+            }
+            ids
+        }
+        Err(_) => Vec::new(),
+    };
+    for id in removed {
+        crate::push_log_line(
+            app,
+            format!("remote tunnel {} closed with the gateway connection", id),
+        );
+    }
+}
+
+fn snapshot(tunnel: &Tunnel) -> TunnelInfo {
+    TunnelInfo {
+        active_streams: tunnel.counters.active_streams.load(Ordering::Relaxed),
+        bytes_sent: tunnel.counters.bytes_sent.load(Ordering::Relaxed),
+        bytes_received: tunnel.counters.bytes_received.load(Ordering::Relaxed),
+        ..tunnel.info.clone()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tauri commands
+// ---------------------------------------------------------------------------
+
+/// Open a tunnel. `direction` "local" listens on `listen_port` here (0 picks a
+/// free port) and forwards to `target_host:target_port` as seen from the
+/// gateway; "remote" has the gateway listen and forwards to the target as
+/// seen from this machine.
+#[tauri::command]
+pub async fn open_tunnel(
+    app: AppHandle,
+    direction: String,
+    listen_port: u16,
+    target_host: String,
+    target_port: u16,
+) -> Result<TunnelInfo, String> {
+    let target_host = target_host.trim().to_string();
+    if target_host.is_empty() || target_port == 0 {
+        return Err("target host and port are required".to_string());
+    }
+    let id = random_id();
+    let counters = Arc::new(Counters::default());
+    let (listen_port, listener) = match direction.as_str() {
+        "local" => {
+            gateway_state(&app, "tunnel.open")?;
+            let listener = TcpListener::bind(("127.0.0.1", listen_port))
+                .await
+                .map_err(|err| format!("cannot listen on port {}: {}", listen_port, err))?;
+            let port = listener.local_addr().map_err(|err| err.to_string())?.port();
+            let task = tauri::async_runtime::spawn(run_local(
+                app.clone(),
+                listener,
+                id.clone(),
+                target_host.clone(),
+                target_port,
+            ));
+            (port, Some(task))
+        }
+        "remote" => {
+            gateway_state(&app, "tunnel.listen")?;
+            let reply = call(
+                &app,
+                "tunnel.listen",
+                json!({ "tunnelId": id, "port": listen_port }),
+            )
+            .await?;
+            let port = reply
+                .get("port")
+                .and_then(Value::as_u64)
+                .and_then(|port| u16::try_from(port).ok())
+                .unwrap_or(listen_port);
+            (port, None)
+        }
+        other => {
+            return Err(format!(
+                "unknown tunnel direction {} (expected local or remote)",
+                other
+            ))
+        }
+    };
+    let tunnel = Tunnel {
+        info: TunnelInfo {
+            id: id.clone(),
+            direction,
+            listen_port,
+            target_host,
+            target_port,
+            opened_at_ms: crate::now_ms(),
+            active_streams: 0,
+            bytes_sent: 0,
+            bytes_received: 0,
+        },
+        counters,
+        listener,
+    };
+    let info = tunnel.info.clone();
+    app.state::<TunnelState>()
+        .tunnels
+        .lock()
+        .map_err(|err| err.to_string())?
+        .insert(id, tunnel);
+    crate::push_log_line(
+        &app,
+        format!(
+            "opened {} tunnel {}: port {} -> {}:{}",
+            info.direction, info.id, info.listen_port, info.target_host, info.target_port
+        ),
+    );
+    Ok(info)
+}
+
+/// Open tunnels with their live stream and byte counts.
+#[tauri::command]
+pub fn list_tunnels(tunnels: tauri::State<'_, TunnelState>) -> Result<Vec<TunnelInfo>, String> {
+    let mut list: Vec<TunnelInfo> = tunnels
+        .tunnels
+        .lock()
+        .map_err(|err| err.to_string())?
+        .values()
+        .map(snapshot)
+        .collect();
+    list.sort_by_key(|info| info.opened_at_ms);
+    Ok(list)
+}
+
+/// Stop a tunnel's listener and close its open streams.
+#[tauri::command]
+pub async fn close_tunnel(app: AppHandle, id: String) -> Result<(), String> {
+    let tunnels = app.state::<TunnelState>();
+    let tunnel = tunnels
+        .tunnels
+        .lock()
+        .map_err(|err| err.to_string())?
+        .remove(&id)
+        .ok_or_else(|| format!("no tunnel {}", id))?;
+    if let Some(listener) = tunnel.listener {
+        listener.abort();
+    }
+    tunnels.remove_streams(|stream| stream.tunnel_id != id);
+    if tunnel.info.direction == "remote" {
+        // Best effort: a dropped connection has already closed it
+        let _ = call(&app, "tunnel.unlisten", json!({ "tunnelId": id })).await;
+    }
+    crate::push_log_line(&app, format!("closed tunnel {}", id));
+    Ok(())
+}
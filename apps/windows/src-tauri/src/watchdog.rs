@@ -0,0 +1,121 @@
+// Gateway-driven node watchdog.
+//
+// A node host process can stay alive while its gateway session is wedged
+// (half-open socket, stuck reconnect loop). The app's own gateway connection
+// periodically asks the gateway whether our node is connected, and restarts
+// the child once it has looked offline for `watchdog_offline_minutes`.
+
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::gateway::{self, GatewayState};
+use crate::AppState;
+
+const WATCHDOG_INTERVAL_SECS: u64 = 60;
+
+enum Presence {
+    Online,
+    Offline,
+    Unknown,
+}
+
+async fn node_presence(gateway: &GatewayState, node_id: &str) -> Presence {
+    if gateway.get_status().state != "connected" {
+        return Presence::Unknown;
+    }
+    match gateway::request(
+        gateway,
+        "node.describe".to_string(),
+        Some(json!({ "nodeId": node_id })),
+    )
+    .await
+    {
+        Ok(Ok(payload)) => {
+            if payload.get("connected").and_then(|v| v.as_bool()) == Some(true) {
+                Presence::Online
+            } else {
+                Presence::Offline
+            }
+        }
+        // The gateway has never seen this node id
+        Ok(Err(err)) if err.contains("unknown nodeId") => Presence::Offline,
+        _ => Presence::Unknown,
+    }
+}
+
+/// Returns the node id to check and the offline threshold, or `None` when
+/// there is no app-owned child to watch.
+fn watch_target(app: &AppHandle) -> Option<(String, u64)> {
+    let state = app.state::<AppState>();
+    let (minutes, config_node_id) = {
+        let config = state.config.lock().ok()?;
+        (config.watchdog_offline_minutes, config.node_id.clone())
+    };
+    if minutes == 0 {
+        return None;
+    }
+    let runtime = state.runtime.lock().ok()?;
+    runtime.child.as_ref()?;
+    // Give a fresh child time to connect before judging it
+    let started_at = runtime.started_at_ms?;
+    let threshold_ms = u64::from(minutes) * 60_000;
+    if crate::now_ms().saturating_sub(started_at) < threshold_ms {
+        return None;
+    }
+    let node_id = runtime
+        .node_id
+        .clone()
+        .or(config_node_id)
+        .filter(|id| !id.trim().is_empty())?;
+    Some((node_id, threshold_ms))
+}
+
+pub async fn run_watchdog(app: AppHandle) {
+    let mut offline_since: Option<u64> = None;
+    loop {
+        tokio::time::sleep(Duration::from_secs(WATCHDOG_INTERVAL_SECS)).await;
+
+        let Some((node_id, threshold_ms)) = watch_target(&app) else {
+            offline_since = None;
+            continue;
+        };
+        let gateway = Arc::clone(&app.state::<Arc<GatewayState>>());
+        match node_presence(&gateway, &node_id).await {
+            Presence::Online => offline_since = None,
+            Presence::Unknown => {}
+            Presence::Offline => {
+                let now = crate::now_ms();
+                let since = *offline_since.get_or_insert(now);
+                if now.saturating_sub(since) < threshold_ms {
+                    continue;
+                }
+                offline_since = None;
+                let offline_for_secs = (now - since) / 1000;
+                crate::push_log_line(
+                    &app,
+                    format!(
+                        "watchdog: gateway reports node {} offline for {}s while the process is \
+                         running; restarting node host",
+                        node_id, offline_for_secs
+                    ),
+                );
+                let _ = app.emit(
+                    "node-watchdog-restart",
+                    json!({ "nodeId": node_id, "offlineForSecs": offline_for_secs }),
+                );
+                let restart_app = app.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || {
+                    crate::restart_node_internal(&restart_app)
+                })
+                .await
+                .map_err(|err| err.to_string())
+                .and_then(|r| r);
+                if let Err(err) = result {
+                    crate::push_log_line(&app, format!("watchdog restart failed: {}", err));
+                }
+            }
+        }
+    }
+}
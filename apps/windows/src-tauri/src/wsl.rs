@@ -0,0 +1,194 @@
+// Running an openclaw installed inside WSL.
+//
+// Many Windows users only have Node.js and openclaw inside a WSL distro.
+// When nothing turns up on the Windows side, discovery asks wsl.exe for an
+// install, and the node host is then started as
+// `wsl.exe -d <distro> --cd <dir> -e sh -lc 'exec "$0" "$@"' <openclaw> ...`
+// with Windows paths translated to their Linux form.
+//
+// A WSL binary is carried through the rest of the app as
+// "wsl:<distro>:<linux path>" (or "wsl:<linux path>" for the default distro).
+
+use std::process::Command;
+
+#[cfg(target_os = "windows")]
+use std::os::windows::process::CommandExt;
+#[cfg(target_os = "windows")]
+use std::process::Stdio;
+
+pub const BIN_PREFIX: &str = "wsl:";
+
+/// Env vars holding Windows paths; WSLENV's `/p` flag translates them.
+const PATH_ENV_VARS: &[&str] = &[
+    "NODE_EXTRA_CA_CERTS",
+    "OPENCLAW_GATEWAY_TLS_CERT",
+    "OPENCLAW_GATEWAY_TLS_KEY",
+];
+
+pub struct WslBin<'a> {
+    pub distro: Option<&'a str>,
+    pub path: &'a str,
+}
+
+/// Split a "wsl:..." binary into distro and Linux path.
+pub fn parse_bin(openclaw_bin: &str) -> Option<WslBin<'_>> {
+    let rest = openclaw_bin.strip_prefix(BIN_PREFIX)?;
+    if rest.starts_with('/') {
+        return Some(WslBin {
+            distro: None,
+            path: rest,
+        });
+    }
+    let (distro, path) = rest.split_once(':')?;
+    if distro.is_empty() || !path.starts_with('/') {
+        return None;
+    }
+    Some(WslBin {
+        distro: Some(distro),
+        path,
+    })
+}
+
+pub fn encode_bin(distro: Option<&str>, linux_path: &str) -> String {
+    match distro {
+        Some(distro) => format!("{}{}:{}", BIN_PREFIX, distro, linux_path),
+        None => format!("{}{}", BIN_PREFIX, linux_path),
+    }
+}
+
+/// The Linux form of a Windows path as seen from inside WSL:
+/// `\\wsl$\<distro>\home\me` (or `\\wsl.localhost\...`) becomes `/home/me`
+/// in that distro, and `C:\Users\me` becomes `/mnt/c/Users/me`.
+pub fn to_wsl_path(path: &str) -> Option<(Option<String>, String)> {
+    let normalized = path.trim().replace('/', "\\");
+    let lower = normalized.to_ascii_lowercase();
+    for prefix in [r"\\wsl$\", r"\\wsl.localhost\"] {
+        if lower.starts_with(prefix) {
+            let rest = &normalized[prefix.len()..];
+            let (distro, tail) = rest.split_once('\\').unwrap_or((rest, ""));
+            if distro.is_empty() {
+                return None;
+            }
+            let linux = format!("/{}", tail.trim_end_matches('\\').replace('\\', "/"));
+            return Some((Some(distro.to_string()), linux));
+        }
+    }
+    let mut chars = normalized.chars();
+    match (chars.next(), chars.next()) {
+        (Some(drive), Some(':')) if drive.is_ascii_alphabetic() => {
+            let tail = normalized[2..].trim_matches('\\').replace('\\', "/");
+            Some((
+                None,
+                format!("/mnt/{}/{}", drive.to_ascii_lowercase(), tail)
+                    .trim_end_matches('/')
+                    .to_string(),
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// The UNC path Windows uses for a Linux path in `distro`.
+#[cfg(target_os = "windows")]
+pub fn to_windows_path(distro: &str, linux_path: &str) -> String {
+    format!(
+        r"\\wsl.localhost\{}{}",
+        distro,
+        linux_path.replace('/', "\\")
+    )
+}
+
+/// `wsl.exe` running `bin` through a login shell, so PATH setup from the
+/// profile (nvm, volta, ...) applies. Arguments added by the caller are
+/// passed through to openclaw.
+pub fn command(bin: &WslBin<'_>, cwd: Option<&str>) -> Command {
+    let mut command = Command::new("wsl.exe");
+    if let Some(distro) = bin.distro {
+        command.args(["-d", distro]);
+    }
+    if let Some(dir) = cwd {
+        command.arg("--cd").arg(dir);
+    }
+    command.args(["-e", "sh", "-lc", r#"exec "$0" "$@""#, bin.path]);
+    command
+}
+
+/// Windows env vars only cross into WSL when listed in WSLENV. Forward
+/// everything set on `command` except PATH, which would replace the
+/// distro's own.
+pub fn forward_env(command: &mut Command) {
+    let mut names: Vec<String> = command
+        .get_envs()
+        .filter(|(_, value)| value.is_some())
+        .map(|(key, _)| key.to_string_lossy().to_string())
+        .filter(|key| !key.eq_ignore_ascii_case("PATH") && !key.eq_ignore_ascii_case("WSLENV"))
+        .map(|key| {
+            if PATH_ENV_VARS.contains(&key.as_str()) {
+                format!("{}/p", key)
+            } else {
+                key
+            }
+        })
+        .collect();
+    if let Ok(existing) = std::env::var("WSLENV") {
+        if !existing.is_empty() {
+            names.insert(0, existing);
+        }
+    }
+    if !names.is_empty() {
+        command.env("WSLENV", names.join(":"));
+    }
+}
+
+/// Ask the default distro for an openclaw on its login PATH. Returns the
+/// encoded binary and its directory as a `\\wsl.localhost` path.
+#[cfg(target_os = "windows")]
+pub fn discover(timeout_ms: u64) -> Option<(String, String)> {
+    let mut child = Command::new("wsl.exe")
+        .args([
+            "-e",
+            "sh",
+            "-lc",
+            r#"command -v openclaw && echo "$WSL_DISTRO_NAME""#,
+        ])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .creation_flags(crate::CREATE_NO_WINDOW)
+        .spawn()
+        .ok()?;
+    // Starting the WSL VM can take a few seconds; don't wait forever
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(timeout_ms);
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if std::time::Instant::now() < deadline => {
+                std::thread::sleep(std::time::Duration::from_millis(50));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let linux_path = lines.next().filter(|path| path.starts_with('/'))?;
+    let distro = lines.next().filter(|name| !name.is_empty())?;
+    let linux_dir = linux_path
+        .rsplit_once('/')
+        .map(|(dir, _)| dir)
+        .unwrap_or("/");
+    Some((
+        encode_bin(Some(distro), linux_path),
+        to_windows_path(distro, linux_dir),
+    ))
+}
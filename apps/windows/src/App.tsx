@@ -22,7 +22,7 @@ import { getErrorMessage } from "./lib/utils";
 import type { PageId, PageState } from "./types";
 import type { NodeStatusString } from "./tauri/types";
 import { getConfig, getStatus, gatewayConnect } from "./tauri/commands";
-import { onNodeStatusChanged } from "./tauri/events";
+import { onConfigChanged, onNodeStatusChanged } from "./tauri/events";
 
 // Initialize gateway event subscriptions for Zustand stores
 subscribeGatewayEvents();
@@ -95,6 +95,29 @@ function AppContent() {
     };
   }, [refreshStatus]);
 
+  // Saved host/port/TLS/credential changes only reach the gateway connection
+  // on reconnect; skip it when the user isn't connected in the first place.
+  const gatewayActive = gwStatus.state !== "disconnected";
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let disposed = false;
+    void onConfigChanged((impact) => {
+      if (impact.reconnectGateway && gatewayActive) {
+        void handleRetryConnect();
+      }
+    }).then((fn) => {
+      if (disposed) {
+        fn();
+        return;
+      }
+      unlisten = fn;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [gatewayActive, handleRetryConnect]);
+
   useEffect(() => {
     if (gwStatus.state === "error" && gwStatus.error) {
       setGatewayActionError(gwStatus.error);
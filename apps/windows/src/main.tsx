@@ -1,6 +1,7 @@
 import React from "react";
 import ReactDOM from "react-dom/client";
 import { App } from "./App";
+import { ApprovalPopup } from "./pages/ApprovalPopup";
 import "./theme/global.css";
 import "./theme/components.css";
 import "./theme/layout.css";
@@ -13,6 +14,9 @@ window.addEventListener("unhandledrejection", (event) => {
   console.error("[unhandled-rejection]", event.reason);
 });
 
+// The always-on-top approval popup loads the same bundle with ?window=approval
+const isApprovalWindow = new URLSearchParams(window.location.search).get("window") === "approval";
+
 const rootElement = document.getElementById("root");
 if (!rootElement) {
   throw new Error('Missing root element "#root"');
@@ -20,6 +24,6 @@ if (!rootElement) {
 
 ReactDOM.createRoot(rootElement).render(
   <React.StrictMode>
-    <App />
+    {isApprovalWindow ? <ApprovalPopup /> : <App />}
   </React.StrictMode>
 );
@@ -0,0 +1,65 @@
+import { useState, useEffect } from "react";
+import { AnimatePresence } from "motion/react";
+import type { ApprovalPreview, ApprovalDecision } from "../tauri/types";
+import { getPendingApprovals, decideApproval } from "../tauri/commands";
+import { onApprovalPending, onApprovalResolved } from "../tauri/events";
+import { ApprovalCard } from "./Approvals";
+
+/**
+ * Contents of the small always-on-top approval window. Lists the exec-host
+ * approvals waiting on this machine; the backend closes the window once the
+ * last one is decided or expires.
+ */
+export function ApprovalPopup() {
+  const [approvals, setApprovals] = useState<ApprovalPreview[]>([]);
+
+  useEffect(() => {
+    const unlistens: Array<() => void> = [];
+
+    void getPendingApprovals().then((pending) => {
+      setApprovals(pending ?? []);
+    }).catch(() => {});
+
+    void onApprovalPending((a) => {
+      setApprovals((prev) => {
+        if (prev.find((x) => x.id === a.id)) { return prev; }
+        return [...prev, a];
+      });
+    }).then((fn) => unlistens.push(fn));
+
+    void onApprovalResolved((id) => {
+      setApprovals((prev) => prev.filter((a) => a.id !== id));
+    }).then((fn) => unlistens.push(fn));
+
+    return () => unlistens.forEach((fn) => fn());
+  }, []);
+
+  async function handleDecide(id: string, decision: ApprovalDecision) {
+    try {
+      await decideApproval(id, decision);
+    } catch { /* already resolved or expired; card removed by event */ }
+  }
+
+  return (
+    <div style={{
+      display: "flex",
+      flexDirection: "column",
+      gap: "10px",
+      padding: "12px",
+      height: "100vh",
+      overflowY: "auto",
+      background: "var(--bg-primary)",
+    }}>
+      <div style={{ fontSize: "12px", color: "var(--text-secondary)" }}>
+        {approvals.length === 1
+          ? "A command is waiting for approval"
+          : `${approvals.length} commands are waiting for approval`}
+      </div>
+      <AnimatePresence mode="popLayout">
+        {approvals.map((a) => (
+          <ApprovalCard key={a.id} approval={a} onDecide={handleDecide} />
+        ))}
+      </AnimatePresence>
+    </div>
+  );
+}
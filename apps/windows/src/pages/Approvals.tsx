@@ -11,7 +11,7 @@ interface ApprovalsProps {
   onCountChange: (n: number) => void;
 }
 
-function ApprovalCard({
+export function ApprovalCard({
   approval,
   onDecide,
 }: {
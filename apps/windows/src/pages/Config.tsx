@@ -5,17 +5,25 @@ import { Card } from "../components/common/Card";
 import { Button } from "../components/common/Button";
 import { Input } from "../components/ui/Input";
 import { Checkbox } from "../components/ui/Checkbox";
+import { Select } from "../components/ui/Select";
 import { PageTransition } from "../components/motion/PageTransition";
 import { FadeIn } from "../components/motion/FadeIn";
-import type { NodeClientConfig } from "../tauri/types";
+import type { KnownHost, MetricGroup, NodeClientConfig, NotifierEvent, NotifierService, OpenclawConfigUpdate } from "../tauri/types";
 import {
   getConfig, setConfig, importOpenclawConfig,
   enableAutostart, disableAutostart, isAutostartEnabled,
   getInstallPath, setInstallPath, detectInstallPath,
-  getDeviceId,
+  getDeviceId, restartNode,
+  exportSettingsBundle, importSettingsBundle,
+  getKnownHosts, forgetKnownHost, applyOpenclawConfigUpdate,
+  revealSecret, SECRET_PLACEHOLDER, testNotifier, sendTestErrorReport,
+  type SecretField,
 } from "../tauri/commands";
-import { onInstallPathDetected } from "../tauri/events";
-import { isWindows, isLinux } from "../utils/platform";
+import {
+  onConfigChanged, onInstallPathDetected,
+  onTlsFingerprintFirstUse, onTlsFingerprintChanged, onOpenclawConfigUpdated,
+} from "../tauri/events";
+import { isWindows, isLinux, isMacOS } from "../utils/platform";
 import {
   Network,
   User,
@@ -27,6 +35,10 @@ import {
   AlertCircle,
   Download,
   Fingerprint,
+  Plug,
+  Radio,
+  Bell,
+  ScrollText,
 } from "lucide-react";
 
 const DEFAULT_CONFIG: NodeClientConfig = {
@@ -39,12 +51,94 @@ const DEFAULT_CONFIG: NodeClientConfig = {
   autoStartNode: false,
   useExecHost: false,
   execHostFallback: true,
+  execHostTokenInKeychain: false,
   gatewayToken: null,
   gatewayPassword: null,
   installPath: null,
   useBundledRuntime: true,
+  useManagedNode: false,
+  devRoot: null,
+  extraEnv: {},
+  extraArgs: [],
+  orphanPolicy: "terminate",
+  lowPriority: false,
+  watchdogOfflineMinutes: 5,
+  runAsUser: null,
+  runAsPassword: null,
+  autoStartDelaySecs: 0,
+  autoStartWaitForGateway: true,
+  autoStartMaxWaitSecs: 120,
+  startMinimizedToTray: false,
+  approvalNotifications: true,
+  workingDir: null,
+  tlsCaPath: null,
+  tlsClientCertPath: null,
+  tlsClientKeyPath: null,
+  tlsInsecureSkipVerify: false,
+  autoRestartOnConfigChange: true,
+  keychainRef: null,
+  encryptAtRest: false,
+  syncOpenclawConfig: false,
+  gatewayReporting: false,
+  crashReportUploadUrl: null,
+  updateChannel: "stable",
+  updateCheckIntervalHours: 24,
+  locale: null,
+  localApiEnabled: false,
+  localApiPort: 18790,
+  localApiToken: null,
+  mqttEnabled: false,
+  mqttHost: null,
+  mqttPort: 1883,
+  mqttUsername: null,
+  mqttPassword: null,
+  mqttTopicPrefix: "openclaw/node-client",
+  mqttHaDiscovery: true,
+  eventHooks: {},
+  remoteTerminalEnabled: false,
+  clipboardSyncEnabled: false,
+  clipboardSyncTypes: ["text"],
+  clipboardSyncMaxBytes: 1048576,
+  metricsReporting: false,
+  metricsIntervalSecs: 60,
+  metricsReported: ["cpu", "memory", "disk", "node"],
+  slackWebhookUrl: null,
+  discordWebhookUrl: null,
+  notifierEvents: ["approval-pending", "approval-resolved", "node-crashed"],
+  notifierTemplates: {},
+  logForwarding: "off",
+  logForwardSyslogAddress: null,
+  windowsEventLog: false,
+  otlpEndpoint: null,
+  otlpHeaders: {},
+  errorReportDsn: null,
+  ntfyTopicUrl: null,
+  ntfyToken: null,
+  pushoverUserKey: null,
+  pushoverAppToken: null,
+  pushApprovalLink: null,
+};
+
+const NOTIFIER_SERVICE_NAMES: Record<NotifierService, string> = {
+  slack: "Slack",
+  discord: "Discord",
+  ntfy: "ntfy",
+  pushover: "Pushover",
 };
 
+const NOTIFIER_EVENTS: { event: NotifierEvent; label: string }[] = [
+  { event: "approval-pending", label: "Approval requested" },
+  { event: "approval-resolved", label: "Approval answered" },
+  { event: "node-crashed", label: "Node host went down" },
+];
+
+const METRIC_GROUPS: { group: MetricGroup; label: string }[] = [
+  { group: "cpu", label: "CPU usage" },
+  { group: "memory", label: "Memory" },
+  { group: "disk", label: "Free disk space" },
+  { group: "node", label: "Node host CPU and memory (including commands it runs)" },
+];
+
 function autostartLabel(): string {
   if (isWindows()) {return "Start app on Windows login";}
   if (isLinux()) {return "Start app on login (XDG autostart)";}
@@ -71,14 +165,49 @@ export function Config() {
   const [detecting, setDetecting] = useState(false);
   const [detectStatus, setDetectStatus] = useState<string | null>(null);
   const [deviceId, setDeviceId] = useState<string | null>(null);
+  const [restartPending, setRestartPending] = useState(false);
+  const [bundlePassphrase, setBundlePassphrase] = useState("");
+  const [bundleStatus, setBundleStatus] = useState<string | null>(null);
+  const [knownHosts, setKnownHosts] = useState<KnownHost[]>([]);
+  const [fingerprintChanged, setFingerprintChanged] = useState<string | null>(null);
+  const [openclawUpdate, setOpenclawUpdate] = useState<OpenclawConfigUpdate | null>(null);
+  const [checkBeforeSave, setCheckBeforeSave] = useState(true);
+  const [validationFailed, setValidationFailed] = useState(false);
+  const [revealed, setRevealed] = useState<Record<string, boolean>>({});
+  const [notifierStatus, setNotifierStatus] = useState<string | null>(null);
+  const [errorReportStatus, setErrorReportStatus] = useState<string | null>(null);
 
   useEffect(() => {
     void getConfig().then((c) => setForm(c)).catch(() => {});
     void isAutostartEnabled().then((v) => setAutostartLogin(Boolean(v))).catch(() => {});
     void getInstallPath().then((p) => setInstallPathState(p)).catch(() => {});
     void getDeviceId().then(setDeviceId).catch(() => {});
+    void getKnownHosts().then(setKnownHosts).catch(() => {});
     const unlisten = onInstallPathDetected((path) => setInstallPathState(path));
-    return () => { void unlisten.then((fn) => fn()); };
+    // Stay in sync with saves made elsewhere (other views, auto-detection,
+    // external edits to node-client.json)
+    const unlistenConfig = onConfigChanged((impact) => {
+      setForm((prev) => ({ ...prev, ...impact.values }));
+      if (impact.restartNode && !impact.restarting) {
+        setRestartPending(true);
+      }
+    });
+    const unlistenFirstUse = onTlsFingerprintFirstUse(() => {
+      void getKnownHosts().then(setKnownHosts).catch(() => {});
+    });
+    const unlistenOpenclaw = onOpenclawConfigUpdated(setOpenclawUpdate);
+    const unlistenChanged = onTlsFingerprintChanged((event) => {
+      setFingerprintChanged(
+        `${event.host} now presents sha256 ${event.actual} (trusted: ${event.expected})`
+      );
+    });
+    return () => {
+      void unlisten.then((fn) => fn());
+      void unlistenConfig.then((fn) => fn());
+      void unlistenFirstUse.then((fn) => fn());
+      void unlistenChanged.then((fn) => fn());
+      void unlistenOpenclaw.then((fn) => fn());
+    };
   }, []);
 
   function set<K extends keyof NodeClientConfig>(key: K, value: NodeClientConfig[K]) {
@@ -93,6 +222,16 @@ export function Config() {
     } catch { /* silent — full save will surface errors */ }
   }
 
+  async function handleForgetKnownHost() {
+    try {
+      await forgetKnownHost(form.host, form.port);
+      setFingerprintChanged(null);
+      setKnownHosts(await getKnownHosts());
+    } catch (e) {
+      setError(String(e));
+    }
+  }
+
   async function handleAutostartLoginChange(checked: boolean) {
     setAutostartLogin(checked);
     try {
@@ -118,20 +257,112 @@ export function Config() {
     }
   }
 
-  async function handleSave() {
+  async function handleSave(validate = checkBeforeSave) {
     setSaving(true);
     setError(null);
+    setValidationFailed(false);
     try {
-      await setConfig(form);
+      const impact = await setConfig(form, validate);
+      setRestartPending(impact.restartNode && !impact.restarting);
       setSaved(true);
       setTimeout(() => setSaved(false), 2500);
     } catch (err) {
       setError(String(err));
+      setValidationFailed(validate);
     } finally {
       setSaving(false);
     }
   }
 
+  async function handleExportBundle() {
+    setError(null);
+    setBundleStatus(null);
+    try {
+      const path = await exportSettingsBundle(bundlePassphrase);
+      if (path) {
+        setBundleStatus(
+          bundlePassphrase
+            ? `Exported to ${path} (credentials encrypted)`
+            : `Exported to ${path} (without credentials)`,
+        );
+      }
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleImportBundle() {
+    setError(null);
+    setBundleStatus(null);
+    try {
+      const impact = await importSettingsBundle(bundlePassphrase);
+      if (impact) {
+        setForm(await getConfig());
+        setRestartPending(impact.restartNode && !impact.restarting);
+        setBundleStatus("Settings imported");
+      }
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleRestartNow() {
+    setError(null);
+    try {
+      await restartNode();
+      setRestartPending(false);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleToggleReveal(field: SecretField) {
+    if (revealed[field]) {
+      setRevealed((prev) => ({ ...prev, [field]: false }));
+      return;
+    }
+    try {
+      if (form[field] === SECRET_PLACEHOLDER) {
+        set(field, await revealSecret(field));
+      }
+      setRevealed((prev) => ({ ...prev, [field]: true }));
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleTestNotifier(service: NotifierService) {
+    setError(null);
+    setNotifierStatus(null);
+    try {
+      await testNotifier(service);
+      setNotifierStatus(`Test message sent to ${NOTIFIER_SERVICE_NAMES[service]}`);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleTestErrorReport() {
+    setError(null);
+    setErrorReportStatus(null);
+    try {
+      await sendTestErrorReport();
+      setErrorReportStatus("Test event sent");
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleApplyOpenclawUpdate() {
+    setError(null);
+    try {
+      await applyOpenclawConfigUpdate();
+      setOpenclawUpdate(null);
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
   async function handleBrowse() {
     const selected = await open({ directory: true, multiple: false, title: "Select OpenClaw install directory" });
     if (selected && typeof selected === "string") {
@@ -148,7 +379,18 @@ export function Config() {
       if (result) {
         setInstallPathState(result.binDir);
         await setInstallPath(result.binDir);
-        setDetectStatus(`Found via ${result.method}: ${result.binPath}`);
+        const version = result.version ? ` (${result.version})` : "";
+        const skipped = result.skipped.length
+          ? `; skipped ${result.skipped.map((c) => `${c.binPath} (${c.reason})`).join(", ")}`
+          : "";
+        const timedOut = result.tiers.filter((t) => t.status === "timed-out");
+        const slow = timedOut.length
+          ? `; timed out: ${timedOut.map((t) => t.method).join(", ")}`
+          : "";
+        const others = result.alternatives.length
+          ? `; also found ${result.alternatives.map((c) => `${c.binPath}${c.version ? ` (${c.version})` : ""}`).join(", ")}`
+          : "";
+        setDetectStatus(`Found via ${result.method}: ${result.binPath}${version}${others}${skipped}${slow}`);
       } else {
         setDetectStatus("Not found. Install with: npm install -g openclaw");
       }
@@ -214,6 +456,57 @@ export function Config() {
                   onChange={(v) => set("tlsFingerprint", v || null)}
                   placeholder="sha256:..."
                 />
+                <div className="flex flex-col gap-3 mt-3">
+                  <Input
+                    label="CA Bundle (optional)"
+                    value={form.tlsCaPath ?? ""}
+                    onChange={(v) => set("tlsCaPath", v || null)}
+                    placeholder="/path/to/ca.pem"
+                  />
+                  <Input
+                    label="Client Certificate (optional)"
+                    value={form.tlsClientCertPath ?? ""}
+                    onChange={(v) => set("tlsClientCertPath", v || null)}
+                    placeholder="/path/to/client.pem"
+                  />
+                  <Input
+                    label="Client Key (optional)"
+                    value={form.tlsClientKeyPath ?? ""}
+                    onChange={(v) => set("tlsClientKeyPath", v || null)}
+                    placeholder="/path/to/client-key.pem"
+                  />
+                  <Checkbox
+                    checked={form.tlsInsecureSkipVerify}
+                    onChange={(v) => set("tlsInsecureSkipVerify", v)}
+                    label="Skip certificate verification (insecure)"
+                  />
+                </div>
+                {!form.tlsFingerprint && (() => {
+                  const known = knownHosts.find(
+                    (h) => h.host === `${form.host.trim().toLowerCase()}:${form.port}`
+                  );
+                  return (
+                    <div className="mt-3">
+                      <p className="text-xs text-neutral-600 break-all">
+                        {known
+                          ? `Trusted on first use: sha256 ${known.fingerprint}`
+                          : "The gateway certificate will be trusted on first connection."}
+                      </p>
+                      {fingerprintChanged && (
+                        <p className="text-xs text-error-400 mt-1 break-all">
+                          Certificate changed: {fingerprintChanged}
+                        </p>
+                      )}
+                      {known && (
+                        <div className="mt-2">
+                          <Button variant="secondary" onClick={() => void handleForgetKnownHost()}>
+                            Forget Certificate
+                          </Button>
+                        </div>
+                      )}
+                    </div>
+                  );
+                })()}
               </motion.div>
             )}
           </AnimatePresence>
@@ -242,19 +535,157 @@ export function Config() {
         <Card>
           <SectionHeader icon={KeyRound} title="Authentication" />
           <div className="flex flex-col gap-3">
-            <Input
-              label="Gateway Token (optional)"
-              type="password"
-              value={form.gatewayToken ?? ""}
-              onChange={(v) => set("gatewayToken", v || null)}
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Gateway Token (optional)"
+                type={revealed.gatewayToken ? "text" : "password"}
+                value={form.gatewayToken ?? ""}
+                onChange={(v) => set("gatewayToken", v || null)}
+              />
+              {form.gatewayToken && (
+                <Button variant="ghost" onClick={() => void handleToggleReveal("gatewayToken")}>
+                  {revealed.gatewayToken ? "Hide" : "Show"}
+                </Button>
+              )}
+            </div>
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Gateway Password (optional)"
+                type={revealed.gatewayPassword ? "text" : "password"}
+                value={form.gatewayPassword ?? ""}
+                onChange={(v) => set("gatewayPassword", v || null)}
+              />
+              {form.gatewayPassword && (
+                <Button variant="ghost" onClick={() => void handleToggleReveal("gatewayPassword")}>
+                  {revealed.gatewayPassword ? "Hide" : "Show"}
+                </Button>
+              )}
+            </div>
+          </div>
+          <div className="mt-3">
+            <Checkbox
+              checked={form.encryptAtRest ?? false}
+              onChange={(v) => set("encryptAtRest", v)}
+              label="Encrypt node-client.json with a key kept in the system keychain"
+            />
+          </div>
+          <div className="mt-3">
+            <Checkbox
+              checked={form.syncOpenclawConfig ?? false}
+              onChange={(v) => set("syncOpenclawConfig", v)}
+              label="Write gateway port, TLS and credential changes back to openclaw.json"
             />
+          </div>
+          <div className="mt-3">
+            <Checkbox
+              checked={form.gatewayReporting ?? false}
+              onChange={(v) => set("gatewayReporting", v)}
+              label="Report this machine's node status and errors to the gateway (shown under Instances)"
+            />
+          </div>
+          <div className="mt-3">
+            <Checkbox
+              checked={form.metricsReporting ?? false}
+              onChange={(v) => set("metricsReporting", v)}
+              label="Report CPU, memory, disk and node host usage to the gateway dashboard"
+            />
+          </div>
+          {form.metricsReporting && (
+            <div className="mt-3 flex flex-col gap-3">
+              <Input
+                label="Metrics Interval (seconds)"
+                type="number"
+                value={String(form.metricsIntervalSecs ?? 60)}
+                onChange={(v) => set("metricsIntervalSecs", Math.max(10, Number(v) || 60))}
+              />
+              {METRIC_GROUPS.map(({ group, label }) => (
+                <Checkbox
+                  key={group}
+                  checked={(form.metricsReported ?? []).includes(group)}
+                  onChange={(v) =>
+                    set(
+                      "metricsReported",
+                      v
+                        ? [...(form.metricsReported ?? []), group]
+                        : (form.metricsReported ?? []).filter((g) => g !== group)
+                    )
+                  }
+                  label={label}
+                />
+              ))}
+            </div>
+          )}
+          <div className="mt-3">
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Crash Report Upload URL (optional)"
+                type={revealed.crashReportUploadUrl ? "text" : "password"}
+                value={form.crashReportUploadUrl ?? ""}
+                onChange={(v) => set("crashReportUploadUrl", v || null)}
+                placeholder="https://crash.example.com/upload"
+              />
+              {form.crashReportUploadUrl && (
+                <Button
+                  variant="ghost"
+                  onClick={() => void handleToggleReveal("crashReportUploadUrl")}
+                >
+                  {revealed.crashReportUploadUrl ? "Hide" : "Show"}
+                </Button>
+              )}
+            </div>
+            <p className="text-xs text-neutral-500 mt-1">
+              Panic backtraces, minidumps and unclean-exit records are sent here on the next
+              launch. Leave empty to keep them on this machine only.
+            </p>
+          </div>
+          <div className="mt-3">
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Error Reporting DSN (Sentry-compatible, optional)"
+                type={revealed.errorReportDsn ? "text" : "password"}
+                value={form.errorReportDsn ?? ""}
+                onChange={(v) => set("errorReportDsn", v || null)}
+                placeholder="https://key@sentry.example.com/1"
+              />
+              {form.errorReportDsn && (
+                <>
+                  <Button variant="ghost" onClick={() => void handleToggleReveal("errorReportDsn")}>
+                    {revealed.errorReportDsn ? "Hide" : "Show"}
+                  </Button>
+                  <Button variant="ghost" onClick={() => void handleTestErrorReport()}>
+                    Test
+                  </Button>
+                </>
+              )}
+            </div>
+            <p className="text-xs text-neutral-500 mt-1">
+              {errorReportStatus ??
+                "Node host start failures, gateway handshake failures and exec-host socket errors are sent here, with secrets and your home directory masked."}
+            </p>
+          </div>
+          <div className="mt-3">
             <Input
-              label="Gateway Password (optional)"
-              type="password"
-              value={form.gatewayPassword ?? ""}
-              onChange={(v) => set("gatewayPassword", v || null)}
+              label="OpenTelemetry Collector (OTLP/HTTP, optional)"
+              value={form.otlpEndpoint ?? ""}
+              onChange={(v) => set("otlpEndpoint", v || null)}
+              placeholder="http://localhost:4318"
             />
+            <p className="text-xs text-neutral-500 mt-1">
+              Traces of the gateway handshake, gateway RPCs and exec requests are exported here.
+              Collector auth headers go in otlpHeaders in node-client.json.
+            </p>
           </div>
+          {(form.gatewayToken || form.gatewayPassword) && (
+            <p className="text-xs text-neutral-600 mt-3">
+              {form.keychainRef
+                ? "Credentials are stored in the system keychain."
+                : "Credentials are stored in node-client.json (no system keychain available)."}
+            </p>
+          )}
         </Card>
 
         {/* ── Startup ────────────────────────────────────── */}
@@ -266,11 +697,31 @@ export function Config() {
               onChange={(v) => void savePartial({ autoStartNode: v })}
               label="Auto-start node when app launches"
             />
+            <Checkbox
+              checked={form.autoStartWaitForGateway ?? true}
+              onChange={(v) => void savePartial({ autoStartWaitForGateway: v })}
+              label="Wait for the gateway to be reachable before auto-starting"
+            />
+            <Checkbox
+              checked={form.lowPriority ?? false}
+              onChange={(v) => void savePartial({ lowPriority: v })}
+              label="Run node at below-normal priority (applies on next start)"
+            />
+            <Checkbox
+              checked={form.autoRestartOnConfigChange ?? true}
+              onChange={(v) => void savePartial({ autoRestartOnConfigChange: v })}
+              label="Restart the running node automatically when saved settings affect it"
+            />
             <Checkbox
               checked={autostartLogin}
               onChange={(v) => void handleAutostartLoginChange(v)}
               label={autostartLabel()}
             />
+            <Checkbox
+              checked={form.startMinimizedToTray ?? false}
+              onChange={(v) => void savePartial({ startMinimizedToTray: v })}
+              label="Stay in the tray when started at login (don't open the window)"
+            />
           </div>
           <p className="text-xs text-neutral-600 mt-3">
             {isWindows()
@@ -296,19 +747,346 @@ export function Config() {
                   initial={{ opacity: 0, height: 0 }}
                   animate={{ opacity: 1, height: "auto" }}
                   exit={{ opacity: 0, height: 0 }}
-                  className="overflow-hidden pl-1"
+                  className="overflow-hidden pl-1 flex flex-col gap-2"
                 >
                   <Checkbox
                     checked={form.execHostFallback}
                     onChange={(v) => void savePartial({ execHostFallback: v })}
                     label="Fall back to direct execution if exec-host is unavailable"
                   />
+                  <Checkbox
+                    checked={form.execHostTokenInKeychain ?? false}
+                    onChange={(v) => void savePartial({ execHostTokenInKeychain: v })}
+                    label="Keep the exec-host token in the system keychain (applies on next launch)"
+                  />
                 </motion.div>
               )}
             </AnimatePresence>
+            {(isWindows() || isMacOS()) && (
+              <Checkbox
+                checked={form.approvalNotifications ?? true}
+                onChange={(v) => void savePartial({ approvalNotifications: v })}
+                label="Ask for approvals with an Allow / Deny notification instead of a popup window"
+              />
+            )}
+            {isWindows() && (
+              <Checkbox
+                checked={form.windowsEventLog ?? false}
+                onChange={(v) => void savePartial({ windowsEventLog: v })}
+                label="Record approvals, executed commands, policy changes and token rotations in the Windows Event Log"
+              />
+            )}
+            <Checkbox
+              checked={form.remoteTerminalEnabled ?? false}
+              onChange={(v) => void savePartial({ remoteTerminalEnabled: v })}
+              label="Allow the gateway to request remote terminal sessions (each session still needs approval)"
+            />
+            <Checkbox
+              checked={form.clipboardSyncEnabled ?? false}
+              onChange={(v) => void savePartial({ clipboardSyncEnabled: v })}
+              label="Allow pushing and pulling the clipboard over the gateway connection (only when you ask)"
+            />
+            {form.clipboardSyncEnabled && (
+              <>
+                <Checkbox
+                  checked={(form.clipboardSyncTypes ?? ["text"]).includes("image")}
+                  onChange={(v) =>
+                    void savePartial({ clipboardSyncTypes: v ? ["text", "image"] : ["text"] })
+                  }
+                  label="Include images (text is always allowed)"
+                />
+                <Input
+                  label="Size limit (KiB)"
+                  type="number"
+                  value={String(Math.round((form.clipboardSyncMaxBytes ?? 1048576) / 1024))}
+                  onChange={(v) => set("clipboardSyncMaxBytes", (Number(v) || 1024) * 1024)}
+                />
+              </>
+            )}
+          </div>
+        </Card>
+
+        {/* ── Local API ──────────────────────────────────── */}
+        <Card>
+          <SectionHeader icon={Plug} title="Local API" />
+          <div className="flex flex-col gap-3">
+            <Checkbox
+              checked={form.localApiEnabled ?? false}
+              onChange={(v) => void savePartial({ localApiEnabled: v })}
+              label="Serve a token-protected HTTP API on localhost for scripts and launchers"
+            />
+            {form.localApiEnabled && (
+              <>
+                <Input
+                  label="Port"
+                  type="number"
+                  value={String(form.localApiPort ?? 18790)}
+                  onChange={(v) => set("localApiPort", Number(v) || 18790)}
+                />
+                <div className="flex items-end gap-2">
+                  <Input
+                    className="flex-1"
+                    label="Bearer Token"
+                    type={revealed.localApiToken ? "text" : "password"}
+                    value={form.localApiToken ?? ""}
+                    onChange={(v) => set("localApiToken", v || null)}
+                  />
+                  {form.localApiToken && (
+                    <Button variant="ghost" onClick={() => void handleToggleReveal("localApiToken")}>
+                      {revealed.localApiToken ? "Hide" : "Show"}
+                    </Button>
+                  )}
+                </div>
+              </>
+            )}
+          </div>
+          <p className="text-xs text-neutral-600 mt-3">
+            Listens on 127.0.0.1 only. Send <code>Authorization: Bearer &lt;token&gt;</code> with
+            GET /v1/status, POST /v1/node/start|stop|restart, GET /v1/approvals and
+            POST /v1/approvals/&lt;id&gt;. A token is generated when the API is first enabled.
+          </p>
+        </Card>
+
+        {/* ── MQTT ───────────────────────────────────────── */}
+        <Card>
+          <SectionHeader icon={Radio} title="MQTT" />
+          <div className="flex flex-col gap-3">
+            <Checkbox
+              checked={form.mqttEnabled ?? false}
+              onChange={(v) => void savePartial({ mqttEnabled: v })}
+              label="Publish node, gateway and approval state to an MQTT broker"
+            />
+            {form.mqttEnabled && (
+              <>
+                <div className="flex items-end gap-2">
+                  <Input
+                    className="flex-1"
+                    label="Broker Host"
+                    value={form.mqttHost ?? ""}
+                    onChange={(v) => set("mqttHost", v || null)}
+                  />
+                  <Input
+                    label="Port"
+                    type="number"
+                    value={String(form.mqttPort ?? 1883)}
+                    onChange={(v) => set("mqttPort", Number(v) || 1883)}
+                  />
+                </div>
+                <Input
+                  label="Username (optional)"
+                  value={form.mqttUsername ?? ""}
+                  onChange={(v) => set("mqttUsername", v || null)}
+                />
+                <div className="flex items-end gap-2">
+                  <Input
+                    className="flex-1"
+                    label="Password (optional)"
+                    type={revealed.mqttPassword ? "text" : "password"}
+                    value={form.mqttPassword ?? ""}
+                    onChange={(v) => set("mqttPassword", v || null)}
+                  />
+                  {form.mqttPassword && (
+                    <Button variant="ghost" onClick={() => void handleToggleReveal("mqttPassword")}>
+                      {revealed.mqttPassword ? "Hide" : "Show"}
+                    </Button>
+                  )}
+                </div>
+                <Input
+                  label="Topic Prefix"
+                  value={form.mqttTopicPrefix ?? "openclaw/node-client"}
+                  onChange={(v) => set("mqttTopicPrefix", v)}
+                />
+                <Checkbox
+                  checked={form.mqttHaDiscovery ?? true}
+                  onChange={(v) => set("mqttHaDiscovery", v)}
+                  label="Publish Home Assistant discovery configs"
+                />
+              </>
+            )}
+          </div>
+          <p className="text-xs text-neutral-600 mt-3">
+            Publishes &lt;prefix&gt;/node, /gateway and /approvals/pending (retained),
+            /approvals/event for each approval, and /availability as the last will.
+          </p>
+        </Card>
+
+        {/* ── Notifications ──────────────────────────────── */}
+        <Card>
+          <SectionHeader icon={Bell} title="Notifications" />
+          <div className="flex flex-col gap-3">
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Slack Incoming Webhook URL"
+                type={revealed.slackWebhookUrl ? "text" : "password"}
+                value={form.slackWebhookUrl ?? ""}
+                onChange={(v) => set("slackWebhookUrl", v || null)}
+                placeholder="https://hooks.slack.com/services/..."
+              />
+              {form.slackWebhookUrl && (
+                <>
+                  <Button
+                    variant="ghost"
+                    onClick={() => void handleToggleReveal("slackWebhookUrl")}
+                  >
+                    {revealed.slackWebhookUrl ? "Hide" : "Show"}
+                  </Button>
+                  <Button variant="ghost" onClick={() => void handleTestNotifier("slack")}>
+                    Test
+                  </Button>
+                </>
+              )}
+            </div>
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Discord Webhook URL"
+                type={revealed.discordWebhookUrl ? "text" : "password"}
+                value={form.discordWebhookUrl ?? ""}
+                onChange={(v) => set("discordWebhookUrl", v || null)}
+                placeholder="https://discord.com/api/webhooks/..."
+              />
+              {form.discordWebhookUrl && (
+                <>
+                  <Button
+                    variant="ghost"
+                    onClick={() => void handleToggleReveal("discordWebhookUrl")}
+                  >
+                    {revealed.discordWebhookUrl ? "Hide" : "Show"}
+                  </Button>
+                  <Button variant="ghost" onClick={() => void handleTestNotifier("discord")}>
+                    Test
+                  </Button>
+                </>
+              )}
+            </div>
+            {NOTIFIER_EVENTS.map(({ event, label }) => (
+              <Checkbox
+                key={event}
+                checked={(form.notifierEvents ?? []).includes(event)}
+                onChange={(v) =>
+                  set(
+                    "notifierEvents",
+                    v
+                      ? [...(form.notifierEvents ?? []), event]
+                      : (form.notifierEvents ?? []).filter((e) => e !== event)
+                  )
+                }
+                label={label}
+              />
+            ))}
+          </div>
+          <div className="flex flex-col gap-3 mt-4">
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="ntfy Topic URL"
+                value={form.ntfyTopicUrl ?? ""}
+                onChange={(v) => set("ntfyTopicUrl", v || null)}
+                placeholder="https://ntfy.sh/my-topic"
+              />
+              {form.ntfyTopicUrl && (
+                <Button variant="ghost" onClick={() => void handleTestNotifier("ntfy")}>
+                  Test
+                </Button>
+              )}
+            </div>
+            {form.ntfyTopicUrl && (
+              <div className="flex items-end gap-2">
+                <Input
+                  className="flex-1"
+                  label="ntfy Access Token"
+                  type={revealed.ntfyToken ? "text" : "password"}
+                  value={form.ntfyToken ?? ""}
+                  onChange={(v) => set("ntfyToken", v || null)}
+                  placeholder="Only for protected topics"
+                />
+                {form.ntfyToken && (
+                  <Button variant="ghost" onClick={() => void handleToggleReveal("ntfyToken")}>
+                    {revealed.ntfyToken ? "Hide" : "Show"}
+                  </Button>
+                )}
+              </div>
+            )}
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Pushover User Key"
+                type={revealed.pushoverUserKey ? "text" : "password"}
+                value={form.pushoverUserKey ?? ""}
+                onChange={(v) => set("pushoverUserKey", v || null)}
+              />
+              {form.pushoverUserKey && (
+                <Button variant="ghost" onClick={() => void handleToggleReveal("pushoverUserKey")}>
+                  {revealed.pushoverUserKey ? "Hide" : "Show"}
+                </Button>
+              )}
+            </div>
+            <div className="flex items-end gap-2">
+              <Input
+                className="flex-1"
+                label="Pushover Application Token"
+                type={revealed.pushoverAppToken ? "text" : "password"}
+                value={form.pushoverAppToken ?? ""}
+                onChange={(v) => set("pushoverAppToken", v || null)}
+              />
+              {form.pushoverAppToken && (
+                <Button variant="ghost" onClick={() => void handleToggleReveal("pushoverAppToken")}>
+                  {revealed.pushoverAppToken ? "Hide" : "Show"}
+                </Button>
+              )}
+              {form.pushoverUserKey && form.pushoverAppToken && (
+                <Button variant="ghost" onClick={() => void handleTestNotifier("pushover")}>
+                  Test
+                </Button>
+              )}
+            </div>
+            <Input
+              label="Approval Link"
+              value={form.pushApprovalLink ?? ""}
+              onChange={(v) => set("pushApprovalLink", v || null)}
+              placeholder="Gateway Control UI ({id} is the approval id)"
+            />
           </div>
+          {notifierStatus && <p className="text-xs text-neutral-500 mt-3">{notifierStatus}</p>}
+          <p className="text-xs text-neutral-600 mt-3">
+            Rate limited to one message a second and 20 a minute per webhook. ntfy and
+            Pushover only get pending approvals. Save before testing; message templates can be
+            changed with notifierTemplates in node-client.json.
+          </p>
         </Card>
 
+        {/* ── System Log ───────────────────────────────── */}
+        {isLinux() && (
+          <Card>
+            <SectionHeader icon={ScrollText} title="System Log" />
+            <div className="flex flex-col gap-3">
+              <Select
+                label="Forward node host output to"
+                value={form.logForwarding}
+                onChange={(v) => set("logForwarding", v as NodeClientConfig["logForwarding"])}
+                options={[
+                  { value: "off", label: "Nothing (app log only)" },
+                  { value: "journald", label: "systemd journal" },
+                  { value: "syslog", label: "Syslog" },
+                ]}
+              />
+              {form.logForwarding === "syslog" && (
+                <Input
+                  label="Syslog Server (host:port, UDP)"
+                  value={form.logForwardSyslogAddress ?? ""}
+                  onChange={(v) => set("logForwardSyslogAddress", v || null)}
+                  placeholder="Local /dev/log"
+                />
+              )}
+            </div>
+            <p className="text-xs text-neutral-600 mt-3">
+              Lines are tagged openclaw-node with a priority from their level; journald entries
+              also carry OPENCLAW_STREAM and OPENCLAW_LEVEL fields.
+            </p>
+          </Card>
+        )}
+
         {/* ── Install Location ───────────────────────────── */}
         <Card>
           <SectionHeader icon={FolderSearch} title="Install Location" />
@@ -319,6 +1097,13 @@ export function Config() {
               label="Prefer bundled Node.js runtime (if available)"
             />
           </div>
+          <div className="mb-3">
+            <Checkbox
+              checked={form.useManagedNode ?? false}
+              onChange={(v) => void savePartial({ useManagedNode: v })}
+              label="Use app-managed Node.js when no system Node.js is installed"
+            />
+          </div>
           <p className="text-xs text-neutral-500 mb-3">
             Override the directory where the{" "}
             <code className="font-mono text-primary-300 bg-neutral-800 px-1 rounded">openclaw</code>{" "}
@@ -361,6 +1146,20 @@ export function Config() {
               </motion.p>
             )}
           </AnimatePresence>
+
+          <div className="mt-3">
+            <Input
+              label="Development checkout (optional)"
+              value={form.devRoot ?? ""}
+              onChange={(v) => set("devRoot", v || null)}
+              placeholder="/path/to/openclaw"
+            />
+            <p className="text-xs text-neutral-500 mt-1">
+              Runs the checkout's built{" "}
+              <code className="font-mono text-primary-300 bg-neutral-800 px-1 rounded">openclaw.mjs</code>{" "}
+              with the system Node.js instead of any installed CLI.
+            </p>
+          </div>
         </Card>
 
         {/* ── Device Identity ──────────────────────────────── */}
@@ -382,11 +1181,43 @@ export function Config() {
           </Card>
         </FadeIn>
 
+        {/* ── Settings bundle ────────────────────────────── */}
+        <Card>
+          <SectionHeader icon={Download} title="Settings Bundle" />
+          <div className="flex flex-col gap-3">
+            <Input
+              label="Bundle Passphrase (optional)"
+              type="password"
+              value={bundlePassphrase}
+              onChange={setBundlePassphrase}
+              placeholder="Include credentials, encrypted with this passphrase"
+            />
+            <div className="flex items-center gap-3">
+              <Button variant="secondary" onClick={() => void handleExportBundle()}>
+                Export Settings…
+              </Button>
+              <Button variant="secondary" onClick={() => void handleImportBundle()}>
+                Import Settings…
+              </Button>
+            </div>
+            {bundleStatus && <p className="text-xs text-neutral-500">{bundleStatus}</p>}
+          </div>
+          <p className="text-xs text-neutral-600 mt-3">
+            Bundles carry gateway, startup and exec-policy settings. Install path and node ID stay
+            per machine.
+          </p>
+        </Card>
+
         {/* ── Save / Import ──────────────────────────────── */}
         <div className="flex items-center gap-3 pb-4">
-          <Button variant="primary" onClick={handleSave} loading={saving}>
+          <Button variant="primary" onClick={() => void handleSave()} loading={saving}>
             {saving ? "Saving…" : "Save Configuration"}
           </Button>
+          <Checkbox
+            checked={checkBeforeSave}
+            onChange={setCheckBeforeSave}
+            label="Check gateway first"
+          />
           <Button variant="ghost" onClick={handleImport} loading={importing}>
             {importing ? "Importing…" : "Import from OpenClaw"}
           </Button>
@@ -405,10 +1236,36 @@ export function Config() {
             )}
           </AnimatePresence>
 
+          {openclawUpdate && (
+            <span className="flex items-center gap-2 text-sm text-warning-400">
+              openclaw.json changed ({openclawUpdate.changedFields.join(", ")}).
+              <Button variant="ghost" onClick={handleApplyOpenclawUpdate}>
+                Apply
+              </Button>
+              <Button variant="ghost" onClick={() => setOpenclawUpdate(null)}>
+                Ignore
+              </Button>
+            </span>
+          )}
+
+          {restartPending && (
+            <span className="flex items-center gap-2 text-sm text-warning-400">
+              Restart the node to apply these changes.
+              <Button variant="ghost" onClick={handleRestartNow}>
+                Restart now
+              </Button>
+            </span>
+          )}
+
           {error && (
             <span className="flex items-center gap-1 text-sm text-error-400">
               <AlertCircle size={14} />
               {error}
+              {validationFailed && (
+                <Button variant="ghost" onClick={() => void handleSave(false)}>
+                  Save anyway
+                </Button>
+              )}
             </span>
           )}
         </div>
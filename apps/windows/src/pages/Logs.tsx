@@ -6,8 +6,9 @@ import { Badge } from "../components/common/Badge";
 import { EmptyState } from "../components/common/EmptyState";
 import { PageTransition } from "../components/motion/PageTransition";
 import { FadeIn } from "../components/motion/FadeIn";
-import { getStatus } from "../tauri/commands";
+import { exportLogs, getStatus } from "../tauri/commands";
 import { onNodeLog } from "../tauri/events";
+import type { NodeLogEntry } from "../tauri/types";
 import { useGateway } from "../gateway/context";
 import { cn, getErrorMessage } from "../lib/utils";
 import {
@@ -18,6 +19,7 @@ import {
   ScrollText,
   Search,
   Filter,
+  Download,
 } from "lucide-react";
 
 // ---------------------------------------------------------------------------
@@ -156,13 +158,32 @@ function parseStructuredEntry(entry: { level?: string; ts?: number; subsystem?:
 // Node log helpers
 // ---------------------------------------------------------------------------
 
-function nodeLineColor(line: string): string {
-  if (line.startsWith("[stdout]")) { return "var(--color-sky-400, #38bdf8)"; }
-  if (line.startsWith("[stderr]")) { return "var(--color-error-400, #f87171)"; }
-  if (line.startsWith("[ui]")) { return "var(--color-neutral-400, #a1a1aa)"; }
+function nodeEntryFilter(entry: NodeLogEntry): LogFilter {
+  return entry.stream === "app" ? "ui" : entry.stream;
+}
+
+function nodeEntryColor(entry: NodeLogEntry): string {
+  if (entry.level === "error") { return "var(--color-error-400, #f87171)"; }
+  if (entry.level === "warn") { return "var(--color-warning-400, #fbbf24)"; }
+  if (entry.stream === "stdout") { return "var(--color-sky-400, #38bdf8)"; }
+  if (entry.stream === "app") { return "var(--color-neutral-400, #a1a1aa)"; }
   return "var(--color-neutral-500, #71717a)";
 }
 
+function formatNodeEntry(entry: NodeLogEntry): string {
+  const line = entry.stream === "app" ? entry.message : `[${entry.stream}] ${entry.message}`;
+  return entry.repeat > 1 ? `${line} (x${entry.repeat})` : line;
+}
+
+function appendNodeEntry(prev: NodeLogEntry[], entry: NodeLogEntry): NodeLogEntry[] {
+  const last = prev[prev.length - 1];
+  // Repeats of the previous line update it in place
+  if (entry.repeat > 1 && last && last.stream === entry.stream && last.message === entry.message) {
+    return [...prev.slice(0, -1), entry];
+  }
+  return [...prev, entry].slice(-NODE_LOG_CAP);
+}
+
 // ---------------------------------------------------------------------------
 // Logs component
 // ---------------------------------------------------------------------------
@@ -173,7 +194,7 @@ export function Logs() {
   const connected = gwStatus.state === "connected";
 
   // ---- Node logs state ----
-  const [nodeLines, setNodeLines] = useState<string[]>([]);
+  const [nodeLines, setNodeLines] = useState<NodeLogEntry[]>([]);
   const [nodeFilters, setNodeFilters] = useState<Set<LogFilter>>(new Set(["stdout", "stderr", "ui"]));
   const [pairingDismissed, setPairingDismissed] = useState(false);
   const [showPairing, setShowPairing] = useState(false);
@@ -225,19 +246,16 @@ export function Logs() {
 
   useEffect(() => {
     void getStatus().then((s) => {
-      const initial = s.logs ?? [];
+      const initial = s.logEntries ?? [];
       setNodeLines(initial.slice(-NODE_LOG_CAP));
     }).catch((error: unknown) => {
       console.error("[getStatus] failed to preload node logs", error);
     });
 
     let disposed = false;
-    void onNodeLog((line) => {
-      setNodeLines((prev) => {
-        const next = [...prev, line].slice(-NODE_LOG_CAP);
-        return next;
-      });
-      checkPairing(line);
+    void onNodeLog((entries) => {
+      setNodeLines((prev) => entries.reduce(appendNodeEntry, prev));
+      entries.forEach((entry) => checkPairing(entry.message));
     })
       .then((fn) => {
         if (disposed) {
@@ -328,12 +346,7 @@ export function Logs() {
 
   // ---- Filter logic ----
   const filteredNodeLines = useMemo(() => {
-    return nodeLines.filter((line) => {
-      if (line.startsWith("[stdout]") && !nodeFilters.has("stdout")) { return false; }
-      if (line.startsWith("[stderr]") && !nodeFilters.has("stderr")) { return false; }
-      if (line.startsWith("[ui]") && !nodeFilters.has("ui")) { return false; }
-      return true;
-    });
+    return nodeLines.filter((entry) => nodeFilters.has(nodeEntryFilter(entry)));
   }, [nodeLines, nodeFilters]);
 
   const filteredGwEntries = useMemo(() => {
@@ -364,6 +377,17 @@ export function Logs() {
     });
   };
 
+  const handleExport = async () => {
+    try {
+      await exportLogs({
+        streams: Array.from(nodeFilters).map((f) => (f === "ui" ? "app" : f)),
+        redact: true,
+      });
+    } catch (err) {
+      console.error("[logs] export failed", err);
+    }
+  };
+
   const handleClear = () => {
     if (tab === "node") {
       setNodeLines([]);
@@ -425,6 +449,13 @@ export function Logs() {
               Clear
             </Button>
 
+            {tab === "node" && (
+              <Button variant="ghost" onClick={() => void handleExport()}>
+                <Download size={14} />
+                Export
+              </Button>
+            )}
+
             <label className="flex items-center gap-1.5 text-xs text-neutral-400 cursor-pointer">
               <input
                 type="checkbox"
@@ -557,9 +588,9 @@ export function Logs() {
               onScroll={handleScroll}
               className="flex-1 overflow-y-auto overflow-x-auto font-mono text-xs leading-5 p-3 scrollbar-thin scrollbar-track-neutral-900 scrollbar-thumb-neutral-700"
             >
-              {tab === "node" && filteredNodeLines.map((line, i) => (
-                <div key={i} style={{ color: nodeLineColor(line) }}>
-                  {line.startsWith("[ui]") ? <em>{line}</em> : line}
+              {tab === "node" && filteredNodeLines.map((entry, i) => (
+                <div key={i} style={{ color: nodeEntryColor(entry) }}>
+                  {entry.stream === "app" ? <em>{formatNodeEntry(entry)}</em> : formatNodeEntry(entry)}
                 </div>
               ))}
 
@@ -5,13 +5,40 @@ import { motion, AnimatePresence } from "motion/react";
 import { Card } from "../components/ui/Card";
 import { Checkbox } from "../components/ui/Checkbox";
 import { Button } from "../components/ui/Button";
-import type { NodeClientConfig } from "../tauri/types";
+import { Select } from "../components/ui/Select";
+import type { AppUpdateInfo, ConfigBackup, ConnectivityReport, ClipboardSummary, CrashReport, DiscoveryReport, InstallCandidate, NodeClientConfig, TerminalSessionInfo } from "../tauri/types";
 import {
   getConfig, setConfig,
   enableAutostart, disableAutostart, isAutostartEnabled,
-  getInstallPath, setInstallPath, detectInstallPath,
+  getInstallPath, setInstallPath, detectInstallPath, listInstallCandidates, getDiscoveryReport,
+  listConfigBackups, rollbackConfig, openConfigDir, openLogsDir, runConnectivityCheck,
+  listCrashReports, clearCrashReports, openCrashDir, checkForAppUpdate, installAppUpdate,
+  listTerminalSessions, closeTerminalSession,
+  pushClipboard, pullClipboard, getClipboardOffer, resolveClipboardOffer,
 } from "../tauri/commands";
-import { onInstallPathDetected } from "../tauri/events";
+import { onAppUpdate, onClipboardOffered, onConfigChanged, onInstallPathDetected, onUpdateAvailable } from "../tauri/events";
+
+/** Languages the backend has strings for; "" follows the OS. */
+const LANGUAGE_OPTIONS: { value: string; label: string }[] = [
+  { value: "", label: "System default" },
+  { value: "en", label: "English" },
+  { value: "de", label: "Deutsch" },
+  { value: "es", label: "Español" },
+  { value: "fr", label: "Français" },
+];
+
+function formatDiscoveryReport(report: DiscoveryReport): string {
+  const lines = [`selected: ${report.selected ?? "none"}`];
+  for (const tier of report.tiers) {
+    const elapsed = tier.elapsedMs !== null ? ` ${tier.elapsedMs}ms` : "";
+    lines.push(`[${tier.method}] ${tier.status}${elapsed}`);
+    for (const check of tier.checks) {
+      const detail = check.detail ? ` (${check.detail})` : "";
+      lines.push(`  ${check.outcome}: ${check.binPath ?? check.dir}${detail}`);
+    }
+  }
+  return lines.join("\n");
+}
 
 export function Settings() {
   const [config, setConfigState] = useState<NodeClientConfig | null>(null);
@@ -20,18 +47,137 @@ export function Settings() {
   const [saving, setSaving] = useState(false);
   const [detecting, setDetecting] = useState(false);
   const [detectStatus, setDetectStatus] = useState<string | null>(null);
+  const [candidates, setCandidates] = useState<InstallCandidate[] | null>(null);
+  const [listing, setListing] = useState(false);
+  const [reporting, setReporting] = useState(false);
+  const [backups, setBackups] = useState<ConfigBackup[]>([]);
+  const [rollbackStatus, setRollbackStatus] = useState<string | null>(null);
+  const [checking, setChecking] = useState(false);
+  const [connectivity, setConnectivity] = useState<ConnectivityReport | null>(null);
+  const [connectivityError, setConnectivityError] = useState<string | null>(null);
+  const [crashReports, setCrashReports] = useState<CrashReport[]>([]);
+  const [appUpdate, setAppUpdate] = useState<AppUpdateInfo | null>(null);
+  const [updateStatus, setUpdateStatus] = useState<string | null>(null);
+  const [updating, setUpdating] = useState(false);
+  const [terminalSessions, setTerminalSessions] = useState<TerminalSessionInfo[]>([]);
+  const [clipboardOffer, setClipboardOffer] = useState<ClipboardSummary | null>(null);
+  const [clipboardStatus, setClipboardStatus] = useState<string | null>(null);
 
   useEffect(() => {
     void getConfig().then((c) => setConfigState(c)).catch(() => {});
     void isAutostartEnabled().then((v) => setAutostartLogin(Boolean(v))).catch(() => {});
     void getInstallPath().then((p) => setInstallPathState(p)).catch(() => {});
+    void listConfigBackups().then(setBackups).catch(() => {});
+    void listCrashReports().then(setCrashReports).catch(() => {});
+    void listTerminalSessions().then(setTerminalSessions).catch(() => {});
+    void getClipboardOffer().then(setClipboardOffer).catch(() => {});
     // Listen for auto-detection fired during node start
     const unlisten = onInstallPathDetected((path) => {
       setInstallPathState(path);
     });
-    return () => { void unlisten.then((fn) => fn()); };
+    const unlistenConfig = onConfigChanged((impact) => {
+      setConfigState((prev) => (prev ? { ...prev, ...impact.values } : prev));
+      void listConfigBackups().then(setBackups).catch(() => {});
+    });
+    const unlistenUpdate = onUpdateAvailable(setAppUpdate);
+    const unlistenUpdateProgress = onAppUpdate((progress) => {
+      const detail = progress.detail ? `: ${progress.detail}` : "";
+      setUpdateStatus(`${progress.stage}${detail}`);
+    });
+    const unlistenClipboard = onClipboardOffered(setClipboardOffer);
+    return () => {
+      void unlisten.then((fn) => fn());
+      void unlistenClipboard.then((fn) => fn());
+      void unlistenConfig.then((fn) => fn());
+      void unlistenUpdate.then((fn) => fn());
+      void unlistenUpdateProgress.then((fn) => fn());
+    };
   }, []);
 
+  async function handleConnectivityCheck() {
+    setChecking(true);
+    setConnectivityError(null);
+    try {
+      setConnectivity(await runConnectivityCheck());
+    } catch (e) {
+      setConnectivity(null);
+      setConnectivityError(String(e));
+    } finally {
+      setChecking(false);
+    }
+  }
+
+  async function handleCheckForUpdate() {
+    setUpdating(true);
+    setUpdateStatus(null);
+    try {
+      const info = await checkForAppUpdate();
+      setAppUpdate(info);
+      if (!info) { setUpdateStatus("Up to date."); }
+    } catch (e) {
+      setUpdateStatus(String(e));
+    }
+    setUpdating(false);
+  }
+
+  async function handleInstallUpdate() {
+    setUpdating(true);
+    try {
+      await installAppUpdate();
+    } catch (e) {
+      setUpdateStatus(String(e));
+    }
+    setUpdating(false);
+  }
+
+  async function handleClearCrashReports() {
+    try {
+      await clearCrashReports();
+    } catch { /* silent */ }
+    setCrashReports(await listCrashReports().catch(() => []));
+  }
+
+  async function handleCloseTerminalSession(id: string) {
+    try {
+      await closeTerminalSession(id);
+    } catch { /* already gone */ }
+    setTerminalSessions(await listTerminalSessions().catch(() => []));
+  }
+
+  async function handleClipboard(action: () => Promise<ClipboardSummary | null>, verb: string) {
+    setClipboardStatus(null);
+    try {
+      const clip = await action();
+      setClipboardStatus(clip ? `${verb} ${clip.kind} (${clip.sizeBytes} bytes)` : "Nothing to pull");
+    } catch (err) {
+      setClipboardStatus(String(err));
+    }
+  }
+
+  async function handleClipboardOffer(accept: boolean) {
+    try {
+      await resolveClipboardOffer(accept);
+      setClipboardStatus(accept ? "Clipboard updated" : null);
+    } catch (err) {
+      setClipboardStatus(String(err));
+    }
+    setClipboardOffer(null);
+  }
+
+  async function handleRollback(backup: ConfigBackup) {
+    setRollbackStatus(null);
+    try {
+      await rollbackConfig(backup.version);
+      setConfigState(await getConfig());
+      setBackups(await listConfigBackups());
+      setRollbackStatus(
+        `Restored ${backup.file} from ${new Date(backup.createdAtMs).toLocaleString()}`
+      );
+    } catch (e) {
+      setRollbackStatus(`Rollback failed: ${String(e)}`);
+    }
+  }
+
   async function saveConfig(updates: Partial<NodeClientConfig>) {
     if (!config) { return; }
     const updated = { ...config, ...updates };
@@ -72,7 +218,18 @@ export function Settings() {
       const result = await detectInstallPath();
       if (result) {
         setInstallPathState(result.binDir);
-        setDetectStatus(`Found via ${result.method}: ${result.binPath}`);
+        const version = result.version ? ` (${result.version})` : "";
+        const skipped = result.skipped.length
+          ? `; skipped ${result.skipped.map((c) => `${c.binPath} (${c.reason})`).join(", ")}`
+          : "";
+        const timedOut = result.tiers.filter((t) => t.status === "timed-out");
+        const slow = timedOut.length
+          ? `; timed out: ${timedOut.map((t) => t.method).join(", ")}`
+          : "";
+        const others = result.alternatives.length
+          ? `; also found ${result.alternatives.map((c) => `${c.binPath}${c.version ? ` (${c.version})` : ""}`).join(", ")}`
+          : "";
+        setDetectStatus(`Found via ${result.method}: ${result.binPath}${version}${others}${skipped}${slow}`);
       } else {
         setDetectStatus("Not found. Install with: npm install -g openclaw");
       }
@@ -82,6 +239,34 @@ export function Settings() {
     setDetecting(false);
   }
 
+  async function handleListCandidates() {
+    setListing(true);
+    try {
+      setCandidates(await listInstallCandidates());
+    } catch {
+      setCandidates([]);
+    }
+    setListing(false);
+  }
+
+  async function handleCopyReport() {
+    setReporting(true);
+    try {
+      const report = await getDiscoveryReport();
+      await navigator.clipboard.writeText(formatDiscoveryReport(report));
+      setDetectStatus("Discovery report copied to clipboard.");
+    } catch {
+      setDetectStatus("Could not build the discovery report.");
+    }
+    setReporting(false);
+  }
+
+  async function handleUseCandidate(candidate: InstallCandidate) {
+    setInstallPathState(candidate.binDir);
+    await setInstallPath(candidate.binDir);
+    setDetectStatus(`Using ${candidate.binPath}`);
+  }
+
   if (!config) {
     return <div style={{ color: "var(--text-muted)", padding: "20px" }}>Loading…</div>;
   }
@@ -106,6 +291,20 @@ export function Settings() {
         </div>
       </Card>
 
+      {/* Language */}
+      <Card>
+        <SectionLabel>Language</SectionLabel>
+        <Select
+          value={config.locale ?? ""}
+          onChange={(v) => void saveConfig({ locale: v || null })}
+          options={LANGUAGE_OPTIONS}
+          disabled={saving}
+        />
+        <div style={{ marginTop: "8px", fontSize: "11px", color: "var(--text-muted)" }}>
+          Used for the tray menu, notifications and messages from the app.
+        </div>
+      </Card>
+
       {/* Exec host */}
       <Card>
         <SectionLabel>Exec Host</SectionLabel>
@@ -172,6 +371,12 @@ export function Settings() {
           <Button variant="ghost" size="sm" onClick={handleAutoDetect} disabled={detecting}>
             {detecting ? "Detecting…" : "Auto-detect"}
           </Button>
+          <Button variant="ghost" size="sm" onClick={handleListCandidates} disabled={listing}>
+            {listing ? "Searching…" : "Show All Installs"}
+          </Button>
+          <Button variant="ghost" size="sm" onClick={handleCopyReport} disabled={reporting}>
+            {reporting ? "Checking…" : "Copy Discovery Report"}
+          </Button>
           {installPath && (
             <Button variant="danger" size="sm" onClick={handleResetInstallPath}>
               Reset to Default
@@ -188,6 +393,307 @@ export function Settings() {
             {detectStatus}
           </div>
         )}
+        {candidates && (
+          <div style={{ marginTop: "10px", display: "flex", flexDirection: "column", gap: "6px" }}>
+            {candidates.length === 0 && (
+              <div style={{ fontSize: "12px", color: "var(--text-muted)" }}>
+                No openclaw installs found.
+              </div>
+            )}
+            {candidates.map((candidate) => (
+              <div
+                key={candidate.binPath}
+                style={{ display: "flex", alignItems: "center", gap: "8px", fontSize: "12px" }}
+              >
+                <span style={{ fontFamily: "var(--font-mono)", color: "var(--text-secondary)", flex: 1 }}>
+                  {candidate.binPath}
+                  <span style={{ color: "var(--text-muted)" }}>
+                    {candidate.realBinPath ? ` → ${candidate.realBinPath}` : ""}
+                    {" "}· {candidate.method}
+                    {candidate.version ? ` · ${candidate.version}` : ""}
+                    {candidate.error ? ` · ${candidate.error}` : ""}
+                  </span>
+                </span>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  onClick={() => void handleUseCandidate(candidate)}
+                  disabled={Boolean(candidate.error) || candidate.binDir === installPath}
+                >
+                  {candidate.binDir === installPath ? "In Use" : "Use"}
+                </Button>
+              </div>
+            ))}
+          </div>
+        )}
+      </Card>
+
+      {/* Connectivity check */}
+      <Card>
+        <SectionLabel>Connectivity Check</SectionLabel>
+        <div style={{ display: "flex", alignItems: "center", gap: "8px", marginBottom: "8px" }}>
+          <Button variant="ghost" size="sm" disabled={checking} onClick={() => void handleConnectivityCheck()}>
+            {checking ? "Checking…" : "Run Check"}
+          </Button>
+          {connectivity && (
+            <span style={{ fontSize: "12px", color: connectivity.ok ? "var(--ok)" : "var(--danger)" }}>
+              {connectivity.ok ? "All stages passed" : "A stage failed"} · {connectivity.gatewayUrl}
+            </span>
+          )}
+        </div>
+        {connectivityError && (
+          <div style={{ fontSize: "12px", color: "var(--danger)" }}>{connectivityError}</div>
+        )}
+        {connectivity && (
+          <div style={{ display: "flex", flexDirection: "column", gap: "6px" }}>
+            {connectivity.stages.map((stage) => (
+              <div key={stage.name} style={{ fontSize: "12px" }}>
+                <span
+                  style={{
+                    fontFamily: "var(--font-mono)",
+                    color:
+                      stage.status === "pass"
+                        ? "var(--ok)"
+                        : stage.status === "fail"
+                        ? "var(--danger)"
+                        : "var(--text-muted)",
+                  }}
+                >
+                  {stage.status.toUpperCase()} {stage.name}
+                </span>
+                {stage.status !== "skip" && (
+                  <span style={{ color: "var(--text-muted)" }}> · {stage.durationMs}ms</span>
+                )}
+                {stage.detail && (
+                  <div style={{ color: "var(--text-secondary)", wordBreak: "break-all" }}>{stage.detail}</div>
+                )}
+                {stage.hint && <div style={{ color: "var(--text-muted)" }}>{stage.hint}</div>}
+              </div>
+            ))}
+          </div>
+        )}
+      </Card>
+
+      {/* Folders */}
+      <Card>
+        <SectionLabel>Folders</SectionLabel>
+        <div style={{ display: "flex", gap: "8px" }}>
+          <Button variant="ghost" size="sm" onClick={() => void openConfigDir().catch(() => {})}>
+            Open Config Folder
+          </Button>
+          <Button variant="ghost" size="sm" onClick={() => void openLogsDir().catch(() => {})}>
+            Open Logs Folder
+          </Button>
+        </div>
+      </Card>
+
+      {/* App updates */}
+      <Card>
+        <SectionLabel>App Updates</SectionLabel>
+        <div style={{ display: "flex", flexDirection: "column", gap: "10px" }}>
+          <Checkbox
+            checked={config.updateCheckIntervalHours > 0}
+            onChange={(v) => void saveConfig({ updateCheckIntervalHours: v ? 24 : 0 })}
+            disabled={saving}
+            label="Check for updates daily"
+          />
+          <Checkbox
+            checked={config.updateChannel === "beta"}
+            onChange={(v) => void saveConfig({ updateChannel: v ? "beta" : "stable" })}
+            disabled={saving}
+            label="Receive beta releases"
+          />
+        </div>
+        {appUpdate && (
+          <div style={{ marginTop: "10px", fontSize: "12px", color: "var(--text-secondary)" }}>
+            Version {appUpdate.latestVersion} is available (current {appUpdate.currentVersion}).
+            {appUpdate.notes && (
+              <div style={{ marginTop: "4px", fontSize: "11px", color: "var(--text-muted)", whiteSpace: "pre-wrap" }}>
+                {appUpdate.notes}
+              </div>
+            )}
+          </div>
+        )}
+        <div style={{ display: "flex", gap: "8px", marginTop: "10px" }}>
+          <Button variant="ghost" size="sm" disabled={updating} onClick={() => void handleCheckForUpdate()}>
+            Check for Updates
+          </Button>
+          {appUpdate && (
+            <Button variant="ghost" size="sm" disabled={updating} onClick={() => void handleInstallUpdate()}>
+              Install &amp; Restart
+            </Button>
+          )}
+        </div>
+        {updateStatus && (
+          <div style={{
+            marginTop: "8px",
+            fontSize: "11px",
+            color: "var(--text-muted)",
+            fontFamily: "var(--font-mono)",
+          }}>
+            {updateStatus}
+          </div>
+        )}
+        <div style={{ marginTop: "8px", fontSize: "11px", color: "var(--text-muted)" }}>
+          The node host is stopped while an update installs.
+        </div>
+      </Card>
+
+      {/* Crash reports */}
+      <Card>
+        <SectionLabel>Crash Reports</SectionLabel>
+        {crashReports.length === 0 ? (
+          <div style={{ fontSize: "12px", color: "var(--text-muted)" }}>
+            No crashes recorded.
+          </div>
+        ) : (
+          <div style={{ display: "flex", flexDirection: "column", gap: "6px" }}>
+            {crashReports.map((report) => (
+              <div key={report.file} style={{ fontSize: "12px" }}>
+                <div style={{ fontFamily: "var(--font-mono)", color: "var(--text-secondary)" }}>
+                  {report.kind} · {new Date(report.createdAtMs).toLocaleString()}
+                  {report.uploaded ? " · uploaded" : ""}
+                </div>
+                {report.summary && (
+                  <div style={{ fontSize: "11px", color: "var(--text-muted)", wordBreak: "break-word" }}>
+                    {report.summary}
+                  </div>
+                )}
+              </div>
+            ))}
+          </div>
+        )}
+        <div style={{ display: "flex", gap: "8px", marginTop: "10px" }}>
+          <Button variant="ghost" size="sm" onClick={() => void openCrashDir().catch(() => {})}>
+            Open Crash Folder
+          </Button>
+          {crashReports.length > 0 && (
+            <Button variant="ghost" size="sm" onClick={() => void handleClearCrashReports()}>
+              Clear
+            </Button>
+          )}
+        </div>
+      </Card>
+
+      {/* Clipboard sync */}
+      {config?.clipboardSyncEnabled && (
+        <Card>
+          <SectionLabel>Clipboard</SectionLabel>
+          {clipboardOffer && (
+            <div style={{ fontSize: "12px", marginBottom: "8px" }}>
+              <div style={{ color: "var(--text-secondary)" }}>
+                The gateway offered {clipboardOffer.kind} ({clipboardOffer.sizeBytes} bytes)
+                {clipboardOffer.from ? ` from ${clipboardOffer.from}` : ""}
+              </div>
+              {clipboardOffer.preview && (
+                <div style={{ fontFamily: "var(--font-mono)", fontSize: "11px", color: "var(--text-muted)", wordBreak: "break-word" }}>
+                  {clipboardOffer.preview}
+                </div>
+              )}
+              <div style={{ display: "flex", gap: "8px", marginTop: "6px" }}>
+                <Button variant="ghost" size="sm" onClick={() => void handleClipboardOffer(true)}>
+                  Copy to Clipboard
+                </Button>
+                <Button variant="ghost" size="sm" onClick={() => void handleClipboardOffer(false)}>
+                  Dismiss
+                </Button>
+              </div>
+            </div>
+          )}
+          <div style={{ display: "flex", gap: "8px" }}>
+            <Button variant="ghost" size="sm" onClick={() => void handleClipboard(() => pushClipboard(), "Sent")}>
+              Send Text
+            </Button>
+            {config.clipboardSyncTypes?.includes("image") && (
+              <Button variant="ghost" size="sm" onClick={() => void handleClipboard(() => pushClipboard("image"), "Sent")}>
+                Send Image
+              </Button>
+            )}
+            <Button variant="ghost" size="sm" onClick={() => void handleClipboard(pullClipboard, "Received")}>
+              Pull from Gateway
+            </Button>
+          </div>
+          {clipboardStatus && (
+            <div style={{ marginTop: "8px", fontSize: "11px", color: "var(--text-muted)" }}>
+              {clipboardStatus}
+            </div>
+          )}
+        </Card>
+      )}
+
+      {/* Remote terminal sessions */}
+      {config?.remoteTerminalEnabled && (
+        <Card>
+          <SectionLabel>Remote Terminal Sessions</SectionLabel>
+          {terminalSessions.length === 0 ? (
+            <div style={{ fontSize: "12px", color: "var(--text-muted)" }}>
+              No sessions running. The gateway can request one; you approve each session.
+            </div>
+          ) : (
+            <div style={{ display: "flex", flexDirection: "column", gap: "6px" }}>
+              {terminalSessions.map((session) => (
+                <div
+                  key={session.id}
+                  style={{ display: "flex", alignItems: "center", gap: "8px", fontSize: "12px" }}
+                >
+                  <span style={{ fontFamily: "var(--font-mono)", color: "var(--text-secondary)", flex: 1 }}>
+                    {session.shell}
+                    {session.requestedBy ? ` · ${session.requestedBy}` : ""}
+                    {" · "}since {new Date(session.startedAtMs).toLocaleTimeString()}
+                  </span>
+                  <Button variant="ghost" size="sm" onClick={() => void handleCloseTerminalSession(session.id)}>
+                    Close
+                  </Button>
+                </div>
+              ))}
+            </div>
+          )}
+          <div style={{ display: "flex", gap: "8px", marginTop: "10px" }}>
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={() => void listTerminalSessions().then(setTerminalSessions).catch(() => {})}
+            >
+              Refresh
+            </Button>
+          </div>
+        </Card>
+      )}
+
+      {/* Config backups */}
+      <Card>
+        <SectionLabel>Config Backups</SectionLabel>
+        {backups.length === 0 ? (
+          <div style={{ fontSize: "12px", color: "var(--text-muted)" }}>
+            No backups yet. A copy is kept each time the config is saved.
+          </div>
+        ) : (
+          <div style={{ display: "flex", flexDirection: "column", gap: "6px" }}>
+            {backups.map((backup) => (
+              <div
+                key={backup.version}
+                style={{ display: "flex", alignItems: "center", gap: "8px", fontSize: "12px" }}
+              >
+                <span style={{ fontFamily: "var(--font-mono)", color: "var(--text-secondary)", flex: 1 }}>
+                  {backup.file} · {new Date(backup.createdAtMs).toLocaleString()}
+                </span>
+                <Button variant="ghost" size="sm" onClick={() => void handleRollback(backup)}>
+                  Restore
+                </Button>
+              </div>
+            ))}
+          </div>
+        )}
+        {rollbackStatus && (
+          <div style={{
+            marginTop: "8px",
+            fontSize: "11px",
+            color: "var(--text-muted)",
+            fontFamily: "var(--font-mono)",
+          }}>
+            {rollbackStatus}
+          </div>
+        )}
       </Card>
     </div>
   );
@@ -2,11 +2,32 @@ import { invoke } from "@tauri-apps/api/core";
 import type {
   NodeClientConfig,
   NodeClientStatus,
+  OpenclawVersionInfo,
   ApprovalPreview,
   ApprovalDecision,
+  DiscoveryReport,
   DiscoveryResult,
+  InstallCandidate,
   ExecPolicyConfig,
   AllowlistEntry,
+  BundledRuntimeUpdateInfo,
+  AppUpdateInfo,
+  ManagedNodeStatus,
+  NodeServiceStatus,
+  PreflightReport,
+  ConfigChangeImpact,
+  LogExportOptions,
+  KnownHost,
+  ConfigBackup,
+  ConnectivityReport,
+  CrashReport,
+  GatewayProbeReport,
+  FileTransferResult,
+  TunnelInfo,
+  TerminalSessionInfo,
+  ClipboardSummary,
+  NodeMetrics,
+  NotifierService,
 } from "./types";
 import type { GatewayConnectionStatus, GatewayRpcResult } from "../gateway/types";
 
@@ -14,14 +35,36 @@ export async function getConfig(): Promise<NodeClientConfig> {
   return invoke<NodeClientConfig>("get_config");
 }
 
-export async function setConfig(config: NodeClientConfig): Promise<void> {
-  return invoke("set_config", { config });
+/** With `validate`, the gateway settings are probed first and nothing is saved if that fails. */
+export async function setConfig(
+  config: NodeClientConfig,
+  validate = false
+): Promise<ConfigChangeImpact> {
+  return invoke<ConfigChangeImpact>("set_config", { config, validate });
+}
+
+export async function validateGatewaySettings(
+  config: NodeClientConfig
+): Promise<GatewayProbeReport> {
+  return invoke<GatewayProbeReport>("validate_gateway_settings", { config });
+}
+
+export async function runConnectivityCheck(): Promise<ConnectivityReport> {
+  return invoke<ConnectivityReport>("run_connectivity_check");
 }
 
 export async function getStatus(): Promise<NodeClientStatus> {
   return invoke<NodeClientStatus>("get_status");
 }
 
+export async function getOpenclawVersion(refresh = false): Promise<OpenclawVersionInfo> {
+  return invoke<OpenclawVersionInfo>("get_openclaw_version", { refresh });
+}
+
+export async function preflightNodeStart(): Promise<PreflightReport> {
+  return invoke<PreflightReport>("preflight_node_start");
+}
+
 export async function startNode(): Promise<void> {
   return invoke("start_node");
 }
@@ -34,6 +77,82 @@ export async function restartNode(): Promise<void> {
   return invoke("restart_node");
 }
 
+/** Resolves to the written path, or null if the save dialog was cancelled. */
+export async function exportLogs(options?: LogExportOptions): Promise<string | null> {
+  return invoke<string | null>("export_logs", { options });
+}
+
+/** Credentials are included (encrypted) only when a passphrase is given. */
+export async function exportSettingsBundle(passphrase?: string): Promise<string | null> {
+  return invoke<string | null>("export_settings_bundle", { passphrase: passphrase || null });
+}
+
+export async function importSettingsBundle(
+  passphrase?: string
+): Promise<ConfigChangeImpact | null> {
+  return invoke<ConfigChangeImpact | null>("import_settings_bundle", {
+    passphrase: passphrase || null,
+  });
+}
+
+export async function getKnownHosts(): Promise<KnownHost[]> {
+  return invoke<KnownHost[]>("get_known_hosts");
+}
+
+export async function applyOpenclawConfigUpdate(): Promise<ConfigChangeImpact> {
+  return invoke<ConfigChangeImpact>("apply_openclaw_config_update");
+}
+
+/** Configs from get_config carry this in place of stored secrets. */
+export const SECRET_PLACEHOLDER = "********";
+
+/** Config fields reveal_secret can unmask. */
+export type SecretField =
+  | "gatewayToken"
+  | "gatewayPassword"
+  | "localApiToken"
+  | "mqttPassword"
+  | "runAsPassword"
+  | "crashReportUploadUrl"
+  | "errorReportDsn"
+  | "slackWebhookUrl"
+  | "discordWebhookUrl"
+  | "ntfyToken"
+  | "pushoverUserKey"
+  | "pushoverAppToken";
+
+export async function revealSecret(field: SecretField): Promise<string | null> {
+  return invoke<string | null>("reveal_secret", { field });
+}
+
+export async function listConfigBackups(): Promise<ConfigBackup[]> {
+  return invoke<ConfigBackup[]>("list_config_backups");
+}
+
+export async function rollbackConfig(version: string): Promise<ConfigChangeImpact | null> {
+  return invoke<ConfigChangeImpact | null>("rollback_config", { version });
+}
+
+export async function listCrashReports(): Promise<CrashReport[]> {
+  return invoke<CrashReport[]>("list_crash_reports");
+}
+
+export async function clearCrashReports(): Promise<void> {
+  return invoke<void>("clear_crash_reports");
+}
+
+export async function openCrashDir(): Promise<void> {
+  return invoke<void>("open_crash_dir");
+}
+
+export async function forgetKnownHost(host: string, port: number): Promise<void> {
+  return invoke("forget_known_host", { host, port });
+}
+
+export async function sendNodeInput(line: string): Promise<void> {
+  return invoke("send_node_input", { line });
+}
+
 export async function getPendingApprovals(): Promise<ApprovalPreview[]> {
   return invoke<ApprovalPreview[]>("get_pending_approvals");
 }
@@ -45,6 +164,15 @@ export async function decideApproval(
   return invoke("decide_approval", { id, decision });
 }
 
+/** Open ~/.openclaw (or the --config file's folder) in the file manager. */
+export async function openConfigDir(): Promise<void> {
+  return invoke("open_config_dir");
+}
+
+export async function openLogsDir(): Promise<void> {
+  return invoke("open_logs_dir");
+}
+
 export async function enableAutostart(): Promise<void> {
   return invoke("enable_autostart");
 }
@@ -73,6 +201,18 @@ export async function detectInstallPath(): Promise<DiscoveryResult | null> {
   return invoke<DiscoveryResult | null>("detect_install_path");
 }
 
+export async function listInstallCandidates(): Promise<InstallCandidate[]> {
+  return invoke<InstallCandidate[]>("list_install_candidates");
+}
+
+export async function getDiscoveryReport(): Promise<DiscoveryReport> {
+  return invoke<DiscoveryReport>("get_discovery_report");
+}
+
+export async function installOpenclaw(): Promise<DiscoveryResult | null> {
+  return invoke<DiscoveryResult | null>("install_openclaw");
+}
+
 export async function getExecPolicy(): Promise<ExecPolicyConfig> {
   return invoke<ExecPolicyConfig>("get_exec_policy");
 }
@@ -97,6 +237,42 @@ export async function removeAllowlistEntry(pattern: string): Promise<void> {
   return invoke("remove_allowlist_entry", { pattern });
 }
 
+export async function checkBundledRuntimeUpdate(): Promise<BundledRuntimeUpdateInfo> {
+  return invoke<BundledRuntimeUpdateInfo>("check_bundled_runtime_update");
+}
+
+export async function updateBundledRuntime(): Promise<BundledRuntimeUpdateInfo> {
+  return invoke<BundledRuntimeUpdateInfo>("update_bundled_runtime");
+}
+
+export async function checkForAppUpdate(): Promise<AppUpdateInfo | null> {
+  return invoke<AppUpdateInfo | null>("check_for_app_update");
+}
+
+export async function installAppUpdate(): Promise<void> {
+  return invoke<void>("install_app_update");
+}
+
+export async function getManagedNodeStatus(): Promise<ManagedNodeStatus> {
+  return invoke<ManagedNodeStatus>("get_managed_node_status");
+}
+
+export async function installManagedNode(): Promise<ManagedNodeStatus> {
+  return invoke<ManagedNodeStatus>("install_managed_node");
+}
+
+export async function getNodeServiceStatus(): Promise<NodeServiceStatus> {
+  return invoke<NodeServiceStatus>("get_node_service_status");
+}
+
+export async function installNodeService(): Promise<NodeServiceStatus> {
+  return invoke<NodeServiceStatus>("install_node_service");
+}
+
+export async function uninstallNodeService(): Promise<NodeServiceStatus> {
+  return invoke<NodeServiceStatus>("uninstall_node_service");
+}
+
 // ---------------------------------------------------------------------------
 // Gateway WebSocket commands
 // ---------------------------------------------------------------------------
@@ -147,6 +323,82 @@ export async function gatewayRpc<T = unknown>(
   return invoke<GatewayRpcResult<T>>("gateway_rpc", { method, params: params ?? null });
 }
 
+/** Upload a local file to the gateway; re-running resumes an interrupted upload. */
+export async function gatewaySendFile(
+  path: string,
+  name?: string
+): Promise<FileTransferResult> {
+  return invoke<FileTransferResult>("gateway_send_file", { path, name: name ?? null });
+}
+
+/** Download a file from the gateway to `dest`; re-running resumes from `<dest>.part`. */
+export async function gatewayFetchFile(name: string, dest: string): Promise<FileTransferResult> {
+  return invoke<FileTransferResult>("gateway_fetch_file", { name, dest });
+}
+
+/**
+ * Forward a port over the gateway connection. "local" listens on this machine
+ * (0 picks a free port) and connects from the gateway; "remote" the reverse.
+ */
+export async function openTunnel(
+  direction: TunnelInfo["direction"],
+  listenPort: number,
+  targetHost: string,
+  targetPort: number
+): Promise<TunnelInfo> {
+  return invoke<TunnelInfo>("open_tunnel", { direction, listenPort, targetHost, targetPort });
+}
+
+export async function listTunnels(): Promise<TunnelInfo[]> {
+  return invoke<TunnelInfo[]>("list_tunnels");
+}
+
+export async function closeTunnel(id: string): Promise<void> {
+  return invoke("close_tunnel", { id });
+}
+
+export async function listTerminalSessions(): Promise<TerminalSessionInfo[]> {
+  return invoke<TerminalSessionInfo[]>("list_terminal_sessions");
+}
+
+export async function closeTerminalSession(id: string): Promise<void> {
+  return invoke("close_terminal_session", { id });
+}
+
+/** Send this machine's clipboard to the gateway. */
+export async function pushClipboard(kind?: ClipboardSummary["kind"]): Promise<ClipboardSummary> {
+  return invoke<ClipboardSummary>("push_clipboard", { kind: kind ?? null });
+}
+
+/** Replace this machine's clipboard with the gateway's; null when it has none. */
+export async function pullClipboard(): Promise<ClipboardSummary | null> {
+  return invoke<ClipboardSummary | null>("pull_clipboard");
+}
+
+export async function getClipboardOffer(): Promise<ClipboardSummary | null> {
+  return invoke<ClipboardSummary | null>("get_clipboard_offer");
+}
+
+/** Write the offered clipboard contents here (accept) or discard them. */
+export async function resolveClipboardOffer(accept: boolean): Promise<void> {
+  return invoke("resolve_clipboard_offer", { accept });
+}
+
+/** A fresh sample of the metrics reported to the gateway. */
+export async function getNodeMetrics(): Promise<NodeMetrics> {
+  return invoke<NodeMetrics>("get_node_metrics");
+}
+
+/** Post a test message to the saved Slack or Discord webhook. */
+export async function testNotifier(service: NotifierService): Promise<void> {
+  return invoke<void>("test_notifier", { service });
+}
+
+/** Send a test event to the saved error reporting DSN. */
+export async function sendTestErrorReport(): Promise<void> {
+  return invoke<void>("send_test_error_report");
+}
+
 export async function getDeviceId(): Promise<string> {
   return invoke<string>("get_device_id");
 }
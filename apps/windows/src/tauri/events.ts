@@ -1,5 +1,26 @@
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
-import type { NodeStatusString, ApprovalPreview } from "./types";
+import type {
+  NodeStatusString,
+  ApprovalPreview,
+  BundledRuntimeUpdateProgress,
+  AppUpdateInfo,
+  AppUpdateProgress,
+  OpenclawInstallProgress,
+  ManagedNodeProgress,
+  NodeCrashReport,
+  NodeWatchdogRestart,
+  NodeLogEntry,
+  BundleIntegrityError,
+  NodeStartProgress,
+  PreflightReport,
+  ConfigChangeImpact,
+  TlsFingerprintFirstUse,
+  TlsFingerprintChanged,
+  OpenclawConfigUpdate,
+  GatewayProbeReport,
+  FileTransferProgress,
+  ClipboardSummary,
+} from "./types";
 
 export function onNodeStatusChanged(
   cb: (status: NodeStatusString) => void
@@ -9,8 +30,17 @@ export function onNodeStatusChanged(
   });
 }
 
-export function onNodeLog(cb: (line: string) => void): Promise<UnlistenFn> {
-  return listen<string>("node-log", (event) => {
+/** Entries arrive in batches, oldest first. */
+export function onNodeLog(cb: (entries: NodeLogEntry[]) => void): Promise<UnlistenFn> {
+  return listen<NodeLogEntry[]>("node-log", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onNodeCrashed(
+  cb: (report: NodeCrashReport) => void
+): Promise<UnlistenFn> {
+  return listen<NodeCrashReport>("node-crashed", (event) => {
     cb(event.payload);
   });
 }
@@ -31,6 +61,14 @@ export function onInstallPathDetected(
   });
 }
 
+export function onOpenclawInstallProgress(
+  cb: (progress: OpenclawInstallProgress) => void
+): Promise<UnlistenFn> {
+  return listen<OpenclawInstallProgress>("openclaw-install-progress", (event) => {
+    cb(event.payload);
+  });
+}
+
 export function onApprovalResolved(
   cb: (id: string) => void
 ): Promise<UnlistenFn> {
@@ -39,3 +77,129 @@ export function onApprovalResolved(
     cb(typeof payload === "string" ? payload : payload.id);
   });
 }
+
+export function onBundledRuntimeUpdate(
+  cb: (progress: BundledRuntimeUpdateProgress) => void
+): Promise<UnlistenFn> {
+  return listen<BundledRuntimeUpdateProgress>("bundled-runtime-update", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onUpdateAvailable(
+  cb: (info: AppUpdateInfo) => void
+): Promise<UnlistenFn> {
+  return listen<AppUpdateInfo>("update-available", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onAppUpdate(
+  cb: (progress: AppUpdateProgress) => void
+): Promise<UnlistenFn> {
+  return listen<AppUpdateProgress>("app-update", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onManagedNodeProgress(
+  cb: (progress: ManagedNodeProgress) => void
+): Promise<UnlistenFn> {
+  return listen<ManagedNodeProgress>("managed-node-progress", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onNodeIdAssigned(cb: (nodeId: string) => void): Promise<UnlistenFn> {
+  return listen<string>("node-id-assigned", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onNodeWatchdogRestart(
+  cb: (info: NodeWatchdogRestart) => void
+): Promise<UnlistenFn> {
+  return listen<NodeWatchdogRestart>("node-watchdog-restart", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onBundledRuntimeIntegrityFailed(
+  cb: (error: BundleIntegrityError) => void
+): Promise<UnlistenFn> {
+  return listen<BundleIntegrityError>("bundled-runtime-integrity-failed", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onNodeStartProgress(
+  cb: (progress: NodeStartProgress) => void
+): Promise<UnlistenFn> {
+  return listen<NodeStartProgress>("node-start-progress", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onNodePreflightFailed(
+  cb: (report: PreflightReport) => void
+): Promise<UnlistenFn> {
+  return listen<PreflightReport>("node-preflight-failed", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onConfigChanged(
+  cb: (impact: ConfigChangeImpact) => void
+): Promise<UnlistenFn> {
+  return listen<ConfigChangeImpact>("config-changed", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onTlsFingerprintFirstUse(
+  cb: (event: TlsFingerprintFirstUse) => void
+): Promise<UnlistenFn> {
+  return listen<TlsFingerprintFirstUse>("tls-fingerprint-first-use", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onTlsFingerprintChanged(
+  cb: (event: TlsFingerprintChanged) => void
+): Promise<UnlistenFn> {
+  return listen<TlsFingerprintChanged>("tls-fingerprint-changed", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onOpenclawConfigUpdated(
+  cb: (update: OpenclawConfigUpdate) => void
+): Promise<UnlistenFn> {
+  return listen<OpenclawConfigUpdate>("openclaw-config-updated", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onGatewayValidationFailed(
+  cb: (report: GatewayProbeReport) => void
+): Promise<UnlistenFn> {
+  return listen<GatewayProbeReport>("gateway-validation-failed", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onFileTransferProgress(
+  cb: (progress: FileTransferProgress) => void
+): Promise<UnlistenFn> {
+  return listen<FileTransferProgress>("file-transfer-progress", (event) => {
+    cb(event.payload);
+  });
+}
+
+export function onClipboardOffered(
+  cb: (offer: ClipboardSummary) => void
+): Promise<UnlistenFn> {
+  return listen<ClipboardSummary>("clipboard-offered", (event) => {
+    cb(event.payload);
+  });
+}
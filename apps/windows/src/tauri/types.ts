@@ -18,10 +18,120 @@ export interface NodeClientConfig {
   autoStartNode: boolean;
   useExecHost: boolean;
   execHostFallback: boolean;
+  /** Keep the exec-host token in the OS keychain (exec-approvals.json gets a tokenRef). */
+  execHostTokenInKeychain: boolean;
   gatewayToken: string | null;
   gatewayPassword: string | null;
   installPath: string | null;
   useBundledRuntime: boolean;
+  useManagedNode: boolean;
+  /** Local openclaw checkout run instead of any installed CLI. */
+  devRoot: string | null;
+  extraEnv: Record<string, string>;
+  extraArgs: string[];
+  orphanPolicy: "terminate" | "adopt";
+  lowPriority: boolean;
+  watchdogOfflineMinutes: number;
+  runAsUser: string | null;
+  runAsPassword: string | null;
+  autoStartDelaySecs: number;
+  autoStartWaitForGateway: boolean;
+  autoStartMaxWaitSecs: number;
+  /** Stay hidden in the tray when launched at login. */
+  startMinimizedToTray: boolean;
+  /** Ask for approvals with an actionable notification instead of the popup. */
+  approvalNotifications: boolean;
+  workingDir: string | null;
+  tlsCaPath: string | null;
+  tlsClientCertPath: string | null;
+  tlsClientKeyPath: string | null;
+  tlsInsecureSkipVerify: boolean;
+  autoRestartOnConfigChange: boolean;
+  /** Keychain service holding the gateway token/password, when not in the file. */
+  keychainRef: string | null;
+  encryptAtRest: boolean;
+  /** Mirror gateway port/TLS/credential changes into openclaw.json. */
+  syncOpenclawConfig: boolean;
+  /** Report status transitions and errors to the gateway. */
+  gatewayReporting: boolean;
+  /** Opt-in endpoint crash reports are uploaded to on the next launch. */
+  crashReportUploadUrl: string | null;
+  /** App release channel. */
+  updateChannel: "stable" | "beta";
+  /** Hours between background app update checks; 0 turns them off. */
+  updateCheckIntervalHours: number;
+  /** Language of tray labels, notifications and app log lines ("de", "fr-CA"); null follows the OS. */
+  locale: string | null;
+  /** Serve the token-protected HTTP API on 127.0.0.1:localApiPort. */
+  localApiEnabled: boolean;
+  localApiPort: number;
+  /** Bearer token for the local API; generated on first enable. */
+  localApiToken: string | null;
+  /** Publish node/gateway/approval state to an MQTT broker. */
+  mqttEnabled: boolean;
+  mqttHost: string | null;
+  mqttPort: number;
+  mqttUsername: string | null;
+  mqttPassword: string | null;
+  mqttTopicPrefix: string;
+  /** Publish Home Assistant discovery configs. */
+  mqttHaDiscovery: boolean;
+  /** Script run on each app event (node-crashed, approval-pending, ...), event JSON on stdin. */
+  eventHooks: Record<string, string>;
+  /** Let the gateway request shell sessions here, each one approved first. */
+  remoteTerminalEnabled: boolean;
+  /** Allow user-triggered clipboard push/pull over the gateway connection. */
+  clipboardSyncEnabled: boolean;
+  /** Clipboard kinds that may be exchanged. */
+  clipboardSyncTypes: ("text" | "image")[];
+  clipboardSyncMaxBytes: number;
+  /** Send host capacity metrics to the gateway. */
+  metricsReporting: boolean;
+  metricsIntervalSecs: number;
+  metricsReported: MetricGroup[];
+  /** Incoming webhook URLs notified of approvals and node crashes. */
+  slackWebhookUrl: string | null;
+  discordWebhookUrl: string | null;
+  notifierEvents: NotifierEvent[];
+  /** Message template per event, overriding the built-in one. */
+  notifierTemplates: Record<string, string>;
+  /** Also send node host output to journald or syslog (Linux only). */
+  logForwarding: "off" | "journald" | "syslog";
+  /** Syslog receiver as host:port (UDP); empty uses the local /dev/log. */
+  logForwardSyslogAddress: string | null;
+  /** Write audit events to the Windows Event Log (Windows only). */
+  windowsEventLog: boolean;
+  /** OTLP/HTTP collector receiving traces, e.g. http://localhost:4318. */
+  otlpEndpoint: string | null;
+  otlpHeaders: Record<string, string>;
+  /** Sentry-compatible DSN receiving handled errors, scrubbed of secrets. */
+  errorReportDsn: string | null;
+  /** ntfy topic URL (e.g. https://ntfy.sh/my-topic) for approval pushes. */
+  ntfyTopicUrl: string | null;
+  ntfyToken: string | null;
+  /** Pushover user key and application token for approval pushes. */
+  pushoverUserKey: string | null;
+  pushoverAppToken: string | null;
+  /** Link opened from an approval push; {id} is the approval id. */
+  pushApprovalLink: string | null;
+}
+
+export type NotifierService = "slack" | "discord" | "ntfy" | "pushover";
+
+export type MetricGroup = "cpu" | "memory" | "disk" | "node";
+
+export type NotifierEvent = "approval-pending" | "approval-resolved" | "node-crashed";
+
+export type NodeLogStream = "stdout" | "stderr" | "app";
+export type NodeLogLevel = "debug" | "info" | "warn" | "error";
+
+export interface NodeLogEntry {
+  tsMs: number;
+  stream: NodeLogStream;
+  level: NodeLogLevel;
+  message: string;
+  /** Consecutive identical lines collapsed into this entry. */
+  repeat: number;
 }
 
 export interface NodeClientStatus {
@@ -30,6 +140,69 @@ export interface NodeClientStatus {
   gatewayUrl: string;
   lastError: string | null;
   logs: string[];
+  logEntries: NodeLogEntry[];
+  openclawVersion: OpenclawVersionInfo | null;
+  serviceManaged: boolean;
+  lowPriority: boolean;
+  nodeId: string | null;
+}
+
+export interface OpenclawVersionInfo {
+  binPath: string;
+  version: string | null;
+  raw: string;
+  minSupported: string;
+  outdated: boolean;
+}
+
+export interface NodeCrashReport {
+  exitCode: number | null;
+  exitStatus: string;
+  startedAtMs: number | null;
+  exitedAtMs: number;
+  logTail: string[];
+  reportPath?: string;
+}
+
+export interface SkippedCandidate {
+  binPath: string;
+  method: string;
+  reason: string;
+}
+
+export interface InstallCandidate {
+  binDir: string;
+  binPath: string;
+  binName: string;
+  method: string;
+  realBinPath: string | null;
+  version: string | null;
+  /** Why `--version` failed; such candidates can't be selected. */
+  error: string | null;
+}
+
+export interface DiscoveryTier {
+  method: string;
+  status: "found" | "not-found" | "timed-out" | "not-needed";
+  elapsedMs: number | null;
+}
+
+export interface DiscoveryCheck {
+  dir: string;
+  binPath: string | null;
+  outcome: "found" | "dir-missing" | "missing" | "not-executable" | "version-failed";
+  /** Version when found, the failure when the version check failed. */
+  detail: string | null;
+}
+
+export interface DiscoveryReportTier extends DiscoveryTier {
+  checks: DiscoveryCheck[];
+}
+
+export interface DiscoveryReport {
+  /** The binary auto-detect would pick. */
+  selected: string | null;
+  tiers: DiscoveryReportTier[];
 }
 
 export interface DiscoveryResult {
@@ -37,6 +210,20 @@ export interface DiscoveryResult {
   binPath: string;
   binName: string;
   method: string;
+  /** Concrete binary and node dir when `binPath` is a version-manager shim. */
+  realBinPath: string | null;
+  realBinDir: string | null;
+  version: string | null;
+  skipped: SkippedCandidate[];
+  /** Other working installs; the newest version is picked. */
+  alternatives: InstallCandidate[];
+  /** How each discovery tier fared, in priority order. */
+  tiers: DiscoveryTier[];
+}
+
+export interface OpenclawInstallProgress {
+  stream: "stdout" | "stderr" | "status";
+  line: string;
 }
 
 export interface ApprovalPreview {
@@ -60,3 +247,251 @@ export interface AllowlistEntry {
   pattern: string;
   lastUsedAt: number | null;
 }
+
+export interface BundledRuntimeUpdateInfo {
+  currentVersion: string | null;
+  latestVersion: string;
+  updateAvailable: boolean;
+  source: "resources" | "app-data";
+}
+
+export type BundledRuntimeUpdateStage =
+  | "checking"
+  | "up-to-date"
+  | "downloading"
+  | "verifying"
+  | "extracting"
+  | "installing-dependencies"
+  | "activating"
+  | "rolled-back"
+  | "failed"
+  | "done";
+
+export interface BundledRuntimeUpdateProgress {
+  stage: BundledRuntimeUpdateStage;
+  detail: string | null;
+}
+
+export interface AppUpdateInfo {
+  currentVersion: string;
+  latestVersion: string;
+  channel: "stable" | "beta";
+  /** Release notes from the update manifest. */
+  notes: string | null;
+}
+
+export type AppUpdateStage =
+  | "checking"
+  | "up-to-date"
+  | "downloading"
+  | "stopping-node"
+  | "installing"
+  | "restarting"
+  | "failed";
+
+export interface AppUpdateProgress {
+  stage: AppUpdateStage;
+  detail: string | null;
+}
+
+export interface ManagedNodeStatus {
+  version: string;
+  installed: boolean;
+  nodePath: string | null;
+  supported: boolean;
+}
+
+export interface NodeServiceStatus {
+  installed: boolean;
+  running: boolean;
+  label: string | null;
+  pid: number | null;
+  detail: string | null;
+}
+
+export interface ManagedNodeProgress {
+  stage: "downloading" | "verifying" | "extracting" | "failed" | "done";
+  detail: string | null;
+}
+
+export interface NodeWatchdogRestart {
+  nodeId: string;
+  offlineForSecs: number;
+}
+
+export interface BundleIntegrityError {
+  dir: string;
+  file: string | null;
+  expected: string | null;
+  actual: string | null;
+  message: string;
+}
+
+export type NodeStartStage =
+  | "preflight"
+  | "resolving-binary"
+  | "spawning"
+  | "waiting-for-gateway"
+  | "connected"
+  | "failed";
+
+export interface NodeStartProgress {
+  stage: NodeStartStage;
+  detail: string | null;
+  /** Stage that was in progress when a start failed. */
+  failedStage?: string;
+}
+
+export interface PreflightIssue {
+  code: "gateway-unreachable" | "node-host-already-running" | "tls-fingerprint-changed";
+  message: string;
+  pids?: number[];
+}
+
+export interface PreflightReport {
+  ok: boolean;
+  gatewayReachable: boolean;
+  issues: PreflightIssue[];
+}
+
+export interface LogExportOptions {
+  streams?: NodeLogStream[];
+  minLevel?: NodeLogLevel;
+  query?: string;
+  redact?: boolean;
+  includeCrashReports?: boolean;
+}
+
+export interface ConfigChangeImpact {
+  changedFields: string[];
+  /** New values of the changed fields. */
+  values: Partial<NodeClientConfig>;
+  restartNode: boolean;
+  reconnectGateway: boolean;
+  restarting: boolean;
+}
+
+export interface GatewayProbeReport {
+  ok: boolean;
+  code?: "dns-failed" | "tcp-refused" | "tls-mismatch" | "handshake-failed" | "auth-rejected";
+  message?: string;
+  resolvedAddr?: string;
+  tlsFingerprint?: string;
+  latencyMs: number;
+}
+
+export interface ConnectivityStage {
+  name: "dns" | "tcp" | "tls" | "websocket" | "auth";
+  status: "pass" | "fail" | "skip";
+  detail?: string;
+  /** What to try next when the stage failed. */
+  hint?: string;
+  durationMs: number;
+}
+
+export interface ConnectivityReport {
+  ok: boolean;
+  gatewayUrl: string;
+  stages: ConnectivityStage[];
+}
+
+export interface OpenclawConfigUpdate {
+  changedFields: string[];
+  /** New values from openclaw.json. */
+  values: Partial<NodeClientConfig>;
+}
+
+export interface ConfigBackup {
+  /** Backup file name; pass to rollbackConfig. */
+  version: string;
+  file: string;
+  createdAtMs: number;
+  sizeBytes: number;
+}
+
+export interface CrashReport {
+  file: string;
+  kind: "panic" | "minidump" | "unclean-exit";
+  createdAtMs: number;
+  sizeBytes: number;
+  /** Panic message or exit details; absent for minidumps. */
+  summary?: string;
+  uploaded: boolean;
+}
+
+export interface KnownHost {
+  /** "host:port" */
+  host: string;
+  /** Lowercase hex SHA-256 of the gateway's leaf certificate. */
+  fingerprint: string;
+  firstSeenMs: number;
+  lastSeenMs: number;
+}
+
+export interface TlsFingerprintFirstUse {
+  host: string;
+  fingerprint: string;
+}
+
+export interface TlsFingerprintChanged {
+  host: string;
+  expected: string;
+  actual: string;
+}
+
+export interface FileTransferResult {
+  transferId: string;
+  name: string;
+  /** Local file that was sent or written. */
+  path: string;
+  size: number;
+  sha256: string;
+  /** Bytes already transferred by an earlier, interrupted attempt. */
+  resumedFrom: number;
+}
+
+export interface FileTransferProgress {
+  transferId: string;
+  direction: "upload" | "download";
+  name: string;
+  transferred: number;
+  total: number;
+}
+
+export interface TunnelInfo {
+  id: string;
+  direction: "local" | "remote";
+  /** Port listened on: here for local tunnels, on the gateway for remote ones. */
+  listenPort: number;
+  targetHost: string;
+  targetPort: number;
+  openedAtMs: number;
+  activeStreams: number;
+  bytesSent: number;
+  bytesReceived: number;
+}
+
+export interface TerminalSessionInfo {
+  id: string;
+  shell: string;
+  requestedBy: string | null;
+  startedAtMs: number;
+}
+
+export interface ClipboardSummary {
+  kind: "text" | "image";
+  sizeBytes: number;
+  /** Start of the text, or the image dimensions. */
+  preview?: string;
+  /** Sender named by the gateway, for offers. */
+  from?: string;
+}
+
+export interface NodeMetrics {
+  tsMs: number;
+  cpu?: { usagePercent: number; cores: number };
+  memory?: { totalBytes: number; usedBytes: number; availableBytes: number };
+  disk?: { mountPoint: string; totalBytes: number; freeBytes: number };
+  /** Node host plus its descendants; cpuPercent 100 = one full core. */
+  node?: { pid: number; cpuPercent: number; memoryBytes: number; processCount: number };
+}
@@ -11,3 +11,4 @@ export function getPlatform(): string {
 
 export function isWindows(): boolean { return getPlatform() === "windows"; }
 export function isLinux(): boolean { return getPlatform() === "linux"; }
+export function isMacOS(): boolean { return getPlatform() === "macos"; }
@@ -75,6 +75,7 @@ export async function processGatewayAllowlist(
     security: params.security,
     ask: params.ask,
     host: "gateway",
+    cwd: params.workdir,
   });
   const allowlistEval = evaluateShellAllowlist({
     command: params.command,
@@ -174,11 +174,14 @@ export function resolveExecHostApprovalContext(params: {
   security: ExecSecurity;
   ask: ExecAsk;
   host: "gateway" | "node";
+  // Only meaningful when the command runs on this host
+  cwd?: string;
 }): ExecHostApprovalContext {
-  const approvals = resolveExecApprovals(params.agentId, {
-    security: params.security,
-    ask: params.ask,
-  });
+  const approvals = resolveExecApprovals(
+    params.agentId,
+    { security: params.security, ask: params.ask },
+    { cwd: params.cwd },
+  );
   const hostSecurity = minSecurity(params.security, approvals.agent.security);
   // An explicit ask=off policy in exec-approvals.json must be able to suppress
   // prompts even when tool/runtime defaults are stricter (for example on-miss).
@@ -1,4 +1,7 @@
 import { Buffer } from "node:buffer";
+import fs from "node:fs";
+import os from "node:os";
+import path from "node:path";
 import { beforeEach, describe, expect, it, vi } from "vitest";
 import type { DeviceIdentity } from "../infra/device-identity.js";
 import { captureEnv } from "../test-utils/env.js";
@@ -25,8 +28,10 @@ class MockWebSocket {
   readonly sent: string[] = [];
   closeCalls = 0;
   terminateCalls = 0;
+  readonly options: Record<string, unknown> | undefined;
 
-  constructor(_url: string, _options?: unknown) {
+  constructor(_url: string, options?: Record<string, unknown>) {
+    this.options = options;
     wsInstances.push(this);
   }
 
@@ -246,6 +251,86 @@ describe("GatewayClient security checks", () => {
   });
 });
 
+describe("GatewayClient tls options", () => {
+  const envSnapshot = captureEnv([
+    "OPENCLAW_GATEWAY_TLS_CERT",
+    "OPENCLAW_GATEWAY_TLS_KEY",
+    "OPENCLAW_GATEWAY_TLS_INSECURE",
+    "NODE_TLS_REJECT_UNAUTHORIZED",
+  ]);
+
+  beforeEach(() => {
+    envSnapshot.restore();
+    delete process.env.OPENCLAW_GATEWAY_TLS_CERT;
+    delete process.env.OPENCLAW_GATEWAY_TLS_KEY;
+    delete process.env.OPENCLAW_GATEWAY_TLS_INSECURE;
+    delete process.env.NODE_TLS_REJECT_UNAUTHORIZED;
+    wsInstances.length = 0;
+  });
+
+  it("reads the client certificate and key when connecting", () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), "openclaw-gateway-tls-"));
+    try {
+      fs.writeFileSync(path.join(dir, "client.crt"), "CERT");
+      fs.writeFileSync(path.join(dir, "client.key"), "KEY");
+      process.env.OPENCLAW_GATEWAY_TLS_CERT = path.join(dir, "client.crt");
+      process.env.OPENCLAW_GATEWAY_TLS_KEY = path.join(dir, "client.key");
+      const readSpy = vi.spyOn(fs, "readFileSync");
+      const client = new GatewayClient({ url: "wss://gateway.example.com:18789" });
+
+      expect(readSpy).not.toHaveBeenCalledWith(path.join(dir, "client.crt"));
+      client.start();
+
+      expect(readSpy).toHaveBeenCalledWith(path.join(dir, "client.crt"));
+      expect(readSpy).toHaveBeenCalledWith(path.join(dir, "client.key"));
+      const options = getLatestWs().options;
+      expect(String(options?.cert)).toBe("CERT");
+      expect(String(options?.key)).toBe("KEY");
+      client.stop();
+      readSpy.mockRestore();
+    } finally {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  it("reports an unreadable client certificate instead of connecting", () => {
+    process.env.OPENCLAW_GATEWAY_TLS_CERT = path.join(os.tmpdir(), "missing-openclaw.crt");
+    process.env.OPENCLAW_GATEWAY_TLS_KEY = path.join(os.tmpdir(), "missing-openclaw.key");
+    const onConnectError = vi.fn();
+    const client = new GatewayClient({ url: "wss://gateway.example.com:18789", onConnectError });
+
+    client.start();
+
+    expect(onConnectError).toHaveBeenCalledWith(
+      expect.objectContaining({
+        message: expect.stringContaining("failed to read gateway tls client certificate"),
+      }),
+    );
+    expect(wsInstances.length).toBe(0);
+    client.stop();
+  });
+
+  it("skips certificate verification for the gateway connection only", () => {
+    process.env.OPENCLAW_GATEWAY_TLS_INSECURE = "1";
+    const client = new GatewayClient({ url: "wss://gateway.example.com:18789" });
+
+    client.start();
+
+    expect(getLatestWs().options?.rejectUnauthorized).toBe(false);
+    expect(process.env.NODE_TLS_REJECT_UNAUTHORIZED).toBeUndefined();
+    client.stop();
+  });
+
+  it("verifies certificates by default", () => {
+    const client = new GatewayClient({ url: "wss://gateway.example.com:18789" });
+
+    client.start();
+
+    expect(getLatestWs().options?.rejectUnauthorized).toBeUndefined();
+    client.stop();
+  });
+});
+
 describe("GatewayClient close handling", () => {
   beforeEach(() => {
     wsInstances.length = 0;
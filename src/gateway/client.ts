@@ -1,4 +1,5 @@
 import { randomUUID } from "node:crypto";
+import fs from "node:fs";
 import { WebSocket, type ClientOptions, type CertMeta } from "ws";
 import {
   clearDeviceAuthToken,
@@ -193,6 +194,29 @@ export class GatewayClient {
     const wsOptions: ClientOptions = {
       maxPayload: 25 * 1024 * 1024,
     };
+    // Optional mutual TLS: the desktop node client passes a client cert/key pair via env.
+    const clientCertPath = process.env.OPENCLAW_GATEWAY_TLS_CERT?.trim();
+    const clientKeyPath = process.env.OPENCLAW_GATEWAY_TLS_KEY?.trim();
+    if (url.startsWith("wss://") && clientCertPath && clientKeyPath) {
+      try {
+        wsOptions.cert = fs.readFileSync(clientCertPath);
+        wsOptions.key = fs.readFileSync(clientKeyPath);
+      } catch (err) {
+        this.opts.onConnectError?.(
+          new Error(`failed to read gateway tls client certificate: ${String(err)}`),
+        );
+        return;
+      }
+    }
+    // Skip-verify for this connection only; the desktop node client used to set
+    // NODE_TLS_REJECT_UNAUTHORIZED=0, which disabled verification process-wide.
+    if (
+      url.startsWith("wss://") &&
+      !this.opts.tlsFingerprint &&
+      process.env.OPENCLAW_GATEWAY_TLS_INSECURE === "1"
+    ) {
+      wsOptions.rejectUnauthorized = false;
+    }
     if (url.startsWith("wss://") && this.opts.tlsFingerprint) {
       wsOptions.rejectUnauthorized = false;
       wsOptions.checkServerIdentity = ((_host: string, cert: CertMeta) => {
@@ -0,0 +1,108 @@
+import fs from "node:fs";
+import path from "node:path";
+import { describe, expect, it } from "vitest";
+import { makeTempDir } from "./exec-approvals-test-helpers.js";
+import {
+  loadProjectExecPolicy,
+  resolveExecApprovalsFromFile,
+  resolveTrustedProjectRoot,
+  type ExecApprovalsFile,
+} from "./exec-approvals.js";
+
+function writeProjectPolicy(root: string, policy: unknown) {
+  const policyPath = path.join(root, ".openclaw", "exec-policy.json");
+  fs.mkdirSync(path.dirname(policyPath), { recursive: true });
+  fs.writeFileSync(policyPath, JSON.stringify(policy));
+}
+
+describe("project exec policy", () => {
+  it("only honours policy files under trusted project roots", () => {
+    const dir = makeTempDir();
+    const trusted = path.join(dir, "trusted");
+    const untrusted = path.join(dir, "untrusted");
+    writeProjectPolicy(trusted, { allowlist: [{ pattern: "/usr/bin/make" }] });
+    writeProjectPolicy(untrusted, { security: "full" });
+    const file: ExecApprovalsFile = { version: 1, trustedProjects: [trusted] };
+
+    expect(loadProjectExecPolicy(file, path.join(trusted, "src", "lib"))?.root).toBe(
+      path.resolve(trusted),
+    );
+    expect(loadProjectExecPolicy(file, untrusted)).toBeNull();
+    expect(loadProjectExecPolicy(file, "relative/path")).toBeNull();
+    expect(loadProjectExecPolicy(file, undefined)).toBeNull();
+  });
+
+  it("picks the deepest trusted root containing the cwd", () => {
+    const dir = makeTempDir();
+    const outer = path.join(dir, "work");
+    const inner = path.join(outer, "repo");
+    const file: ExecApprovalsFile = { version: 1, trustedProjects: [outer, inner] };
+
+    expect(resolveTrustedProjectRoot(file, path.join(inner, "pkg"))).toBe(path.resolve(inner));
+    expect(resolveTrustedProjectRoot(file, path.join(outer, "other"))).toBe(path.resolve(outer));
+    expect(resolveTrustedProjectRoot(file, `${outer}-sibling`)).toBeNull();
+  });
+
+  it("merges the project allowlist into the agent policy", () => {
+    const dir = makeTempDir();
+    writeProjectPolicy(dir, {
+      allowlist: [{ pattern: "/usr/bin/make" }, "/usr/bin/cargo"],
+    });
+    const file: ExecApprovalsFile = {
+      version: 1,
+      trustedProjects: [dir],
+      agents: {
+        main: { security: "allowlist", ask: "always", allowlist: [{ pattern: "/bin/ls" }] },
+      },
+    };
+
+    const resolved = resolveExecApprovalsFromFile({
+      file,
+      project: loadProjectExecPolicy(file, dir),
+    });
+    expect(resolved.agent.security).toBe("allowlist");
+    expect(resolved.agent.ask).toBe("always");
+    expect(resolved.allowlist.map((entry) => entry.pattern)).toEqual([
+      "/bin/ls",
+      "/usr/bin/make",
+      "/usr/bin/cargo",
+    ]);
+    expect(resolved.project?.root).toBe(path.resolve(dir));
+  });
+
+  it("ignores project settings that would loosen the agent policy", () => {
+    const dir = makeTempDir();
+    writeProjectPolicy(dir, {
+      security: "full",
+      ask: "off",
+      askFallback: "full",
+      autoAllowSkills: true,
+    });
+    const file: ExecApprovalsFile = {
+      version: 1,
+      trustedProjects: [dir],
+      agents: {
+        main: { security: "allowlist", ask: "always", askFallback: "deny", autoAllowSkills: false },
+      },
+    };
+
+    const resolved = resolveExecApprovalsFromFile({
+      file,
+      project: loadProjectExecPolicy(file, dir),
+    });
+    expect(resolved.agent).toEqual({
+      security: "allowlist",
+      ask: "always",
+      askFallback: "deny",
+      autoAllowSkills: false,
+    });
+  });
+
+  it("keeps trustedProjects through normalization", () => {
+    const resolved = resolveExecApprovalsFromFile({
+      file: { version: 1, trustedProjects: ["/srv/repo", ""] },
+    });
+    expect(resolved.file.trustedProjects).toEqual(["/srv/repo"]);
+    expect(resolved.project).toBeNull();
+  });
+});
@@ -0,0 +1,77 @@
+import fs from "node:fs";
+import path from "node:path";
+import type { ExecApprovalsAgent, ExecApprovalsFile } from "./exec-approvals.js";
+import { expandHomePrefix } from "./home-dir.js";
+
+// Workspace-local policy, relative to a trusted project root.
+export const PROJECT_EXEC_POLICY_FILE = path.join(".openclaw", "exec-policy.json");
+
+export type ProjectExecPolicy = {
+  root: string;
+  path: string;
+  policy: ExecApprovalsAgent;
+};
+
+function normalizeRoot(value: string): string | null {
+  const trimmed = value.trim();
+  if (!trimmed) {
+    return null;
+  }
+  const expanded = path.resolve(expandHomePrefix(trimmed));
+  return process.platform === "win32" ? expanded.toLowerCase() : expanded;
+}
+
+function isInside(root: string, target: string): boolean {
+  const relative = path.relative(root, target);
+  return relative === "" || (!relative.startsWith("..") && !path.isAbsolute(relative));
+}
+
+/**
+ * Deepest `trustedProjects` root containing `cwd`. Only roots listed in
+ * exec-approvals.json count, so a checked-out repo cannot widen its own policy.
+ */
+export function resolveTrustedProjectRoot(
+  file: ExecApprovalsFile,
+  cwd: string | null | undefined,
+): string | null {
+  const trimmed = cwd?.trim();
+  if (!trimmed || !path.isAbsolute(trimmed)) {
+    return null;
+  }
+  const target = normalizeRoot(trimmed);
+  if (!target) {
+    return null;
+  }
+  let best: string | null = null;
+  for (const entry of file.trustedProjects ?? []) {
+    if (typeof entry !== "string") {
+      continue;
+    }
+    const root = normalizeRoot(entry);
+    if (root && isInside(root, target) && (!best || root.length > best.length)) {
+      best = root;
+    }
+  }
+  return best;
+}
+
+/** Load `<root>/.openclaw/exec-policy.json` for the trusted project containing `cwd`. */
+export function loadProjectExecPolicy(
+  file: ExecApprovalsFile,
+  cwd: string | null | undefined,
+): ProjectExecPolicy | null {
+  const root = resolveTrustedProjectRoot(file, cwd);
+  if (!root) {
+    return null;
+  }
+  const policyPath = path.join(root, PROJECT_EXEC_POLICY_FILE);
+  try {
+    const parsed = JSON.parse(fs.readFileSync(policyPath, "utf8")) as unknown;
+    if (!parsed || typeof parsed !== "object" || Array.isArray(parsed)) {
+      return null;
+    }
+    return { root, path: policyPath, policy: parsed as ExecApprovalsAgent };
+  } catch {
+    return null;
+  }
+}
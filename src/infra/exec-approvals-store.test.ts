@@ -17,13 +17,25 @@ import {
   readExecApprovalsSnapshot,
   recordAllowlistUse,
   requestExecApprovalViaSocket,
+  resolveExecApprovals,
   resolveExecApprovalsPath,
   resolveExecApprovalsSocketPath,
+  resolveExecHostTokenRef,
   type ExecApprovalsFile,
 } from "./exec-approvals.js";
 
 const tempDirs: string[] = [];
 const originalOpenClawHome = process.env.OPENCLAW_HOME;
+const originalExecHostSocket = process.env.OPENCLAW_EXEC_HOST_SOCKET;
+const originalExecHostToken = process.env.OPENCLAW_EXEC_HOST_TOKEN;
+
+function restoreEnv(key: string, value: string | undefined) {
+  if (value === undefined) {
+    delete process.env[key];
+  } else {
+    process.env[key] = value;
+  }
+}
 
 beforeEach(() => {
   requestJsonlSocketMock.mockReset();
@@ -31,11 +43,9 @@ beforeEach(() => {
 
 afterEach(() => {
   vi.restoreAllMocks();
-  if (originalOpenClawHome === undefined) {
-    delete process.env.OPENCLAW_HOME;
-  } else {
-    process.env.OPENCLAW_HOME = originalOpenClawHome;
-  }
+  restoreEnv("OPENCLAW_HOME", originalOpenClawHome);
+  restoreEnv("OPENCLAW_EXEC_HOST_SOCKET", originalExecHostSocket);
+  restoreEnv("OPENCLAW_EXEC_HOST_TOKEN", originalExecHostToken);
   for (const dir of tempDirs.splice(0)) {
     fs.rmSync(dir, { recursive: true, force: true });
   }
@@ -188,6 +198,94 @@ describe("exec approvals store helpers", () => {
     expect(readApprovalsFile(dir).agents?.main?.allowlist?.[0]?.id).toMatch(/^[0-9a-f-]{36}$/i);
   });
 
+  it("prefers the socket path and token passed in the environment", () => {
+    const dir = createHomeDir();
+    fs.mkdirSync(path.join(dir, ".openclaw"), { recursive: true });
+    fs.writeFileSync(
+      approvalsFilePath(dir),
+      JSON.stringify({ version: 1, socket: { path: "/tmp/file.sock", token: "file-token" } }),
+    );
+
+    expect(resolveExecApprovals()).toMatchObject({
+      socketPath: "/tmp/file.sock",
+      token: "file-token",
+    });
+
+    process.env.OPENCLAW_EXEC_HOST_SOCKET = "/run/user/1000/openclaw/exec-host.sock";
+    process.env.OPENCLAW_EXEC_HOST_TOKEN = "app-token";
+    expect(resolveExecApprovals()).toMatchObject({
+      socketPath: "/run/user/1000/openclaw/exec-host.sock",
+      token: "app-token",
+    });
+  });
+
+  it("keeps a keychain tokenRef instead of writing a plaintext token", () => {
+    const dir = createHomeDir();
+    const tokenRef = "keychain:ai.openclaw.nodeclient/exec-host-token";
+    fs.mkdirSync(path.join(dir, ".openclaw"), { recursive: true });
+    fs.writeFileSync(
+      approvalsFilePath(dir),
+      JSON.stringify({ version: 1, socket: { path: "/tmp/file.sock", tokenRef } }),
+    );
+
+    ensureExecApprovals();
+    expect(readApprovalsFile(dir).socket).toEqual({ path: "/tmp/file.sock", tokenRef });
+    expect(
+      mergeExecApprovalsSocketDefaults({
+        normalized: normalizeExecApprovals({ version: 1 }),
+        current: readApprovalsFile(dir),
+      }).socket?.tokenRef,
+    ).toBe(tokenRef);
+
+    process.env.OPENCLAW_EXEC_HOST_TOKEN = "app-token";
+    expect(resolveExecApprovals().token).toBe("app-token");
+  });
+
+  it("resolves keychain token references with the platform tool", () => {
+    const execFileSync = vi.fn(() => "secret-token\n");
+    const tokenRef = "keychain:ai.openclaw.nodeclient/exec-host-token";
+
+    expect(
+      resolveExecHostTokenRef(tokenRef, {
+        platform: "darwin",
+        execFileSync: execFileSync as never,
+      }),
+    ).toBe("secret-token");
+    expect(execFileSync).toHaveBeenLastCalledWith(
+      "security",
+      ["find-generic-password", "-s", "ai.openclaw.nodeclient", "-a", "exec-host-token", "-w"],
+      expect.any(Object),
+    );
+
+    expect(
+      resolveExecHostTokenRef(tokenRef, { platform: "linux", execFileSync: execFileSync as never }),
+    ).toBe("secret-token");
+    expect(execFileSync).toHaveBeenLastCalledWith(
+      "secret-tool",
+      ["lookup", "service", "ai.openclaw.nodeclient", "username", "exec-host-token"],
+      expect.any(Object),
+    );
+
+    execFileSync.mockClear();
+    expect(
+      resolveExecHostTokenRef(tokenRef, { platform: "win32", execFileSync: execFileSync as never }),
+    ).toBeNull();
+    expect(
+      resolveExecHostTokenRef("env:EXEC_TOKEN", {
+        platform: "linux",
+        execFileSync: execFileSync as never,
+      }),
+    ).toBeNull();
+    expect(execFileSync).not.toHaveBeenCalled();
+
+    const failing = vi.fn(() => {
+      throw new Error("no such item");
+    });
+    expect(
+      resolveExecHostTokenRef(tokenRef, { platform: "linux", execFileSync: failing as never }),
+    ).toBeNull();
+  });
+
   it("returns null when approval socket credentials are missing", async () => {
     await expect(
       requestExecApprovalViaSocket({
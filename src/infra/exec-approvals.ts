@@ -1,11 +1,14 @@
+import { execFileSync } from "node:child_process";
 import crypto from "node:crypto";
 import fs from "node:fs";
 import path from "node:path";
 import { DEFAULT_AGENT_ID } from "../routing/session-key.js";
+import { loadProjectExecPolicy, type ProjectExecPolicy } from "./exec-approvals-project.js";
 import { expandHomePrefix } from "./home-dir.js";
 import { requestJsonlSocket } from "./jsonl-socket.js";
 export * from "./exec-approvals-analysis.js";
 export * from "./exec-approvals-allowlist.js";
+export * from "./exec-approvals-project.js";
 
 export type ExecHost = "sandbox" | "gateway" | "node";
 export type ExecSecurity = "deny" | "allowlist" | "full";
@@ -120,9 +123,13 @@ export type ExecApprovalsFile = {
   socket?: {
     path?: string;
     token?: string;
+    // "keychain:<service>/<account>", written by the desktop app in place of `token`.
+    tokenRef?: string;
   };
   defaults?: ExecApprovalsDefaults;
   agents?: Record<string, ExecApprovalsAgent>;
+  // Workspace roots whose `.openclaw/exec-policy.json` is merged over the agent policy.
+  trustedProjects?: string[];
 };
 
 export type ExecApprovalsSnapshot = {
@@ -141,6 +148,7 @@ export type ExecApprovalsResolved = {
   agent: Required<ExecApprovalsDefaults>;
   allowlist: ExecAllowlistEntry[];
   file: ExecApprovalsFile;
+  project?: ProjectExecPolicy | null;
 };
 
 // Keep CLI + gateway defaults in sync.
@@ -262,6 +270,7 @@ function ensureAllowlistIds(
 export function normalizeExecApprovals(file: ExecApprovalsFile): ExecApprovalsFile {
   const socketPath = file.socket?.path?.trim();
   const token = file.socket?.token?.trim();
+  const tokenRef = file.socket?.tokenRef?.trim();
   const agents = { ...file.agents };
   const legacyDefault = agents.default;
   if (legacyDefault) {
@@ -281,6 +290,7 @@ export function normalizeExecApprovals(file: ExecApprovalsFile): ExecApprovalsFi
     socket: {
       path: socketPath && socketPath.length > 0 ? socketPath : undefined,
       token: token && token.length > 0 ? token : undefined,
+      tokenRef: tokenRef && tokenRef.length > 0 ? tokenRef : undefined,
     },
     defaults: {
       security: file.defaults?.security,
@@ -289,6 +299,11 @@ export function normalizeExecApprovals(file: ExecApprovalsFile): ExecApprovalsFi
       autoAllowSkills: file.defaults?.autoAllowSkills,
     },
     agents,
+    trustedProjects: Array.isArray(file.trustedProjects)
+      ? file.trustedProjects.filter(
+          (entry): entry is string => typeof entry === "string" && entry.trim().length > 0,
+        )
+      : undefined,
   };
   return normalized;
 }
@@ -302,11 +317,14 @@ export function mergeExecApprovalsSocketDefaults(params: {
   const socketPath =
     params.normalized.socket?.path?.trim() ?? currentSocketPath ?? resolveExecApprovalsSocketPath();
   const token = params.normalized.socket?.token?.trim() ?? currentToken ?? "";
+  const tokenRef =
+    params.normalized.socket?.tokenRef?.trim() ?? params.current?.socket?.tokenRef?.trim();
   return {
     ...params.normalized,
     socket: {
       path: socketPath,
       token,
+      ...(tokenRef ? { tokenRef } : {}),
     },
   };
 }
@@ -380,11 +398,15 @@ export function ensureExecApprovals(): ExecApprovalsFile {
   const next = normalizeExecApprovals(loaded);
   const socketPath = next.socket?.path?.trim();
   const token = next.socket?.token?.trim();
+  const tokenRef = next.socket?.tokenRef;
   const updated: ExecApprovalsFile = {
     ...next,
     socket: {
       path: socketPath && socketPath.length > 0 ? socketPath : resolveExecApprovalsSocketPath(),
-      token: token && token.length > 0 ? token : generateToken(),
+      // A token kept in the keychain is resolved on use; don't mint a plaintext one beside it
+      ...(tokenRef && !token
+        ? { tokenRef }
+        : { token: token && token.length > 0 ? token : generateToken() }),
     },
   };
   saveExecApprovals(updated);
@@ -412,18 +434,89 @@ export type ExecApprovalsDefaultOverrides = {
   autoAllowSkills?: boolean;
 };
 
+/**
+ * Socket path and token handed over by the app that launched the node host.
+ * They win over exec-approvals.json, which belongs to whichever account the
+ * node host runs as.
+ */
+export function resolveExecHostEnvOverrides(env: NodeJS.ProcessEnv = process.env): {
+  socketPath?: string;
+  token?: string;
+} {
+  const socketPath = env.OPENCLAW_EXEC_HOST_SOCKET?.trim();
+  const token = env.OPENCLAW_EXEC_HOST_TOKEN?.trim();
+  return {
+    ...(socketPath ? { socketPath } : {}),
+    ...(token ? { token } : {}),
+  };
+}
+
+const EXEC_HOST_TOKEN_REF_SCHEME = "keychain:";
+
+/**
+ * Read the exec-host token a `tokenRef` ("keychain:<service>/<account>") points at, from
+ * the macOS Keychain or the Secret Service. Windows Credential Manager isn't readable from
+ * here; there the app passes the token to the node host in `OPENCLAW_EXEC_HOST_TOKEN`.
+ */
+export function resolveExecHostTokenRef(
+  tokenRef: string | undefined,
+  options?: { platform?: NodeJS.Platform; execFileSync?: typeof execFileSync },
+): string | null {
+  const ref = tokenRef?.trim();
+  if (!ref?.startsWith(EXEC_HOST_TOKEN_REF_SCHEME)) {
+    return null;
+  }
+  const rest = ref.slice(EXEC_HOST_TOKEN_REF_SCHEME.length);
+  const slash = rest.lastIndexOf("/");
+  if (slash <= 0 || slash === rest.length - 1) {
+    return null;
+  }
+  const service = rest.slice(0, slash);
+  const account = rest.slice(slash + 1);
+  const platform = options?.platform ?? process.platform;
+  const execFileSyncImpl = options?.execFileSync ?? execFileSync;
+  const lookup: [string, string[]] | null =
+    platform === "darwin"
+      ? ["security", ["find-generic-password", "-s", service, "-a", account, "-w"]]
+      : platform === "linux"
+        ? ["secret-tool", ["lookup", "service", service, "username", account]]
+        : null;
+  if (!lookup) {
+    return null;
+  }
+  try {
+    const token = execFileSyncImpl(lookup[0], lookup[1], {
+      encoding: "utf8",
+      timeout: 5000,
+      stdio: ["pipe", "pipe", "pipe"],
+    }).trim();
+    return token || null;
+  } catch {
+    return null;
+  }
+}
+
 export function resolveExecApprovals(
   agentId?: string,
   overrides?: ExecApprovalsDefaultOverrides,
+  opts?: { cwd?: string | null },
 ): ExecApprovalsResolved {
   const file = ensureExecApprovals();
+  const envOverrides = resolveExecHostEnvOverrides();
   return resolveExecApprovalsFromFile({
     file,
     agentId,
     overrides,
+    project: loadProjectExecPolicy(file, opts?.cwd),
     path: resolveExecApprovalsPath(),
-    socketPath: expandHomePrefix(file.socket?.path ?? resolveExecApprovalsSocketPath()),
-    token: file.socket?.token ?? "",
+    socketPath: expandHomePrefix(
+      envOverrides.socketPath ?? file.socket?.path ?? resolveExecApprovalsSocketPath(),
+    ),
+    token:
+      envOverrides.token ??
+      file.socket?.token ??
+      resolveExecHostTokenRef(file.socket?.tokenRef) ??
+      "",
   });
 }
 
@@ -431,6 +524,7 @@ export function resolveExecApprovalsFromFile(params: {
   file: ExecApprovalsFile;
   agentId?: string;
   overrides?: ExecApprovalsDefaultOverrides;
+  project?: ProjectExecPolicy | null;
   path?: string;
   socketPath?: string;
   token?: string;
@@ -467,9 +561,13 @@ export function resolveExecApprovalsFromFile(params: {
       agent.autoAllowSkills ?? wildcard.autoAllowSkills ?? resolvedDefaults.autoAllowSkills,
     ),
   };
+  // A trusted project's policy file only extends the allowlist; its security, ask and
+  // autoAllowSkills are ignored so a checked-out repo can never loosen the agent policy.
+  const project = params.project?.policy ?? {};
   const allowlist = [
     ...(Array.isArray(wildcard.allowlist) ? wildcard.allowlist : []),
     ...(Array.isArray(agent.allowlist) ? agent.allowlist : []),
+    ...(coerceAllowlistEntries(project.allowlist) ?? []),
   ];
   return {
     path: params.path ?? resolveExecApprovalsPath(),
@@ -481,6 +579,7 @@ export function resolveExecApprovalsFromFile(params: {
     agent: resolvedAgent,
     allowlist,
     file,
+    project: params.project ?? null,
   };
 }
 
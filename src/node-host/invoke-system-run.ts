@@ -281,10 +281,11 @@ async function evaluateSystemRunPolicyPhase(
     agentExec?.security ?? cfg.tools?.exec?.security,
   );
   const configuredAsk = opts.resolveExecAsk(agentExec?.ask ?? cfg.tools?.exec?.ask);
-  const approvals = resolveExecApprovals(parsed.agentId, {
-    security: configuredSecurity,
-    ask: configuredAsk,
-  });
+  const approvals = resolveExecApprovals(
+    parsed.agentId,
+    { security: configuredSecurity, ask: configuredAsk },
+    { cwd: parsed.cwd },
+  );
   const security = approvals.agent.security;
   const ask = approvals.agent.ask;
   const autoAllowSkills = approvals.agent.autoAllowSkills;